@@ -56,6 +56,8 @@ criterion_group! {
         bench_execute_regex_redux_v1,
         bench_execute_count_until_v0,
         bench_execute_count_until_v1,
+        bench_execute_count_primes_v0,
+        bench_execute_count_primes_v1,
         bench_execute_fac_recursive_v0,
         bench_execute_fac_recursive_v1,
         bench_execute_fac_opt_v0,
@@ -431,6 +433,44 @@ fn bench_execute_count_until_v1(c: &mut Criterion) {
     });
 }
 
+const COUNT_PRIMES_LIMIT: i32 = 10_000;
+const COUNT_PRIMES_RESULT: i32 = 1229;
+
+fn bench_execute_count_primes_v0(c: &mut Criterion) {
+    c.bench_function("execute/count_primes/v0", |b| {
+        let instance = load_instance_from_wat_v0(include_bytes!("wat/count_primes.wat"));
+        b.iter(|| {
+            let value = instance
+                .invoke_export(
+                    "count_primes",
+                    &[Value::I32(COUNT_PRIMES_LIMIT)],
+                    &mut v0::NopExternals,
+                )
+                .unwrap();
+            assert_eq!(value, Some(Value::I32(COUNT_PRIMES_RESULT)));
+        })
+    });
+}
+
+fn bench_execute_count_primes_v1(c: &mut Criterion) {
+    c.bench_function("execute/count_primes/v1", |b| {
+        let (mut store, instance) =
+            load_instance_from_wat_v1(include_bytes!("wat/count_primes.wat"));
+        let count_primes = instance
+            .get_export(&store, "count_primes")
+            .and_then(v1::Extern::into_func)
+            .unwrap();
+        let mut result = [Value::I32(0)];
+
+        b.iter(|| {
+            count_primes
+                .call(&mut store, &[Value::I32(COUNT_PRIMES_LIMIT)], &mut result)
+                .unwrap();
+            assert_eq!(result, [Value::I32(COUNT_PRIMES_RESULT)]);
+        })
+    });
+}
+
 fn bench_execute_fac_recursive_v0(c: &mut Criterion) {
     c.bench_function("execute/factorial_recursive/v0", |b| {
         let instance = load_instance_from_wat_v0(include_bytes!("wat/factorial.wat"));
@@ -0,0 +1,43 @@
+;; Exports a function `count_primes` that returns the number of primes
+;; below the given `limit` using trial division.
+;;
+;; The loops are written in the shape compilers usually emit: conditional
+;; branches on comparisons and `i32.eqz` as well as counters that are
+;; incremented by constants.
+(module
+    (func (export "count_primes") (param $limit i32) (result i32)
+        (local $n i32)
+        (local $d i32)
+        (local $count i32)
+        (local.set $n (i32.const 2))
+        (block $exit
+            (loop $outer
+                (br_if $exit ;; exit loop if $n >= $limit
+                    (i32.ge_u (local.get $n) (local.get $limit))
+                )
+                (local.set $d (i32.const 2))
+                (block $composite
+                    (block $prime
+                        (loop $inner
+                            (br_if $prime ;; $n is prime if $d * $d > $n
+                                (i32.gt_u
+                                    (i32.mul (local.get $d) (local.get $d))
+                                    (local.get $n)
+                                )
+                            )
+                            (br_if $composite ;; $n is composite if $d divides $n
+                                (i32.eqz (i32.rem_u (local.get $n) (local.get $d)))
+                            )
+                            (local.set $d (i32.add (local.get $d) (i32.const 1)))
+                            (br $inner)
+                        )
+                    )
+                    (local.set $count (i32.add (local.get $count) (i32.const 1)))
+                )
+                (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                (br $outer)
+            )
+        )
+        (local.get $count)
+    )
+)
@@ -1,10 +1,17 @@
 use clap::Parser;
-use std::fs;
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, Write},
+};
 use wasmi::{
     core::{Value, ValueType, F32, F64},
+    Coverage,
     Func,
     FuncType,
+    Profile,
     Store,
+    TraceEvent,
 };
 use wasmi_v1 as wasmi;
 
@@ -23,17 +30,24 @@ struct Args {
     /// The arguments provided to the called function.
     #[clap(value_parser)]
     func_args: Vec<String>,
-}
 
-#[derive(Debug)]
-enum AppError {
-    Interpreter(wasmi_v1::Error),
-}
+    /// Prints a trace of the executed `wasmi` bytecode instructions to stderr.
+    #[clap(long)]
+    trace: bool,
 
-impl From<wasmi_v1::Error> for AppError {
-    fn from(e: wasmi_v1::Error) -> Self {
-        Self::Interpreter(e)
-    }
+    /// Profiles the called function and writes its call stacks in folded stacks format to FILE.
+    ///
+    /// A summary of the calls, executed instructions and timings
+    /// of all executed functions is printed to stderr.
+    #[clap(long, value_name = "FILE")]
+    profile: Option<String>,
+
+    /// Collects coverage of the executed Wasm functions and branches and writes it to FILE.
+    ///
+    /// The coverage is written in lcov format if the Wasm module provides names
+    /// via its name section and as raw Wasm offsets otherwise.
+    #[clap(long, value_name = "FILE")]
+    coverage: Option<String>,
 }
 
 fn main() -> Result<(), String> {
@@ -51,8 +65,30 @@ fn main() -> Result<(), String> {
 
     print_execution_start(&wasm_file, &func_name, &func_args);
 
-    func.call(&mut store, &func_args, &mut results)
-        .map_err(|error| format!("failed during exeuction of {func_name}: {error:#?}"))?;
+    if args.trace {
+        store.set_trace_hook(print_trace_event);
+    }
+
+    if args.profile.is_some() {
+        store.enable_profiler();
+    }
+
+    if args.coverage.is_some() {
+        store.enable_coverage();
+    }
+
+    let outcome = func.call(&mut store, &func_args, &mut results);
+
+    if let (Some(profile_file), Some(profile)) = (&args.profile, store.profile()) {
+        write_profile(profile_file, &profile)?;
+        print_profile_summary(&profile);
+    }
+
+    if let (Some(coverage_file), Some(coverage)) = (&args.coverage, store.coverage()) {
+        write_coverage(coverage_file, &coverage)?;
+    }
+
+    outcome.map_err(|error| format!("failed during exeuction of {func_name}: {error:#?}"))?;
 
     print_pretty_results(&results);
 
@@ -94,16 +130,15 @@ fn load_wasm_func(
     wasm_file: &str,
     wasm_bytes: &[u8],
     func_name: &str,
-) -> Result<(Func, Store<(), AppError>), String> {
+) -> Result<(Func, Store<()>), String> {
     let engine = wasmi::Engine::default();
-    let mut store = wasmi::Store::<_, AppError>::new(&engine, ());
+    let mut store = wasmi::Store::new(&engine, ());
     let module = wasmi::Module::new(&engine, &mut &wasm_bytes[..]).map_err(|error| {
         format!("failed to parse and validate Wasm module {wasm_file}: {error}")
     })?;
     let mut linker = <wasmi::Linker<()>>::new();
     let instance = linker
         .instantiate(&mut store, &module)
-        .map_err(Into::into)
         .and_then(|pre| pre.start(&mut store))
         .map_err(|error| format!("failed to instantiate and start the Wasm module: {error:#?}"))?;
     let func = instance
@@ -185,6 +220,78 @@ fn print_execution_start(wasm_file: &str, func_name: &str, func_args: &[Value])
     println!(") ...");
 }
 
+/// Prints the executed `wasmi` bytecode instruction of the [`TraceEvent`] to stderr.
+///
+/// The instruction is followed by the values of the registers of its function frame.
+fn print_trace_event(event: &TraceEvent) {
+    let registers = event
+        .registers()
+        .iter()
+        .map(|value| format!("{:#x}", value.to_bits()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    eprintln!(
+        "func[{}] @ {:>4}: {:?} [{registers}]",
+        event.func_index(),
+        event.pc(),
+        event.instruction(),
+    );
+}
+
+/// Writes the call stacks of the [`Profile`] in folded stacks format to `profile_file`.
+///
+/// # Errors
+///
+/// If the `profile_file` cannot be created or written.
+fn write_profile(profile_file: &str, profile: &Profile) -> Result<(), String> {
+    let error = |error: io::Error| format!("failed to write profile {profile_file}: {error}");
+    let file = fs::File::create(profile_file).map_err(error)?;
+    let mut writer = io::BufWriter::new(file);
+    profile.write_folded(&mut writer).map_err(error)?;
+    writer.flush().map_err(error)
+}
+
+/// Writes the [`Coverage`] to `coverage_file`.
+///
+/// # Note
+///
+/// The [`Coverage`] is written in lcov format if names are available and as raw Wasm offsets otherwise.
+///
+/// # Errors
+///
+/// If the `coverage_file` cannot be created or written.
+fn write_coverage(coverage_file: &str, coverage: &Coverage) -> Result<(), String> {
+    let error = |error: io::Error| format!("failed to write coverage {coverage_file}: {error}");
+    let file = fs::File::create(coverage_file).map_err(error)?;
+    let mut writer = io::BufWriter::new(file);
+    match coverage.has_names() {
+        true => coverage.write_lcov(&mut writer),
+        false => coverage.write_raw(&mut writer),
+    }
+    .map_err(error)?;
+    writer.flush().map_err(error)
+}
+
+/// Prints the profiled functions of the [`Profile`] to stderr ordered by their exclusive time.
+fn print_profile_summary(profile: &Profile) {
+    let mut funcs = profile.funcs().iter().collect::<Vec<_>>();
+    funcs.sort_by_key(|func| Reverse(func.exclusive_time()));
+    eprintln!(
+        "{:>10} {:>14} {:>16} {:>16}  function",
+        "calls", "instructions", "inclusive (µs)", "exclusive (µs)",
+    );
+    for func in funcs {
+        eprintln!(
+            "{:>10} {:>14} {:>16} {:>16}  {}",
+            func.calls(),
+            func.instructions(),
+            func.inclusive_time().as_micros(),
+            func.exclusive_time().as_micros(),
+            func.name(),
+        );
+    }
+}
+
 /// Prints the results of the Wasm computation in a human readable form.
 fn print_pretty_results(results: &[Value]) {
     let pretty_results = results
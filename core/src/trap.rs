@@ -83,6 +83,31 @@ pub enum TrapCode {
     /// with an index that points to a function with signature different of what is
     /// expected by this indirect call, this trap is raised.
     UnexpectedSignature,
+
+    /// Ran out of fuel while executing a Wasm function.
+    ///
+    /// This can only happen if fuel metering is enabled and the
+    /// fuel supplied to the executing store has been fully consumed.
+    OutOfFuel,
+
+    /// Execution has been interrupted via an interrupt handle.
+    ///
+    /// This happens if an interrupt was requested for the executing store
+    /// and execution reached the next loop back-edge or function call.
+    Interrupted,
+
+    /// Called a Wasm function of a module instance that has been dropped.
+    ///
+    /// This happens if a Wasm function of a dropped instance is still
+    /// referenced, for example via an import or a table element, or if an
+    /// execution is resumed after the instance of one of its calls has been dropped.
+    DroppedInstance,
+
+    /// A resource limit has been exceeded.
+    ///
+    /// This happens if the resource limiter of the executing store traps
+    /// upon a linear memory growth of the executed Wasm code.
+    ResourceLimitExceeded,
 }
 
 impl TrapCode {
@@ -103,6 +128,10 @@ impl TrapCode {
             TrapCode::InvalidConversionToInt => "invalid conversion to integer",
             TrapCode::StackOverflow => "call stack exhausted",
             TrapCode::UnexpectedSignature => "indirect call type mismatch",
+            TrapCode::OutOfFuel => "all fuel consumed",
+            TrapCode::Interrupted => "execution interrupted",
+            TrapCode::DroppedInstance => "call to dropped instance",
+            TrapCode::ResourceLimitExceeded => "resource limit exceeded",
         }
     }
 }
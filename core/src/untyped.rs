@@ -781,6 +781,32 @@ impl UntypedValue {
         self.execute_unary(<F32 as ExtendInto<F64>>::extend_into)
     }
 
+    /// Replaces an `f32` NaN value with the canonical Wasm NaN.
+    ///
+    /// # Note
+    ///
+    /// The canonical Wasm NaN is positive and has only the most significant bit of its payload set.
+    /// Values that are not NaN are returned unchanged.
+    pub fn f32_canonicalize_nan(self) -> Self {
+        match F32::from(self).is_nan() {
+            true => Self::from(F32::from_bits(0x7FC0_0000)),
+            false => self,
+        }
+    }
+
+    /// Replaces an `f64` NaN value with the canonical Wasm NaN.
+    ///
+    /// # Note
+    ///
+    /// The canonical Wasm NaN is positive and has only the most significant bit of its payload set.
+    /// Values that are not NaN are returned unchanged.
+    pub fn f64_canonicalize_nan(self) -> Self {
+        match F64::from(self).is_nan() {
+            true => Self::from(F64::from_bits(0x7FF8_0000_0000_0000)),
+            false => self,
+        }
+    }
+
     /// Execute `i32.extend8_s` Wasm operation.
     pub fn i32_extend8_s(self) -> Self {
         self.execute_unary(<i32 as SignExtendFrom<i8>>::sign_extend_from)
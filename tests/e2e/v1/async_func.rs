@@ -0,0 +1,261 @@
+//! Tests for asynchronous host functions and calls in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+use wasmi_core::Value;
+use wasmi_v1::{
+    Caller,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    HostError,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Trap,
+};
+
+/// A host error returned by the asynchronous host functions.
+#[derive(Debug, PartialEq, Eq)]
+struct NegativeInput(i32);
+
+impl fmt::Display for NegativeInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "negative input: {}", self.0)
+    }
+}
+
+impl HostError for NegativeInput {}
+
+/// Wakes the thread that runs [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs the `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// A [`Future`] that is pending `n` times before it is ready.
+struct YieldNow(usize);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 == 0 {
+            return Poll::Ready(());
+        }
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Instantiates a Wasm module exporting `run` which sums up the results of
+/// the imported `env.host` for all inputs from `0` up to its parameter and
+/// `fail` which calls `env.host` with a negative input.
+///
+/// The host function is asynchronous and returns its input plus the
+/// host data of the [`Store`] after yielding once.
+/// Negative inputs make the host function fail with [`NegativeInput`].
+fn test_setup() -> (Store<i32>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, 100);
+    let host = Func::wrap_async(&mut store, |caller: Caller<i32>, input: i32| {
+        let offset = *caller.host_data();
+        async move {
+            YieldNow(1).await;
+            if input < 0 {
+                return Err(Trap::host(NegativeInput(input)));
+            }
+            Ok((input + offset,))
+        }
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func (export "run") (param $n i32) (result i32)
+                (local $i i32)
+                (local $sum i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.ge_s (local.get $i) (local.get $n)))
+                        (local.set $sum
+                            (i32.add (local.get $sum) (call $host (local.get $i)))
+                        )
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+                (local.get $sum)
+            )
+            (func (export "fail") (result i32)
+                (call $host (i32.const -1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<i32>>::new();
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the exported function `name` of the `instance`.
+fn get_func(store: &Store<i32>, instance: Instance, name: &str) -> Func {
+    instance
+        .get_export(store, name)
+        .and_then(Extern::into_func)
+        .unwrap()
+}
+
+#[test]
+fn call_async_works() {
+    let (mut store, instance) = test_setup();
+    let run = get_func(&store, instance, "run");
+    let mut result = [Value::I32(0)];
+    block_on(run.call_async(&mut store, &[Value::I32(3)], &mut result)).unwrap();
+    // Note: (0 + 100) + (1 + 100) + (2 + 100)
+    assert_eq!(result, [Value::I32(303)]);
+    // The host data is read upon every call of the host function.
+    *store.state_mut() = 0;
+    block_on(run.call_async(&mut store, &[Value::I32(4)], &mut result)).unwrap();
+    assert_eq!(result, [Value::I32(6)]);
+}
+
+#[test]
+fn typed_call_async_works() {
+    let (mut store, instance) = test_setup();
+    let run = get_func(&store, instance, "run");
+    let run = run.typed::<i32, i32, _>(&store).unwrap();
+    assert_eq!(block_on(run.call_async(&mut store, 2)).unwrap(), 201);
+}
+
+#[test]
+fn call_async_without_async_host_calls_works() {
+    let (mut store, instance) = test_setup();
+    let run = get_func(&store, instance, "run");
+    let run = run.typed::<i32, i32, _>(&store).unwrap();
+    assert_eq!(block_on(run.call_async(&mut store, 0)).unwrap(), 0);
+}
+
+#[test]
+fn call_async_of_host_func_works() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let host = Func::wrap_async(&mut store, |a: i32, b: i32| async move {
+        YieldNow(3).await;
+        a * b
+    });
+    let host = host.typed::<(i32, i32), i32, _>(&store).unwrap();
+    assert_eq!(block_on(host.call_async(&mut store, (6, 7))).unwrap(), 42);
+}
+
+#[test]
+fn async_host_error_is_returned() {
+    let (mut store, instance) = test_setup();
+    let fail = get_func(&store, instance, "fail");
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        block_on(fail.call_async(&mut store, &[], &mut result)),
+        Err(Error::Trap(trap)) if trap.downcast_ref() == Some(&NegativeInput(-1))
+    );
+    // The store can be used for further calls after the failed call.
+    let run = get_func(&store, instance, "run");
+    block_on(run.call_async(&mut store, &[Value::I32(1)], &mut result)).unwrap();
+    assert_eq!(result, [Value::I32(100)]);
+}
+
+#[test]
+fn sync_call_of_async_host_func_fails() {
+    let (mut store, instance) = test_setup();
+    let run = get_func(&store, instance, "run");
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        run.call(&mut store, &[Value::I32(1)], &mut result),
+        Err(Error::Trap(trap)) if trap.to_string().contains("asynchronous")
+    );
+}
+
+#[test]
+fn nested_sync_call_of_async_host_func_fails() {
+    let (mut store, instance) = test_setup();
+    let run = get_func(&store, instance, "run");
+    // The synchronous host function calls back into Wasm that calls the
+    // asynchronous host function and passes on its error.
+    let sync = Func::wrap(
+        &mut store,
+        move |mut caller: Caller<i32>, input: i32| -> Result<(i32,), Trap> {
+            let mut result = [Value::I32(0)];
+            run.call(&mut caller, &[Value::I32(input)], &mut result)?;
+            match result {
+                [Value::I32(result)] => Ok((result,)),
+                result => panic!("expected an `i32` result but found: {result:?}"),
+            }
+        },
+    );
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "sync" (func $sync (param i32) (result i32)))
+            (func (export "outer") (param i32) (result i32)
+                (call $sync (local.get 0))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<i32>>::new();
+    linker.define("env", "sync", sync).unwrap();
+    let outer = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let outer = get_func(&store, outer, "outer");
+    let mut result = [Value::I32(0)];
+    // The pending call of the asynchronous host function must not resume the
+    // frame of the synchronous host function with its results.
+    assert_matches!(
+        block_on(outer.call_async(&mut store, &[Value::I32(1)], &mut result)),
+        Err(Error::Trap(trap)) if trap.to_string().contains("asynchronous")
+    );
+    assert_matches!(
+        outer.call_resumable(&mut store, &[Value::I32(1)], &mut result),
+        Err(Error::Trap(trap)) if trap.to_string().contains("asynchronous")
+    );
+    // The store remains usable afterwards.
+    block_on(run.call_async(&mut store, &[Value::I32(2)], &mut result)).unwrap();
+    assert_eq!(result, [Value::I32(201)]);
+}
@@ -0,0 +1,194 @@
+//! Tests for Wasm backtraces of traps in `wasmi_v1`.
+
+use wasmi_core::{TrapCode, Value, ValueType};
+use wasmi_v1::{Engine, Error, Extern, Func, FuncType, Linker, Module, Store, Trap};
+
+/// The `call` Wasm opcode.
+const CALL: u8 = 0x10;
+
+/// The `unreachable` Wasm opcode.
+const UNREACHABLE: u8 = 0x00;
+
+/// The `i32.div_s` Wasm opcode.
+const I32_DIV_S: u8 = 0x6D;
+
+/// Compiles and instantiates the Wasm module given in `.wat` format.
+///
+/// The module may import a host function `env.host` of type `[i32] -> []`
+/// that calls the exported function `callback` of the calling instance.
+///
+/// Returns the [`Store`], the Wasm binary and the exported function `run`.
+fn test_setup(wat: &str) -> (Store<()>, Vec<u8>, Func) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let host = Func::new(
+        &mut store,
+        FuncType::new([ValueType::I32], []),
+        |mut caller, inputs, _outputs| {
+            let callback = caller
+                .get_export("callback")
+                .and_then(Extern::into_func)
+                .unwrap();
+            callback.call(&mut caller, inputs, &mut [])?;
+            Ok(())
+        },
+    );
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, wasm, run)
+}
+
+/// Calls `run` with the given `inputs` and returns the resulting [`Trap`].
+///
+/// # Panics
+///
+/// If the call does not result in a [`Trap`].
+fn run_trap(store: &mut Store<()>, run: Func, inputs: &[Value]) -> Trap {
+    match run.call(&mut *store, inputs, &mut []) {
+        Err(Error::Trap(trap)) => trap,
+        unexpected => panic!("expected a trap but found: {:?}", unexpected),
+    }
+}
+
+#[test]
+fn backtrace_of_nested_calls() {
+    let (mut store, wasm, run) = test_setup(
+        r#"
+        (module $test
+            (func $run (export "run")
+                (call $middle)
+            )
+            (func $middle
+                (nop)
+                (call $inner)
+            )
+            (func $inner
+                (unreachable)
+            )
+        )
+        "#,
+    );
+    let trap = run_trap(&mut store, run, &[]);
+    assert_eq!(trap.trap_code(), Some(TrapCode::Unreachable));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 3);
+    let expected = [
+        ("inner", 2, UNREACHABLE),
+        ("middle", 1, CALL),
+        ("run", 0, CALL),
+    ];
+    for (frame, (name, index, opcode)) in frames.iter().zip(expected) {
+        assert_eq!(frame.module_name(), Some("test"));
+        assert_eq!(frame.func_name(), Some(name));
+        assert_eq!(frame.func_index(), index);
+        assert_eq!(wasm[frame.wasm_offset()], opcode);
+    }
+    let inner = frames[0].wasm_offset();
+    let middle = frames[1].wasm_offset();
+    let run = frames[2].wasm_offset();
+    assert_eq!(
+        trap.to_string(),
+        format!(
+            "unreachable\n\
+             wasm backtrace:\n   \
+             0: {inner:#x} - test!inner\n   \
+             1: {middle:#x} - test!middle\n   \
+             2: {run:#x} - test!run"
+        ),
+    );
+}
+
+#[test]
+fn backtrace_without_names() {
+    let (mut store, wasm, run) = test_setup(
+        r#"
+        (module
+            (func (export "run") (param i32)
+                (drop (i32.div_s (i32.const 1) (local.get 0)))
+            )
+        )
+        "#,
+    );
+    let trap = run_trap(&mut store, run, &[Value::I32(0)]);
+    assert_eq!(trap.trap_code(), Some(TrapCode::DivisionByZero));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].module_name(), None);
+    assert_eq!(frames[0].func_name(), None);
+    assert_eq!(frames[0].func_index(), 0);
+    assert_eq!(wasm[frames[0].wasm_offset()], I32_DIV_S);
+    assert_eq!(
+        frames[0].to_string(),
+        format!(
+            "{:#x} - <unknown>!<wasm function 0>",
+            frames[0].wasm_offset()
+        ),
+    );
+}
+
+#[test]
+fn backtrace_includes_frames_of_enclosing_executions() {
+    let (mut store, wasm, run) = test_setup(
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32)))
+            (func $run (export "run") (param i32)
+                (call $host (local.get 0))
+            )
+            (func $callback (export "callback") (param i32)
+                (drop (i32.div_s (i32.const 1) (local.get 0)))
+            )
+        )
+        "#,
+    );
+    let trap = run_trap(&mut store, run, &[Value::I32(0)]);
+    assert_eq!(trap.trap_code(), Some(TrapCode::DivisionByZero));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 2);
+    // The imported host function is the function at index 0.
+    assert_eq!(frames[0].func_name(), Some("callback"));
+    assert_eq!(frames[0].func_index(), 2);
+    assert_eq!(wasm[frames[0].wasm_offset()], I32_DIV_S);
+    assert_eq!(frames[1].func_name(), Some("run"));
+    assert_eq!(frames[1].func_index(), 1);
+    assert_eq!(wasm[frames[1].wasm_offset()], CALL);
+}
+
+#[test]
+fn backtrace_of_stack_overflow() {
+    let (mut store, wasm, run) = test_setup(
+        r#"
+        (module
+            (func $run (export "run")
+                (call $run)
+            )
+        )
+        "#,
+    );
+    let trap = run_trap(&mut store, run, &[]);
+    assert_eq!(trap.trap_code(), Some(TrapCode::StackOverflow));
+    let frames = trap.backtrace().unwrap().frames();
+    assert!(frames.len() > 1);
+    for frame in frames {
+        assert_eq!(frame.func_name(), Some("run"));
+        assert_eq!(wasm[frame.wasm_offset()], CALL);
+    }
+}
+
+#[test]
+fn host_trap_has_no_backtrace() {
+    let trap = Trap::from(TrapCode::Unreachable);
+    assert!(trap.backtrace().is_none());
+    assert_eq!(trap.to_string(), "unreachable");
+}
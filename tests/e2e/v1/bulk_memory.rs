@@ -0,0 +1,272 @@
+//! Tests for the Wasm `bulk-memory` proposal in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{Config, Engine, Error, Extern, Instance, Linker, Memory, Module, Store};
+
+/// A Wasm module using bulk memory operations.
+///
+/// - Data segment `0` is passive and data segment `1` is active.
+/// - Element segment `0` is passive and element segment `1` is declared.
+/// - `call(index)` calls the function at `index` of the table.
+/// - All other exports forward their parameters to the bulk memory
+///   instruction of the same name.
+const WASM: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (table 4 funcref)
+        (data $passive "\01\02\03\04")
+        (data (i32.const 0) "abcd")
+        (elem $funcs func $one $two $three)
+        (elem declare func $one)
+        (func $one (result i32) (i32.const 1))
+        (func $two (result i32) (i32.const 2))
+        (func $three (result i32) (i32.const 3))
+        (func (export "memory.copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "memory.fill") (param $dst i32) (param $value i32) (param $len i32)
+            (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+        )
+        (func (export "memory.init") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init $passive (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "memory.init.active") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init 1 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "memory.init.at_1") (param $dst i32) (param $len i32)
+            (memory.init $passive
+                (local.get $dst)
+                (i32.add (i32.const 0) (i32.const 1))
+                (local.get $len)
+            )
+        )
+        (func (export "data.drop")
+            (data.drop $passive)
+        )
+        (func (export "table.copy") (param $dst i32) (param $src i32) (param $len i32)
+            (table.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "table.init") (param $dst i32) (param $src i32) (param $len i32)
+            (table.init $funcs (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "table.init.declared") (param $dst i32) (param $src i32) (param $len i32)
+            (table.init 1 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "elem.drop")
+            (elem.drop $funcs)
+        )
+        (func (export "call") (param $index i32) (result i32)
+            (call_indirect (result i32) (local.get $index))
+        )
+    )
+"#;
+
+/// Returns the [`Config`] used by the tests.
+fn config() -> Config {
+    Config::default()
+}
+
+/// Instantiates the test Wasm module using the given [`Config`].
+fn instantiate(config: &Config) -> (Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of the `instance` with the given `inputs`.
+fn call(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    inputs: &[i32],
+) -> Result<Option<i32>, Error> {
+    let func = instance
+        .get_export(&*store, name)
+        .and_then(Extern::into_func)
+        .unwrap();
+    let inputs = inputs.iter().copied().map(Value::I32).collect::<Vec<_>>();
+    let mut results = vec![Value::I32(0); func.func_type(&*store).results().len()];
+    func.call(&mut *store, &inputs, &mut results)?;
+    Ok(results.first().map(|result| match result {
+        Value::I32(value) => *value,
+        result => panic!("expected an `i32` result but found: {result:?}"),
+    }))
+}
+
+/// Returns the exported linear memory of the `instance`.
+fn memory(store: &Store<()>, instance: Instance) -> Memory {
+    instance
+        .get_export(store, "memory")
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+/// Asserts that the `result` is a trap with the given `trap_code`.
+fn assert_trap(result: Result<Option<i32>, Error>, trap_code: TrapCode) {
+    assert_matches!(
+        result,
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(trap_code)
+    );
+}
+
+#[test]
+fn bulk_memory_is_enabled_by_default() {
+    assert!(Config::default().bulk_memory());
+    assert!(!Config::mvp().bulk_memory());
+    let wasm = wat::parse_str(WASM).unwrap();
+    assert!(Module::new(&Engine::new(&Config::mvp()), &wasm[..]).is_err());
+    let config = Config::default().enable_bulk_memory(false);
+    assert!(Module::new(&Engine::new(&config), &wasm[..]).is_err());
+}
+
+#[test]
+fn memory_fill_and_copy_work() {
+    let (mut store, instance) = instantiate(&config());
+    let memory = memory(&store, instance);
+    assert_eq!(&memory.data(&store)[..6], b"abcd\0\0");
+    call(&mut store, instance, "memory.fill", &[4, 0x178, 2]).unwrap();
+    assert_eq!(&memory.data(&store)[..7], b"abcdxx\0");
+    // Overlapping copy towards higher addresses.
+    call(&mut store, instance, "memory.copy", &[2, 0, 4]).unwrap();
+    assert_eq!(&memory.data(&store)[..7], b"ababcd\0");
+    // Overlapping copy towards lower addresses.
+    call(&mut store, instance, "memory.copy", &[1, 3, 4]).unwrap();
+    assert_eq!(&memory.data(&store)[..7], b"abcd\0d\0");
+}
+
+#[test]
+fn memory_out_of_bounds_access_traps() {
+    let (mut store, instance) = instantiate(&config());
+    let memory = memory(&store, instance);
+    let len = memory.data(&store).len() as i32;
+    let before = memory.data(&store).to_vec();
+    let out_of_bounds = [
+        ("memory.fill", [len - 1, 0, 2]),
+        ("memory.fill", [len + 1, 0, 0]),
+        ("memory.copy", [len - 1, 0, 2]),
+        ("memory.copy", [0, len - 1, 2]),
+        ("memory.copy", [0, -1, 1]),
+    ];
+    for (name, inputs) in out_of_bounds {
+        assert_trap(
+            call(&mut store, instance, name, &inputs),
+            TrapCode::MemoryAccessOutOfBounds,
+        );
+    }
+    // Out of bounds operations do not modify the linear memory.
+    assert_eq!(memory.data(&store), &before[..]);
+    // Operations of zero length at the end of the linear memory are in bounds.
+    call(&mut store, instance, "memory.fill", &[len, 0, 0]).unwrap();
+    call(&mut store, instance, "memory.copy", &[len, len, 0]).unwrap();
+}
+
+#[test]
+fn memory_init_and_data_drop_work() {
+    let (mut store, instance) = instantiate(&config());
+    let memory = memory(&store, instance);
+    call(&mut store, instance, "memory.init", &[8, 0, 4]).unwrap();
+    assert_eq!(&memory.data(&store)[8..12], &[1, 2, 3, 4]);
+    call(&mut store, instance, "memory.init.at_1", &[16, 3]).unwrap();
+    assert_eq!(&memory.data(&store)[16..20], &[2, 3, 4, 0]);
+    assert_trap(
+        call(&mut store, instance, "memory.init", &[0, 2, 3]),
+        TrapCode::MemoryAccessOutOfBounds,
+    );
+    // Active data segments are dropped upon instantiation.
+    call(&mut store, instance, "memory.init.active", &[0, 0, 0]).unwrap();
+    assert_trap(
+        call(&mut store, instance, "memory.init.active", &[0, 0, 1]),
+        TrapCode::MemoryAccessOutOfBounds,
+    );
+    call(&mut store, instance, "data.drop", &[]).unwrap();
+    call(&mut store, instance, "data.drop", &[]).unwrap();
+    call(&mut store, instance, "memory.init", &[0, 0, 0]).unwrap();
+    assert_trap(
+        call(&mut store, instance, "memory.init", &[0, 0, 1]),
+        TrapCode::MemoryAccessOutOfBounds,
+    );
+}
+
+#[test]
+fn table_init_copy_and_elem_drop_work() {
+    let (mut store, instance) = instantiate(&config());
+    call(&mut store, instance, "table.init", &[1, 0, 3]).unwrap();
+    assert_trap(
+        call(&mut store, instance, "call", &[0]),
+        TrapCode::ElemUninitialized,
+    );
+    for (index, expected) in [(1, 1), (2, 2), (3, 3)] {
+        assert_eq!(
+            call(&mut store, instance, "call", &[index]).unwrap(),
+            Some(expected)
+        );
+    }
+    // Overlapping copy towards lower indices.
+    call(&mut store, instance, "table.copy", &[0, 1, 3]).unwrap();
+    for (index, expected) in [(0, 1), (1, 2), (2, 3), (3, 3)] {
+        assert_eq!(
+            call(&mut store, instance, "call", &[index]).unwrap(),
+            Some(expected)
+        );
+    }
+    assert_trap(
+        call(&mut store, instance, "table.copy", &[2, 0, 3]),
+        TrapCode::TableAccessOutOfBounds,
+    );
+    assert_trap(
+        call(&mut store, instance, "table.init", &[2, 1, 3]),
+        TrapCode::TableAccessOutOfBounds,
+    );
+    // Declared element segments are dropped upon instantiation.
+    call(&mut store, instance, "table.init.declared", &[0, 0, 0]).unwrap();
+    assert_trap(
+        call(&mut store, instance, "table.init.declared", &[0, 0, 1]),
+        TrapCode::TableAccessOutOfBounds,
+    );
+    call(&mut store, instance, "elem.drop", &[]).unwrap();
+    call(&mut store, instance, "table.init", &[4, 0, 0]).unwrap();
+    assert_trap(
+        call(&mut store, instance, "table.init", &[0, 0, 1]),
+        TrapCode::TableAccessOutOfBounds,
+    );
+}
+
+#[test]
+fn dropped_segments_are_per_instance() {
+    let engine = Engine::new(&config());
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut instantiate = || {
+        <Linker<()>>::new()
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+    };
+    let dropped = instantiate();
+    let fresh = instantiate();
+    call(&mut store, dropped, "data.drop", &[]).unwrap();
+    call(&mut store, dropped, "elem.drop", &[]).unwrap();
+    call(&mut store, fresh, "memory.init", &[0, 0, 4]).unwrap();
+    call(&mut store, fresh, "table.init", &[0, 0, 3]).unwrap();
+    assert_eq!(&memory(&store, fresh).data(&store)[..4], &[1, 2, 3, 4]);
+}
+
+#[test]
+fn bulk_memory_works_with_optimizations() {
+    let (mut store, instance) = instantiate(&config().enable_optimizations(true));
+    let memory = memory(&store, instance);
+    call(&mut store, instance, "memory.init.at_1", &[4, 3]).unwrap();
+    call(&mut store, instance, "memory.copy", &[0, 4, 3]).unwrap();
+    call(&mut store, instance, "memory.fill", &[3, 0, 4]).unwrap();
+    assert_eq!(&memory.data(&store)[..8], &[2, 3, 4, 0, 0, 0, 0, 0]);
+}
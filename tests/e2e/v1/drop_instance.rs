@@ -0,0 +1,252 @@
+//! Tests for dropping instances from the store in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use core::fmt;
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{
+    Engine,
+    Error,
+    Extern,
+    Func,
+    HostError,
+    Instance,
+    Linker,
+    Module,
+    ResumableCall,
+    Store,
+    Trap,
+};
+
+/// A host error that allows to resume the execution.
+#[derive(Debug)]
+struct Suspend;
+
+impl fmt::Display for Suspend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "suspended")
+    }
+}
+
+impl HostError for Suspend {
+    fn can_resume(&self) -> bool {
+        true
+    }
+}
+
+/// Instantiates the Wasm module given as `wat` with the definitions of the `linker`.
+fn instantiate(store: &mut Store<()>, linker: &mut Linker<()>, wat: &str) -> Instance {
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Instantiates a Wasm module exporting a linear memory and a function `inc`
+/// that increments its parameter.
+fn test_setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(
+        &mut store,
+        &mut <Linker<()>>::new(),
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "inc") (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+        )
+        "#,
+    );
+    (store, instance)
+}
+
+/// Returns the exported function `name` of the `instance`.
+fn get_func(store: &Store<()>, instance: Instance, name: &str) -> Func {
+    instance
+        .get_export(store, name)
+        .and_then(Extern::into_func)
+        .unwrap()
+}
+
+#[test]
+fn drop_instance_works() {
+    let (mut store, instance) = test_setup();
+    assert!(store.drop_instance(instance));
+    // Dropping an already dropped instance does nothing.
+    assert!(!store.drop_instance(instance));
+    // Other instances of the store are not affected.
+    let other = instantiate(
+        &mut store,
+        &mut <Linker<()>>::new(),
+        r#"(module (func (export "inc") (param i32) (result i32) (local.get 0)))"#,
+    );
+    let inc = get_func(&store, other, "inc");
+    let mut result = [Value::I32(0)];
+    inc.call(&mut store, &[Value::I32(1)], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(1)]);
+}
+
+#[test]
+#[should_panic]
+fn stale_memory_panics() {
+    let (mut store, instance) = test_setup();
+    let memory = instance
+        .get_export(&store, "memory")
+        .and_then(Extern::into_memory)
+        .unwrap();
+    store.drop_instance(instance);
+    memory.current_pages(&store);
+}
+
+#[test]
+#[should_panic]
+fn stale_func_panics() {
+    let (mut store, instance) = test_setup();
+    let inc = get_func(&store, instance, "inc");
+    store.drop_instance(instance);
+    inc.call(&mut store, &[Value::I32(1)], &mut [Value::I32(0)])
+        .unwrap();
+}
+
+#[test]
+#[should_panic]
+fn stale_instance_panics() {
+    let (mut store, instance) = test_setup();
+    store.drop_instance(instance);
+    instance.get_export(&store, "inc");
+}
+
+#[test]
+fn call_to_dropped_instance_traps() {
+    let (mut store, instance) = test_setup();
+    let mut linker = <Linker<()>>::new();
+    linker
+        .define("env", "inc", get_func(&store, instance, "inc"))
+        .unwrap();
+    let importer = instantiate(
+        &mut store,
+        &mut linker,
+        r#"
+        (module
+            (import "env" "inc" (func $inc (param i32) (result i32)))
+            (table funcref (elem $inc))
+            (func (export "call") (param i32) (result i32)
+                (call $inc (local.get 0))
+            )
+            (func (export "call_indirect") (param i32) (result i32)
+                (call_indirect (param i32) (result i32) (local.get 0) (i32.const 0))
+            )
+        )
+        "#,
+    );
+    let call = get_func(&store, importer, "call");
+    let call_indirect = get_func(&store, importer, "call_indirect");
+    let mut result = [Value::I32(0)];
+    call.call(&mut store, &[Value::I32(1)], &mut result)
+        .unwrap();
+    assert_eq!(result, [Value::I32(2)]);
+    store.drop_instance(instance);
+    for func in [call, call_indirect] {
+        assert_matches!(
+            func.call(&mut store, &[Value::I32(1)], &mut result),
+            Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::DroppedInstance)
+        );
+    }
+}
+
+#[test]
+fn resume_dropped_instance_traps() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new();
+    let host = Func::wrap(&mut store, || -> Result<(), Trap> {
+        Err(Trap::host(Suspend))
+    });
+    linker.define("env", "host", host).unwrap();
+    let instance = instantiate(
+        &mut store,
+        &mut linker,
+        r#"
+        (module
+            (import "env" "host" (func $host))
+            (func (export "run") (call $host))
+        )
+        "#,
+    );
+    let run = get_func(&store, instance, "run");
+    let invocation = match run.call_resumable(&mut store, &[], &mut []).unwrap() {
+        ResumableCall::Resumable(invocation) => invocation,
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    };
+    store.drop_instance(instance);
+    assert_matches!(
+        invocation.resume(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::DroppedInstance)
+    );
+}
+
+#[test]
+fn dropped_exporter_keeps_imported_entities_alive() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new();
+    let exporter = instantiate(
+        &mut store,
+        &mut linker,
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (table (export "table") 1 funcref)
+            (global (export "counter") (mut i32) (i32.const 0))
+            (func (export "inc") (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+        )
+        "#,
+    );
+    for name in ["memory", "table", "counter", "inc"] {
+        let export = exporter.get_export(&store, name).unwrap();
+        linker.define("env", name, export).unwrap();
+    }
+    let importer = instantiate(
+        &mut store,
+        &mut linker,
+        r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (import "env" "table" (table 1 funcref))
+            (import "env" "counter" (global $counter (mut i32)))
+            (import "env" "inc" (func $inc (param i32) (result i32)))
+            (func (export "count") (result i32)
+                (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+                (i32.store (i32.const 0) (global.get $counter))
+                (i32.load (i32.const 0))
+            )
+            (func (export "call") (param i32) (result i32)
+                (call $inc (local.get 0))
+            )
+        )
+        "#,
+    );
+    let count = get_func(&store, importer, "count");
+    let call = get_func(&store, importer, "call");
+    let mut result = [Value::I32(0)];
+    count.call(&mut store, &[], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(1)]);
+    store.drop_instance(exporter);
+    // The imported linear memory and global variable are still usable.
+    count.call(&mut store, &[], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(2)]);
+    // Calling an imported function of the dropped instance traps.
+    assert_matches!(
+        call.call(&mut store, &[Value::I32(1)], &mut result),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::DroppedInstance)
+    );
+    count.call(&mut store, &[], &mut result).unwrap();
+    assert_eq!(result, [Value::I32(3)]);
+    assert!(store.drop_instance(importer));
+}
@@ -0,0 +1,199 @@
+//! Tests for fuel metering in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::TrapCode;
+use wasmi_v1::{
+    bytecode::Instruction,
+    errors::FuelError,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    FuelCosts,
+    Func,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Creates a [`Store`] with fuel metering enabled using the given [`FuelCosts`].
+fn test_setup(costs: FuelCosts) -> Store<()> {
+    let config = Config::default()
+        .enable_fuel_metering(true)
+        .with_fuel_costs(costs);
+    let engine = Engine::new(&config);
+    Store::new(&engine, ())
+}
+
+/// Compiles and instantiates the Wasm module given in `.wat` format.
+///
+/// The module may import a host function `env.host` of type `[] -> []`.
+fn instantiate(store: &mut Store<()>, wat: &str) -> wasmi_v1::Instance {
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let host = Func::wrap(&mut *store, || ());
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns the exported function `name` of the `instance`.
+fn get_func(store: &Store<()>, instance: wasmi_v1::Instance, name: &str) -> Func {
+    instance
+        .get_export(store, name)
+        .and_then(Extern::into_func)
+        .unwrap()
+}
+
+/// Runs the exported function `run` of the Wasm module given in `.wat` format.
+///
+/// Returns the result of the execution and the amount of fuel consumed.
+fn run(costs: FuelCosts, fuel: u64, wat: &str) -> (Result<(), Error>, u64) {
+    let mut store = test_setup(costs);
+    let instance = instantiate(&mut store, wat);
+    let func = get_func(&store, instance, "run");
+    store.add_fuel(fuel).unwrap();
+    let result = func.call(&mut store, &[], &mut []);
+    let consumed = store.fuel_consumed().unwrap();
+    assert_eq!(store.fuel_remaining(), Some(fuel - consumed));
+    (result, consumed)
+}
+
+const INFINITE_LOOP: &str = r#"
+    (module
+        (func (export "run")
+            (loop $continue
+                (br $continue)
+            )
+        )
+    )
+"#;
+
+const GROW_MEMORY: &str = r#"
+    (module
+        (memory 0)
+        (func (export "run")
+            (drop (memory.grow (i32.const 3)))
+        )
+    )
+"#;
+
+const FILL_MEMORY: &str = r#"
+    (module
+        (memory 1)
+        (func (export "run")
+            (memory.fill (i32.const 0) (i32.const 42) (i32.const 100))
+        )
+    )
+"#;
+
+const CALL_HOST: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (func (export "run")
+            (call $host)
+        )
+    )
+"#;
+
+#[test]
+fn fuel_api_fails_if_disabled() {
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    assert_eq!(store.add_fuel(1), Err(FuelError::FuelMeteringDisabled));
+    assert_eq!(store.consume_fuel(1), Err(FuelError::FuelMeteringDisabled));
+    assert_eq!(store.fuel_consumed(), None);
+    assert_eq!(store.fuel_remaining(), None);
+}
+
+#[test]
+fn add_and_consume_fuel_works() {
+    let mut store = test_setup(FuelCosts::default());
+    assert_eq!(store.fuel_consumed(), Some(0));
+    assert_eq!(store.fuel_remaining(), Some(0));
+    store.add_fuel(10).unwrap();
+    assert_eq!(store.consume_fuel(3), Ok(7));
+    assert_eq!(store.consume_fuel(8), Err(FuelError::OutOfFuel));
+    assert_eq!(store.fuel_consumed(), Some(3));
+    assert_eq!(store.fuel_remaining(), Some(7));
+}
+
+#[test]
+fn infinite_loop_runs_out_of_fuel() {
+    let (result, consumed) = run(FuelCosts::default(), 1_000, INFINITE_LOOP);
+    assert_matches!(result, Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::OutOfFuel));
+    assert_eq!(consumed, 1_000);
+}
+
+#[test]
+fn custom_instruction_costs_work() {
+    let (result, default_consumed) = run(FuelCosts::default(), 1_000, GROW_MEMORY);
+    assert_matches!(result, Ok(()));
+    let costs = FuelCosts::default().with_instruction_costs(|_| 5);
+    let (result, custom_consumed) = run(costs, 1_000, GROW_MEMORY);
+    assert_matches!(result, Ok(()));
+    assert_eq!(custom_consumed, default_consumed * 5);
+}
+
+#[test]
+fn per_variant_instruction_costs_work() {
+    fn costs(instr: &Instruction) -> u64 {
+        match instr {
+            Instruction::Br(_) => 10,
+            _ => 0,
+        }
+    }
+    let costs = FuelCosts::default().with_instruction_costs(costs);
+    let (result, consumed) = run(costs, 95, INFINITE_LOOP);
+    assert_matches!(result, Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::OutOfFuel));
+    assert_eq!(consumed, 90);
+}
+
+#[test]
+fn memory_grow_costs_work() {
+    let (_, default_consumed) = run(FuelCosts::default(), 1_000, GROW_MEMORY);
+    let costs = FuelCosts::default().with_memory_grow_page_cost(100);
+    let (result, consumed) = run(costs, 1_000, GROW_MEMORY);
+    assert_matches!(result, Ok(()));
+    assert_eq!(consumed, default_consumed + 300);
+    // Not enough fuel to pay for the requested pages.
+    let (result, _) = run(costs, 200, GROW_MEMORY);
+    assert_matches!(result, Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::OutOfFuel));
+}
+
+#[test]
+fn bulk_memory_costs_work() {
+    let (_, default_consumed) = run(FuelCosts::default(), 1_000, FILL_MEMORY);
+    let costs = FuelCosts::default().with_bulk_element_cost(2);
+    let (result, consumed) = run(costs, 1_000, FILL_MEMORY);
+    assert_matches!(result, Ok(()));
+    assert_eq!(consumed, default_consumed + 200);
+    // Not enough fuel to pay for the processed bytes.
+    let (result, _) = run(costs, 150, FILL_MEMORY);
+    assert_matches!(result, Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::OutOfFuel));
+}
+
+#[test]
+fn host_call_costs_work() {
+    let (_, default_consumed) = run(FuelCosts::default(), 1_000, CALL_HOST);
+    let costs = FuelCosts::default().with_host_call_cost(42);
+    let (result, consumed) = run(costs, 1_000, CALL_HOST);
+    assert_matches!(result, Ok(()));
+    assert_eq!(consumed, default_consumed + 42);
+}
+
+#[test]
+fn store_is_usable_after_running_out_of_fuel() {
+    let mut store = test_setup(FuelCosts::default());
+    let instance = instantiate(&mut store, CALL_HOST);
+    let func = get_func(&store, instance, "run");
+    assert_matches!(
+        func.call(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::OutOfFuel)
+    );
+    store.add_fuel(1_000).unwrap();
+    assert_matches!(func.call(&mut store, &[], &mut []), Ok(()));
+}
@@ -0,0 +1,91 @@
+//! Tests for interrupting executions in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use std::{thread, time::Duration};
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{Engine, Error, Extern, Func, InterruptHandle, Linker, Module, Store};
+
+/// Instantiates a Wasm module and returns its exported functions
+/// `loop` which loops infinitely, `recurse` which recurses infinitely
+/// and `add` which adds its two parameters.
+fn test_setup() -> (Store<()>, [Func; 3]) {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "loop")
+                (loop $continue
+                    (br $continue)
+                )
+            )
+            (func $recurse (export "recurse")
+                (call $recurse)
+            )
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let funcs = ["loop", "recurse", "add"].map(|name| {
+        instance
+            .get_export(&store, name)
+            .and_then(Extern::into_func)
+            .unwrap()
+    });
+    (store, funcs)
+}
+
+/// Asserts that `add` can still be called successfully on the `store`.
+fn assert_usable(store: &mut Store<()>, add: Func) {
+    let mut result = [Value::I32(0)];
+    add.call(&mut *store, &[Value::I32(1), Value::I32(2)], &mut result)
+        .unwrap();
+    assert_eq!(result, [Value::I32(3)]);
+}
+
+#[test]
+fn interrupt_handle_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<InterruptHandle>();
+}
+
+#[test]
+fn interrupt_infinite_loop_from_other_thread() {
+    let (mut store, [looping, _, add]) = test_setup();
+    let handle = store.interrupt_handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        handle.interrupt();
+    });
+    assert_matches!(
+        looping.call(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::Interrupted)
+    );
+    interrupter.join().unwrap();
+    assert_usable(&mut store, add);
+}
+
+#[test]
+fn interrupt_before_call_traps_at_function_call() {
+    let (mut store, [_, recurse, add]) = test_setup();
+    store.interrupt_handle().interrupt();
+    assert_matches!(
+        recurse.call(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::Interrupted)
+    );
+    // The interrupt has been consumed by the interrupted execution.
+    assert_usable(&mut store, add);
+    assert_matches!(
+        recurse.call(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::StackOverflow)
+    );
+}
@@ -0,0 +1,160 @@
+//! Tests for lazy compilation of Wasm function bodies in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::{TrapCode, Value, ValueType};
+use wasmi_v1::{Config, Engine, Error, Extern, Func, FuncType, Linker, Module, Store};
+
+/// The `unreachable` Wasm opcode.
+const UNREACHABLE: u8 = 0x00;
+
+/// Creates an [`Engine`] with lazy compilation enabled.
+fn lazy_engine() -> Engine {
+    Engine::new(&Config::default().enable_lazy_compilation(true))
+}
+
+/// Instantiates the Wasm `module` and returns its exported function `name`.
+///
+/// The module may import a host function `env.host` of type `[i32] -> [i32]`
+/// that calls the exported function `callback` of the calling instance.
+fn instantiate(store: &mut Store<()>, module: &Module, name: &str) -> Func {
+    let host = Func::new(
+        &mut *store,
+        FuncType::new([ValueType::I32], [ValueType::I32]),
+        |mut caller, inputs, outputs| {
+            let callback = caller
+                .get_export("callback")
+                .and_then(Extern::into_func)
+                .unwrap();
+            callback.call(&mut caller, inputs, outputs)?;
+            Ok(())
+        },
+    );
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    linker
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+        .get_export(&*store, name)
+        .and_then(Extern::into_func)
+        .unwrap()
+}
+
+/// Calls `func` with the single `input` and returns its single result.
+fn call_i32(store: &mut Store<()>, func: Func, input: i32) -> Result<Value, Error> {
+    let mut results = [Value::I32(0)];
+    func.call(&mut *store, &[Value::I32(input)], &mut results)?;
+    Ok(results[0])
+}
+
+const FIB: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (func $fib (export "fib") (param $n i32) (result i32)
+            (if (result i32) (i32.lt_s (local.get $n) (i32.const 2))
+                (then (local.get $n))
+                (else
+                    (i32.add
+                        (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                        (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                    )
+                )
+            )
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $host (local.get 0))
+        )
+        (func (export "callback") (param i32) (result i32)
+            (call $fib (local.get 0))
+        )
+        (func $never_called (param i32) (result i32)
+            (i32.mul (local.get 0) (local.get 0))
+        )
+    )
+"#;
+
+#[test]
+fn lazy_compilation_works() {
+    let wasm = wat::parse_str(FIB).unwrap();
+    let engine = lazy_engine();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let fib = instantiate(&mut store, &module, "fib");
+    assert_eq!(call_i32(&mut store, fib, 20).unwrap(), Value::I32(6765));
+    // Calling again uses the function bodies translated during the first call.
+    assert_eq!(call_i32(&mut store, fib, 10).unwrap(), Value::I32(55));
+}
+
+#[test]
+fn lazy_compilation_through_host_functions() {
+    let wasm = wat::parse_str(FIB).unwrap();
+    let engine = lazy_engine();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let run = instantiate(&mut store, &module, "run");
+    assert_eq!(call_i32(&mut store, run, 15).unwrap(), Value::I32(610));
+}
+
+#[test]
+fn lazy_compilation_shared_by_instances() {
+    let wasm = wat::parse_str(FIB).unwrap();
+    let engine = lazy_engine();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store1 = Store::new(&engine, ());
+    let mut store2 = Store::new(&engine, ());
+    let fib1 = instantiate(&mut store1, &module, "fib");
+    let fib2 = instantiate(&mut store2, &module, "fib");
+    let run2 = instantiate(&mut store2, &module, "run");
+    assert_eq!(call_i32(&mut store1, fib1, 12).unwrap(), Value::I32(144));
+    assert_eq!(call_i32(&mut store2, fib2, 12).unwrap(), Value::I32(144));
+    assert_eq!(call_i32(&mut store2, run2, 13).unwrap(), Value::I32(233));
+}
+
+#[test]
+fn lazy_compilation_validates_eagerly() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "run") (result i32)
+                (i64.const 0)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    assert!(Module::new(&lazy_engine(), &wasm[..]).is_err());
+}
+
+#[test]
+fn lazy_compilation_traps_with_backtrace() {
+    let wasm = wat::parse_str(
+        r#"
+        (module $test
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func $run (export "run") (param i32) (result i32)
+                (call $inner (local.get 0))
+            )
+            (func $inner (param i32) (result i32)
+                (if (local.get 0)
+                    (then (unreachable))
+                )
+                (local.get 0)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = lazy_engine();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let run = instantiate(&mut store, &module, "run");
+    assert_eq!(call_i32(&mut store, run, 0).unwrap(), Value::I32(0));
+    let trap = assert_matches!(call_i32(&mut store, run, 1), Err(Error::Trap(trap)) => trap);
+    assert_eq!(trap.trap_code(), Some(TrapCode::Unreachable));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].func_name(), Some("inner"));
+    assert_eq!(wasm[frames[0].wasm_offset()], UNREACHABLE);
+    assert_eq!(frames[1].func_name(), Some("run"));
+}
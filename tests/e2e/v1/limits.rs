@@ -0,0 +1,280 @@
+//! Tests for the resource limiter of the store in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use std::sync::{Arc, Mutex};
+use wasmi_core::{memory_units::Pages, TrapCode, Value};
+use wasmi_v1::{
+    errors::{InstantiationError, MemoryError, TableError},
+    Engine,
+    Error,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    ResourceLimiter,
+    Store,
+    Table,
+    TableType,
+};
+
+/// A request reported to the [`TestLimiter`].
+#[derive(Debug, PartialEq, Eq)]
+enum Request {
+    Memory(usize, usize, Option<usize>),
+    Table(usize, usize, Option<usize>),
+    Instance(usize),
+}
+
+/// A [`ResourceLimiter`] limiting the total amount of linear memory pages.
+///
+/// Traps if more than `trap_pages` are requested and records all requests.
+#[derive(Debug, Default, Clone)]
+struct TestLimiter {
+    max_pages: usize,
+    trap_pages: usize,
+    max_table_elements: usize,
+    max_instances: usize,
+    total_pages: usize,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestLimiter {
+    fn new() -> Self {
+        Self {
+            max_pages: 4,
+            trap_pages: 10,
+            max_table_elements: 10,
+            max_instances: 2,
+            ..Self::default()
+        }
+    }
+
+    fn take_requests(&self) -> Vec<Request> {
+        core::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+impl ResourceLimiter for TestLimiter {
+    fn memory_growing(
+        &mut self,
+        current: Pages,
+        desired: Pages,
+        maximum: Option<Pages>,
+    ) -> Result<bool, TrapCode> {
+        self.requests.lock().unwrap().push(Request::Memory(
+            current.0,
+            desired.0,
+            maximum.map(|pages| pages.0),
+        ));
+        let total_pages = self.total_pages - current.0 + desired.0;
+        if total_pages > self.trap_pages {
+            return Err(TrapCode::ResourceLimitExceeded);
+        }
+        if total_pages > self.max_pages {
+            return Ok(false);
+        }
+        self.total_pages = total_pages;
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<bool, TrapCode> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(Request::Table(current, desired, maximum));
+        Ok(desired <= self.max_table_elements)
+    }
+
+    fn instance_creating(&mut self, current: usize) -> Result<bool, TrapCode> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(Request::Instance(current));
+        Ok(current < self.max_instances)
+    }
+}
+
+/// Creates a [`Store`] with a [`TestLimiter`].
+fn test_setup() -> (Store<()>, TestLimiter) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let limiter = TestLimiter::new();
+    store.set_limiter(limiter.clone());
+    (store, limiter)
+}
+
+/// Instantiates the Wasm module given as `wat`.
+fn instantiate(store: &mut Store<()>, wat: &str) -> Result<Instance, Error> {
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    <Linker<()>>::new()
+        .instantiate(&mut *store, &module)
+        .and_then(|pre| pre.start(&mut *store))
+}
+
+/// A Wasm module with a linear memory of one page exporting `grow`.
+const GROW_MEMORY: &str = r#"
+    (module
+        (memory 1)
+        (func (export "grow") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+    )
+"#;
+
+#[test]
+fn memory_grow_works() {
+    let (mut store, limiter) = test_setup();
+    let instance = instantiate(&mut store, GROW_MEMORY).unwrap();
+    let grow = instance
+        .get_export(&store, "grow")
+        .and_then(Extern::into_func)
+        .unwrap();
+    let grow = |store: &mut Store<()>, delta: i32| {
+        let mut result = [Value::I32(0)];
+        grow.call(store, &[Value::I32(delta)], &mut result)
+            .map(|_| result[0])
+    };
+    assert_eq!(grow(&mut store, 2).unwrap(), Value::I32(1));
+    // The growth is denied and returns `-1`.
+    assert_eq!(grow(&mut store, 2).unwrap(), Value::I32(-1));
+    // The growth traps.
+    assert_matches!(
+        grow(&mut store, 10),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::ResourceLimitExceeded)
+    );
+    // A growth by zero pages is not reported.
+    assert_eq!(grow(&mut store, 0).unwrap(), Value::I32(3));
+    assert_eq!(
+        limiter.take_requests(),
+        [
+            Request::Instance(0),
+            Request::Memory(0, 1, None),
+            Request::Memory(1, 3, None),
+            Request::Memory(3, 5, None),
+            Request::Memory(3, 13, None),
+        ]
+    );
+}
+
+#[test]
+fn memory_grow_beyond_maximum_is_not_reported() {
+    let (mut store, limiter) = test_setup();
+    let memory = Memory::new(&mut store, MemoryType::new(1, Some(2))).unwrap();
+    assert_matches!(
+        memory.grow(&mut store, Pages(2)),
+        Err(MemoryError::OutOfBoundsGrowth)
+    );
+    assert_eq!(limiter.take_requests(), [Request::Memory(0, 1, Some(2))]);
+}
+
+#[test]
+fn memory_api_works() {
+    let (mut store, limiter) = test_setup();
+    let memory = Memory::new(&mut store, MemoryType::new(2, None)).unwrap();
+    assert_matches!(
+        memory.grow(&mut store, Pages(3)),
+        Err(MemoryError::ResourceLimitReached)
+    );
+    assert_matches!(
+        memory.grow(&mut store, Pages(9)),
+        Err(MemoryError::ResourceLimiterTrap(
+            TrapCode::ResourceLimitExceeded
+        ))
+    );
+    assert_eq!(memory.current_pages(&store), Pages(2));
+    assert_matches!(
+        Memory::new(&mut store, MemoryType::new(3, None)),
+        Err(MemoryError::ResourceLimitReached)
+    );
+    assert_eq!(
+        limiter.take_requests(),
+        [
+            Request::Memory(0, 2, None),
+            Request::Memory(2, 5, None),
+            Request::Memory(2, 11, None),
+            Request::Memory(0, 3, None),
+        ]
+    );
+    // Without the limiter all requests are allowed.
+    store.clear_limiter();
+    memory.grow(&mut store, Pages(9)).unwrap();
+    Memory::new(&mut store, MemoryType::new(3, None)).unwrap();
+    assert!(limiter.take_requests().is_empty());
+}
+
+#[test]
+fn table_api_works() {
+    let (mut store, limiter) = test_setup();
+    let table = Table::new(&mut store, TableType::new(5, Some(20))).unwrap();
+    table.grow(&mut store, 5).unwrap();
+    assert_matches!(
+        table.grow(&mut store, 1),
+        Err(TableError::ResourceLimitReached)
+    );
+    assert_eq!(table.len(&store), 10);
+    assert_matches!(
+        Table::new(&mut store, TableType::new(11, None)),
+        Err(TableError::ResourceLimitReached)
+    );
+    assert_eq!(
+        limiter.take_requests(),
+        [
+            Request::Table(0, 5, Some(20)),
+            Request::Table(5, 10, Some(20)),
+            Request::Table(10, 11, Some(20)),
+            Request::Table(0, 11, None),
+        ]
+    );
+}
+
+#[test]
+fn instantiation_is_limited() {
+    let (mut store, limiter) = test_setup();
+    instantiate(&mut store, "(module)").unwrap();
+    let instance = instantiate(&mut store, "(module)").unwrap();
+    assert_matches!(
+        instantiate(&mut store, "(module)"),
+        Err(Error::Instantiation(
+            InstantiationError::ResourceLimitReached
+        ))
+    );
+    // Dropped instances no longer count towards the limit.
+    store.drop_instance(instance);
+    instantiate(&mut store, "(module)").unwrap();
+    // Denied linear memories and tables fail the instantiation.
+    let (mut store, limiter2) = test_setup();
+    assert_matches!(
+        instantiate(&mut store, "(module (memory 5))"),
+        Err(Error::Memory(MemoryError::ResourceLimitReached))
+    );
+    assert_matches!(
+        instantiate(&mut store, "(module (table 11 funcref))"),
+        Err(Error::Table(TableError::ResourceLimitReached))
+    );
+    assert_eq!(
+        limiter.take_requests(),
+        [
+            Request::Instance(0),
+            Request::Instance(1),
+            Request::Instance(2),
+            Request::Instance(1),
+        ]
+    );
+    assert_eq!(
+        limiter2.take_requests(),
+        [
+            Request::Instance(0),
+            Request::Memory(0, 5, None),
+            Request::Instance(0),
+            Request::Table(0, 11, None),
+        ]
+    );
+}
@@ -1 +1,16 @@
+mod async_func;
+mod backtrace;
+mod bulk_memory;
+mod drop_instance;
+mod fuel;
 mod func;
+mod interrupt;
+mod lazy;
+mod limits;
+mod nan;
+mod reentrancy;
+mod resumable;
+mod serialize;
+mod stack;
+mod tail_call;
+mod trap;
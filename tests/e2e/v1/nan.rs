@@ -0,0 +1,287 @@
+//! Tests for NaN canonicalization in `wasmi_v1`.
+
+use wasmi_core::Value;
+use wasmi_v1::{Config, Engine, Extern, Instance, Linker, Module, Store};
+
+/// The bits of the canonical `f32` NaN.
+const F32_CANONICAL_NAN: u32 = 0x7FC0_0000;
+
+/// The bits of the canonical `f64` NaN.
+const F64_CANONICAL_NAN: u64 = 0x7FF8_0000_0000_0000;
+
+/// The bits of a negative `f32` NaN with a non-canonical payload.
+const F32_NAN: u32 = 0xFFC0_0123;
+
+/// The bits of a negative `f64` NaN with a non-canonical payload.
+const F64_NAN: u64 = 0xFFF8_0000_0000_0123;
+
+/// Wasm functions operating on the bits of float values.
+///
+/// Every function reinterprets its integer parameters as float values,
+/// applies the float instruction of its name and returns the bits of its result.
+const WAT: &str = r#"
+    (module
+        (func (export "f32.add") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.add (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.sub") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.sub (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.mul") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.mul (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.div") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.div (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.min") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.min (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.max") (param i32 i32) (result i32)
+            (i32.reinterpret_f32 (f32.max (f32.reinterpret_i32 (local.get 0)) (f32.reinterpret_i32 (local.get 1)))))
+        (func (export "f32.sqrt") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.sqrt (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.ceil") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.ceil (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.floor") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.floor (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.trunc") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.trunc (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.nearest") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.nearest (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.neg") (param i32) (result i32)
+            (i32.reinterpret_f32 (f32.neg (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.demote_f64") (param i64) (result i32)
+            (i32.reinterpret_f32 (f32.demote_f64 (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.add") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.add (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.sub") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.sub (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.mul") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.mul (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.div") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.div (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.min") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.min (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.max") (param i64 i64) (result i64)
+            (i64.reinterpret_f64 (f64.max (f64.reinterpret_i64 (local.get 0)) (f64.reinterpret_i64 (local.get 1)))))
+        (func (export "f64.sqrt") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.sqrt (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.ceil") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.ceil (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.floor") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.floor (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.trunc") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.trunc (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.nearest") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.nearest (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.neg") (param i64) (result i64)
+            (i64.reinterpret_f64 (f64.neg (f64.reinterpret_i64 (local.get 0)))))
+        (func (export "f64.promote_f32") (param i32) (result i64)
+            (i64.reinterpret_f64 (f64.promote_f32 (f32.reinterpret_i32 (local.get 0)))))
+        (func (export "f32.div_consts") (result i32)
+            (i32.reinterpret_f32 (f32.div (f32.const 0) (f32.const 0))))
+        (func (export "f64.div_consts") (result i64)
+            (i64.reinterpret_f64 (f64.div (f64.const 0) (f64.const 0))))
+    )
+"#;
+
+/// Instantiates the [`WAT`] module using the given [`Config`].
+fn setup(config: &Config) -> (Store<()>, Instance) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` with `inputs` and returns its single result.
+fn call(store: &mut Store<()>, instance: Instance, name: &str, inputs: &[Value]) -> Value {
+    let func = instance
+        .get_export(&*store, name)
+        .and_then(Extern::into_func)
+        .unwrap();
+    let mut results = [Value::I32(0)];
+    func.call(&mut *store, inputs, &mut results).unwrap();
+    results[0]
+}
+
+/// Returns the bits of the `f32` result of `name` for the `f32` inputs given as bits.
+fn call_f32(store: &mut Store<()>, instance: Instance, name: &str, inputs: &[u32]) -> u32 {
+    let inputs = inputs
+        .iter()
+        .map(|bits| Value::I32(*bits as i32))
+        .collect::<Vec<_>>();
+    let result: i32 = call(store, instance, name, &inputs).try_into().unwrap();
+    result as u32
+}
+
+/// Returns the bits of the `f64` result of `name` for the `f64` inputs given as bits.
+fn call_f64(store: &mut Store<()>, instance: Instance, name: &str, inputs: &[u64]) -> u64 {
+    let inputs = inputs
+        .iter()
+        .map(|bits| Value::I64(*bits as i64))
+        .collect::<Vec<_>>();
+    let result: i64 = call(store, instance, name, &inputs).try_into().unwrap();
+    result as u64
+}
+
+#[test]
+fn nan_canonicalization_is_disabled_by_default() {
+    assert!(!Config::default().nan_canonicalization());
+    let (mut store, instance) = setup(&Config::default());
+    let one = 1.0_f32.to_bits();
+    // Without canonicalization the NaN result preserves the sign and payload of its NaN input.
+    assert_eq!(
+        call_f32(&mut store, instance, "f32.add", &[F32_NAN, one]),
+        F32_NAN
+    );
+    let one = 1.0_f64.to_bits();
+    assert_eq!(
+        call_f64(&mut store, instance, "f64.add", &[F64_NAN, one]),
+        F64_NAN
+    );
+}
+
+#[test]
+fn nan_canonicalization_canonicalizes_f32_results() {
+    let config = Config::default().enable_nan_canonicalization(true);
+    let (mut store, instance) = setup(&config);
+    let one = 1.0_f32.to_bits();
+    for name in [
+        "f32.add", "f32.sub", "f32.mul", "f32.div", "f32.min", "f32.max",
+    ] {
+        assert_eq!(
+            call_f32(&mut store, instance, name, &[F32_NAN, one]),
+            F32_CANONICAL_NAN,
+            "{name}"
+        );
+        assert_eq!(
+            call_f32(&mut store, instance, name, &[one, F32_NAN]),
+            F32_CANONICAL_NAN,
+            "{name}"
+        );
+    }
+    for name in [
+        "f32.sqrt",
+        "f32.ceil",
+        "f32.floor",
+        "f32.trunc",
+        "f32.nearest",
+    ] {
+        assert_eq!(
+            call_f32(&mut store, instance, name, &[F32_NAN]),
+            F32_CANONICAL_NAN,
+            "{name}"
+        );
+    }
+    // NaN results computed from non-NaN inputs are canonicalized as well.
+    let zero = 0.0_f32.to_bits();
+    assert_eq!(
+        call_f32(&mut store, instance, "f32.div", &[zero, zero]),
+        F32_CANONICAL_NAN
+    );
+    assert_eq!(
+        call_f32(&mut store, instance, "f32.sqrt", &[(-1.0_f32).to_bits()]),
+        F32_CANONICAL_NAN
+    );
+    let demoted = call(
+        &mut store,
+        instance,
+        "f32.demote_f64",
+        &[Value::I64(F64_NAN as i64)],
+    );
+    assert_eq!(demoted, Value::I32(F32_CANONICAL_NAN as i32));
+}
+
+#[test]
+fn nan_canonicalization_canonicalizes_f64_results() {
+    let config = Config::default().enable_nan_canonicalization(true);
+    let (mut store, instance) = setup(&config);
+    let one = 1.0_f64.to_bits();
+    for name in [
+        "f64.add", "f64.sub", "f64.mul", "f64.div", "f64.min", "f64.max",
+    ] {
+        assert_eq!(
+            call_f64(&mut store, instance, name, &[F64_NAN, one]),
+            F64_CANONICAL_NAN,
+            "{name}"
+        );
+        assert_eq!(
+            call_f64(&mut store, instance, name, &[one, F64_NAN]),
+            F64_CANONICAL_NAN,
+            "{name}"
+        );
+    }
+    for name in [
+        "f64.sqrt",
+        "f64.ceil",
+        "f64.floor",
+        "f64.trunc",
+        "f64.nearest",
+    ] {
+        assert_eq!(
+            call_f64(&mut store, instance, name, &[F64_NAN]),
+            F64_CANONICAL_NAN,
+            "{name}"
+        );
+    }
+    // NaN results computed from non-NaN inputs are canonicalized as well.
+    let zero = 0.0_f64.to_bits();
+    assert_eq!(
+        call_f64(&mut store, instance, "f64.div", &[zero, zero]),
+        F64_CANONICAL_NAN
+    );
+    assert_eq!(
+        call_f64(&mut store, instance, "f64.sqrt", &[(-1.0_f64).to_bits()]),
+        F64_CANONICAL_NAN
+    );
+    let promoted = call(
+        &mut store,
+        instance,
+        "f64.promote_f32",
+        &[Value::I32(F32_NAN as i32)],
+    );
+    assert_eq!(promoted, Value::I64(F64_CANONICAL_NAN as i64));
+}
+
+#[test]
+fn nan_canonicalization_preserves_other_results() {
+    let config = Config::default().enable_nan_canonicalization(true);
+    let (mut store, instance) = setup(&config);
+    // Non-NaN results are not affected.
+    let sum = call_f32(
+        &mut store,
+        instance,
+        "f32.add",
+        &[1.5_f32.to_bits(), 2.0_f32.to_bits()],
+    );
+    assert_eq!(sum, 3.5_f32.to_bits());
+    let sum = call_f64(
+        &mut store,
+        instance,
+        "f64.add",
+        &[1.5_f64.to_bits(), 2.0_f64.to_bits()],
+    );
+    assert_eq!(sum, 3.5_f64.to_bits());
+    // Instructions operating on the sign bit only are not canonicalized.
+    assert_eq!(
+        call_f32(&mut store, instance, "f32.neg", &[F32_NAN]),
+        F32_NAN & !(1 << 31)
+    );
+    assert_eq!(
+        call_f64(&mut store, instance, "f64.neg", &[F64_NAN]),
+        F64_NAN & !(1 << 63)
+    );
+}
+
+#[test]
+fn nan_canonicalization_applies_to_constant_folding() {
+    let config = Config::default()
+        .enable_nan_canonicalization(true)
+        .enable_optimizations(true);
+    let (mut store, instance) = setup(&config);
+    let result = call(&mut store, instance, "f32.div_consts", &[]);
+    assert_eq!(result, Value::I32(F32_CANONICAL_NAN as i32));
+    let result = call(&mut store, instance, "f64.div_consts", &[]);
+    assert_eq!(result, Value::I64(F64_CANONICAL_NAN as i64));
+}
@@ -0,0 +1,119 @@
+//! Tests for host functions calling back into Wasm in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::{TrapCode, Value, ValueType};
+use wasmi_v1::{Engine, Error, Extern, Func, FuncType, Linker, Module, Store};
+
+/// Instantiates a Wasm module importing `env.host` of type `[i32] -> [i32]`.
+///
+/// The module exports the following functions:
+///
+/// - `run(n)`: Returns `0` if `n` is `0` and `host(n - 1) + 1` otherwise.
+/// - `recurse()`: Recurses infinitely and thus overflows the call stack.
+/// - `trap()`: Traps immediately.
+///
+/// The host function calls the exported function of the calling instance
+/// that is selected by `select` with its input.
+fn test_setup(select: fn(i32) -> &'static str) -> (Store<()>, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let host = Func::new(
+        &mut store,
+        FuncType::new([ValueType::I32], [ValueType::I32]),
+        move |mut caller, inputs, outputs| {
+            let input = match inputs[0] {
+                Value::I32(value) => value,
+                _ => unreachable!(),
+            };
+            let name = select(input);
+            let func = caller.get_export(name).and_then(Extern::into_func).unwrap();
+            match name {
+                "run" => func.call(&mut caller, inputs, outputs)?,
+                _ => func.call(&mut caller, &[], &mut [])?,
+            }
+            Ok(())
+        },
+    );
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func (export "run") (param $n i32) (result i32)
+                (if (result i32) (i32.eqz (local.get $n))
+                    (then (i32.const 0))
+                    (else
+                        (i32.add
+                            (call $host (i32.sub (local.get $n) (i32.const 1)))
+                            (i32.const 1)
+                        )
+                    )
+                )
+            )
+            (func $recurse (export "recurse")
+                (call $recurse)
+            )
+            (func (export "trap")
+                (unreachable)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, run)
+}
+
+/// Calls `run` with the given input and returns its result.
+fn call_run(store: &mut Store<()>, run: Func, input: i32) -> Result<i32, Error> {
+    let mut result = [Value::I32(0)];
+    run.call(&mut *store, &[Value::I32(input)], &mut result)?;
+    match result[0] {
+        Value::I32(value) => Ok(value),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn host_calls_back_into_wasm() {
+    let (mut store, run) = test_setup(|_| "run");
+    assert_eq!(call_run(&mut store, run, 0).unwrap(), 0);
+    assert_eq!(call_run(&mut store, run, 1).unwrap(), 1);
+    assert_eq!(call_run(&mut store, run, 100).unwrap(), 100);
+}
+
+#[test]
+fn nested_call_respects_call_stack_limit() {
+    let (mut store, run) = test_setup(|input| match input {
+        0 => "recurse",
+        _ => "run",
+    });
+    assert_matches!(
+        call_run(&mut store, run, 10),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::StackOverflow)
+    );
+}
+
+#[test]
+fn store_is_usable_after_nested_trap() {
+    let (mut store, run) = test_setup(|input| match input {
+        5 => "trap",
+        _ => "run",
+    });
+    assert_matches!(
+        call_run(&mut store, run, 10),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::Unreachable)
+    );
+    assert_eq!(call_run(&mut store, run, 5).unwrap(), 5);
+    assert_eq!(call_run(&mut store, run, 3).unwrap(), 3);
+}
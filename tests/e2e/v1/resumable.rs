@@ -0,0 +1,226 @@
+//! Tests for resumable function calls in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use core::fmt;
+use wasmi_core::{TrapCode, Value, ValueType};
+use wasmi_v1::{
+    Engine,
+    Error,
+    Extern,
+    Func,
+    FuncType,
+    HostError,
+    Linker,
+    Module,
+    ResumableCall,
+    ResumableInvocation,
+    Store,
+    Trap,
+};
+
+#[derive(Debug)]
+enum TestError {
+    /// Asks to suspend the execution with the given host function parameter.
+    Suspend(i32),
+    /// A host error that does not allow to resume the execution.
+    Fatal,
+}
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Suspend(input) => write!(f, "suspended with input {}", input),
+            Self::Fatal => write!(f, "fatal host error"),
+        }
+    }
+}
+
+impl HostError for TestError {
+    fn can_resume(&self) -> bool {
+        matches!(self, Self::Suspend(_))
+    }
+}
+
+/// Instantiates a Wasm module exporting `run` which calls the imported `env.host`.
+///
+/// `run(x)` returns `host(x) + host(x + 1)`.
+/// The host function suspends on even inputs, fails on negative inputs and
+/// returns its input multiplied by 10 otherwise.
+fn test_setup() -> (Store<()>, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let host = Func::new(
+        &mut store,
+        FuncType::new([ValueType::I32], [ValueType::I32]),
+        |_caller, inputs, outputs| {
+            let input = match inputs[0] {
+                Value::I32(value) => value,
+                _ => unreachable!(),
+            };
+            if input < 0 {
+                return Err(Trap::host(TestError::Fatal));
+            }
+            if input % 2 == 0 {
+                return Err(Trap::host(TestError::Suspend(input)));
+            }
+            outputs[0] = Value::I32(input * 10);
+            Ok(())
+        },
+    );
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func (export "run") (param $x i32) (result i32)
+                (i32.add
+                    (call $host (local.get $x))
+                    (call $host (i32.add (local.get $x) (i32.const 1)))
+                )
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, run)
+}
+
+/// Unwraps the [`ResumableInvocation`] and asserts that it was suspended with `expected`.
+fn unwrap_suspended(call: ResumableCall, expected: i32) -> ResumableInvocation {
+    match call {
+        ResumableCall::Resumable(invocation) => {
+            assert_matches!(
+                invocation.host_error().downcast_ref(),
+                Some(TestError::Suspend(input)) if *input == expected
+            );
+            invocation
+        }
+        ResumableCall::Finished => panic!("expected the call to be suspended"),
+    }
+}
+
+#[test]
+fn call_with_late_suspension_works() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(1)], &mut result)
+        .unwrap();
+    // Note: host(1) == 10 and host(2) suspends.
+    let invocation = unwrap_suspended(call, 2);
+    let call = invocation
+        .resume(&mut store, &[Value::I32(20)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(30)]);
+}
+
+#[test]
+fn call_with_early_suspension_works() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(4)], &mut result)
+        .unwrap();
+    let invocation = unwrap_suspended(call, 4);
+    assert_eq!(
+        invocation.func().func_type(&store).results(),
+        [ValueType::I32]
+    );
+    let call = invocation
+        .resume(&mut store, &[Value::I32(100)], &mut result)
+        .unwrap();
+    // Note: host(5) == 50 and does not suspend.
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(150)]);
+}
+
+#[test]
+fn engine_is_usable_while_suspended() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(4)], &mut result)
+        .unwrap();
+    let invocation = unwrap_suspended(call, 4);
+    // Use the engine for another suspending call in the meantime.
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(8)], &mut result)
+        .unwrap();
+    let other = unwrap_suspended(call, 8);
+    let call = invocation
+        .resume(&mut store, &[Value::I32(1)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(51)]);
+    let call = other
+        .resume(&mut store, &[Value::I32(2)], &mut result)
+        .unwrap();
+    assert_matches!(call, ResumableCall::Finished);
+    assert_eq!(result, [Value::I32(92)]);
+}
+
+#[test]
+fn non_resumable_error_is_returned() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        run.call_resumable(&mut store, &[Value::I32(-5)], &mut result),
+        Err(Error::Trap(trap)) if matches!(trap.downcast_ref(), Some(TestError::Fatal))
+    );
+}
+
+#[test]
+fn call_returns_resumable_error() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        run.call(&mut store, &[Value::I32(2)], &mut result),
+        Err(Error::Trap(trap)) if matches!(trap.downcast_ref(), Some(TestError::Suspend(2)))
+    );
+}
+
+#[test]
+fn resume_with_mismatching_types_fails() {
+    let (mut store, run) = test_setup();
+    let mut result = [Value::I32(0)];
+    let call = run
+        .call_resumable(&mut store, &[Value::I32(4)], &mut result)
+        .unwrap();
+    let invocation = unwrap_suspended(call, 4);
+    assert_matches!(
+        invocation.resume(&mut store, &[Value::I64(1)], &mut result),
+        Err(Error::Func(_))
+    );
+}
+
+#[test]
+fn host_func_with_mismatching_results_traps() {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let host = Func::new(
+        &mut store,
+        FuncType::new([], [ValueType::I32]),
+        |_caller, _inputs, outputs| {
+            outputs[0] = Value::I64(1);
+            Ok(())
+        },
+    );
+    let mut result = [Value::I32(0)];
+    assert_matches!(
+        host.call(&mut store, &[], &mut result),
+        Err(Error::Trap(trap))
+            if trap.trap_code() == Some(TrapCode::UnexpectedSignature)
+    );
+}
@@ -0,0 +1,326 @@
+//! Tests for serialization and deserialization of `wasmi_v1` modules.
+
+use assert_matches::assert_matches;
+use wasmi_core::{TrapCode, Value, ValueType};
+use wasmi_v1::{
+    errors::{DeserializeError, ModuleError},
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    FuncType,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The `unreachable` Wasm opcode.
+const UNREACHABLE: u8 = 0x00;
+
+/// The length of the magic bytes of serialized modules.
+const MAGIC_LEN: usize = 8;
+
+const WAT: &str = r#"
+    (module $test
+        (import "env" "double" (func $double (param i32) (result i32)))
+        (type $binop (func (param i32 i32) (result i32)))
+        (memory (export "memory") 1 2)
+        (data (i32.const 8) "\2A\00\00\00")
+        (table 2 funcref)
+        (elem (i32.const 0) $add $sub)
+        (global $counter (mut i32) (i32.const 0))
+        (global $pi f64 (f64.const 3.14))
+        (func $add (type $binop) (i32.add (local.get 0) (local.get 1)))
+        (func $sub (type $binop) (i32.sub (local.get 0) (local.get 1)))
+        (func $start
+            (global.set $counter (i32.const 100))
+        )
+        (start $start)
+        (func (export "run") (param $op i32) (param $n i32) (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (call $double
+                (call_indirect (type $binop)
+                    (i32.load (i32.const 8))
+                    (local.get $n)
+                    (local.get $op)
+                )
+            )
+        )
+        (func (export "counter") (result i32)
+            (global.get $counter)
+        )
+        (func (export "pi") (result f64)
+            (global.get $pi)
+        )
+        (func $trap (export "trap")
+            (unreachable)
+        )
+    )
+"#;
+
+/// Serializes the Wasm module given in `.wat` format compiled with `config`.
+///
+/// Returns the Wasm binary and the serialized module.
+fn serialize(config: &Config, wat: &str) -> (Vec<u8>, Vec<u8>) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    (wasm, module.serialize())
+}
+
+/// Deserializes `bytes` into a [`Module`] for a new [`Engine`] with `config`.
+fn deserialize(config: &Config, bytes: &[u8]) -> Result<Module, Error> {
+    let engine = Engine::new(config);
+    // Safety: the bytes are either created by `Module::serialize`
+    //         or rejected upon deserialization.
+    unsafe { Module::deserialize(&engine, bytes) }
+}
+
+/// Instantiates the `module` and returns the [`Store`] and the exported function getter.
+fn instantiate(module: &Module) -> (Store<()>, impl Fn(&Store<()>, &str) -> Func) {
+    let mut store = Store::new(module.engine(), ());
+    let double = Func::wrap(&mut store, |value: i32| value * 2);
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "double", double).unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get = move |store: &Store<()>, name: &str| {
+        instance
+            .get_export(store, name)
+            .and_then(Extern::into_func)
+            .unwrap()
+    };
+    (store, get)
+}
+
+/// Calls `func` with `inputs` and returns its single result.
+fn call(store: &mut Store<()>, func: Func, inputs: &[Value]) -> Result<Value, Error> {
+    let ty = func.func_type(&*store);
+    let mut results = ty
+        .results()
+        .iter()
+        .copied()
+        .map(Value::default)
+        .collect::<Vec<_>>();
+    func.call(&mut *store, inputs, &mut results)?;
+    Ok(results[0])
+}
+
+fn assert_roundtrip_works(config: &Config) {
+    let (wasm, bytes) = serialize(config, WAT);
+    let module = deserialize(&Config::default(), &bytes).unwrap();
+    // Serializing the deserialized module yields the same bytes.
+    assert_eq!(module.serialize(), bytes);
+    let (mut store, get) = instantiate(&module);
+    let run = get(&store, "run");
+    let counter = get(&store, "counter");
+    assert_eq!(call(&mut store, counter, &[]).unwrap(), Value::I32(100));
+    let add = [Value::I32(0), Value::I32(8)];
+    let sub = [Value::I32(1), Value::I32(2)];
+    assert_eq!(call(&mut store, run, &add).unwrap(), Value::I32(100));
+    assert_eq!(call(&mut store, run, &sub).unwrap(), Value::I32(80));
+    assert_eq!(call(&mut store, counter, &[]).unwrap(), Value::I32(102));
+    let pi = get(&store, "pi");
+    assert_eq!(call(&mut store, pi, &[]).unwrap(), Value::F64(3.14.into()));
+    // The original Wasm offsets and function names are preserved.
+    let trap = get(&store, "trap");
+    let trap = assert_matches!(call(&mut store, trap, &[]), Err(Error::Trap(trap)) => trap);
+    assert_eq!(trap.trap_code(), Some(TrapCode::Unreachable));
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].func_name(), Some("trap"));
+    assert_eq!(wasm[frames[0].wasm_offset()], UNREACHABLE);
+}
+
+#[test]
+fn roundtrip_works() {
+    assert_roundtrip_works(&Config::default())
+}
+
+#[test]
+fn roundtrip_of_lazily_compiled_module_works() {
+    assert_roundtrip_works(&Config::default().enable_lazy_compilation(true))
+}
+
+#[test]
+fn roundtrip_preserves_func_types() {
+    let (_, bytes) = serialize(&Config::default(), WAT);
+    let module = deserialize(&Config::default(), &bytes).unwrap();
+    let (store, get) = instantiate(&module);
+    assert_eq!(
+        get(&store, "run").func_type(&store),
+        FuncType::new([ValueType::I32; 2], [ValueType::I32])
+    );
+    assert_eq!(
+        get(&store, "pi").func_type(&store),
+        FuncType::new([], [ValueType::F64])
+    );
+}
+
+#[test]
+fn roundtrip_preserves_bulk_memory_segments() {
+    const WAT: &str = r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (data $passive "\2A\00\00\00")
+            (data (i32.const 0) "\01\00\00\00")
+            (elem $funcs func $answer)
+            (elem declare func $answer)
+            (func $answer (result i32) (i32.const 42))
+            (func (export "run") (result i32)
+                (memory.init $passive (i32.const 4) (i32.const 0) (i32.const 4))
+                (table.init $funcs (i32.const 0) (i32.const 0) (i32.const 1))
+                (i32.add
+                    (i32.load (i32.const 0))
+                    (i32.add
+                        (i32.load (i32.const 4))
+                        (call_indirect (result i32) (i32.const 0))
+                    )
+                )
+            )
+        )
+    "#;
+    let (_, bytes) = serialize(&Config::default(), WAT);
+    let module = deserialize(&Config::default(), &bytes).unwrap();
+    assert_eq!(module.serialize(), bytes);
+    let (mut store, get) = instantiate(&module);
+    let run = get(&store, "run");
+    assert_eq!(call(&mut store, run, &[]).unwrap(), Value::I32(85));
+}
+
+#[test]
+fn version_mismatch_is_rejected() {
+    let (_, mut bytes) = serialize(&Config::default(), WAT);
+    // The format version directly follows the magic bytes.
+    bytes[MAGIC_LEN] = bytes[MAGIC_LEN].wrapping_add(1);
+    assert_matches!(
+        deserialize(&Config::default(), &bytes),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::VersionMismatch { .. }
+        )))
+    );
+}
+
+#[test]
+fn crate_version_mismatch_is_rejected() {
+    let (_, mut bytes) = serialize(&Config::default(), WAT);
+    // The crate version follows the format version and its length.
+    let version = MAGIC_LEN + 4 + 8;
+    bytes[version] = b'X';
+    assert_matches!(
+        deserialize(&Config::default(), &bytes),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::VersionMismatch { .. }
+        )))
+    );
+}
+
+#[test]
+fn config_mismatch_is_rejected() {
+    let configs = [
+        Config::default().enable_tail_call(true),
+        Config::default().enable_bulk_memory(false),
+        Config::default().enable_nan_canonicalization(true),
+        Config::mvp(),
+    ];
+    for config in &configs {
+        let (_, bytes) = serialize(config, WAT);
+        assert_matches!(
+            deserialize(&Config::default(), &bytes),
+            Err(Error::Module(ModuleError::Deserialize(
+                DeserializeError::VersionMismatch { .. }
+            )))
+        );
+        let (_, bytes) = serialize(&Config::default(), WAT);
+        assert_matches!(
+            deserialize(config, &bytes),
+            Err(Error::Module(ModuleError::Deserialize(
+                DeserializeError::VersionMismatch { .. }
+            )))
+        );
+    }
+}
+
+#[test]
+fn floating_point_module_is_rejected_without_floating_point() {
+    const INT_WAT: &str = r#"
+        (module
+            (func (export "run") (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+        )
+    "#;
+    let config = Config::default().enable_floating_point(false);
+    assert!(Module::new(&Engine::new(&config), &wat::parse_str(WAT).unwrap()[..]).is_err());
+    let (_, bytes) = serialize(&Config::default(), WAT);
+    assert_matches!(
+        deserialize(&config, &bytes),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::VersionMismatch { .. }
+        )))
+    );
+    let (_, bytes) = serialize(&config, INT_WAT);
+    let module = deserialize(&config, &bytes).unwrap();
+    assert_eq!(module.serialize(), bytes);
+}
+
+#[test]
+fn config_without_translation_effect_is_accepted() {
+    let configs = [
+        Config::default().enable_optimizations(true),
+        Config::default().enable_lazy_compilation(true),
+        Config::default().enable_fuel_metering(true),
+    ];
+    let (_, bytes) = serialize(&Config::default(), WAT);
+    for config in &configs {
+        let module = deserialize(config, &bytes).unwrap();
+        assert_eq!(module.serialize(), bytes);
+    }
+}
+
+#[test]
+fn invalid_magic_is_rejected() {
+    let (wasm, _) = serialize(&Config::default(), WAT);
+    assert_matches!(
+        deserialize(&Config::default(), &wasm),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::InvalidMagic
+        )))
+    );
+    assert_matches!(
+        deserialize(&Config::default(), &[]),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::InvalidMagic
+        )))
+    );
+}
+
+#[test]
+fn truncated_input_is_rejected() {
+    let (_, bytes) = serialize(&Config::default(), WAT);
+    for len in MAGIC_LEN..bytes.len() {
+        assert_matches!(
+            deserialize(&Config::default(), &bytes[..len]),
+            Err(Error::Module(ModuleError::Deserialize(_))),
+            "unexpectedly deserialized module truncated to {} bytes",
+            len,
+        );
+    }
+}
+
+#[test]
+fn trailing_bytes_are_rejected() {
+    let (_, mut bytes) = serialize(&Config::default(), WAT);
+    bytes.push(0x00);
+    assert_matches!(
+        deserialize(&Config::default(), &bytes),
+        Err(Error::Module(ModuleError::Deserialize(
+            DeserializeError::Malformed { .. }
+        )))
+    );
+}
@@ -0,0 +1,128 @@
+//! Tests for the execution stacks of `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use std::{sync::Arc, thread};
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{Config, Engine, Error, Extern, Func, Linker, Module, Store};
+
+/// A Wasm module exporting `recurse(n)` which recurses `n` times
+/// and `fib(n)` which iteratively computes the `n`-th Fibonacci number.
+const WASM: &str = r#"
+    (module
+        (func $recurse (export "recurse") (param $n i32)
+            (if (i32.ne (local.get $n) (i32.const 0))
+                (then
+                    (call $recurse (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+        )
+        (func (export "fib") (param $n i64) (result i64)
+            (local $a i64)
+            (local $b i64)
+            (local $tmp i64)
+            (local.set $b (i64.const 1))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i64.eqz (local.get $n)))
+                    (local.set $tmp (i64.add (local.get $a) (local.get $b)))
+                    (local.set $a (local.get $b))
+                    (local.set $b (local.get $tmp))
+                    (local.set $n (i64.sub (local.get $n) (i64.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $a)
+        )
+    )
+"#;
+
+/// Compiles the test Wasm module using the given [`Engine`].
+fn compile(engine: &Engine) -> Module {
+    let wasm = wat::parse_str(WASM).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Instantiates the `module` within a new [`Store`] and returns its exported function `name`.
+fn instantiate(engine: &Engine, module: &Module, name: &str) -> (Store<()>, Func) {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new()
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_export(&store, name)
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, func)
+}
+
+/// Computes the `n`-th Fibonacci number using the exported `fib` function.
+fn fib(store: &mut Store<()>, func: Func, n: i64) -> i64 {
+    let mut result = [Value::I64(0)];
+    func.call(&mut *store, &[Value::I64(n)], &mut result)
+        .unwrap();
+    match result[0] {
+        Value::I64(value) => value,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn call_stack_limit_works() {
+    let config = Config::default().set_call_stack_limit(10);
+    assert_eq!(config.call_stack_limit(), 10);
+    let engine = Engine::new(&config);
+    let module = compile(&engine);
+    let (mut store, recurse) = instantiate(&engine, &module, "recurse");
+    assert_matches!(recurse.call(&mut store, &[Value::I32(9)], &mut []), Ok(()));
+    assert_matches!(
+        recurse.call(&mut store, &[Value::I32(20)], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::StackOverflow)
+    );
+    // The store is still usable after the stack overflow.
+    assert_matches!(recurse.call(&mut store, &[Value::I32(5)], &mut []), Ok(()));
+}
+
+#[test]
+fn value_stack_limit_works() {
+    let config = Config::default().set_value_stack_limit(100);
+    assert_eq!(config.value_stack_limit(), 100);
+    let engine = Engine::new(&config);
+    let module = compile(&engine);
+    let (mut store, recurse) = instantiate(&engine, &module, "recurse");
+    assert_matches!(recurse.call(&mut store, &[Value::I32(10)], &mut []), Ok(()));
+    assert_matches!(
+        recurse.call(&mut store, &[Value::I32(100)], &mut []),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::StackOverflow)
+    );
+}
+
+#[test]
+fn stores_sharing_engine_execute_on_threads() {
+    let engine = Engine::default();
+    let module = Arc::new(compile(&engine));
+    let expected = {
+        let (mut store, func) = instantiate(&engine, &module, "fib");
+        fib(&mut store, func, 90)
+    };
+    let workers = (0..4)
+        .map(|_| {
+            let engine = engine.clone();
+            let module = Arc::clone(&module);
+            thread::spawn(move || {
+                let (mut store, func) = instantiate(&engine, &module, "fib");
+                (0..100)
+                    .map(|_| fib(&mut store, func, 90))
+                    .all(|result| result == expected)
+            })
+        })
+        .collect::<Vec<_>>();
+    // Compiling while other threads execute must not interfere with them.
+    let other = compile(&engine);
+    let (mut store, func) = instantiate(&engine, &other, "fib");
+    assert_eq!(fib(&mut store, func, 90), expected);
+    for worker in workers {
+        assert!(worker.join().unwrap());
+    }
+}
@@ -0,0 +1,198 @@
+//! Tests for the Wasm `tail-call` proposal in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{Config, Engine, Error, Extern, Func, Instance, Linker, Module, Store};
+
+/// A Wasm module using tail calls.
+///
+/// - `even(n)` and `odd(n)` are mutually tail recursive where `odd` tail
+///   calls `even` indirectly via the table.
+/// - `sum(n, acc)` tail recursively sums up all values from `1` up to `n`.
+/// - `recurse(n)` recurses `n` times without tail calls.
+/// - `double(n)` tail calls the imported host function `env.double`.
+/// - `call_table(index, n)` tail calls the function at `index` of the table.
+const WASM: &str = r#"
+    (module
+        (import "env" "double" (func $double (param i32) (result i32)))
+        (type $unary (func (param i32) (result i32)))
+        (table funcref (elem $even $odd $double $nullary))
+        (func $even (export "even") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 1))
+                (else (return_call $odd (i32.sub (local.get $n) (i32.const 1))))
+            )
+        )
+        (func $odd (export "odd") (param $n i32) (result i32)
+            (local $unused i64)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (return_call_indirect (type $unary)
+                        (i32.sub (local.get $n) (i32.const 1))
+                        (i32.const 0)
+                    )
+                )
+            )
+        )
+        (func $sum (export "sum") (param $n i64) (param $acc i64) (result i64)
+            (if (result i64) (i64.eqz (local.get $n))
+                (then (local.get $acc))
+                (else
+                    (return_call $sum
+                        (i64.sub (local.get $n) (i64.const 1))
+                        (i64.add (local.get $acc) (local.get $n))
+                    )
+                )
+            )
+        )
+        (func $recurse (export "recurse") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else (call $recurse (i32.sub (local.get $n) (i32.const 1))))
+            )
+        )
+        (func (export "double") (param $n i32) (result i32)
+            (return_call $double (local.get $n))
+        )
+        (func (export "call_table") (param $index i32) (param $n i32) (result i32)
+            (return_call_indirect (type $unary) (local.get $n) (local.get $index))
+        )
+        (func $nullary (result i32)
+            (i32.const 0)
+        )
+    )
+"#;
+
+/// The call stack limit of the tests.
+///
+/// Tail recursion far exceeds this limit while plain recursion does not.
+const CALL_STACK_LIMIT: usize = 100;
+
+/// Returns the [`Config`] used by the tests with the `tail-call` proposal enabled.
+fn config() -> Config {
+    Config::default()
+        .enable_tail_call(true)
+        .set_call_stack_limit(CALL_STACK_LIMIT)
+}
+
+/// Instantiates the test Wasm module using the given [`Config`].
+fn instantiate(config: &Config) -> (Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let double = Func::wrap(&mut store, |n: i32| n * 2);
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "double", double).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of the `instance` with the given `inputs`.
+fn call(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    inputs: &[Value],
+) -> Result<Value, Error> {
+    let func = instance
+        .get_export(&*store, name)
+        .and_then(Extern::into_func)
+        .unwrap();
+    let mut results = [Value::I32(0)];
+    func.call(&mut *store, inputs, &mut results)?;
+    Ok(results[0].clone())
+}
+
+#[test]
+fn tail_call_is_disabled_by_default() {
+    assert!(!Config::default().tail_call());
+    assert!(config().tail_call());
+    let wasm = wat::parse_str(WASM).unwrap();
+    assert!(Module::new(&Engine::default(), &wasm[..]).is_err());
+}
+
+#[test]
+fn mutual_tail_recursion_runs_in_constant_call_stack() {
+    let (mut store, instance) = instantiate(&config());
+    let n = 100_000;
+    assert_eq!(
+        call(&mut store, instance, "even", &[Value::I32(n)]).unwrap(),
+        Value::I32(1)
+    );
+    assert_eq!(
+        call(&mut store, instance, "odd", &[Value::I32(n)]).unwrap(),
+        Value::I32(0)
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            instance,
+            "sum",
+            &[Value::I64(n.into()), Value::I64(0)]
+        )
+        .unwrap(),
+        Value::I64(5_000_050_000)
+    );
+    // Plain recursion of the same depth exceeds the call stack limit.
+    assert_matches!(
+        call(&mut store, instance, "recurse", &[Value::I32(n)]),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::StackOverflow)
+    );
+}
+
+#[test]
+fn tail_recursion_works_with_lazy_compilation() {
+    let (mut store, instance) = instantiate(&config().enable_lazy_compilation(true));
+    assert_eq!(
+        call(&mut store, instance, "odd", &[Value::I32(1001)]).unwrap(),
+        Value::I32(1)
+    );
+}
+
+#[test]
+fn tail_call_of_host_func_works() {
+    let (mut store, instance) = instantiate(&config());
+    assert_eq!(
+        call(&mut store, instance, "double", &[Value::I32(21)]).unwrap(),
+        Value::I32(42)
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            instance,
+            "call_table",
+            &[Value::I32(2), Value::I32(5)]
+        )
+        .unwrap(),
+        Value::I32(10)
+    );
+}
+
+#[test]
+fn return_call_indirect_traps() {
+    let (mut store, instance) = instantiate(&config());
+    assert_eq!(
+        call(
+            &mut store,
+            instance,
+            "call_table",
+            &[Value::I32(0), Value::I32(10)]
+        )
+        .unwrap(),
+        Value::I32(1)
+    );
+    assert_matches!(
+        call(&mut store, instance, "call_table", &[Value::I32(3), Value::I32(0)]),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::UnexpectedSignature)
+    );
+    assert_matches!(
+        call(&mut store, instance, "call_table", &[Value::I32(4), Value::I32(0)]),
+        Err(Error::Trap(trap)) if trap.trap_code() == Some(TrapCode::TableAccessOutOfBounds)
+    );
+}
@@ -0,0 +1,140 @@
+//! Tests for traps returned by host functions in `wasmi_v1`.
+
+use assert_matches::assert_matches;
+use core::fmt;
+use wasmi_core::{TrapCode, Value};
+use wasmi_v1::{Engine, Error, Extern, Func, HostError, Linker, Module, Store, Trap};
+
+/// A custom host error carrying a payload.
+#[derive(Debug, PartialEq, Eq)]
+struct MyError(i32);
+
+impl fmt::Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "my error: {}", self.0)
+    }
+}
+
+impl HostError for MyError {}
+
+/// Instantiates a Wasm module exporting `run` which calls the imported `env.host`
+/// with its parameter.
+///
+/// The host function is of type `[i32] -> []` and traps depending on its input:
+///
+/// - `0`: traps with [`TrapCode::Unreachable`].
+/// - `1`: exits with the exit status `42`.
+/// - `2`: traps with the host error [`MyError`].
+/// - Otherwise it returns successfully.
+fn test_setup() -> (Store<()>, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let host = Func::wrap(&mut store, |input: i32| -> Result<(), Trap> {
+        match input {
+            0 => Err(TrapCode::Unreachable.into()),
+            1 => Err(Trap::i32_exit(42)),
+            2 => Err(Trap::host(MyError(input))),
+            _ => Ok(()),
+        }
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32)))
+            (func (export "run") (param i32)
+                (call $host (local.get 0))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new();
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(Extern::into_func)
+        .unwrap();
+    (store, run)
+}
+
+/// Calls `run` with `input` and returns the resulting [`Trap`].
+///
+/// # Panics
+///
+/// If the call does not result in a [`Trap`].
+fn run_trap(store: &mut Store<()>, run: Func, input: i32) -> Trap {
+    match run.call(&mut *store, &[Value::I32(input)], &mut []) {
+        Err(Error::Trap(trap)) => trap,
+        unexpected => panic!("expected a trap but found: {:?}", unexpected),
+    }
+}
+
+#[test]
+fn host_trap_code_works() {
+    let (mut store, run) = test_setup();
+    let trap = run_trap(&mut store, run, 0);
+    assert_eq!(trap.trap_code(), Some(TrapCode::Unreachable));
+    assert_eq!(trap.i32_exit_status(), None);
+    assert!(trap.as_host().is_none());
+}
+
+#[test]
+fn host_i32_exit_works() {
+    let (mut store, run) = test_setup();
+    let trap = run_trap(&mut store, run, 1);
+    assert_eq!(trap.i32_exit_status(), Some(42));
+    assert_eq!(trap.trap_code(), None);
+    assert!(trap
+        .to_string()
+        .starts_with("exited with i32 exit status 42"));
+}
+
+#[test]
+fn host_error_downcast_works() {
+    let (mut store, run) = test_setup();
+    let mut trap = run_trap(&mut store, run, 2);
+    assert_eq!(trap.trap_code(), None);
+    assert_eq!(trap.i32_exit_status(), None);
+    assert_eq!(trap.downcast_ref::<MyError>(), Some(&MyError(2)));
+    assert_eq!(trap.downcast_ref::<Error>().map(|_| ()), None);
+    trap.downcast_mut::<MyError>().unwrap().0 = 5;
+    let error = trap.into_host().unwrap();
+    assert!(error.is::<MyError>());
+    assert_eq!(error.downcast_ref::<MyError>(), Some(&MyError(5)));
+    assert_eq!(error.to_string(), "my error: 5");
+}
+
+#[test]
+fn host_trap_has_backtrace_of_caller() {
+    let (mut store, run) = test_setup();
+    let trap = run_trap(&mut store, run, 2);
+    let frames = trap.backtrace().unwrap().frames();
+    assert_eq!(frames.len(), 1);
+    // The imported host function is the function at index 0.
+    assert_eq!(frames[0].func_index(), 1);
+}
+
+#[test]
+fn host_call_without_trap_works() {
+    let (mut store, run) = test_setup();
+    assert_matches!(run.call(&mut store, &[Value::I32(3)], &mut []), Ok(()));
+}
+
+#[test]
+fn host_trap_from_host_call_has_no_backtrace() {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let host = Func::wrap(&mut store, || -> Result<(), Trap> {
+        Err(Trap::i32_exit(1))
+    });
+    assert_matches!(
+        host.call(&mut store, &[], &mut []),
+        Err(Error::Trap(trap)) if trap.i32_exit_status() == Some(1) && trap.backtrace().is_none()
+    );
+}
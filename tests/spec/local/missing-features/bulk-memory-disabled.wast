@@ -0,0 +1,88 @@
+(assert_invalid
+  (module
+    (memory 1)
+    (func (param i32 i32 i32)
+      local.get 0
+      local.get 1
+      local.get 2
+      memory.copy
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (memory 1)
+    (func (param i32 i32 i32)
+      local.get 0
+      local.get 1
+      local.get 2
+      memory.fill
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (memory 1)
+    (data "abc")
+    (func (param i32 i32 i32)
+      local.get 0
+      local.get 1
+      local.get 2
+      memory.init 0
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (memory 1)
+    (data "abc")
+    (func
+      data.drop 0
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (table 1 funcref)
+    (func (param i32 i32 i32)
+      local.get 0
+      local.get 1
+      local.get 2
+      table.copy
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (table 1 funcref)
+    (elem func 0)
+    (func (param i32 i32 i32)
+      local.get 0
+      local.get 1
+      local.get 2
+      table.init 0
+    )
+  )
+  "bulk memory support is not enabled"
+)
+
+(assert_invalid
+  (module
+    (table 1 funcref)
+    (elem func 0)
+    (func
+      elem.drop 0
+    )
+  )
+  "bulk memory support is not enabled"
+)
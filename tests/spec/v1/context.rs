@@ -29,7 +29,7 @@ pub struct TestContext<'a> {
     /// The linker for linking together Wasm test modules.
     linker: Linker<()>,
     /// The store to hold all runtime data during the test.
-    store: Store<(), TestError>,
+    store: Store<()>,
     /// The list of all encountered Wasm modules belonging to the test.
     modules: Vec<Module>,
     /// The list of all instantiated modules.
@@ -53,7 +53,7 @@ impl<'a> TestContext<'a> {
         let mut linker = Linker::default();
         let mut store = Store::new(&engine, ());
         let default_memory = Memory::new(&mut store, MemoryType::new(1, Some(2))).unwrap();
-        let default_table = Table::new(&mut store, TableType::new(10, Some(20)));
+        let default_table = Table::new(&mut store, TableType::new(10, Some(20))).unwrap();
         let global_i32 = Global::new(&mut store, Value::I32(666), Mutability::Const);
         let global_f32 = Global::new(&mut store, Value::F32(666.0.into()), Mutability::Const);
         let global_f64 = Global::new(&mut store, Value::F64(666.0.into()), Mutability::Const);
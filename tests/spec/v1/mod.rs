@@ -46,6 +46,7 @@ mod missing_features {
         fn wasm_mutable_global("missing-features/mutable-global-disabled");
         fn wasm_sign_extension("missing-features/sign-extension-disabled");
         fn wasm_saturating_float_to_int("missing-features/saturating-float-to-int-disabled");
+        fn wasm_bulk_memory("missing-features/bulk-memory-disabled");
     }
 }
 
@@ -113,6 +114,123 @@ mod multi_value {
     }
 }
 
+mod optimizations {
+    use super::Config;
+
+    /// Run Wasm spec test suite using translation-time optimizations enabled.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_optimizations(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_block("block");
+        fn wasm_br("br");
+        fn wasm_br_if("br_if");
+        fn wasm_br_table("br_table");
+        fn wasm_break_drop("break-drop");
+        fn wasm_call("call");
+        fn wasm_const("const");
+        fn wasm_f32_bitwise("f32_bitwise");
+        fn wasm_f32_cmp("f32_cmp");
+        fn wasm_f64_bitwise("f64_bitwise");
+        fn wasm_f64_cmp("f64_cmp");
+        fn wasm_fac("fac");
+        fn wasm_float_memory("float_memory");
+        fn wasm_i32("i32");
+        fn wasm_i64("i64");
+        fn wasm_if("if");
+        fn wasm_int_exprs("int_exprs");
+        fn wasm_labels("labels");
+        fn wasm_left_to_right("left-to-right");
+        fn wasm_local_get("local_get");
+        fn wasm_local_set("local_set");
+        fn wasm_local_tee("local_tee");
+        fn wasm_loop("loop");
+        fn wasm_memory_trap("memory_trap");
+        fn wasm_return("return");
+        fn wasm_select("select");
+        fn wasm_stack("stack");
+        fn wasm_switch("switch");
+        fn wasm_traps("traps");
+        fn wasm_unwind("unwind");
+    }
+}
+
+mod lazy_compilation {
+    use super::Config;
+
+    /// Run Wasm spec test suite using lazy compilation of function bodies.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_lazy_compilation(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_block("block");
+        fn wasm_br_table("br_table");
+        fn wasm_call("call");
+        fn wasm_call_indirect("call_indirect");
+        fn wasm_fac("fac");
+        fn wasm_func("func");
+        fn wasm_func_ptrs("func_ptrs");
+        fn wasm_if("if");
+        fn wasm_imports("imports");
+        fn wasm_left_to_right("left-to-right");
+        fn wasm_local_tee("local_tee");
+        fn wasm_loop("loop");
+        fn wasm_memory_trap("memory_trap");
+        fn wasm_nop("nop");
+        fn wasm_return("return");
+        fn wasm_stack("stack");
+        fn wasm_start("start");
+        fn wasm_switch("switch");
+        fn wasm_traps("traps");
+        fn wasm_unreachable("unreachable");
+        fn wasm_unwind("unwind");
+    }
+}
+
+mod parallel_compilation {
+    use super::Config;
+
+    /// Run Wasm spec test suite using parallel compilation of function bodies.
+    fn run_wasm_spec_test(file_name: &str) {
+        let config = Config::mvp()
+            .enable_mutable_global(true)
+            .enable_parallel_compilation(true);
+        super::run::run_wasm_spec_test(file_name, config)
+    }
+
+    define_spec_tests! {
+        fn wasm_address("address");
+        fn wasm_block("block");
+        fn wasm_br_table("br_table");
+        fn wasm_call("call");
+        fn wasm_call_indirect("call_indirect");
+        fn wasm_fac("fac");
+        fn wasm_func("func");
+        fn wasm_func_ptrs("func_ptrs");
+        fn wasm_if("if");
+        fn wasm_imports("imports");
+        fn wasm_left_to_right("left-to-right");
+        fn wasm_loop("loop");
+        fn wasm_memory_trap("memory_trap");
+        fn wasm_nop("nop");
+        fn wasm_return("return");
+        fn wasm_stack("stack");
+        fn wasm_start("start");
+        fn wasm_switch("switch");
+        fn wasm_traps("traps");
+        fn wasm_unreachable("unreachable");
+        fn wasm_unwind("unwind");
+    }
+}
+
 define_spec_tests! {
     fn wasm_address("address");
     fn wasm_align("align");
@@ -200,10 +200,10 @@ fn execute_directives(wast: Wast, test_context: &mut TestContext) -> Result<()>
 /// - If the trap message of the `error` is not as expected.
 fn assert_trap(test_context: &TestContext, span: Span, error: TestError, message: &str) {
     match error {
-        TestError::Wasmi(WasmiError::Trap(trap_code)) => {
+        TestError::Wasmi(WasmiError::Trap(trap)) => {
             assert_eq!(
-                trap_code.trap_message(),
-                message,
+                trap.trap_code().map(|code| code.trap_message()),
+                Some(message),
                 "{}: the directive trapped as expected but with an unexpected message",
                 test_context.spanned(span),
             );
@@ -1,14 +1,19 @@
 use super::{Arena, Index, Iter, IterMut};
-use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::ops;
 
 /// A deduplicating arena allocator with a given index and entity type.
 ///
-/// For performance reasons the arena cannot deallocate single entities.
+/// # Note
+///
+/// Deallocated entities keep occupying their slot until the slot
+/// is reused by a later allocation of a new entity.
 #[derive(Debug)]
 pub struct DedupArena<Idx, T> {
     entity2idx: BTreeMap<T, Idx>,
     entities: Arena<Idx, T>,
+    /// The indices of deallocated entities that can be reused.
+    free: Vec<Idx>,
 }
 
 impl<Idx, T> Default for DedupArena<Idx, T> {
@@ -34,12 +39,17 @@ impl<Idx, T> DedupArena<Idx, T> {
         Self {
             entity2idx: BTreeMap::new(),
             entities: Arena::new(),
+            free: Vec::new(),
         }
     }
 
     /// Returns the allocated number of entities.
+    ///
+    /// # Note
+    ///
+    /// Deallocated entities are not included.
     pub fn len(&self) -> usize {
-        self.entities.len()
+        self.entity2idx.len()
     }
 
     /// Returns `true` if the [`Arena`] has not yet allocated entities.
@@ -51,6 +61,7 @@ impl<Idx, T> DedupArena<Idx, T> {
     pub fn clear(&mut self) {
         self.entity2idx.clear();
         self.entities.clear();
+        self.free.clear();
     }
 
     /// Returns an iterator over the shared reference of the [`Arena`] entities.
@@ -78,16 +89,46 @@ where
     ///
     /// # Note
     ///
-    /// Only allocates if the entity does not already exist in the [`DedupArena`].
+    /// - Only allocates if the entity does not already exist in the [`DedupArena`].
+    /// - Reuses the slot of a deallocated entity if any.
     pub fn alloc(&mut self, entity: T) -> Idx {
         match self.entity2idx.get(&entity) {
             Some(index) => *index,
-            None => {
-                let index = self.next_index();
-                self.entity2idx.insert(entity.clone(), index);
-                self.entities.alloc(entity);
-                index
-            }
+            None => match self.free.pop() {
+                Some(index) => {
+                    self.entity2idx.insert(entity.clone(), index);
+                    self.entities[index] = entity;
+                    index
+                }
+                None => {
+                    let index = self.next_index();
+                    self.entity2idx.insert(entity.clone(), index);
+                    self.entities.alloc(entity);
+                    index
+                }
+            },
+        }
+    }
+
+    /// Deallocates the entity at the given index.
+    ///
+    /// # Note
+    ///
+    /// - The index may be returned by later allocations of different entities.
+    /// - Does nothing if there is no allocated entity at the given index.
+    pub fn dealloc(&mut self, index: Idx) {
+        let entity = match self.entities.get(index) {
+            Some(entity) => entity,
+            None => return,
+        };
+        let is_allocated = self
+            .entity2idx
+            .get(entity)
+            .map(|allocated| allocated.into_usize() == index.into_usize())
+            .unwrap_or(false);
+        if is_allocated {
+            self.entity2idx.remove(entity);
+            self.free.push(index);
         }
     }
 
@@ -119,6 +160,7 @@ where
         Self {
             entity2idx,
             entities,
+            free: Vec::new(),
         }
     }
 }
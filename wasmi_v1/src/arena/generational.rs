@@ -0,0 +1,168 @@
+use super::Index;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// An index of a [`GenerationalArena`] entity paired with the generation of its slot.
+///
+/// # Note
+///
+/// The generation of a slot is incremented whenever its entity is removed.
+/// Therefore indices of removed entities are rejected even if their slot
+/// has been reused by another entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GenerationalIdx<Idx> {
+    index: Idx,
+    generation: u32,
+}
+
+impl<Idx> GenerationalIdx<Idx>
+where
+    Idx: Copy,
+{
+    /// Returns the index of the slot of the entity.
+    #[cfg(any(test, feature = "profiler"))]
+    pub fn index(&self) -> Idx {
+        self.index
+    }
+}
+
+/// A slot of a [`GenerationalArena`].
+#[derive(Debug)]
+struct Slot<T> {
+    /// The generation of the slot.
+    generation: u32,
+    /// The entity of the slot or `None` if the entity has been removed.
+    entity: Option<T>,
+}
+
+/// An arena allocator with a given index and entity type that can remove single entities.
+///
+/// # Note
+///
+/// The slots of removed entities are reused by later allocations.
+/// Slots that exhausted their generations are no longer reused.
+#[derive(Debug)]
+pub struct GenerationalArena<Idx, T> {
+    slots: Vec<Slot<T>>,
+    /// The indices of the free slots.
+    free: Vec<usize>,
+    /// The number of allocated entities.
+    len: usize,
+    __marker: PhantomData<fn() -> Idx>,
+}
+
+impl<Idx, T> Default for GenerationalArena<Idx, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Idx, T> GenerationalArena<Idx, T> {
+    /// Creates a new empty generational entity arena.
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            __marker: PhantomData,
+        }
+    }
+
+    /// Returns the allocated number of entities.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the arena has no allocated entities.
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<Idx, T> GenerationalArena<Idx, T>
+where
+    Idx: Index,
+{
+    /// Allocates a new entity and returns its index.
+    pub fn alloc(&mut self, entity: T) -> GenerationalIdx<Idx> {
+        self.len += 1;
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].entity = Some(entity);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entity: Some(entity),
+                });
+                self.slots.len() - 1
+            }
+        };
+        GenerationalIdx {
+            index: Idx::from_usize(index),
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Returns the slot of the entity at the given index if it has not been removed.
+    fn slot(&self, index: GenerationalIdx<Idx>) -> Option<&Slot<T>> {
+        self.slots
+            .get(index.index.into_usize())
+            .filter(|slot| slot.generation == index.generation)
+    }
+
+    /// Returns a shared reference to the entity at the given index if any.
+    ///
+    /// Returns `None` if the entity has been removed.
+    pub fn get(&self, index: GenerationalIdx<Idx>) -> Option<&T> {
+        self.slot(index)?.entity.as_ref()
+    }
+
+    /// Returns an exclusive reference to the entity at the given index if any.
+    ///
+    /// Returns `None` if the entity has been removed.
+    pub fn get_mut(&mut self, index: GenerationalIdx<Idx>) -> Option<&mut T> {
+        self.slots
+            .get_mut(index.index.into_usize())
+            .filter(|slot| slot.generation == index.generation)?
+            .entity
+            .as_mut()
+    }
+
+    /// Returns `true` if the entity at the given index has not been removed.
+    pub fn contains(&self, index: GenerationalIdx<Idx>) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Removes the entity at the given index and returns it.
+    ///
+    /// Returns `None` if the entity has already been removed.
+    pub fn remove(&mut self, index: GenerationalIdx<Idx>) -> Option<T> {
+        let slot_index = index.index.into_usize();
+        let slot = self
+            .slots
+            .get_mut(slot_index)
+            .filter(|slot| slot.generation == index.generation)?;
+        let entity = slot.entity.take()?;
+        self.len -= 1;
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            self.free.push(slot_index);
+        }
+        Some(entity)
+    }
+
+    /// Returns an iterator over the shared references of the allocated entities.
+    #[cfg(any(test, feature = "coverage"))]
+    pub fn iter(&self) -> impl Iterator<Item = (GenerationalIdx<Idx>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let index = GenerationalIdx {
+                index: Idx::from_usize(index),
+                generation: slot.generation,
+            };
+            slot.entity.as_ref().map(|entity| (index, entity))
+        })
+    }
+}
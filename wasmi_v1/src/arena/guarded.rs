@@ -20,7 +20,7 @@ impl<GuardIdx, EntityIdx> GuardedEntity<GuardIdx, EntityIdx> {
 impl<GuardIdx, EntityIdx> GuardedEntity<GuardIdx, EntityIdx>
 where
     GuardIdx: Index,
-    EntityIdx: Index,
+    EntityIdx: Copy,
 {
     /// Returns the entity index of the [`GuardedEntity`].
     ///
@@ -1,16 +1,23 @@
 //! Fast arena allocators for different usage purposes.
 //!
-//! They cannot deallocate single allocated entities for extra efficiency.
+//! They cannot deallocate single allocated entities for extra efficiency
+//! with the exception of the [`DedupArena`] that reuses deallocated slots
+//! and the [`GenerationalArena`] that rejects indices of removed entities.
 //! These allocators mainly serve as the backbone for an efficient Wasm store
 //! implementation.
 
 mod dedup;
+mod generational;
 mod guarded;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{dedup::DedupArena, guarded::GuardedEntity};
+pub use self::{
+    dedup::DedupArena,
+    generational::{GenerationalArena, GenerationalIdx},
+    guarded::GuardedEntity,
+};
 use alloc::vec::Vec;
 use core::{
     iter,
@@ -158,4 +158,92 @@ mod dedup_arena {
         // Assert that the deduplicating arena did not increase in size.
         assert_eq!(arena.len(), TEST_ENTITIES.len());
     }
+
+    #[test]
+    fn dealloc_works() {
+        let mut arena = alloc_dedup_arena(TEST_ENTITIES);
+        arena.dealloc(1);
+        assert_eq!(arena.len(), TEST_ENTITIES.len() - 1);
+        // Deallocating an already deallocated entity does nothing.
+        arena.dealloc(1);
+        assert_eq!(arena.len(), TEST_ENTITIES.len() - 1);
+        // The slot of the deallocated entity is reused by new entities.
+        assert_eq!(arena.alloc("new"), 1);
+        assert_eq!(arena.get(1), Some(&"new"));
+        assert_eq!(arena.len(), TEST_ENTITIES.len());
+        // Re-allocating the deallocated entity yields a new index.
+        assert_eq!(arena.alloc(TEST_ENTITIES[1]), TEST_ENTITIES.len());
+    }
+}
+
+mod generational_arena {
+    use super::*;
+
+    fn alloc_generational_arena(
+        entities: &[&'static str],
+    ) -> (
+        GenerationalArena<usize, &'static str>,
+        Vec<GenerationalIdx<usize>>,
+    ) {
+        let mut arena = <GenerationalArena<usize, &'static str>>::new();
+        // Check that the given arena is actually empty.
+        assert_eq!(arena.len(), 0);
+        assert!(arena.is_empty());
+        // Fill arena and check invariants while doing so.
+        let indices = entities
+            .iter()
+            .map(|str| arena.alloc(str))
+            .collect::<Vec<_>>();
+        // Check state of filled arena.
+        assert_eq!(arena.len(), entities.len());
+        assert!(!arena.is_empty());
+        for (idx, str) in indices.iter().zip(entities) {
+            assert_eq!(arena.get(*idx), Some(str));
+            assert!(arena.contains(*idx));
+        }
+        // Return filled arena.
+        (arena, indices)
+    }
+
+    #[test]
+    fn alloc_works() {
+        alloc_generational_arena(TEST_ENTITIES);
+    }
+
+    #[test]
+    fn iter_works() {
+        let (arena, indices) = alloc_generational_arena(TEST_ENTITIES);
+        assert!(arena.iter().eq(indices.into_iter().zip(TEST_ENTITIES)));
+    }
+
+    #[test]
+    fn remove_works() {
+        let (mut arena, indices) = alloc_generational_arena(TEST_ENTITIES);
+        assert_eq!(arena.remove(indices[1]), Some(TEST_ENTITIES[1]));
+        assert_eq!(arena.len(), TEST_ENTITIES.len() - 1);
+        assert_eq!(arena.get(indices[1]), None);
+        assert!(!arena.contains(indices[1]));
+        // Removing an already removed entity does nothing.
+        assert_eq!(arena.remove(indices[1]), None);
+        assert_eq!(arena.len(), TEST_ENTITIES.len() - 1);
+        // Other entities are not affected.
+        assert_eq!(arena.get(indices[2]), Some(&TEST_ENTITIES[2]));
+        assert_eq!(arena.iter().count(), TEST_ENTITIES.len() - 1);
+    }
+
+    #[test]
+    fn stale_indices_are_rejected() {
+        let (mut arena, indices) = alloc_generational_arena(TEST_ENTITIES);
+        arena.remove(indices[1]);
+        // The slot of the removed entity is reused by new entities.
+        let new = arena.alloc("new");
+        assert_eq!(new.index(), indices[1].index());
+        assert_eq!(arena.len(), TEST_ENTITIES.len());
+        // The stale index does not resolve to the new entity.
+        assert_ne!(new, indices[1]);
+        assert_eq!(arena.get(indices[1]), None);
+        assert_eq!(arena.get_mut(indices[1]), None);
+        assert_eq!(arena.remove(indices[1]), None);
+        assert_eq!(arena.get(new), Some(&"new"));
+    }
 }
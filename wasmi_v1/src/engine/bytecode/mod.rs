@@ -1,11 +1,22 @@
 //! The instruction architecture of the `wasmi` interpreter.
 
+mod operation;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::utils::{DropKeep, FuncIdx, GlobalIdx, LocalIdx, Offset, SignatureIdx, Target};
+pub use self::utils::{
+    DataSegmentIdx,
+    DropKeep,
+    ElementSegmentIdx,
+    FuncIdx,
+    GlobalIdx,
+    Offset,
+    Register,
+    SignatureIdx,
+    Target,
+};
 use wasmi_core::UntypedValue;
 
 /// The internal `wasmi` bytecode that is stored for Wasm functions.
@@ -16,194 +27,1020 @@ use wasmi_core::UntypedValue;
 ///
 /// For example the `BrTable` instruciton is unrolled into separate instructions
 /// each representing either the `BrTable` head or one of its branching targets.
+///
+/// Instructions do not operate on an implicit value stack but refer to their
+/// inputs and outputs via [`Register`] operands instead. For example the Wasm
+/// `i32.add` instruction is represented as `I32Add { result, lhs, rhs }`
+/// where all three operands are registers of the executed function frame.
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
-    GetLocal {
-        local_depth: LocalIdx,
+    /// Copies the value of the `input` register into the `result` register.
+    Copy {
+        result: Register,
+        input: Register,
+    },
+    Br(Target),
+    /// Branches to `target` if the value of the `condition` register is zero.
+    BrIfEqz {
+        condition: Register,
+        target: Target,
     },
-    SetLocal {
-        local_depth: LocalIdx,
+    /// Branches to `target` if the value of the `condition` register is not zero.
+    BrIfNez {
+        condition: Register,
+        target: Target,
     },
-    TeeLocal {
-        local_depth: LocalIdx,
+    // Compare-and-branch instructions.
+    //
+    // These fuse a Wasm integer comparison with a subsequent conditional
+    // branch on its result so that only a single instruction is dispatched.
+    /// Branches to `target` if `lhs == rhs` for the `i32` values of both registers.
+    BrIfI32Eq {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
     },
-    Br(Target),
-    BrIfEqz(Target),
-    BrIfNez(Target),
-    ReturnIfNez(DropKeep),
+    /// Branches to `target` if `lhs != rhs` for the `i32` values of both registers.
+    BrIfI32Ne {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs < rhs` for the `i32` values of both registers as signed integers.
+    BrIfI32LtS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs < rhs` for the `i32` values of both registers as unsigned integers.
+    BrIfI32LtU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs > rhs` for the `i32` values of both registers as signed integers.
+    BrIfI32GtS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs > rhs` for the `i32` values of both registers as unsigned integers.
+    BrIfI32GtU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs <= rhs` for the `i32` values of both registers as signed integers.
+    BrIfI32LeS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs <= rhs` for the `i32` values of both registers as unsigned integers.
+    BrIfI32LeU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs >= rhs` for the `i32` values of both registers as signed integers.
+    BrIfI32GeS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs >= rhs` for the `i32` values of both registers as unsigned integers.
+    BrIfI32GeU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs == rhs` for the `i64` values of both registers.
+    BrIfI64Eq {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs != rhs` for the `i64` values of both registers.
+    BrIfI64Ne {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs < rhs` for the `i64` values of both registers as signed integers.
+    BrIfI64LtS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs < rhs` for the `i64` values of both registers as unsigned integers.
+    BrIfI64LtU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs > rhs` for the `i64` values of both registers as signed integers.
+    BrIfI64GtS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs > rhs` for the `i64` values of both registers as unsigned integers.
+    BrIfI64GtU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs <= rhs` for the `i64` values of both registers as signed integers.
+    BrIfI64LeS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs <= rhs` for the `i64` values of both registers as unsigned integers.
+    BrIfI64LeU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs >= rhs` for the `i64` values of both registers as signed integers.
+    BrIfI64GeS {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Branches to `target` if `lhs >= rhs` for the `i64` values of both registers as unsigned integers.
+    BrIfI64GeU {
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    },
+    /// Returns from the function if the value of the `condition` register is not zero.
+    ReturnIfNez {
+        condition: Register,
+        results: DropKeep,
+    },
+    /// Branches to one of the `len_targets` instructions following it
+    /// depending on the value of the `index` register.
     BrTable {
+        index: Register,
         len_targets: usize,
     },
     Unreachable,
     Return(DropKeep),
-    Call(FuncIdx),
-    CallIndirect(SignatureIdx),
-    Drop,
-    Select,
-    GetGlobal(GlobalIdx),
-    SetGlobal(GlobalIdx),
-    I32Load(Offset),
-    I64Load(Offset),
-    F32Load(Offset),
-    F64Load(Offset),
-    I32Load8S(Offset),
-    I32Load8U(Offset),
-    I32Load16S(Offset),
-    I32Load16U(Offset),
-    I64Load8S(Offset),
-    I64Load8U(Offset),
-    I64Load16S(Offset),
-    I64Load16U(Offset),
-    I64Load32S(Offset),
-    I64Load32U(Offset),
-    I32Store(Offset),
-    I64Store(Offset),
-    F32Store(Offset),
-    F64Store(Offset),
-    I32Store8(Offset),
-    I32Store16(Offset),
-    I64Store8(Offset),
-    I64Store16(Offset),
-    I64Store32(Offset),
-    CurrentMemory,
-    GrowMemory,
-    Const(UntypedValue),
-    I32Eqz,
-    I32Eq,
-    I32Ne,
-    I32LtS,
-    I32LtU,
-    I32GtS,
-    I32GtU,
-    I32LeS,
-    I32LeU,
-    I32GeS,
-    I32GeU,
-    I64Eqz,
-    I64Eq,
-    I64Ne,
-    I64LtS,
-    I64LtU,
-    I64GtS,
-    I64GtU,
-    I64LeS,
-    I64LeU,
-    I64GeS,
-    I64GeU,
-    F32Eq,
-    F32Ne,
-    F32Lt,
-    F32Gt,
-    F32Le,
-    F32Ge,
-    F64Eq,
-    F64Ne,
-    F64Lt,
-    F64Gt,
-    F64Le,
-    F64Ge,
-    I32Clz,
-    I32Ctz,
-    I32Popcnt,
-    I32Add,
-    I32Sub,
-    I32Mul,
-    I32DivS,
-    I32DivU,
-    I32RemS,
-    I32RemU,
-    I32And,
-    I32Or,
-    I32Xor,
-    I32Shl,
-    I32ShrS,
-    I32ShrU,
-    I32Rotl,
-    I32Rotr,
-    I64Clz,
-    I64Ctz,
-    I64Popcnt,
-    I64Add,
-    I64Sub,
-    I64Mul,
-    I64DivS,
-    I64DivU,
-    I64RemS,
-    I64RemU,
-    I64And,
-    I64Or,
-    I64Xor,
-    I64Shl,
-    I64ShrS,
-    I64ShrU,
-    I64Rotl,
-    I64Rotr,
-    F32Abs,
-    F32Neg,
-    F32Ceil,
-    F32Floor,
-    F32Trunc,
-    F32Nearest,
-    F32Sqrt,
-    F32Add,
-    F32Sub,
-    F32Mul,
-    F32Div,
-    F32Min,
-    F32Max,
-    F32Copysign,
-    F64Abs,
-    F64Neg,
-    F64Ceil,
-    F64Floor,
-    F64Trunc,
-    F64Nearest,
-    F64Sqrt,
-    F64Add,
-    F64Sub,
-    F64Mul,
-    F64Div,
-    F64Min,
-    F64Max,
-    F64Copysign,
-    I32WrapI64,
-    I32TruncSF32,
-    I32TruncUF32,
-    I32TruncSF64,
-    I32TruncUF64,
-    I64ExtendSI32,
-    I64ExtendUI32,
-    I64TruncSF32,
-    I64TruncUF32,
-    I64TruncSF64,
-    I64TruncUF64,
-    F32ConvertSI32,
-    F32ConvertUI32,
-    F32ConvertSI64,
-    F32ConvertUI64,
-    F32DemoteF64,
-    F64ConvertSI32,
-    F64ConvertUI32,
-    F64ConvertSI64,
-    F64ConvertUI64,
-    F64PromoteF32,
-    I32ReinterpretF32,
-    I64ReinterpretF64,
-    F32ReinterpretI32,
-    F64ReinterpretI64,
-    I32Extend8S,
-    I32Extend16S,
-    I64Extend8S,
-    I64Extend16S,
-    I64Extend32S,
-    I32TruncSatF32S,
-    I32TruncSatF32U,
-    I32TruncSatF64S,
-    I32TruncSatF64U,
-    I64TruncSatF32S,
-    I64TruncSatF32U,
-    I64TruncSatF64S,
-    I64TruncSatF64U,
+    /// Calls the function at `func_index`.
+    ///
+    /// The parameters of the call are stored in the registers directly
+    /// preceding the `params_end` register. The results of the call
+    /// are stored starting at the register of the first parameter.
+    Call {
+        func_index: FuncIdx,
+        params_end: Register,
+    },
+    /// Calls the function stored in the default table at the value of the `index` register.
+    ///
+    /// Parameters and results are handled the same as for [`Instruction::Call`].
+    CallIndirect {
+        signature: SignatureIdx,
+        index: Register,
+        params_end: Register,
+    },
+    /// Tail calls the function at `func_index`.
+    ///
+    /// Parameters are handled the same as for [`Instruction::Call`].
+    ///
+    /// # Note
+    ///
+    /// Tail called Wasm functions replace the function frame of the caller
+    /// and return their results to the caller of the caller.
+    /// Tail called host functions are called the same as for [`Instruction::Call`]
+    /// and the following [`Instruction::Return`] returns their results.
+    ReturnCall {
+        func_index: FuncIdx,
+        params_end: Register,
+    },
+    /// Tail calls the function stored in the default table at the value of the `index` register.
+    ///
+    /// Parameters and results are handled the same as for [`Instruction::ReturnCall`].
+    ReturnCallIndirect {
+        signature: SignatureIdx,
+        index: Register,
+        params_end: Register,
+    },
+    /// Stores the value of the `if_false` register into the `result` register
+    /// if the value of the `condition` register is zero.
+    ///
+    /// # Note
+    ///
+    /// The `result` register is expected to already hold the value that is
+    /// selected if the value of the `condition` register is not zero.
+    Select {
+        result: Register,
+        condition: Register,
+        if_false: Register,
+    },
+    GetGlobal {
+        result: Register,
+        global: GlobalIdx,
+    },
+    SetGlobal {
+        global: GlobalIdx,
+        input: Register,
+    },
+    I32Load {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    F32Load {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    F64Load {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I32Load8S {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I32Load8U {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I32Load16S {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I32Load16U {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load8S {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load8U {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load16S {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load16U {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load32S {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I64Load32U {
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    },
+    I32Store {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I64Store {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    F32Store {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    F64Store {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I32Store8 {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I32Store16 {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I64Store8 {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I64Store16 {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    I64Store32 {
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    },
+    CurrentMemory {
+        result: Register,
+    },
+    GrowMemory {
+        result: Register,
+        delta: Register,
+    },
+    /// Copies `len` bytes from `src` to `dst` within the default linear memory.
+    ///
+    /// The source and destination regions may overlap.
+    MemoryCopy {
+        dst: Register,
+        src: Register,
+        len: Register,
+    },
+    /// Sets `len` bytes of the default linear memory starting at `dst` to `value`.
+    MemoryFill {
+        dst: Register,
+        value: Register,
+        len: Register,
+    },
+    /// Copies bytes of the data `segment` into the default linear memory.
+    ///
+    /// The `dst`, `src` and `len` operands are stored in this order
+    /// in the three registers starting at the `operands` register.
+    MemoryInit {
+        segment: DataSegmentIdx,
+        operands: Register,
+    },
+    /// Drops the data `segment` so that it is empty afterwards.
+    DataDrop {
+        segment: DataSegmentIdx,
+    },
+    /// Copies `len` elements from `src` to `dst` within the default table.
+    ///
+    /// The source and destination regions may overlap.
+    TableCopy {
+        dst: Register,
+        src: Register,
+        len: Register,
+    },
+    /// Copies elements of the element `segment` into the default table.
+    ///
+    /// Operands are handled the same as for [`Instruction::MemoryInit`].
+    TableInit {
+        segment: ElementSegmentIdx,
+        operands: Register,
+    },
+    /// Drops the element `segment` so that it is empty afterwards.
+    ElemDrop {
+        segment: ElementSegmentIdx,
+    },
+    Const {
+        result: Register,
+        value: UntypedValue,
+    },
+    I32Eqz {
+        result: Register,
+        input: Register,
+    },
+    I32Eq {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Ne {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32LtS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32LtU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32GtS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32GtU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32LeS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32LeU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32GeS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32GeU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Eqz {
+        result: Register,
+        input: Register,
+    },
+    I64Eq {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Ne {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64LtS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64LtU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64GtS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64GtU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64LeS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64LeU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64GeS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64GeU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Eq {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Ne {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Lt {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Gt {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Le {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Ge {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Eq {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Ne {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Lt {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Gt {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Le {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Ge {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Clz {
+        result: Register,
+        input: Register,
+    },
+    I32Ctz {
+        result: Register,
+        input: Register,
+    },
+    I32Popcnt {
+        result: Register,
+        input: Register,
+    },
+    I32Add {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Sub {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    /// Adds the constant `value` to the `i32` value of the `input` register.
+    ///
+    /// # Note
+    ///
+    /// This fuses a Wasm `i32.const` followed by `i32.add` or `i32.sub`.
+    I32AddImm {
+        result: Register,
+        input: Register,
+        value: i32,
+    },
+    I32Mul {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32DivS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32DivU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32RemS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32RemU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32And {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Or {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Xor {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Shl {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32ShrS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32ShrU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Rotl {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32Rotr {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Clz {
+        result: Register,
+        input: Register,
+    },
+    I64Ctz {
+        result: Register,
+        input: Register,
+    },
+    I64Popcnt {
+        result: Register,
+        input: Register,
+    },
+    I64Add {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Sub {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    /// Adds the constant `value` to the `i64` value of the `input` register.
+    ///
+    /// # Note
+    ///
+    /// This fuses a Wasm `i64.const` followed by `i64.add` or `i64.sub`. The `value` is sign extended to `i64`.
+    I64AddImm {
+        result: Register,
+        input: Register,
+        value: i32,
+    },
+    I64Mul {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64DivS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64DivU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64RemS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64RemU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64And {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Or {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Xor {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Shl {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64ShrS {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64ShrU {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Rotl {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I64Rotr {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Abs {
+        result: Register,
+        input: Register,
+    },
+    F32Neg {
+        result: Register,
+        input: Register,
+    },
+    F32Ceil {
+        result: Register,
+        input: Register,
+    },
+    F32Floor {
+        result: Register,
+        input: Register,
+    },
+    F32Trunc {
+        result: Register,
+        input: Register,
+    },
+    F32Nearest {
+        result: Register,
+        input: Register,
+    },
+    F32Sqrt {
+        result: Register,
+        input: Register,
+    },
+    F32Add {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Sub {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Mul {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Div {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Min {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Max {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F32Copysign {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Abs {
+        result: Register,
+        input: Register,
+    },
+    F64Neg {
+        result: Register,
+        input: Register,
+    },
+    F64Ceil {
+        result: Register,
+        input: Register,
+    },
+    F64Floor {
+        result: Register,
+        input: Register,
+    },
+    F64Trunc {
+        result: Register,
+        input: Register,
+    },
+    F64Nearest {
+        result: Register,
+        input: Register,
+    },
+    F64Sqrt {
+        result: Register,
+        input: Register,
+    },
+    F64Add {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Sub {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Mul {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Div {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Min {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Max {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    F64Copysign {
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    },
+    I32WrapI64 {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSF32 {
+        result: Register,
+        input: Register,
+    },
+    I32TruncUF32 {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSF64 {
+        result: Register,
+        input: Register,
+    },
+    I32TruncUF64 {
+        result: Register,
+        input: Register,
+    },
+    I64ExtendSI32 {
+        result: Register,
+        input: Register,
+    },
+    I64ExtendUI32 {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSF32 {
+        result: Register,
+        input: Register,
+    },
+    I64TruncUF32 {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSF64 {
+        result: Register,
+        input: Register,
+    },
+    I64TruncUF64 {
+        result: Register,
+        input: Register,
+    },
+    F32ConvertSI32 {
+        result: Register,
+        input: Register,
+    },
+    F32ConvertUI32 {
+        result: Register,
+        input: Register,
+    },
+    F32ConvertSI64 {
+        result: Register,
+        input: Register,
+    },
+    F32ConvertUI64 {
+        result: Register,
+        input: Register,
+    },
+    F32DemoteF64 {
+        result: Register,
+        input: Register,
+    },
+    F64ConvertSI32 {
+        result: Register,
+        input: Register,
+    },
+    F64ConvertUI32 {
+        result: Register,
+        input: Register,
+    },
+    F64ConvertSI64 {
+        result: Register,
+        input: Register,
+    },
+    F64ConvertUI64 {
+        result: Register,
+        input: Register,
+    },
+    F64PromoteF32 {
+        result: Register,
+        input: Register,
+    },
+    I32Extend8S {
+        result: Register,
+        input: Register,
+    },
+    I32Extend16S {
+        result: Register,
+        input: Register,
+    },
+    I64Extend8S {
+        result: Register,
+        input: Register,
+    },
+    I64Extend16S {
+        result: Register,
+        input: Register,
+    },
+    I64Extend32S {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSatF32S {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSatF32U {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSatF64S {
+        result: Register,
+        input: Register,
+    },
+    I32TruncSatF64U {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSatF32S {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSatF32U {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSatF64S {
+        result: Register,
+        input: Register,
+    },
+    I64TruncSatF64U {
+        result: Register,
+        input: Register,
+    },
 
     /// The start of a Wasm function body.
     ///
@@ -232,7 +1069,12 @@ pub enum Instruction {
         /// Note: Storing the amount of locals inline with the rest of the
         ///       function body eliminates one indirection when calling a function.
         len_locals: u32,
+        /// The maximum amount of registers used by the function body.
+        ///
+        /// Note: This includes the registers of parameters and local variables.
         max_stack_height: u32,
+        /// The number of parameters of the function.
+        len_params: u16,
     },
     /// The end of a Wasm function body.
     ///
@@ -244,41 +1086,273 @@ pub enum Instruction {
 }
 
 impl Instruction {
-    /// Creates a new `Const` instruction from the given value.
-    pub fn constant<T>(value: T) -> Self
+    /// Creates a new `Const` instruction storing the given value into `result`.
+    pub fn constant<T>(result: Register, value: T) -> Self
     where
         T: Into<UntypedValue>,
     {
-        Self::Const(value.into())
+        Self::Const {
+            result,
+            value: value.into(),
+        }
     }
 
-    /// Creates a new `local.get` instruction from the given local depth.
-    pub fn local_get<T>(local_depth: T) -> Self
-    where
-        T: Into<LocalIdx>,
-    {
-        Self::GetLocal {
-            local_depth: local_depth.into(),
+    /// Returns an exclusive reference to the `result` register of the instruction if any.
+    ///
+    /// # Note
+    ///
+    /// This only returns registers of instructions whose result
+    /// does not depend on the prior value of the `result` register.
+    pub fn result_mut(&mut self) -> Option<&mut Register> {
+        match self {
+            Self::Copy { result, .. }
+            | Self::GetGlobal { result, .. }
+            | Self::I32Load { result, .. }
+            | Self::I64Load { result, .. }
+            | Self::F32Load { result, .. }
+            | Self::F64Load { result, .. }
+            | Self::I32Load8S { result, .. }
+            | Self::I32Load8U { result, .. }
+            | Self::I32Load16S { result, .. }
+            | Self::I32Load16U { result, .. }
+            | Self::I64Load8S { result, .. }
+            | Self::I64Load8U { result, .. }
+            | Self::I64Load16S { result, .. }
+            | Self::I64Load16U { result, .. }
+            | Self::I64Load32S { result, .. }
+            | Self::I64Load32U { result, .. }
+            | Self::CurrentMemory { result, .. }
+            | Self::GrowMemory { result, .. }
+            | Self::Const { result, .. }
+            | Self::I32Eqz { result, .. }
+            | Self::I32Eq { result, .. }
+            | Self::I32Ne { result, .. }
+            | Self::I32LtS { result, .. }
+            | Self::I32LtU { result, .. }
+            | Self::I32GtS { result, .. }
+            | Self::I32GtU { result, .. }
+            | Self::I32LeS { result, .. }
+            | Self::I32LeU { result, .. }
+            | Self::I32GeS { result, .. }
+            | Self::I32GeU { result, .. }
+            | Self::I64Eqz { result, .. }
+            | Self::I64Eq { result, .. }
+            | Self::I64Ne { result, .. }
+            | Self::I64LtS { result, .. }
+            | Self::I64LtU { result, .. }
+            | Self::I64GtS { result, .. }
+            | Self::I64GtU { result, .. }
+            | Self::I64LeS { result, .. }
+            | Self::I64LeU { result, .. }
+            | Self::I64GeS { result, .. }
+            | Self::I64GeU { result, .. }
+            | Self::F32Eq { result, .. }
+            | Self::F32Ne { result, .. }
+            | Self::F32Lt { result, .. }
+            | Self::F32Gt { result, .. }
+            | Self::F32Le { result, .. }
+            | Self::F32Ge { result, .. }
+            | Self::F64Eq { result, .. }
+            | Self::F64Ne { result, .. }
+            | Self::F64Lt { result, .. }
+            | Self::F64Gt { result, .. }
+            | Self::F64Le { result, .. }
+            | Self::F64Ge { result, .. }
+            | Self::I32Clz { result, .. }
+            | Self::I32Ctz { result, .. }
+            | Self::I32Popcnt { result, .. }
+            | Self::I32Add { result, .. }
+            | Self::I32Sub { result, .. }
+            | Self::I32AddImm { result, .. }
+            | Self::I32Mul { result, .. }
+            | Self::I32DivS { result, .. }
+            | Self::I32DivU { result, .. }
+            | Self::I32RemS { result, .. }
+            | Self::I32RemU { result, .. }
+            | Self::I32And { result, .. }
+            | Self::I32Or { result, .. }
+            | Self::I32Xor { result, .. }
+            | Self::I32Shl { result, .. }
+            | Self::I32ShrS { result, .. }
+            | Self::I32ShrU { result, .. }
+            | Self::I32Rotl { result, .. }
+            | Self::I32Rotr { result, .. }
+            | Self::I64Clz { result, .. }
+            | Self::I64Ctz { result, .. }
+            | Self::I64Popcnt { result, .. }
+            | Self::I64Add { result, .. }
+            | Self::I64Sub { result, .. }
+            | Self::I64AddImm { result, .. }
+            | Self::I64Mul { result, .. }
+            | Self::I64DivS { result, .. }
+            | Self::I64DivU { result, .. }
+            | Self::I64RemS { result, .. }
+            | Self::I64RemU { result, .. }
+            | Self::I64And { result, .. }
+            | Self::I64Or { result, .. }
+            | Self::I64Xor { result, .. }
+            | Self::I64Shl { result, .. }
+            | Self::I64ShrS { result, .. }
+            | Self::I64ShrU { result, .. }
+            | Self::I64Rotl { result, .. }
+            | Self::I64Rotr { result, .. }
+            | Self::F32Abs { result, .. }
+            | Self::F32Neg { result, .. }
+            | Self::F32Ceil { result, .. }
+            | Self::F32Floor { result, .. }
+            | Self::F32Trunc { result, .. }
+            | Self::F32Nearest { result, .. }
+            | Self::F32Sqrt { result, .. }
+            | Self::F32Add { result, .. }
+            | Self::F32Sub { result, .. }
+            | Self::F32Mul { result, .. }
+            | Self::F32Div { result, .. }
+            | Self::F32Min { result, .. }
+            | Self::F32Max { result, .. }
+            | Self::F32Copysign { result, .. }
+            | Self::F64Abs { result, .. }
+            | Self::F64Neg { result, .. }
+            | Self::F64Ceil { result, .. }
+            | Self::F64Floor { result, .. }
+            | Self::F64Trunc { result, .. }
+            | Self::F64Nearest { result, .. }
+            | Self::F64Sqrt { result, .. }
+            | Self::F64Add { result, .. }
+            | Self::F64Sub { result, .. }
+            | Self::F64Mul { result, .. }
+            | Self::F64Div { result, .. }
+            | Self::F64Min { result, .. }
+            | Self::F64Max { result, .. }
+            | Self::F64Copysign { result, .. }
+            | Self::I32WrapI64 { result, .. }
+            | Self::I32TruncSF32 { result, .. }
+            | Self::I32TruncUF32 { result, .. }
+            | Self::I32TruncSF64 { result, .. }
+            | Self::I32TruncUF64 { result, .. }
+            | Self::I64ExtendSI32 { result, .. }
+            | Self::I64ExtendUI32 { result, .. }
+            | Self::I64TruncSF32 { result, .. }
+            | Self::I64TruncUF32 { result, .. }
+            | Self::I64TruncSF64 { result, .. }
+            | Self::I64TruncUF64 { result, .. }
+            | Self::F32ConvertSI32 { result, .. }
+            | Self::F32ConvertUI32 { result, .. }
+            | Self::F32ConvertSI64 { result, .. }
+            | Self::F32ConvertUI64 { result, .. }
+            | Self::F32DemoteF64 { result, .. }
+            | Self::F64ConvertSI32 { result, .. }
+            | Self::F64ConvertUI32 { result, .. }
+            | Self::F64ConvertSI64 { result, .. }
+            | Self::F64ConvertUI64 { result, .. }
+            | Self::F64PromoteF32 { result, .. }
+            | Self::I32Extend8S { result, .. }
+            | Self::I32Extend16S { result, .. }
+            | Self::I64Extend8S { result, .. }
+            | Self::I64Extend16S { result, .. }
+            | Self::I64Extend32S { result, .. }
+            | Self::I32TruncSatF32S { result, .. }
+            | Self::I32TruncSatF32U { result, .. }
+            | Self::I32TruncSatF64S { result, .. }
+            | Self::I32TruncSatF64U { result, .. }
+            | Self::I64TruncSatF32S { result, .. }
+            | Self::I64TruncSatF32U { result, .. }
+            | Self::I64TruncSatF64S { result, .. }
+            | Self::I64TruncSatF64U { result, .. } => Some(result),
+            _ => None,
         }
     }
 
-    /// Creates a new `local.set` instruction from the given local depth.
-    pub fn local_set<T>(local_depth: T) -> Self
-    where
-        T: Into<LocalIdx>,
-    {
-        Self::SetLocal {
-            local_depth: local_depth.into(),
+    /// Returns an exclusive reference to the branch [`Target`] of the instruction if any.
+    pub fn branch_target_mut(&mut self) -> Option<&mut Target> {
+        match self {
+            Self::Br(target)
+            | Self::BrIfEqz { target, .. }
+            | Self::BrIfNez { target, .. }
+            | Self::BrIfI32Eq { target, .. }
+            | Self::BrIfI32Ne { target, .. }
+            | Self::BrIfI32LtS { target, .. }
+            | Self::BrIfI32LtU { target, .. }
+            | Self::BrIfI32GtS { target, .. }
+            | Self::BrIfI32GtU { target, .. }
+            | Self::BrIfI32LeS { target, .. }
+            | Self::BrIfI32LeU { target, .. }
+            | Self::BrIfI32GeS { target, .. }
+            | Self::BrIfI32GeU { target, .. }
+            | Self::BrIfI64Eq { target, .. }
+            | Self::BrIfI64Ne { target, .. }
+            | Self::BrIfI64LtS { target, .. }
+            | Self::BrIfI64LtU { target, .. }
+            | Self::BrIfI64GtS { target, .. }
+            | Self::BrIfI64GtU { target, .. }
+            | Self::BrIfI64LeS { target, .. }
+            | Self::BrIfI64LeU { target, .. }
+            | Self::BrIfI64GeS { target, .. }
+            | Self::BrIfI64GeU { target, .. } => Some(target),
+            _ => None,
         }
     }
 
-    /// Creates a new `local.tee` instruction from the given local depth.
-    pub fn local_tee<T>(local_depth: T) -> Self
-    where
-        T: Into<LocalIdx>,
-    {
-        Self::TeeLocal {
-            local_depth: local_depth.into(),
+    /// Returns `true` if the instruction either branches or falls through
+    /// to the next instruction depending on a condition.
+    #[cfg(feature = "coverage")]
+    pub fn is_conditional_branch(&self) -> bool {
+        match self {
+            Self::Br(_) => false,
+            Self::ReturnIfNez { .. } => true,
+            instr => {
+                let mut instr = *instr;
+                instr.branch_target_mut().is_some()
+            }
+        }
+    }
+
+    /// Returns the compare-and-branch instruction that fuses the instruction
+    /// with a subsequent conditional branch on its result.
+    ///
+    /// The returned instruction branches to `target` if the result of the
+    /// instruction is zero in case `branch_if_zero` is `true` and if it is not
+    /// zero otherwise.
+    ///
+    /// Returns `None` if the instruction is not an integer comparison.
+    pub fn fuse_br_if(&self, branch_if_zero: bool, target: Target) -> Option<Self> {
+        macro_rules! fuse {
+            ( $( $cmp:ident => $br_if:ident, $br_if_not:ident; )* ) => {
+                match *self {
+                    $(
+                        Self::$cmp { lhs, rhs, .. } => {
+                            if branch_if_zero {
+                                Self::$br_if_not { lhs, rhs, target }
+                            } else {
+                                Self::$br_if { lhs, rhs, target }
+                            }
+                        }
+                    )*
+                    _ => return None,
+                }
+            };
         }
+        let fused = fuse! {
+            I32Eq => BrIfI32Eq, BrIfI32Ne;
+            I32Ne => BrIfI32Ne, BrIfI32Eq;
+            I32LtS => BrIfI32LtS, BrIfI32GeS;
+            I32LtU => BrIfI32LtU, BrIfI32GeU;
+            I32GtS => BrIfI32GtS, BrIfI32LeS;
+            I32GtU => BrIfI32GtU, BrIfI32LeU;
+            I32LeS => BrIfI32LeS, BrIfI32GtS;
+            I32LeU => BrIfI32LeU, BrIfI32GtU;
+            I32GeS => BrIfI32GeS, BrIfI32LtS;
+            I32GeU => BrIfI32GeU, BrIfI32LtU;
+            I64Eq => BrIfI64Eq, BrIfI64Ne;
+            I64Ne => BrIfI64Ne, BrIfI64Eq;
+            I64LtS => BrIfI64LtS, BrIfI64GeS;
+            I64LtU => BrIfI64LtU, BrIfI64GeU;
+            I64GtS => BrIfI64GtS, BrIfI64LeS;
+            I64GtU => BrIfI64GtU, BrIfI64LeU;
+            I64LeS => BrIfI64LeS, BrIfI64GtS;
+            I64LeU => BrIfI64LeU, BrIfI64GtU;
+            I64GeS => BrIfI64GeS, BrIfI64LtS;
+            I64GeU => BrIfI64GeU, BrIfI64LtU;
+        };
+        Some(fused)
     }
 }
@@ -0,0 +1,742 @@
+//! Pure computations of `wasmi` bytecode instructions.
+
+use super::{Instruction, Register};
+use wasmi_core::{TrapCode, UntypedValue};
+
+/// The pure computation performed by an [`Instruction`].
+///
+/// # Note
+///
+/// This is used to evaluate instructions with constant inputs during
+/// translation. The computations are the same as the ones performed
+/// by the interpreter when executing the respective instruction.
+#[derive(Debug, Copy, Clone)]
+pub enum Operation {
+    /// Computes the result from the `input` register and cannot trap.
+    Unary {
+        input: Register,
+        f: fn(UntypedValue) -> UntypedValue,
+    },
+    /// Computes the result from the `input` register and might trap.
+    TryUnary {
+        input: Register,
+        f: fn(UntypedValue) -> Result<UntypedValue, TrapCode>,
+    },
+    /// Computes the result from the `lhs` and `rhs` registers and cannot trap.
+    Binary {
+        lhs: Register,
+        rhs: Register,
+        f: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    },
+    /// Computes the result from the `lhs` and `rhs` registers and might trap.
+    TryBinary {
+        lhs: Register,
+        rhs: Register,
+        f: fn(UntypedValue, UntypedValue) -> Result<UntypedValue, TrapCode>,
+    },
+}
+
+impl Operation {
+    /// Returns `true` if the computation can never trap.
+    pub fn is_infallible(&self) -> bool {
+        matches!(self, Self::Unary { .. } | Self::Binary { .. })
+    }
+
+    /// Returns the number of input registers of the computation.
+    pub fn len_inputs(&self) -> usize {
+        match self {
+            Self::Unary { .. } | Self::TryUnary { .. } => 1,
+            Self::Binary { .. } | Self::TryBinary { .. } => 2,
+        }
+    }
+
+    /// Evaluates the computation for the input values returned by `value_of`.
+    ///
+    /// Returns `None` if `value_of` returns `None` for any of the input registers.
+    pub fn evaluate<F>(&self, mut value_of: F) -> Option<Result<UntypedValue, TrapCode>>
+    where
+        F: FnMut(Register) -> Option<UntypedValue>,
+    {
+        let result = match *self {
+            Self::Unary { input, f } => Ok(f(value_of(input)?)),
+            Self::TryUnary { input, f } => f(value_of(input)?),
+            Self::Binary { lhs, rhs, f } => Ok(f(value_of(lhs)?, value_of(rhs)?)),
+            Self::TryBinary { lhs, rhs, f } => f(value_of(lhs)?, value_of(rhs)?),
+        };
+        Some(result)
+    }
+}
+
+impl Instruction {
+    /// Returns `true` if the instruction only writes its result register.
+    ///
+    /// # Note
+    ///
+    /// Pure instructions can never trap and have no other side effects.
+    /// Therefore they can be removed if their result is never used.
+    pub fn is_pure(&self) -> bool {
+        match self {
+            Self::Copy { .. }
+            | Self::Select { .. }
+            | Self::Const { .. }
+            | Self::GetGlobal { .. }
+            | Self::CurrentMemory { .. }
+            | Self::I32AddImm { .. }
+            | Self::I64AddImm { .. } => true,
+            _ => self
+                .operation()
+                .map(|operation| operation.is_infallible())
+                .unwrap_or(false),
+        }
+    }
+
+    /// Returns the pure computation performed by the instruction if any.
+    ///
+    /// # Note
+    ///
+    /// This only covers instructions that compute their result
+    /// solely from the values of their input registers.
+    pub fn operation(&self) -> Option<Operation> {
+        let operation = match *self {
+            Self::I32Eqz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_eqz,
+            },
+            Self::I32Eq { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_eq,
+            },
+            Self::I32Ne { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_ne,
+            },
+            Self::I32LtS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_lt_s,
+            },
+            Self::I32LtU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_lt_u,
+            },
+            Self::I32GtS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_gt_s,
+            },
+            Self::I32GtU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_gt_u,
+            },
+            Self::I32LeS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_le_s,
+            },
+            Self::I32LeU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_le_u,
+            },
+            Self::I32GeS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_ge_s,
+            },
+            Self::I32GeU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_ge_u,
+            },
+            Self::I64Eqz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_eqz,
+            },
+            Self::I64Eq { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_eq,
+            },
+            Self::I64Ne { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_ne,
+            },
+            Self::I64LtS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_lt_s,
+            },
+            Self::I64LtU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_lt_u,
+            },
+            Self::I64GtS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_gt_s,
+            },
+            Self::I64GtU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_gt_u,
+            },
+            Self::I64LeS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_le_s,
+            },
+            Self::I64LeU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_le_u,
+            },
+            Self::I64GeS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_ge_s,
+            },
+            Self::I64GeU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_ge_u,
+            },
+            Self::F32Eq { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_eq,
+            },
+            Self::F32Ne { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_ne,
+            },
+            Self::F32Lt { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_lt,
+            },
+            Self::F32Gt { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_gt,
+            },
+            Self::F32Le { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_le,
+            },
+            Self::F32Ge { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_ge,
+            },
+            Self::F64Eq { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_eq,
+            },
+            Self::F64Ne { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_ne,
+            },
+            Self::F64Lt { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_lt,
+            },
+            Self::F64Gt { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_gt,
+            },
+            Self::F64Le { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_le,
+            },
+            Self::F64Ge { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_ge,
+            },
+            Self::I32Clz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_clz,
+            },
+            Self::I32Ctz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_ctz,
+            },
+            Self::I32Popcnt { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_popcnt,
+            },
+            Self::I32Add { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_add,
+            },
+            Self::I32Sub { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_sub,
+            },
+            Self::I32Mul { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_mul,
+            },
+            Self::I32DivS { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_div_s,
+            },
+            Self::I32DivU { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_div_u,
+            },
+            Self::I32RemS { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_rem_s,
+            },
+            Self::I32RemU { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_rem_u,
+            },
+            Self::I32And { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_and,
+            },
+            Self::I32Or { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_or,
+            },
+            Self::I32Xor { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_xor,
+            },
+            Self::I32Shl { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_shl,
+            },
+            Self::I32ShrS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_shr_s,
+            },
+            Self::I32ShrU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_shr_u,
+            },
+            Self::I32Rotl { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_rotl,
+            },
+            Self::I32Rotr { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i32_rotr,
+            },
+            Self::I64Clz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_clz,
+            },
+            Self::I64Ctz { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_ctz,
+            },
+            Self::I64Popcnt { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_popcnt,
+            },
+            Self::I64Add { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_add,
+            },
+            Self::I64Sub { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_sub,
+            },
+            Self::I64Mul { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_mul,
+            },
+            Self::I64DivS { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_div_s,
+            },
+            Self::I64DivU { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_div_u,
+            },
+            Self::I64RemS { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_rem_s,
+            },
+            Self::I64RemU { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_rem_u,
+            },
+            Self::I64And { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_and,
+            },
+            Self::I64Or { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_or,
+            },
+            Self::I64Xor { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_xor,
+            },
+            Self::I64Shl { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_shl,
+            },
+            Self::I64ShrS { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_shr_s,
+            },
+            Self::I64ShrU { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_shr_u,
+            },
+            Self::I64Rotl { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_rotl,
+            },
+            Self::I64Rotr { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::i64_rotr,
+            },
+            Self::F32Abs { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_abs,
+            },
+            Self::F32Neg { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_neg,
+            },
+            Self::F32Ceil { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_ceil,
+            },
+            Self::F32Floor { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_floor,
+            },
+            Self::F32Trunc { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_trunc,
+            },
+            Self::F32Nearest { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_nearest,
+            },
+            Self::F32Sqrt { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_sqrt,
+            },
+            Self::F32Add { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_add,
+            },
+            Self::F32Sub { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_sub,
+            },
+            Self::F32Mul { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_mul,
+            },
+            Self::F32Div { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_div,
+            },
+            Self::F32Min { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_min,
+            },
+            Self::F32Max { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_max,
+            },
+            Self::F32Copysign { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f32_copysign,
+            },
+            Self::F64Abs { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_abs,
+            },
+            Self::F64Neg { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_neg,
+            },
+            Self::F64Ceil { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_ceil,
+            },
+            Self::F64Floor { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_floor,
+            },
+            Self::F64Trunc { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_trunc,
+            },
+            Self::F64Nearest { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_nearest,
+            },
+            Self::F64Sqrt { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_sqrt,
+            },
+            Self::F64Add { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_add,
+            },
+            Self::F64Sub { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_sub,
+            },
+            Self::F64Mul { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_mul,
+            },
+            Self::F64Div { lhs, rhs, .. } => Operation::TryBinary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_div,
+            },
+            Self::F64Min { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_min,
+            },
+            Self::F64Max { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_max,
+            },
+            Self::F64Copysign { lhs, rhs, .. } => Operation::Binary {
+                lhs,
+                rhs,
+                f: UntypedValue::f64_copysign,
+            },
+            Self::I32WrapI64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_wrap_i64,
+            },
+            Self::I32TruncSF32 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i32_trunc_f32_s,
+            },
+            Self::I32TruncUF32 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i32_trunc_f32_u,
+            },
+            Self::I32TruncSF64 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i32_trunc_f64_s,
+            },
+            Self::I32TruncUF64 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i32_trunc_f64_u,
+            },
+            Self::I64ExtendSI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_extend_i32_s,
+            },
+            Self::I64ExtendUI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_extend_i32_u,
+            },
+            Self::I64TruncSF32 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i64_trunc_f32_s,
+            },
+            Self::I64TruncUF32 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i64_trunc_f32_u,
+            },
+            Self::I64TruncSF64 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i64_trunc_f64_s,
+            },
+            Self::I64TruncUF64 { input, .. } => Operation::TryUnary {
+                input,
+                f: UntypedValue::i64_trunc_f64_u,
+            },
+            Self::F32ConvertSI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_convert_i32_s,
+            },
+            Self::F32ConvertUI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_convert_i32_u,
+            },
+            Self::F32ConvertSI64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_convert_i64_s,
+            },
+            Self::F32ConvertUI64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_convert_i64_u,
+            },
+            Self::F32DemoteF64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f32_demote_f64,
+            },
+            Self::F64ConvertSI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_convert_i32_s,
+            },
+            Self::F64ConvertUI32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_convert_i32_u,
+            },
+            Self::F64ConvertSI64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_convert_i64_s,
+            },
+            Self::F64ConvertUI64 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_convert_i64_u,
+            },
+            Self::F64PromoteF32 { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::f64_promote_f32,
+            },
+            Self::I32Extend8S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_extend8_s,
+            },
+            Self::I32Extend16S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_extend16_s,
+            },
+            Self::I64Extend8S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_extend8_s,
+            },
+            Self::I64Extend16S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_extend16_s,
+            },
+            Self::I64Extend32S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_extend32_s,
+            },
+            Self::I32TruncSatF32S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_trunc_sat_f32_s,
+            },
+            Self::I32TruncSatF32U { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_trunc_sat_f32_u,
+            },
+            Self::I32TruncSatF64S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_trunc_sat_f64_s,
+            },
+            Self::I32TruncSatF64U { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i32_trunc_sat_f64_u,
+            },
+            Self::I64TruncSatF32S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_trunc_sat_f32_s,
+            },
+            Self::I64TruncSatF32U { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_trunc_sat_f32_u,
+            },
+            Self::I64TruncSatF64S { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_trunc_sat_f64_s,
+            },
+            Self::I64TruncSatF64U { input, .. } => Operation::Unary {
+                input,
+                f: UntypedValue::i64_trunc_sat_f64_u,
+            },
+            _ => return None,
+        };
+        Some(operation)
+    }
+
+    /// Returns the function replacing NaN results of the instruction with the canonical Wasm NaN.
+    ///
+    /// Returns `None` if the instruction is not a float instruction with canonicalized NaN results.
+    pub fn nan_canonicalization(&self) -> Option<fn(UntypedValue) -> UntypedValue> {
+        match self {
+            Self::F32Ceil { .. }
+            | Self::F32Floor { .. }
+            | Self::F32Trunc { .. }
+            | Self::F32Nearest { .. }
+            | Self::F32Sqrt { .. }
+            | Self::F32Add { .. }
+            | Self::F32Sub { .. }
+            | Self::F32Mul { .. }
+            | Self::F32Div { .. }
+            | Self::F32Min { .. }
+            | Self::F32Max { .. }
+            | Self::F32DemoteF64 { .. } => Some(UntypedValue::f32_canonicalize_nan),
+            Self::F64Ceil { .. }
+            | Self::F64Floor { .. }
+            | Self::F64Trunc { .. }
+            | Self::F64Nearest { .. }
+            | Self::F64Sqrt { .. }
+            | Self::F64Add { .. }
+            | Self::F64Sub { .. }
+            | Self::F64Mul { .. }
+            | Self::F64Div { .. }
+            | Self::F64Min { .. }
+            | Self::F64Max { .. }
+            | Self::F64PromoteF32 { .. } => Some(UntypedValue::f64_canonicalize_nan),
+            _ => None,
+        }
+    }
+}
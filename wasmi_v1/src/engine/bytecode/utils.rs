@@ -1,6 +1,12 @@
 use super::super::super::engine::InstructionIdx;
 
-/// Defines how many stack values are going to be dropped and kept after branching.
+/// Defines how many stack values are going to be dropped and kept upon returning.
+///
+/// # Note
+///
+/// Upon returning from a function the `keep` values starting at register `drop`
+/// are moved to the start of the function frame. This effectively drops the
+/// `drop` registers below the returned values.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct DropKeep {
     /// The amount of stack values dropped.
@@ -43,21 +49,23 @@ impl DropKeep {
 
 /// A branching target.
 ///
-/// This also specifies how many values on the stack
-/// need to be dropped and kept in order to maintain
-/// value stack integrity.
+/// # Note
+///
+/// Values that are kept upon branching are copied into the registers
+/// expected at the branch destination by explicit [`Instruction::Copy`]
+/// instructions prior to the branch.
+///
+/// [`Instruction::Copy`]: [`super::Instruction::Copy`]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Target {
     /// The destination program counter.
     dst_pc: InstructionIdx,
-    /// How many values on the stack need to be dropped and kept.
-    drop_keep: DropKeep,
 }
 
 impl Target {
     /// Creates a new `wasmi` branching target.
-    pub fn new(dst_pc: InstructionIdx, drop_keep: DropKeep) -> Self {
-        Self { dst_pc, drop_keep }
+    pub fn new(dst_pc: InstructionIdx) -> Self {
+        Self { dst_pc }
     }
 
     /// Returns the destination program counter (as index).
@@ -80,11 +88,6 @@ impl Target {
         );
         self.dst_pc = new_destination_pc;
     }
-
-    /// Returns the amount of stack values to drop and keep upon taking the branch.
-    pub fn drop_keep(self) -> DropKeep {
-        self.drop_keep
-    }
 }
 
 /// A function index.
@@ -123,26 +126,70 @@ impl SignatureIdx {
     }
 }
 
-/// A local variable index.
+/// An index of a data segment of the instance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct DataSegmentIdx(u32);
+
+impl From<u32> for DataSegmentIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl DataSegmentIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// An index of an element segment of the instance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ElementSegmentIdx(u32);
+
+impl From<u32> for ElementSegmentIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl ElementSegmentIdx {
+    /// Returns the inner `u32` index.
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
+/// A register of the currently executed function frame.
 ///
 /// # Note
 ///
-/// Refers to a local variable of the currently executed function.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Registers are indexed relative to the start of the function frame.
+/// The first registers refer to the function parameters followed by the
+/// local variables of the function. All remaining registers hold the
+/// temporary values computed during execution of the function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
-pub struct LocalIdx(u32);
+pub struct Register(u32);
 
-impl From<u32> for LocalIdx {
+impl From<u32> for Register {
     fn from(index: u32) -> Self {
         Self(index)
     }
 }
 
-impl LocalIdx {
+impl Register {
     /// Returns the inner `u32` index.
     pub fn into_inner(self) -> u32 {
         self.0
     }
+
+    /// Returns the register index as `usize`.
+    pub fn into_usize(self) -> usize {
+        self.0 as usize
+    }
 }
 
 /// A global variable index.
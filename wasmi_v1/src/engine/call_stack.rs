@@ -1,16 +1,9 @@
 //! Data structures to represent the Wasm call stack during execution.
 
+#[cfg(any(feature = "trace", feature = "debugger"))]
+use super::CodeMap;
 use super::{
-    super::{
-        func::WasmFuncEntity,
-        AsContext,
-        Func,
-        FuncBody,
-        FuncEntityInternal,
-        Instance,
-        Memory,
-        Table,
-    },
+    super::{func::WasmFuncEntity, AsContext, Func, FuncBody, Instance, Memory, Table},
     ResolvedFuncBody,
     ValueStack,
     DEFAULT_CALL_STACK_LIMIT,
@@ -20,6 +13,7 @@ use crate::{
     module::{DEFAULT_MEMORY_INDEX, DEFAULT_TABLE_INDEX},
 };
 use alloc::vec::Vec;
+use core::slice;
 
 /// A function frame of a function in the call stack.
 #[derive(Debug, Copy, Clone)]
@@ -71,24 +65,23 @@ pub struct FunctionFrame {
     /// The program counter always points to the instruction
     /// that is going to executed next.
     pc: usize,
-}
-
-impl FunctionFrame {
-    /// Creates a new [`FunctionFrame`] from the given `func`.
+    /// The index of the first register of the function frame on the value stack.
     ///
-    /// # Panics
+    /// # Note
     ///
-    /// If the `func` has no instance handle, i.e. is not a Wasm function.
-    pub fn new(ctx: impl AsContext, func: Func) -> Self {
-        match func.as_internal(ctx.as_context()) {
-            FuncEntityInternal::Wasm(wasm_func) => Self::new_wasm(func, wasm_func),
-            FuncEntityInternal::Host(host_func) => panic!(
-                "cannot execute host functions using Wasm interpreter: {:?}",
-                host_func
-            ),
-        }
-    }
+    /// The registers of the function frame start with the function
+    /// parameters followed by its local variables and temporary values.
+    base: usize,
+    /// The index of the executed Wasm function within its module.
+    ///
+    /// # Note
+    ///
+    /// This is set upon the first initialization of the function frame.
+    #[cfg(any(feature = "trace", feature = "debugger"))]
+    func_index: u32,
+}
 
+impl FunctionFrame {
     /// Returns the program counter.
     pub(super) fn pc(&self) -> usize {
         self.pc
@@ -99,6 +92,35 @@ impl FunctionFrame {
         self.pc = new_pc;
     }
 
+    /// Returns the index of the first register of the function frame on the value stack.
+    pub(super) fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns the index of the executed Wasm function within its module.
+    #[cfg(any(feature = "trace", feature = "debugger"))]
+    pub(super) fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Looks up the index of the executed Wasm function if the function frame is not yet initialized.
+    #[cfg(any(feature = "trace", feature = "debugger"))]
+    pub(super) fn init_func_index(&mut self, code_map: &CodeMap) {
+        if !self.instantiated {
+            self.func_index = code_map.func_index(self.func_body);
+        }
+    }
+
+    /// Relocates the registers of the function frame from the value stack
+    /// offset `from` to the value stack offset `to`.
+    ///
+    /// # Note
+    ///
+    /// This is required when the values of suspended executions are moved.
+    pub(super) fn relocate(&mut self, from: usize, to: usize) {
+        self.base = self.base - from + to;
+    }
+
     /// Creates a new [`FunctionFrame`] from the given Wasm function entity.
     pub(super) fn new_wasm(func: Func, wasm_func: &WasmFuncEntity) -> Self {
         let instance = wasm_func.instance();
@@ -111,6 +133,9 @@ impl FunctionFrame {
             default_memory: None,
             default_table: None,
             pc: 0,
+            base: 0,
+            #[cfg(any(feature = "trace", feature = "debugger"))]
+            func_index: 0,
         }
     }
 
@@ -168,24 +193,31 @@ impl FunctionFrame {
     ///
     /// # Note
     ///
-    /// Does nothing if the function frame has already been initialized.
+    /// - Upon first initialization the function parameters are expected as the
+    ///   last values on the `value_stack`. They are followed by the zero initialized
+    ///   local variables and the registers for temporary values of the function.
+    /// - Upon re-entering an already initialized function frame, for example after
+    ///   returning from a nested call, the `value_stack` is restored to cover all
+    ///   registers of the function frame.
     pub fn initialize(
         &mut self,
         resolved_func_body: ResolvedFuncBody,
         value_stack: &mut ValueStack,
     ) -> Result<(), TrapCode> {
+        let max_stack_height = resolved_func_body.max_stack_height();
         if self.instantiated {
-            // Nothing to do if the function frame has already been initialized.
-            return Ok(());
+            return value_stack.resize(self.base + max_stack_height);
         }
-        let max_stack_height = resolved_func_body.max_stack_height();
-        value_stack.reserve(max_stack_height)?;
+        let len_params = resolved_func_body.len_params();
         let len_locals = resolved_func_body.len_locals();
+        self.base = value_stack.len() - len_params;
+        value_stack.reserve(max_stack_height - len_params)?;
         value_stack
             .extend_zeros(len_locals)
             .unwrap_or_else(|error| {
                 panic!("encountered stack overflow while pushing locals: {}", error)
             });
+        value_stack.resize(self.base + max_stack_height)?;
         self.instantiated = true;
         Ok(())
     }
@@ -243,15 +275,44 @@ impl CallStack {
         self.frames.len()
     }
 
-    /// Clears the [`CallStack`] entirely.
+    /// Returns an iterator over the [`FunctionFrame`] of the [`CallStack`].
+    ///
+    /// The iteration starts at the outermost [`FunctionFrame`].
+    pub fn iter(&self) -> slice::Iter<'_, FunctionFrame> {
+        self.frames.iter()
+    }
+
+    /// Pushes all `frames` to the [`CallStack`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`CallStack`] cannot fit all `frames` within its recursion limit.
+    pub fn extend(&mut self, frames: Vec<FunctionFrame>) -> Result<(), TrapCode> {
+        if self.len() + frames.len() > self.recursion_limit {
+            return Err(TrapCode::StackOverflow);
+        }
+        self.frames.extend(frames);
+        Ok(())
+    }
+
+    /// Removes all [`FunctionFrame`] starting at `len` from the [`CallStack`] and returns them.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is greater than the current length of the [`CallStack`].
+    pub fn split_off(&mut self, len: usize) -> Vec<FunctionFrame> {
+        self.frames.split_off(len)
+    }
+
+    /// Shortens the [`CallStack`] to the given `len`.
     ///
     /// # Note
     ///
     /// This is required since sometimes execution can halt in the middle of
     /// function execution which leaves the [`CallStack`] in an unspecified
-    /// state. Therefore the [`CallStack`] is required to be reset before
-    /// function execution happens.
-    pub fn clear(&mut self) {
-        self.frames.clear();
+    /// state. Therefore the [`CallStack`] is required to be reset to the
+    /// length it had before the function execution.
+    pub fn truncate(&mut self, len: usize) {
+        self.frames.truncate(len);
     }
 }
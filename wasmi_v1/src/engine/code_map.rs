@@ -1,13 +1,30 @@
 //! Datastructure to efficiently store function bodies and their instructions.
 
 use super::{super::Index, Instruction};
-use alloc::vec::Vec;
-use core::iter;
+use crate::module::UncompiledFuncBody;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{iter, mem};
 
 /// A reference to a Wasm function body stored in the [`CodeMap`].
+///
+/// # Note
+///
+/// References to lazily translated function bodies have their most significant
+/// bit set and refer to an entry of the lazily translated function bodies.
 #[derive(Debug, Copy, Clone)]
 pub struct FuncBody(usize);
 
+impl FuncBody {
+    /// The bit that marks references to lazily translated function bodies.
+    const LAZY_BIT: usize = 1 << (usize::BITS - 1);
+
+    /// Returns `true` if the [`FuncBody`] refers to a lazily translated function body.
+    #[inline]
+    pub fn is_lazy(self) -> bool {
+        self.0 & Self::LAZY_BIT != 0
+    }
+}
+
 impl Index for FuncBody {
     fn into_usize(self) -> usize {
         self.0
@@ -18,6 +35,78 @@ impl Index for FuncBody {
     }
 }
 
+/// A translated Wasm function body that is not yet allocated to a [`CodeMap`].
+///
+/// # Note
+///
+/// This allows to translate multiple Wasm function bodies concurrently
+/// and allocate them to the [`CodeMap`] in a deterministic order afterwards.
+#[derive(Debug)]
+pub struct TranslatedFuncBody {
+    /// The index of the Wasm function within its module.
+    func_index: u32,
+    /// The amount of parameters of the function.
+    len_params: usize,
+    /// The amount of local variables of the function.
+    len_locals: usize,
+    /// The maximum height of the value stack of the function.
+    max_stack_height: usize,
+    /// The `wasmi` instructions of the function body.
+    insts: Vec<Instruction>,
+    /// The original Wasm offsets of the instructions in `insts`.
+    offsets: Vec<u32>,
+}
+
+impl TranslatedFuncBody {
+    /// Creates a new [`TranslatedFuncBody`].
+    ///
+    /// The `insts` are paired with their original Wasm `offsets`.
+    /// The `func_index` is the index of the Wasm function within its module.
+    pub fn new(
+        func_index: u32,
+        len_params: usize,
+        len_locals: usize,
+        max_stack_height: usize,
+        insts: Vec<Instruction>,
+        offsets: Vec<u32>,
+    ) -> Self {
+        assert_eq!(insts.len(), offsets.len());
+        Self {
+            func_index,
+            len_params,
+            len_locals,
+            max_stack_height,
+            insts,
+            offsets,
+        }
+    }
+
+    /// Returns the amount of parameters of the function.
+    pub fn len_params(&self) -> usize {
+        self.len_params
+    }
+
+    /// Returns the amount of local variables of the function.
+    pub fn len_locals(&self) -> usize {
+        self.len_locals
+    }
+
+    /// Returns the maximum height of the value stack of the function.
+    pub fn max_stack_height(&self) -> usize {
+        self.max_stack_height
+    }
+
+    /// Returns the `wasmi` instructions of the function body.
+    pub fn insts(&self) -> &[Instruction] {
+        &self.insts
+    }
+
+    /// Returns the original Wasm offsets of the instructions of the function body.
+    pub fn offsets(&self) -> &[u32] {
+        &self.offsets
+    }
+}
+
 /// Datastructure to efficiently store Wasm function bodies.
 #[derive(Debug, Default)]
 pub struct CodeMap {
@@ -31,12 +120,64 @@ pub struct CodeMap {
     /// Also this improves efficiency of deallocating the [`CodeMap`]
     /// and generally improves data locality.
     insts: Vec<Instruction>,
+    /// The original Wasm offsets of all instructions in `insts`.
+    ///
+    /// # Note
+    ///
+    /// This is used to map `wasmi` bytecode back to the original Wasm
+    /// bytecode, e.g. when creating backtraces upon traps.
+    /// The artificial instructions surrounding function bodies have an
+    /// offset of zero.
+    offsets: Vec<u32>,
+    /// The Wasm function indices of all allocated function bodies.
+    func_indices: BTreeMap<usize, u32>,
+    /// The ranges of `insts` of freed function bodies.
+    ///
+    /// # Note
+    ///
+    /// Maps the start of each range to its length. Adjacent ranges are merged
+    /// and ranges at the end of `insts` are truncated. The remaining ranges are
+    /// reused by later allocated function bodies.
+    free_ranges: BTreeMap<usize, usize>,
+    /// All lazily translated function bodies.
+    lazy: Vec<LazyFuncBody>,
+    /// The indices of freed entries of `lazy` that can be reused.
+    free_lazy: Vec<usize>,
+}
+
+/// The state of a lazily translated function body.
+#[derive(Debug)]
+enum LazyFuncBody {
+    /// The function body has not yet been translated.
+    Uncompiled(UncompiledFuncBody),
+    /// The function body has been translated into the referenced function body.
+    Compiled(FuncBody),
+    /// The function body has been freed.
+    Freed,
 }
 
 impl CodeMap {
-    /// Returns the next [`FuncBody`] index.
-    fn next_index(&self) -> FuncBody {
-        FuncBody(self.insts.len())
+    /// Returns the next [`FuncBody`] index for a function body with `len` instructions.
+    ///
+    /// # Note
+    ///
+    /// Reuses the first freed range of instructions that is large enough if any.
+    fn next_index(&mut self, len: usize) -> FuncBody {
+        let reused = self
+            .free_ranges
+            .iter()
+            .find(|(_, &free_len)| free_len >= len)
+            .map(|(&start, &free_len)| (start, free_len));
+        match reused {
+            Some((start, free_len)) => {
+                self.free_ranges.remove(&start);
+                if free_len > len {
+                    self.free_ranges.insert(start + len, free_len - len);
+                }
+                FuncBody(start)
+            }
+            None => FuncBody(self.insts.len()),
+        }
     }
 
     /// Allocates a new function body to the [`CodeMap`].
@@ -44,16 +185,21 @@ impl CodeMap {
     /// Returns a reference to the allocated function body that can
     /// be used with [`CodeMap::resolve`] in order to resolve its
     /// instructions.
-    pub fn alloc<I>(&mut self, len_locals: usize, max_stack_height: usize, insts: I) -> FuncBody
-    where
-        I: IntoIterator<Item = Instruction>,
-        I::IntoIter: ExactSizeIterator,
-    {
-        let idx = self.next_index();
+    pub fn alloc(&mut self, func_body: TranslatedFuncBody) -> FuncBody {
+        let TranslatedFuncBody {
+            func_index,
+            len_params,
+            len_locals,
+            max_stack_height,
+            insts,
+            offsets,
+        } = func_body;
+        // The function body is surrounded by its `FuncBodyStart` and `FuncBodyEnd`.
+        let idx = self.next_index(insts.len() + 2);
         // We are inserting an artificial `unreachable` Wasm instruction
         // in between instructions of different function bodies as a small
         // safety precaution.
-        let insts = insts.into_iter();
+        let insts = insts.into_iter().zip(offsets);
         let len_instructions = insts.len().try_into().unwrap_or_else(|error| {
             panic!(
                 "encountered too many instructions (= {}) for function: {}",
@@ -61,7 +207,7 @@ impl CodeMap {
                 error
             )
         });
-        let max_stack_height = (max_stack_height + len_locals)
+        let max_stack_height = (max_stack_height + len_params + len_locals)
             .try_into()
             .unwrap_or_else(|error| {
                 panic!(
@@ -75,16 +221,221 @@ impl CodeMap {
                 len_locals, error
             )
         });
-        let start = iter::once(Instruction::FuncBodyStart {
-            len_instructions,
-            len_locals,
-            max_stack_height,
+        let len_params = len_params.try_into().unwrap_or_else(|error| {
+            panic!(
+                "encountered too many parameters (= {}) for function: {}",
+                len_params, error
+            )
         });
-        let end = iter::once(Instruction::FuncBodyEnd);
-        self.insts.extend(start.chain(insts).chain(end));
+        let start = iter::once((
+            Instruction::FuncBodyStart {
+                len_instructions,
+                len_locals,
+                max_stack_height,
+                len_params,
+            },
+            0,
+        ));
+        let end = iter::once((Instruction::FuncBodyEnd, 0));
+        for (n, (inst, offset)) in start.chain(insts).chain(end).enumerate() {
+            let index = idx.into_usize() + n;
+            match index < self.insts.len() {
+                true => {
+                    self.insts[index] = inst;
+                    self.offsets[index] = offset;
+                }
+                false => {
+                    self.insts.push(inst);
+                    self.offsets.push(offset);
+                }
+            }
+        }
+        self.func_indices.insert(idx.into_usize(), func_index);
         idx
     }
 
+    /// Frees the function body from the [`CodeMap`].
+    ///
+    /// # Note
+    ///
+    /// - Freeing a lazily translated function body also frees its translation.
+    /// - The freed instructions are reused by later allocated function bodies.
+    ///   Therefore `func_body` and all copies of it must no longer be used afterwards.
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    pub fn free(&mut self, func_body: FuncBody) {
+        if func_body.is_lazy() {
+            let index = Self::lazy_index(func_body);
+            match mem::replace(&mut self.lazy[index], LazyFuncBody::Freed) {
+                LazyFuncBody::Uncompiled(_) => {}
+                LazyFuncBody::Compiled(compiled) => self.free(compiled),
+                LazyFuncBody::Freed => {
+                    panic!("tried to free already freed function body: {:?}", func_body)
+                }
+            }
+            self.free_lazy.push(index);
+            return;
+        }
+        let start = func_body.into_usize();
+        let len = self.resolve(func_body).insts.len() + 2;
+        self.func_indices.remove(&start);
+        self.free_range(start, len);
+    }
+
+    /// Marks the instructions of the range as free.
+    ///
+    /// Merges the range with adjacent free ranges and truncates
+    /// the instructions if the range ends up at their end.
+    fn free_range(&mut self, mut start: usize, mut len: usize) {
+        if let Some(next_len) = self.free_ranges.remove(&(start + len)) {
+            len += next_len;
+        }
+        let previous = self
+            .free_ranges
+            .range(..start)
+            .next_back()
+            .map(|(&previous, &previous_len)| (previous, previous_len));
+        if let Some((previous, previous_len)) = previous {
+            if previous + previous_len == start {
+                self.free_ranges.remove(&previous);
+                start = previous;
+                len += previous_len;
+            }
+        }
+        if start + len == self.insts.len() {
+            self.insts.truncate(start);
+            self.offsets.truncate(start);
+            return;
+        }
+        self.free_ranges.insert(start, len);
+    }
+
+    /// Returns the number of allocated instructions including
+    /// the ones of freed function bodies that have not been truncated.
+    #[cfg(test)]
+    pub fn len_insts(&self) -> usize {
+        self.insts.len()
+    }
+
+    /// Allocates a new lazily translated function body to the [`CodeMap`].
+    ///
+    /// # Note
+    ///
+    /// The returned [`FuncBody`] must be resolved via [`CodeMap::compiled`]
+    /// before its instructions can be resolved.
+    pub fn alloc_lazy(&mut self, func_body: UncompiledFuncBody) -> FuncBody {
+        let entry = LazyFuncBody::Uncompiled(func_body);
+        let index = match self.free_lazy.pop() {
+            Some(index) => {
+                self.lazy[index] = entry;
+                index
+            }
+            None => {
+                self.lazy.push(entry);
+                self.lazy.len() - 1
+            }
+        };
+        FuncBody(index | FuncBody::LAZY_BIT)
+    }
+
+    /// Returns the index of the lazily translated function body.
+    fn lazy_index(func_body: FuncBody) -> usize {
+        debug_assert!(func_body.is_lazy());
+        func_body.0 & !FuncBody::LAZY_BIT
+    }
+
+    /// Returns the translated function body of `func_body`.
+    ///
+    /// # Note
+    ///
+    /// - Returns `func_body` itself if it is not lazily translated.
+    /// - Returns the [`UncompiledFuncBody`] of `func_body` if it is
+    ///   lazily translated and has not yet been translated.
+    #[inline]
+    pub fn compiled(&self, func_body: FuncBody) -> Result<FuncBody, &UncompiledFuncBody> {
+        if !func_body.is_lazy() {
+            return Ok(func_body);
+        }
+        match &self.lazy[Self::lazy_index(func_body)] {
+            LazyFuncBody::Compiled(compiled) => Ok(*compiled),
+            LazyFuncBody::Uncompiled(uncompiled) => Err(uncompiled),
+            LazyFuncBody::Freed => panic!("encountered freed function body: {:?}", func_body),
+        }
+    }
+
+    /// Marks the lazily translated `func_body` as translated into `compiled`.
+    ///
+    /// Returns the translated function body.
+    ///
+    /// # Note
+    ///
+    /// If `func_body` has concurrently been translated already
+    /// the previously translated function body is kept and returned
+    /// while `compiled` is freed.
+    pub fn set_compiled(&mut self, func_body: FuncBody, compiled: FuncBody) -> FuncBody {
+        let entry = &mut self.lazy[Self::lazy_index(func_body)];
+        match entry {
+            LazyFuncBody::Compiled(previous) => {
+                let previous = *previous;
+                self.free(compiled);
+                previous
+            }
+            LazyFuncBody::Uncompiled(_) => {
+                *entry = LazyFuncBody::Compiled(compiled);
+                compiled
+            }
+            LazyFuncBody::Freed => panic!("encountered freed function body: {:?}", func_body),
+        }
+    }
+
+    /// Returns the Wasm function index of the function body.
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    pub fn func_index(&self, func_body: FuncBody) -> u32 {
+        self.func_indices
+            .get(&func_body.into_usize())
+            .copied()
+            .unwrap_or_else(|| panic!("encountered invalid function body: {:?}", func_body))
+    }
+
+    /// Returns the original Wasm offset of the instruction at `pc` of the function body.
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    pub fn wasm_offset(&self, func_body: FuncBody, pc: usize) -> usize {
+        // The first instruction of a function body follows its `FuncBodyStart`.
+        self.offsets[func_body.into_usize() + 1 + pc] as usize
+    }
+
+    /// Returns a copy of the translated function body.
+    ///
+    /// # Note
+    ///
+    /// This is the inverse operation of [`CodeMap::alloc`].
+    ///
+    /// # Panics
+    ///
+    /// If the given `func_body` is invalid for this [`CodeMap`].
+    pub fn translated(&self, func_body: FuncBody) -> TranslatedFuncBody {
+        let resolved = self.resolve(func_body);
+        // The first instruction of a function body follows its `FuncBodyStart`.
+        let first_inst = func_body.into_usize() + 1;
+        let offsets = &self.offsets[first_inst..first_inst + resolved.insts.len()];
+        TranslatedFuncBody::new(
+            self.func_index(func_body),
+            resolved.len_params(),
+            resolved.len_locals(),
+            resolved.max_stack_height() - resolved.len_params() - resolved.len_locals(),
+            resolved.insts.to_vec(),
+            offsets.to_vec(),
+        )
+    }
+
     /// Resolves the instruction of the function body.
     ///
     /// # Panics
@@ -92,18 +443,26 @@ impl CodeMap {
     /// If the given `func_body` is invalid for this [`CodeMap`].
     pub fn resolve(&self, func_body: FuncBody) -> ResolvedFuncBody {
         let offset = func_body.into_usize();
-        let (len_instructions, len_locals, max_stack_height) = match &self.insts[offset] {
+        let (len_instructions, len_params, len_locals, max_stack_height) = match &self.insts[offset]
+        {
             Instruction::FuncBodyStart {
                 len_instructions,
+                len_params,
                 len_locals,
                 max_stack_height,
-            } => (*len_instructions, *len_locals, *max_stack_height),
+            } => (
+                *len_instructions,
+                *len_params,
+                *len_locals,
+                *max_stack_height,
+            ),
             unexpected => panic!(
                 "expected function start instruction but found: {:?}",
                 unexpected
             ),
         };
         let len_instructions = len_instructions as usize;
+        let len_params = len_params as usize;
         let len_locals = len_locals as usize;
         let max_stack_height = max_stack_height as usize;
         // The index of the first instruction in the function body.
@@ -125,6 +484,9 @@ impl CodeMap {
         let insts = &self.insts[first_inst..(first_inst + len_instructions)];
         ResolvedFuncBody {
             insts,
+            #[cfg(feature = "debugger")]
+            offsets: &self.offsets[first_inst..(first_inst + len_instructions)],
+            len_params,
             len_locals,
             max_stack_height,
         }
@@ -144,6 +506,10 @@ impl CodeMap {
 #[derive(Debug, Copy, Clone)]
 pub struct ResolvedFuncBody<'a> {
     insts: &'a [Instruction],
+    /// The original Wasm offsets of the instructions in `insts`.
+    #[cfg(feature = "debugger")]
+    offsets: &'a [u32],
+    len_params: usize,
     len_locals: usize,
     max_stack_height: usize,
 }
@@ -159,20 +525,38 @@ impl ResolvedFuncBody<'_> {
         self.insts.get(index)
     }
 
+    /// Returns the amount of parameters of the function.
+    pub fn len_params(&self) -> usize {
+        self.len_params
+    }
+
     /// Returns the amount of local variable of the function.
     pub fn len_locals(&self) -> usize {
         self.len_locals
     }
 
-    /// Returns the amount of stack values required by the function.
+    /// Returns the amount of registers required by the function.
     ///
     /// # Note
     ///
-    /// This amount includes the amount of local variables but does
-    /// _not_ include the amount of input parameters to the function.
+    /// This amount includes the amount of input parameters and
+    /// local variables of the function.
     pub fn max_stack_height(&self) -> usize {
         self.max_stack_height
     }
+
+    /// Returns the original Wasm offset of the instruction at `pc`.
+    #[cfg(feature = "debugger")]
+    pub fn wasm_offset(&self, pc: usize) -> usize {
+        self.offsets[pc] as usize
+    }
+
+    /// Returns `true` if the instruction at `pc` is the first instruction
+    /// translated from the Wasm instruction at its original Wasm offset.
+    #[cfg(feature = "debugger")]
+    pub fn starts_wasm_instr(&self, pc: usize) -> bool {
+        pc == 0 || self.offsets[pc - 1] != self.offsets[pc]
+    }
 }
 
 impl<'a> ResolvedFuncBody<'a> {
@@ -0,0 +1,516 @@
+//! Coverage of executed Wasm functions, instructions and branches.
+
+use super::{bytecode::Instruction, CodeMap, FuncBody};
+use crate::{arena::Index, module::ModuleNames};
+use std::{
+    collections::BTreeMap,
+    format,
+    io,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+/// The execution count of the `wasmi` bytecode translated from the Wasm instruction at a Wasm offset.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OffsetCoverage {
+    /// The offset of the Wasm instruction within the Wasm binary.
+    wasm_offset: usize,
+    /// The number of times the Wasm instruction has been executed.
+    hits: u64,
+}
+
+impl OffsetCoverage {
+    /// Returns the offset of the Wasm instruction within the Wasm binary.
+    pub fn wasm_offset(&self) -> usize {
+        self.wasm_offset
+    }
+
+    /// Returns the number of times the Wasm instruction has been executed.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+}
+
+/// The execution counts of the outcomes of a conditional or multi-way branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// The offset of the branching Wasm instruction within the Wasm binary.
+    wasm_offset: usize,
+    /// The number of times each outcome of the branch has been taken.
+    outcomes: Vec<u64>,
+}
+
+impl BranchCoverage {
+    /// Returns the offset of the branching Wasm instruction within the Wasm binary.
+    pub fn wasm_offset(&self) -> usize {
+        self.wasm_offset
+    }
+
+    /// Returns the number of times each outcome of the branch has been taken.
+    ///
+    /// # Note
+    ///
+    /// - Conditional branches have two outcomes: the branch is taken or not taken.
+    /// - A `br_table` has one outcome per branch target followed by its default target.
+    pub fn outcomes(&self) -> &[u64] {
+        &self.outcomes
+    }
+}
+
+/// The coverage of a single Wasm function body.
+#[derive(Debug, Clone)]
+pub struct FuncCoverage {
+    /// The name of the Wasm module of the function if any.
+    module_name: Option<Arc<str>>,
+    /// The index of the function within its Wasm module.
+    func_index: u32,
+    /// The name of the function if any.
+    func_name: Option<Arc<str>>,
+    /// The number of calls to the function.
+    calls: u64,
+    /// The execution counts of all Wasm instructions of the function ordered by their Wasm offset.
+    offsets: Vec<OffsetCoverage>,
+    /// The execution counts of all branches of the function ordered by their Wasm offset.
+    branches: Vec<BranchCoverage>,
+}
+
+impl FuncCoverage {
+    /// Returns the name of the Wasm module of the function if any.
+    pub fn module_name(&self) -> Option<&str> {
+        self.module_name.as_deref()
+    }
+
+    /// Returns the index of the function within its Wasm module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the function if any.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the number of calls to the function.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the execution counts of the Wasm instructions of the function.
+    ///
+    /// # Note
+    ///
+    /// Wasm instructions that are not translated into their own `wasmi` bytecode,
+    /// such as `local.get` or `i32.const`, are not included.
+    pub fn offsets(&self) -> &[OffsetCoverage] {
+        &self.offsets
+    }
+
+    /// Returns the execution counts of the branches of the function.
+    pub fn branches(&self) -> &[BranchCoverage] {
+        &self.branches
+    }
+
+    /// Returns the name of the function used in coverage reports.
+    fn label(&self) -> String {
+        match &self.func_name {
+            Some(name) => name.to_string(),
+            None => format!("func[{}]", self.func_index),
+        }
+    }
+}
+
+/// The coverage of all Wasm functions of a [`Store`] since coverage has been enabled.
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    /// The coverage of all Wasm function bodies in the order of their compilation.
+    funcs: Vec<FuncCoverage>,
+}
+
+impl Coverage {
+    /// Returns the coverage of all Wasm function bodies.
+    ///
+    /// # Note
+    ///
+    /// Instances of the same Wasm module share their function bodies
+    /// and thus their coverage.
+    pub fn funcs(&self) -> &[FuncCoverage] {
+        &self.funcs
+    }
+
+    /// Returns `true` if the Wasm modules of the covered functions provide names
+    /// via their Wasm name section.
+    pub fn has_names(&self) -> bool {
+        self.funcs
+            .iter()
+            .any(|func| func.module_name.is_some() || func.func_name.is_some())
+    }
+
+    /// Writes the coverage as lcov tracefile.
+    ///
+    /// # Note
+    ///
+    /// - Every Wasm module is a source file named after its module name.
+    /// - Every Wasm offset of a Wasm instruction is a line.
+    /// - Every branch is identified by its Wasm offset and branch number.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the `writer` fails.
+    pub fn write_lcov(&self, mut writer: impl io::Write) -> io::Result<()> {
+        let mut modules = BTreeMap::<&str, Vec<&FuncCoverage>>::new();
+        for func in &self.funcs {
+            let module = func.module_name().unwrap_or("wasm");
+            modules.entry(module).or_default().push(func);
+        }
+        for (module, funcs) in modules {
+            writeln!(writer, "TN:")?;
+            writeln!(writer, "SF:{module}")?;
+            for func in &funcs {
+                let line = func.offsets.first().map(|offset| offset.wasm_offset);
+                writeln!(writer, "FN:{},{}", line.unwrap_or(0), func.label())?;
+            }
+            for func in &funcs {
+                writeln!(writer, "FNDA:{},{}", func.calls, func.label())?;
+            }
+            let hit_funcs = funcs.iter().filter(|func| func.calls > 0).count();
+            writeln!(writer, "FNF:{}", funcs.len())?;
+            writeln!(writer, "FNH:{hit_funcs}")?;
+            let (mut branches, mut hit_branches) = (0, 0);
+            for func in &funcs {
+                for (block, branch) in func.branches.iter().enumerate() {
+                    let executed = branch.outcomes.iter().any(|&count| count > 0);
+                    for (number, count) in branch.outcomes.iter().enumerate() {
+                        let taken = match executed {
+                            true => count.to_string(),
+                            false => String::from("-"),
+                        };
+                        writeln!(
+                            writer,
+                            "BRDA:{},{block},{number},{taken}",
+                            branch.wasm_offset
+                        )?;
+                        branches += 1;
+                        hit_branches += usize::from(*count > 0);
+                    }
+                }
+            }
+            writeln!(writer, "BRF:{branches}")?;
+            writeln!(writer, "BRH:{hit_branches}")?;
+            let (mut lines, mut hit_lines) = (0, 0);
+            for func in &funcs {
+                for offset in &func.offsets {
+                    writeln!(writer, "DA:{},{}", offset.wasm_offset, offset.hits)?;
+                    lines += 1;
+                    hit_lines += usize::from(offset.hits > 0);
+                }
+            }
+            writeln!(writer, "LF:{lines}")?;
+            writeln!(writer, "LH:{hit_lines}")?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the coverage as plain text using raw Wasm offsets.
+    ///
+    /// # Note
+    ///
+    /// Every function starts with a `func <module> <index> <calls>` line followed by
+    /// an `offset <wasm offset> <hits>` line per Wasm instruction and a
+    /// `branch <wasm offset> <outcomes>` line per branch with comma separated outcomes.
+    /// The `<module>` is `-` for Wasm modules without a name.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the `writer` fails.
+    pub fn write_raw(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for func in &self.funcs {
+            let module = func.module_name().unwrap_or("-");
+            writeln!(writer, "func {module} {} {}", func.func_index, func.calls)?;
+            for offset in &func.offsets {
+                writeln!(writer, "offset {:#x} {}", offset.wasm_offset, offset.hits)?;
+            }
+            for branch in &func.branches {
+                let outcomes = branch
+                    .outcomes
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                writeln!(writer, "branch {:#x} {outcomes}", branch.wasm_offset)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records the executed `wasmi` bytecode instructions within a [`Store`].
+///
+/// # Note
+///
+/// The counts are indexed by the position of the instructions within the [`CodeMap`].
+/// The position of the [`Instruction::FuncBodyStart`] of a function body counts its calls.
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Default)]
+pub(crate) struct CoverageRecorder {
+    /// The number of times every instruction has been executed.
+    hits: Vec<u64>,
+    /// The number of times every instruction has been followed by the next instruction.
+    ///
+    /// # Note
+    ///
+    /// This is used to tell how often conditional branches have not been taken.
+    fallthroughs: Vec<u64>,
+}
+
+/// Increments the count at `index` of `counts`.
+#[inline]
+fn increment(counts: &mut Vec<u64>, index: usize) {
+    if index >= counts.len() {
+        counts.resize(index + 1, 0);
+    }
+    counts[index] += 1;
+}
+
+/// Returns the position of the instruction at `pc` of the `func_body` within the [`CodeMap`].
+fn position(func_body: FuncBody, pc: usize) -> usize {
+    // The first instruction of a function body follows its `FuncBodyStart`.
+    func_body.into_usize() + 1 + pc
+}
+
+impl CoverageRecorder {
+    /// Counts a call to the `func_body`.
+    pub fn enter(&mut self, func_body: FuncBody) {
+        increment(&mut self.hits, func_body.into_usize());
+    }
+
+    /// Counts the execution of the instruction at `pc` of the `func_body`.
+    ///
+    /// The `last_pc` is the `pc` of the previously executed instruction of the same function frame if any.
+    #[inline]
+    pub fn hit(&mut self, func_body: FuncBody, pc: usize, last_pc: Option<usize>) {
+        increment(&mut self.hits, position(func_body, pc));
+        if let Some(last_pc) = last_pc {
+            if last_pc + 1 == pc {
+                increment(&mut self.fallthroughs, position(func_body, last_pc));
+            }
+        }
+    }
+
+    /// Returns the count of `counts` at `index`.
+    fn count(counts: &[u64], index: usize) -> u64 {
+        counts.get(index).copied().unwrap_or(0)
+    }
+
+    /// Returns the [`FuncCoverage`] of the compiled `func_body` of a Wasm module with the given `names`.
+    pub fn func_coverage(
+        &self,
+        code_map: &CodeMap,
+        func_body: FuncBody,
+        names: &ModuleNames,
+    ) -> FuncCoverage {
+        let translated = code_map.translated(func_body);
+        let func_index = code_map.func_index(func_body);
+        let hits = |pc| Self::count(&self.hits, position(func_body, pc));
+        let mut offsets = BTreeMap::<usize, u64>::new();
+        let mut branches = Vec::new();
+        let mut pc = 0;
+        while let Some(instr) = translated.insts().get(pc) {
+            let wasm_offset = translated.offsets()[pc] as usize;
+            // The `wasmi` bytecode translated from a single Wasm instruction
+            // is executed as many times as its most executed instruction.
+            let count = offsets.entry(wasm_offset).or_default();
+            *count = (*count).max(hits(pc));
+            match instr {
+                Instruction::BrTable { len_targets, .. } => {
+                    // The targets of a `br_table` are the instructions following it.
+                    let outcomes = (1..=*len_targets).map(|n| hits(pc + n)).collect();
+                    branches.push(BranchCoverage {
+                        wasm_offset,
+                        outcomes,
+                    });
+                    pc += len_targets;
+                }
+                instr if instr.is_conditional_branch() => {
+                    let fallthroughs = Self::count(&self.fallthroughs, position(func_body, pc));
+                    let taken = hits(pc).saturating_sub(fallthroughs);
+                    branches.push(BranchCoverage {
+                        wasm_offset,
+                        outcomes: Vec::from([taken, fallthroughs]),
+                    });
+                }
+                _ => {}
+            }
+            pc += 1;
+        }
+        FuncCoverage {
+            module_name: names.module().cloned(),
+            func_index,
+            func_name: names.func(func_index).cloned(),
+            calls: Self::count(&self.hits, func_body.into_usize()),
+            offsets: offsets
+                .into_iter()
+                .map(|(wasm_offset, hits)| OffsetCoverage { wasm_offset, hits })
+                .collect(),
+            branches,
+        }
+    }
+}
+
+impl Coverage {
+    /// Creates a new [`Coverage`] from the coverage of all Wasm function bodies.
+    pub(crate) fn new(funcs: Vec<FuncCoverage>) -> Self {
+        Self { funcs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coverage, FuncCoverage};
+    use crate::{core::Value, Engine, Extern, Instance, Module, Store};
+    use std::{string::String, vec::Vec};
+
+    const WAT: &str = r#"
+        (module $test
+            (func $run (export "run") (param $n i32) (result i32)
+                (if (result i32) (local.get $n)
+                    (then (call $double (local.get $n)))
+                    (else (i32.const 0))
+                )
+            )
+            (func $double (param i32) (result i32)
+                (i32.add (local.get 0) (local.get 0))
+            )
+            (func $unused (result i32)
+                (i32.const 1)
+            )
+            (func $switch (export "switch") (param i32) (result i32)
+                (block
+                    (block
+                        (block
+                            (br_table 0 1 2 (local.get 0))
+                        )
+                        (return (i32.const 10))
+                    )
+                    (return (i32.const 20))
+                )
+                (i32.const 30)
+            )
+        )
+    "#;
+
+    /// Instantiates the [`WAT`] module.
+    fn setup() -> (Store<()>, Instance) {
+        let wasm = wat::parse_str(WAT).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = module
+            .instantiate(&mut store, [])
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        (store, instance)
+    }
+
+    /// Calls the exported function `name` of the `instance` with `input`.
+    fn call(store: &mut Store<()>, instance: Instance, name: &str, input: i32) -> i32 {
+        let func = instance
+            .get_export(&*store, name)
+            .and_then(Extern::into_func)
+            .unwrap();
+        let mut results = [Value::I32(0)];
+        func.call(&mut *store, &[Value::I32(input)], &mut results)
+            .unwrap();
+        results[0].try_into::<i32>().unwrap()
+    }
+
+    /// Returns the [`FuncCoverage`] of the function with the given `name`.
+    fn func<'a>(coverage: &'a Coverage, name: &str) -> &'a FuncCoverage {
+        coverage
+            .funcs()
+            .iter()
+            .find(|func| func.func_name() == Some(name))
+            .unwrap_or_else(|| panic!("missing coverage of {name}"))
+    }
+
+    #[test]
+    fn coverage_is_disabled_by_default() {
+        let (mut store, instance) = setup();
+        assert_eq!(call(&mut store, instance, "run", 1), 2);
+        assert!(store.coverage().is_none());
+    }
+
+    #[test]
+    fn coverage_counts_calls_and_branches() {
+        let (mut store, instance) = setup();
+        store.enable_coverage();
+        for input in [1, 0, 0] {
+            call(&mut store, instance, "run", input);
+        }
+        let coverage = store.coverage().unwrap();
+        assert_eq!(coverage.funcs().len(), 4);
+        let run = func(&coverage, "run");
+        assert_eq!(run.module_name(), Some("test"));
+        assert_eq!((run.func_index(), run.calls()), (0, 3));
+        assert!(run.offsets().iter().any(|offset| offset.hits() == 3));
+        // The `if` is taken once and not taken twice in either order of its outcomes.
+        assert_eq!(run.branches().len(), 1);
+        let mut outcomes = run.branches()[0].outcomes().to_vec();
+        outcomes.sort_unstable();
+        assert_eq!(outcomes, [1, 2]);
+        assert_eq!(func(&coverage, "double").calls(), 1);
+        // Functions that have never been executed are reported as well.
+        let unused = func(&coverage, "unused");
+        assert_eq!(unused.calls(), 0);
+        assert!(!unused.offsets().is_empty());
+        assert!(unused.offsets().iter().all(|offset| offset.hits() == 0));
+        // Disabling coverage discards the collected coverage.
+        store.disable_coverage();
+        assert!(store.coverage().is_none());
+    }
+
+    #[test]
+    fn coverage_counts_br_table_targets() {
+        let (mut store, instance) = setup();
+        store.enable_coverage();
+        for (input, expected) in [(0, 10), (1, 20), (1, 20), (5, 30)] {
+            assert_eq!(call(&mut store, instance, "switch", input), expected);
+        }
+        let coverage = store.coverage().unwrap();
+        let switch = func(&coverage, "switch");
+        assert_eq!(switch.calls(), 4);
+        assert_eq!(switch.branches().len(), 1);
+        assert_eq!(switch.branches()[0].outcomes(), [1, 2, 1]);
+    }
+
+    #[test]
+    fn coverage_reports_work() {
+        let (mut store, instance) = setup();
+        store.enable_coverage();
+        call(&mut store, instance, "run", 1);
+        let coverage = store.coverage().unwrap();
+        assert!(coverage.has_names());
+        let mut lcov = Vec::new();
+        coverage.write_lcov(&mut lcov).unwrap();
+        let lcov = String::from_utf8(lcov).unwrap();
+        let lines = lcov.lines().collect::<Vec<_>>();
+        assert_eq!(lines[..2], ["TN:", "SF:test"]);
+        assert!(lines.contains(&"FNDA:1,run"));
+        assert!(lines.contains(&"FNDA:0,unused"));
+        assert!(lines.contains(&"FNF:4"));
+        assert!(lines.contains(&"FNH:2"));
+        assert!(lines.iter().any(|line| line.starts_with("BRDA:")));
+        assert_eq!(lines.last(), Some(&"end_of_record"));
+        let mut raw = Vec::new();
+        coverage.write_raw(&mut raw).unwrap();
+        let raw = String::from_utf8(raw).unwrap();
+        assert!(raw.lines().any(|line| line == "func test 0 1"));
+        assert!(raw.lines().any(|line| line == "func test 2 0"));
+        assert!(raw.lines().any(|line| line.starts_with("offset 0x")));
+    }
+}
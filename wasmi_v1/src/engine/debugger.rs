@@ -0,0 +1,607 @@
+//! Breakpoints, stepping and state inspection of paused Wasm executions.
+
+use super::{resumable::SuspendedState, EngineInner, FunctionFrame};
+use crate::{
+    core::{UntypedValue, Value},
+    errors::FuncError,
+    AsContext,
+    AsContextMut,
+    Error,
+    FrameInfo,
+    Func,
+    Global,
+    Memory,
+};
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+use core::{fmt, mem};
+
+/// Determines how a paused or newly started debug execution proceeds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StepMode {
+    /// Runs until a breakpoint is hit or the execution finishes.
+    Continue,
+    /// Pauses before the next executed Wasm instruction, stepping into calls.
+    StepInto,
+    /// Pauses before the next executed Wasm instruction of the current function
+    /// or of one of its callers, stepping over calls.
+    StepOver,
+    /// Pauses before the next executed Wasm instruction of a caller of the current function.
+    StepOut,
+}
+
+/// The reason why a debug execution has been paused.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PauseReason {
+    /// The execution hit a breakpoint.
+    Breakpoint,
+    /// The execution finished a step requested via [`StepMode`].
+    Step,
+}
+
+/// Returned by calling a [`Func`] with the debugger.
+///
+/// See [`Func::call_debug`] for more information.
+#[derive(Debug)]
+pub enum DebugCall {
+    /// The debug call has finished properly and returned its results.
+    Finished,
+    /// The debug call was paused at a breakpoint or after a step.
+    ///
+    /// The paused call can be inspected via [`PausedInvocation::frames`]
+    /// and continued via [`PausedInvocation::resume`].
+    Paused(PausedInvocation),
+}
+
+/// State required to inspect and resume a paused [`Func`] invocation.
+///
+/// # Note
+///
+/// While paused the [`Store`] can be used to inspect global variables
+/// and linear memories or to execute other functions.
+///
+/// [`Store`]: [`crate::Store`]
+pub struct PausedInvocation {
+    /// The [`Func`] that was originally called.
+    func: Func,
+    /// The reason why the invocation has been paused.
+    reason: PauseReason,
+    /// The suspended state of the invocation.
+    state: SuspendedState,
+}
+
+impl fmt::Debug for PausedInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PausedInvocation")
+            .field("func", &self.func)
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+impl PausedInvocation {
+    /// Creates a new [`PausedInvocation`].
+    pub(super) fn new(func: Func, reason: PauseReason, state: SuspendedState) -> Self {
+        Self {
+            func,
+            reason,
+            state,
+        }
+    }
+
+    /// Returns the [`Func`] that was originally called.
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the reason why the invocation has been paused.
+    pub fn reason(&self) -> PauseReason {
+        self.reason
+    }
+
+    /// Returns the function frames of the paused invocation.
+    ///
+    /// The first [`DebugFrame`] refers to the paused Wasm function, followed by its callers.
+    /// The Wasm offset of the first [`DebugFrame`] refers to the Wasm instruction that is
+    /// executed next while the Wasm offsets of its callers refer to their call instructions.
+    pub fn frames(&self, ctx: impl AsContext) -> Vec<DebugFrame> {
+        let engine = ctx.as_context().store.engine().clone();
+        let inner = engine.inner.read();
+        inner.debug_frames(&ctx, &self.state)
+    }
+
+    /// Returns the suspended state of the [`PausedInvocation`].
+    pub(super) fn into_state(self) -> SuspendedState {
+        self.state
+    }
+
+    /// Resumes the paused invocation according to the given [`StepMode`].
+    ///
+    /// The results of the original [`Func`] are written into the `outputs`
+    /// buffer once the invocation has finished.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of the original [`Func`].
+    /// - If the resumed execution traps or a host function returns an error.
+    /// - If an instance of the paused invocation has been dropped from the
+    ///   [`Store`] in the meantime.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn resume<C>(
+        self,
+        mut ctx: C,
+        mode: StepMode,
+        outputs: &mut [Value],
+    ) -> Result<DebugCall, Error>
+    where
+        C: AsContextMut,
+    {
+        self.state.ensure_alive(&ctx)?;
+        if self.func.func_type(&ctx).results().len() != outputs.len() {
+            return Err(Error::Func(FuncError::MismatchingResults {
+                func: self.func,
+            }));
+        }
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().resume_func_debug(
+            ctx.as_context_mut(),
+            self,
+            mode,
+            outputs,
+        )
+    }
+}
+
+/// A Wasm function frame of a [`PausedInvocation`].
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    /// Information about the executed Wasm function.
+    info: FrameInfo,
+    /// The values of the function parameters followed by the local variables.
+    locals: Box<[UntypedValue]>,
+    /// The values of the registers for temporary values.
+    operands: Box<[UntypedValue]>,
+}
+
+impl DebugFrame {
+    /// Returns information about the executed Wasm function and its current Wasm offset.
+    pub fn info(&self) -> &FrameInfo {
+        &self.info
+    }
+
+    /// Returns the values of the function parameters followed by its local variables.
+    ///
+    /// # Note
+    ///
+    /// The values are untyped since `wasmi` does not store type information at runtime.
+    pub fn locals(&self) -> &[UntypedValue] {
+        &self.locals
+    }
+
+    /// Returns the values of the operand stack of the function frame.
+    ///
+    /// # Note
+    ///
+    /// - `wasmi` stores operands in registers following the local variables.
+    ///   Registers that are not live at the current position may contain stale values.
+    /// - The values are untyped since `wasmi` does not store type information at runtime.
+    pub fn operands(&self) -> &[UntypedValue] {
+        &self.operands
+    }
+
+    /// Returns the global variable at the Wasm `index` of the instance of the function frame.
+    pub fn global(&self, ctx: impl AsContext, index: u32) -> Option<Global> {
+        self.info.instance().get_global(ctx, index)
+    }
+
+    /// Returns the linear memory at the Wasm `index` of the instance of the function frame.
+    pub fn memory(&self, ctx: impl AsContext, index: u32) -> Option<Memory> {
+        self.info.instance().get_memory(ctx, index)
+    }
+}
+
+/// The condition upon which a debug execution pauses before executing an instruction.
+#[derive(Debug, Copy, Clone)]
+enum PauseCondition {
+    /// Pauses only at breakpoints.
+    Breakpoint,
+    /// Pauses at the next Wasm instruction.
+    Next,
+    /// Pauses at the next Wasm instruction at a call depth of at most `depth`.
+    Depth(usize),
+}
+
+/// The state of a debug execution.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct DebugExecution {
+    /// The condition upon which the debug execution pauses.
+    condition: PauseCondition,
+    /// Is `true` if the next instruction resumes a paused execution.
+    ///
+    /// # Note
+    ///
+    /// This prevents a resumed execution from pausing at the very
+    /// same instruction at which it has been paused.
+    resuming: bool,
+}
+
+/// The debugger state of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Default)]
+pub(crate) struct DebugState {
+    /// The breakpoints given as Wasm function index and Wasm offset.
+    breakpoints: BTreeSet<(u32, usize)>,
+    /// The state of the debug execution in progress if any.
+    execution: Option<DebugExecution>,
+    /// The amount of function frames below the executed function frame on the call stack.
+    depth: usize,
+}
+
+impl DebugState {
+    /// Sets a breakpoint at the `wasm_offset` within the Wasm function at `func_index`.
+    ///
+    /// Returns `false` if the breakpoint was already set.
+    pub fn set_breakpoint(&mut self, func_index: u32, wasm_offset: usize) -> bool {
+        self.breakpoints.insert((func_index, wasm_offset))
+    }
+
+    /// Removes the breakpoint at the `wasm_offset` within the Wasm function at `func_index`.
+    ///
+    /// Returns `false` if there was no such breakpoint.
+    pub fn remove_breakpoint(&mut self, func_index: u32, wasm_offset: usize) -> bool {
+        self.breakpoints.remove(&(func_index, wasm_offset))
+    }
+
+    /// Removes all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear()
+    }
+
+    /// Starts a debug execution with the given [`StepMode`].
+    ///
+    /// The `depth` is the call depth of the paused function frame if the
+    /// execution is `resuming` and otherwise the call depth of the called function.
+    pub(super) fn start(&mut self, mode: StepMode, depth: usize, resuming: bool) {
+        let condition = match mode {
+            StepMode::Continue => PauseCondition::Breakpoint,
+            StepMode::StepInto => PauseCondition::Next,
+            StepMode::StepOver => PauseCondition::Depth(depth),
+            StepMode::StepOut => match depth.checked_sub(1) {
+                Some(depth) => PauseCondition::Depth(depth),
+                // Stepping out of the called function finishes the execution
+                // unless a breakpoint is hit on the way.
+                None => PauseCondition::Breakpoint,
+            },
+        };
+        self.execution = Some(DebugExecution {
+            condition,
+            resuming,
+        });
+    }
+
+    /// Stops the debug execution in progress if any and returns its state.
+    ///
+    /// # Note
+    ///
+    /// The returned state can be restored via [`DebugState::restore`] which
+    /// is required around nested executions of host functions.
+    pub(super) fn stop(&mut self) -> Option<DebugExecution> {
+        self.execution.take()
+    }
+
+    /// Sets the call `depth` of the function frame that is about to be executed.
+    #[inline]
+    pub(super) fn enter_frame(&mut self, depth: usize) {
+        self.depth = depth;
+    }
+
+    /// Restores the state of a debug execution stopped via [`DebugState::stop`].
+    pub(super) fn restore(&mut self, execution: Option<DebugExecution>) {
+        self.execution = execution;
+    }
+
+    /// Returns the [`PauseReason`] if the execution shall pause before
+    /// executing the instruction at `wasm_offset` within the Wasm function at `func_index`.
+    ///
+    /// The `starts_instr` flag tells if the instruction is the first `wasmi`
+    /// bytecode instruction translated from the Wasm instruction at `wasm_offset`.
+    #[inline]
+    pub(super) fn should_pause(
+        &mut self,
+        func_index: u32,
+        wasm_offset: usize,
+        starts_instr: bool,
+    ) -> Option<PauseReason> {
+        let execution = self.execution.as_mut()?;
+        if mem::take(&mut execution.resuming) || !starts_instr {
+            return None;
+        }
+        let step = match execution.condition {
+            PauseCondition::Breakpoint => false,
+            PauseCondition::Next => true,
+            PauseCondition::Depth(max_depth) => self.depth <= max_depth,
+        };
+        if step {
+            return Some(PauseReason::Step);
+        }
+        if self.breakpoints.contains(&(func_index, wasm_offset)) {
+            return Some(PauseReason::Breakpoint);
+        }
+        None
+    }
+}
+
+impl EngineInner {
+    /// Returns the [`DebugFrame`] of the suspended `state` of a [`PausedInvocation`].
+    fn debug_frames(&self, ctx: impl AsContext, state: &SuspendedState) -> Vec<DebugFrame> {
+        let store = ctx.as_context().store;
+        let values = &state.values[..];
+        let debug_frame = |frame: &FunctionFrame, pc: usize, end: usize| {
+            let instance = frame.instance();
+            let names = store.resolve_instance(instance).names();
+            let func_index = frame.func_index();
+            let info = FrameInfo::new(
+                instance,
+                names.module().cloned(),
+                func_index,
+                names.func(func_index).cloned(),
+                self.code_map.wasm_offset(frame.func_body, pc),
+            );
+            let resolved = self.code_map.resolve(frame.func_body);
+            let len_locals = resolved.len_params() + resolved.len_locals();
+            let base = frame.base();
+            let end = end.min(base + resolved.max_stack_height());
+            DebugFrame {
+                info,
+                locals: values[base..base + len_locals].into(),
+                operands: values[base + len_locals..end].into(),
+            }
+        };
+        let mut frames = Vec::with_capacity(state.frames.len() + 1);
+        frames.push(debug_frame(&state.frame, state.frame.pc(), values.len()));
+        // The registers of calling frames end where the registers of their callee start.
+        // Also their `pc` refers to the instruction following their call instruction.
+        let mut end = state.frame.base();
+        for frame in state.frames.iter().rev() {
+            frames.push(debug_frame(frame, frame.pc() - 1, end));
+            end = frame.base();
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DebugCall, PauseReason, PausedInvocation, StepMode};
+    use crate::{core::Value, Engine, Extern, Func, Linker, Module, Store};
+    use alloc::vec::Vec;
+
+    /// The `call` Wasm opcode.
+    const CALL: u8 = 0x10;
+
+    /// The `i32.mul` Wasm opcode.
+    const I32_MUL: u8 = 0x6C;
+
+    const WAT: &str = r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (memory 1)
+            (global $g (mut i32) (i32.const 0))
+            (func (export "run") (param $n i32) (result i32)
+                (local $x i32)
+                (local.set $x (i32.add (local.get $n) (i32.const 1)))
+                (global.set $g (local.get $x))
+                (i32.store (i32.const 8) (local.get $x))
+                (call $host (call $square (local.get $x)))
+            )
+            (func $square (param i32) (result i32)
+                (i32.mul (local.get 0) (local.get 0))
+            )
+        )
+    "#;
+
+    /// Instantiates the [`WAT`] module and returns its Wasm binary and exported function `run`.
+    fn setup() -> (Vec<u8>, Store<()>, Func) {
+        let wasm = wat::parse_str(WAT).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let host = Func::wrap(&mut store, |value: i32| value + 1);
+        let mut linker = <Linker<()>>::new();
+        linker.define("env", "host", host).unwrap();
+        let run = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_export(&store, "run")
+            .and_then(Extern::into_func)
+            .unwrap();
+        (wasm, store, run)
+    }
+
+    /// Unwraps the [`PausedInvocation`] of a [`DebugCall`].
+    fn paused(call: DebugCall) -> PausedInvocation {
+        match call {
+            DebugCall::Paused(invocation) => invocation,
+            DebugCall::Finished => panic!("expected paused execution but it finished"),
+        }
+    }
+
+    /// Returns the function index and Wasm offset of the paused function frame.
+    fn position(store: &Store<()>, invocation: &PausedInvocation) -> (u32, usize) {
+        let frames = invocation.frames(store);
+        (
+            frames[0].info().func_index(),
+            frames[0].info().wasm_offset(),
+        )
+    }
+
+    /// Steps through `run` with `mode` and returns the positions of all pauses.
+    fn step_through(store: &mut Store<()>, run: Func, mode: StepMode) -> Vec<(u32, usize)> {
+        let mut results = [Value::I32(0)];
+        let mut positions = Vec::new();
+        let mut call = run
+            .call_debug(&mut *store, &[Value::I32(2)], &mut results, mode)
+            .unwrap();
+        while let DebugCall::Paused(invocation) = call {
+            assert_eq!(invocation.reason(), PauseReason::Step);
+            positions.push(position(store, &invocation));
+            call = invocation.resume(&mut *store, mode, &mut results).unwrap();
+        }
+        assert_eq!(results, [Value::I32(10)]);
+        positions
+    }
+
+    /// Returns the Wasm offset of the `i32.mul` instruction of `$square`.
+    fn square_mul_offset(wasm: &[u8], store: &mut Store<()>, run: Func) -> usize {
+        step_through(store, run, StepMode::StepInto)
+            .into_iter()
+            .find(|(func_index, offset)| *func_index == 2 && wasm[*offset] == I32_MUL)
+            .map(|(_, offset)| offset)
+            .unwrap()
+    }
+
+    #[test]
+    fn step_into_visits_all_functions() {
+        let (wasm, mut store, run) = setup();
+        let positions = step_through(&mut store, run, StepMode::StepInto);
+        // The first pause happens before the first instruction of `run`.
+        assert_eq!(positions[0].0, 1);
+        assert!(positions.iter().any(|(func_index, _)| *func_index == 2));
+        // Host functions are never paused in.
+        assert!(positions.iter().all(|(func_index, _)| *func_index != 0));
+        // Every Wasm instruction pauses at most once.
+        let mut offsets = positions
+            .iter()
+            .map(|(_, offset)| *offset)
+            .collect::<Vec<_>>();
+        offsets.dedup();
+        assert_eq!(offsets.len(), positions.len());
+        assert!(offsets.iter().all(|offset| *offset < wasm.len()));
+    }
+
+    #[test]
+    fn step_over_skips_calls() {
+        let (_, mut store, run) = setup();
+        let into = step_through(&mut store, run, StepMode::StepInto);
+        let over = step_through(&mut store, run, StepMode::StepOver);
+        let expected = into
+            .into_iter()
+            .filter(|(func_index, _)| *func_index == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(over, expected);
+    }
+
+    #[test]
+    fn breakpoint_pauses_and_inspects_state() {
+        let (wasm, mut store, run) = setup();
+        let offset = square_mul_offset(&wasm, &mut store, run);
+        assert!(store.set_breakpoint(2, offset));
+        assert!(!store.set_breakpoint(2, offset));
+        let mut results = [Value::I32(0)];
+        let invocation = paused(
+            run.call_debug(
+                &mut store,
+                &[Value::I32(2)],
+                &mut results,
+                StepMode::Continue,
+            )
+            .unwrap(),
+        );
+        assert_eq!(invocation.reason(), PauseReason::Breakpoint);
+        let frames = invocation.frames(&store);
+        assert_eq!(frames.len(), 2);
+        // The paused `$square` observes its parameter.
+        assert_eq!(frames[0].info().wasm_offset(), offset);
+        assert_eq!(frames[0].locals()[0].to_bits(), 3);
+        // The calling `run` is paused at its call instruction.
+        let caller = &frames[1];
+        assert_eq!(caller.info().func_index(), 1);
+        assert_eq!(wasm[caller.info().wasm_offset()], CALL);
+        let locals = caller.locals().iter().map(|value| value.to_bits());
+        assert!(locals.eq([2, 3]));
+        // Globals and memories of the paused instance are accessible.
+        let global = caller.global(&store, 0).unwrap();
+        assert_eq!(global.get(&store), Value::I32(3));
+        let memory = caller.memory(&store, 0).unwrap();
+        let mut buffer = [0x00; 4];
+        memory.read(&store, 8, &mut buffer).unwrap();
+        assert_eq!(i32::from_le_bytes(buffer), 3);
+        assert!(caller.global(&store, 1).is_none());
+        // Continuing finishes the execution.
+        let call = invocation
+            .resume(&mut store, StepMode::Continue, &mut results)
+            .unwrap();
+        assert!(matches!(call, DebugCall::Finished));
+        assert_eq!(results, [Value::I32(10)]);
+    }
+
+    #[test]
+    fn step_out_returns_to_caller() {
+        let (wasm, mut store, run) = setup();
+        let offset = square_mul_offset(&wasm, &mut store, run);
+        store.set_breakpoint(2, offset);
+        let mut results = [Value::I32(0)];
+        let invocation = paused(
+            run.call_debug(
+                &mut store,
+                &[Value::I32(2)],
+                &mut results,
+                StepMode::Continue,
+            )
+            .unwrap(),
+        );
+        let invocation = paused(
+            invocation
+                .resume(&mut store, StepMode::StepOut, &mut results)
+                .unwrap(),
+        );
+        assert_eq!(invocation.reason(), PauseReason::Step);
+        let frames = invocation.frames(&store);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].info().func_index(), 1);
+        // Stepping out of the called function finishes the execution.
+        let call = invocation
+            .resume(&mut store, StepMode::StepOut, &mut results)
+            .unwrap();
+        assert!(matches!(call, DebugCall::Finished));
+        assert_eq!(results, [Value::I32(10)]);
+    }
+
+    #[test]
+    fn removed_breakpoints_are_not_hit() {
+        let (wasm, mut store, run) = setup();
+        let offset = square_mul_offset(&wasm, &mut store, run);
+        store.set_breakpoint(2, offset);
+        assert!(store.remove_breakpoint(2, offset));
+        assert!(!store.remove_breakpoint(2, offset));
+        let mut results = [Value::I32(0)];
+        let call = run
+            .call_debug(
+                &mut store,
+                &[Value::I32(2)],
+                &mut results,
+                StepMode::Continue,
+            )
+            .unwrap();
+        assert!(matches!(call, DebugCall::Finished));
+        // Breakpoints do not pause regular executions.
+        store.set_breakpoint(2, offset);
+        run.call(&mut store, &[Value::I32(2)], &mut results)
+            .unwrap();
+        assert_eq!(results, [Value::I32(10)]);
+        store.clear_breakpoints();
+        let call = run
+            .call_debug(
+                &mut store,
+                &[Value::I32(2)],
+                &mut results,
+                StepMode::Continue,
+            )
+            .unwrap();
+        assert!(matches!(call, DebugCall::Finished));
+    }
+}
@@ -1,10 +1,24 @@
+#[cfg(feature = "debugger")]
+use super::PauseReason;
+#[cfg(feature = "trace")]
+use super::TraceEvent;
 use super::{
     super::{Global, Memory, Table},
-    bytecode::{FuncIdx, GlobalIdx, Instruction, LocalIdx, Offset, SignatureIdx},
+    bytecode::{
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        GlobalIdx,
+        Instruction,
+        Offset,
+        Register,
+        SignatureIdx,
+    },
     AsContextMut,
     CallOutcome,
     DropKeep,
     EngineInner,
+    FuelCosts,
     FunctionFrame,
     ResolvedFuncBody,
     Target,
@@ -12,9 +26,11 @@ use super::{
 };
 use crate::{
     core::{TrapCode, F32, F64},
+    errors::MemoryError,
     Func,
 };
-use core::cmp;
+use alloc::sync::Arc;
+use core::{cmp, ops::Range};
 use wasmi_core::{memory_units::Pages, ExtendInto, LittleEndianConvert, UntypedValue, WrapInto};
 
 /// State that is used during Wasm function execution.
@@ -26,20 +42,29 @@ pub struct FunctionExecutor<'engine, 'func> {
     frame: &'func mut FunctionFrame,
     /// The resolved function body of the executed function frame.
     func_body: ResolvedFuncBody<'engine>,
+    /// The fuel cost schedule if fuel metering is enabled.
+    fuel_costs: Option<FuelCosts>,
+    /// Is `true` if NaN results of float instructions are canonicalized.
+    nan_canonicalization: bool,
 }
 
 impl<'engine, 'func> FunctionExecutor<'engine, 'func> {
     /// Creates an execution context for the given [`FunctionFrame`].
     pub fn new(
-        engine: &'engine mut EngineInner,
+        engine: &'engine EngineInner,
+        value_stack: &'engine mut ValueStack,
         frame: &'func mut FunctionFrame,
     ) -> Result<Self, TrapCode> {
         let resolved = engine.code_map.resolve(frame.func_body);
-        frame.initialize(resolved, &mut engine.value_stack)?;
+        #[cfg(any(feature = "trace", feature = "debugger"))]
+        frame.init_func_index(&engine.code_map);
+        frame.initialize(resolved, value_stack)?;
         Ok(Self {
-            value_stack: &mut engine.value_stack,
+            value_stack,
             frame,
             func_body: resolved,
+            fuel_costs: engine.config.metered_fuel_costs().copied(),
+            nan_canonicalization: engine.config.nan_canonicalization(),
         })
     }
 
@@ -50,209 +75,289 @@ impl<'engine, 'func> FunctionExecutor<'engine, 'func> {
     /// This executes instructions sequentially until either the function
     /// calls into another function or the function returns to its caller.
     #[inline(always)]
-    #[rustfmt::skip]
     pub fn execute_frame(self, mut ctx: impl AsContextMut) -> Result<CallOutcome, TrapCode> {
+        let pc = self.frame.pc();
+        let mut exec_ctx = ExecutionContext::new(
+            self.value_stack,
+            self.frame,
+            &mut ctx,
+            pc,
+            self.fuel_costs,
+            self.nan_canonicalization,
+        );
+        let outcome = Self::execute_instrs(&mut exec_ctx, self.func_body, self.fuel_costs);
+        if outcome.is_err() {
+            // The `pc` of the trapping instruction is required for Wasm backtraces.
+            exec_ctx.frame.update_pc(exec_ctx.pc);
+        }
+        outcome
+    }
+
+    /// Executes the instructions of the current function frame.
+    ///
+    /// # Note
+    ///
+    /// Upon a trap the program counter of the [`ExecutionContext`] refers
+    /// to the instruction that caused the trap.
+    #[inline(always)]
+    #[rustfmt::skip]
+    fn execute_instrs<Ctx>(
+        exec_ctx: &mut ExecutionContext<'engine, 'func, Ctx>,
+        func_body: ResolvedFuncBody<'engine>,
+        fuel_costs: Option<FuelCosts>,
+    ) -> Result<CallOutcome, TrapCode>
+    where
+        Ctx: AsContextMut,
+    {
         use Instruction as Instr;
-        let mut exec_ctx = ExecutionContext::new(self.value_stack, self.frame, &mut ctx, self.frame.pc());
+        #[cfg(feature = "coverage")]
+        let mut last_pc = None;
         loop {
             // # Safety
             //
             // Properly constructed `wasmi` bytecode can never produce invalid `pc`.
             let instr = unsafe {
-                self.func_body.get_release_unchecked(exec_ctx.pc)
+                func_body.get_release_unchecked(exec_ctx.pc)
             };
+            #[cfg(feature = "debugger")]
+            if let Some(reason) = exec_ctx.should_pause(func_body) {
+                exec_ctx.frame.update_pc(exec_ctx.pc);
+                return Ok(CallOutcome::Pause(reason))
+            }
+            #[cfg(feature = "profiler")]
+            exec_ctx.count_instruction();
+            #[cfg(feature = "coverage")]
+            {
+                exec_ctx.cover(last_pc);
+                last_pc = Some(exec_ctx.pc);
+            }
+            #[cfg(feature = "trace")]
+            exec_ctx.trace(func_body, instr);
+            if let Some(costs) = &fuel_costs {
+                exec_ctx.consume_fuel(costs.instruction_cost(instr))?;
+            }
             match instr {
-                Instr::GetLocal { local_depth } => { exec_ctx.visit_get_local(*local_depth)?; }
-                Instr::SetLocal { local_depth } => { exec_ctx.visit_set_local(*local_depth)?; }
-                Instr::TeeLocal { local_depth } => { exec_ctx.visit_tee_local(*local_depth)?; }
+                Instr::Copy { result, input } => { exec_ctx.visit_copy(*result, *input)?; }
                 Instr::Br(target) => { exec_ctx.visit_br(*target)?; }
-                Instr::BrIfEqz(target) => { exec_ctx.visit_br_if_eqz(*target)?; }
-                Instr::BrIfNez(target) => { exec_ctx.visit_br_if_nez(*target)?; }
-                Instr::ReturnIfNez(drop_keep)  => {
-                    if let MaybeReturn::Return = exec_ctx.visit_return_if_nez(*drop_keep)? {
+                Instr::BrIfEqz { condition, target } => { exec_ctx.visit_br_if_eqz(*condition, *target)?; }
+                Instr::BrIfNez { condition, target } => { exec_ctx.visit_br_if_nez(*condition, *target)?; }
+                Instr::BrIfI32Eq { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_eq(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32Ne { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_ne(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32LtS { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_lt_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32LtU { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_lt_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32GtS { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_gt_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32GtU { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_gt_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32LeS { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_le_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32LeU { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_le_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32GeS { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_ge_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI32GeU { lhs, rhs, target } => { exec_ctx.visit_br_if_i32_ge_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64Eq { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_eq(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64Ne { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_ne(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64LtS { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_lt_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64LtU { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_lt_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64GtS { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_gt_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64GtU { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_gt_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64LeS { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_le_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64LeU { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_le_u(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64GeS { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_ge_s(*lhs, *rhs, *target)?; }
+                Instr::BrIfI64GeU { lhs, rhs, target } => { exec_ctx.visit_br_if_i64_ge_u(*lhs, *rhs, *target)?; }
+                Instr::ReturnIfNez { condition, results } => {
+                    if let MaybeReturn::Return = exec_ctx.visit_return_if_nez(*condition, *results)? {
                         return Ok(CallOutcome::Return)
                     }
                 }
-                Instr::BrTable { len_targets } => {
-                    exec_ctx.visit_br_table(*len_targets)?;
+                Instr::BrTable { index, len_targets } => {
+                    exec_ctx.visit_br_table(*index, *len_targets)?;
                 }
                 Instr::Unreachable => { exec_ctx.visit_unreachable()?; }
-                Instr::Return(drop_keep)  => {
-                    exec_ctx.visit_ret(*drop_keep)?;
+                Instr::Return(results) => {
+                    exec_ctx.visit_ret(*results)?;
                     return Ok(CallOutcome::Return)
                 }
-                Instr::Call(func) => {
-                    return exec_ctx.visit_call(*func)
+                Instr::Call { func_index, params_end } => {
+                    return exec_ctx.visit_call(*func_index, *params_end)
+                }
+                Instr::CallIndirect { signature, index, params_end } => {
+                    return exec_ctx.visit_call_indirect(*signature, *index, *params_end)
+                }
+                Instr::ReturnCall { func_index, params_end } => {
+                    return exec_ctx.visit_return_call(*func_index, *params_end)
+                }
+                Instr::ReturnCallIndirect { signature, index, params_end } => {
+                    return exec_ctx.visit_return_call_indirect(*signature, *index, *params_end)
                 }
-                Instr::CallIndirect(signature)  => {
-                    return exec_ctx.visit_call_indirect(*signature)
+                Instr::Select { result, condition, if_false } => {
+                    exec_ctx.visit_select(*result, *condition, *if_false)?;
                 }
-                Instr::Drop => { exec_ctx.visit_drop()?; }
-                Instr::Select => { exec_ctx.visit_select()?; }
-                Instr::GetGlobal(global_idx)  => { exec_ctx.visit_get_global(*global_idx)?; }
-                Instr::SetGlobal(global_idx)  => { exec_ctx.visit_set_global(*global_idx)?; }
-                Instr::I32Load(offset)  => { exec_ctx.visit_i32_load(*offset)?; }
-                Instr::I64Load(offset)  => { exec_ctx.visit_i64_load(*offset)?; }
-                Instr::F32Load(offset)  => { exec_ctx.visit_f32_load(*offset)?; }
-                Instr::F64Load(offset)  => { exec_ctx.visit_f64_load(*offset)?; }
-                Instr::I32Load8S(offset)  => { exec_ctx.visit_i32_load_i8(*offset)?; }
-                Instr::I32Load8U(offset)  => { exec_ctx.visit_i32_load_u8(*offset)?; }
-                Instr::I32Load16S(offset)  => { exec_ctx.visit_i32_load_i16(*offset)?; }
-                Instr::I32Load16U(offset)  => { exec_ctx.visit_i32_load_u16(*offset)?; }
-                Instr::I64Load8S(offset)  => { exec_ctx.visit_i64_load_i8(*offset)?; }
-                Instr::I64Load8U(offset)  => { exec_ctx.visit_i64_load_u8(*offset)?; }
-                Instr::I64Load16S(offset)  => { exec_ctx.visit_i64_load_i16(*offset)?; }
-                Instr::I64Load16U(offset)  => { exec_ctx.visit_i64_load_u16(*offset)?; }
-                Instr::I64Load32S(offset)  => { exec_ctx.visit_i64_load_i32(*offset)?; }
-                Instr::I64Load32U(offset)  => { exec_ctx.visit_i64_load_u32(*offset)?; }
-                Instr::I32Store(offset)  => { exec_ctx.visit_i32_store(*offset)?; }
-                Instr::I64Store(offset)  => { exec_ctx.visit_i64_store(*offset)?; }
-                Instr::F32Store(offset)  => { exec_ctx.visit_f32_store(*offset)?; }
-                Instr::F64Store(offset)  => { exec_ctx.visit_f64_store(*offset)?; }
-                Instr::I32Store8(offset)  => { exec_ctx.visit_i32_store_8(*offset)?; }
-                Instr::I32Store16(offset)  => { exec_ctx.visit_i32_store_16(*offset)?; }
-                Instr::I64Store8(offset)  => { exec_ctx.visit_i64_store_8(*offset)?; }
-                Instr::I64Store16(offset)  => { exec_ctx.visit_i64_store_16(*offset)?; }
-                Instr::I64Store32(offset)  => { exec_ctx.visit_i64_store_32(*offset)?; }
-                Instr::CurrentMemory => { exec_ctx.visit_current_memory()?; }
-                Instr::GrowMemory => { exec_ctx.visit_grow_memory()?; }
-                Instr::Const(bytes)  => { exec_ctx.visit_const(*bytes)?; }
-                Instr::I32Eqz => { exec_ctx.visit_i32_eqz()?; }
-                Instr::I32Eq => { exec_ctx.visit_i32_eq()?; }
-                Instr::I32Ne => { exec_ctx.visit_i32_ne()?; }
-                Instr::I32LtS => { exec_ctx.visit_i32_lt_s()?; }
-                Instr::I32LtU => { exec_ctx.visit_i32_lt_u()?; }
-                Instr::I32GtS => { exec_ctx.visit_i32_gt_s()?; }
-                Instr::I32GtU => { exec_ctx.visit_i32_gt_u()?; }
-                Instr::I32LeS => { exec_ctx.visit_i32_le_s()?; }
-                Instr::I32LeU => { exec_ctx.visit_i32_le_u()?; }
-                Instr::I32GeS => { exec_ctx.visit_i32_ge_s()?; }
-                Instr::I32GeU => { exec_ctx.visit_i32_ge_u()?; }
-                Instr::I64Eqz => { exec_ctx.visit_i64_eqz()?; }
-                Instr::I64Eq => { exec_ctx.visit_i64_eq()?; }
-                Instr::I64Ne => { exec_ctx.visit_i64_ne()?; }
-                Instr::I64LtS => { exec_ctx.visit_i64_lt_s()?; }
-                Instr::I64LtU => { exec_ctx.visit_i64_lt_u()?; }
-                Instr::I64GtS => { exec_ctx.visit_i64_gt_s()?; }
-                Instr::I64GtU => { exec_ctx.visit_i64_gt_u()?; }
-                Instr::I64LeS => { exec_ctx.visit_i64_le_s()?; }
-                Instr::I64LeU => { exec_ctx.visit_i64_le_u()?; }
-                Instr::I64GeS => { exec_ctx.visit_i64_ge_s()?; }
-                Instr::I64GeU => { exec_ctx.visit_i64_ge_u()?; }
-                Instr::F32Eq => { exec_ctx.visit_f32_eq()?; }
-                Instr::F32Ne => { exec_ctx.visit_f32_ne()?; }
-                Instr::F32Lt => { exec_ctx.visit_f32_lt()?; }
-                Instr::F32Gt => { exec_ctx.visit_f32_gt()?; }
-                Instr::F32Le => { exec_ctx.visit_f32_le()?; }
-                Instr::F32Ge => { exec_ctx.visit_f32_ge()?; }
-                Instr::F64Eq => { exec_ctx.visit_f64_eq()?; }
-                Instr::F64Ne => { exec_ctx.visit_f64_ne()?; }
-                Instr::F64Lt => { exec_ctx.visit_f64_lt()?; }
-                Instr::F64Gt => { exec_ctx.visit_f64_gt()?; }
-                Instr::F64Le => { exec_ctx.visit_f64_le()?; }
-                Instr::F64Ge => { exec_ctx.visit_f64_ge()?; }
-                Instr::I32Clz => { exec_ctx.visit_i32_clz()?; }
-                Instr::I32Ctz => { exec_ctx.visit_i32_ctz()?; }
-                Instr::I32Popcnt => { exec_ctx.visit_i32_popcnt()?; }
-                Instr::I32Add => { exec_ctx.visit_i32_add()?; }
-                Instr::I32Sub => { exec_ctx.visit_i32_sub()?; }
-                Instr::I32Mul => { exec_ctx.visit_i32_mul()?; }
-                Instr::I32DivS => { exec_ctx.visit_i32_div_s()?; }
-                Instr::I32DivU => { exec_ctx.visit_i32_div_u()?; }
-                Instr::I32RemS => { exec_ctx.visit_i32_rem_s()?; }
-                Instr::I32RemU => { exec_ctx.visit_i32_rem_u()?; }
-                Instr::I32And => { exec_ctx.visit_i32_and()?; }
-                Instr::I32Or => { exec_ctx.visit_i32_or()?; }
-                Instr::I32Xor => { exec_ctx.visit_i32_xor()?; }
-                Instr::I32Shl => { exec_ctx.visit_i32_shl()?; }
-                Instr::I32ShrS => { exec_ctx.visit_i32_shr_s()?; }
-                Instr::I32ShrU => { exec_ctx.visit_i32_shr_u()?; }
-                Instr::I32Rotl => { exec_ctx.visit_i32_rotl()?; }
-                Instr::I32Rotr => { exec_ctx.visit_i32_rotr()?; }
-                Instr::I64Clz => { exec_ctx.visit_i64_clz()?; }
-                Instr::I64Ctz => { exec_ctx.visit_i64_ctz()?; }
-                Instr::I64Popcnt => { exec_ctx.visit_i64_popcnt()?; }
-                Instr::I64Add => { exec_ctx.visit_i64_add()?; }
-                Instr::I64Sub => { exec_ctx.visit_i64_sub()?; }
-                Instr::I64Mul => { exec_ctx.visit_i64_mul()?; }
-                Instr::I64DivS => { exec_ctx.visit_i64_div_s()?; }
-                Instr::I64DivU => { exec_ctx.visit_i64_div_u()?; }
-                Instr::I64RemS => { exec_ctx.visit_i64_rem_s()?; }
-                Instr::I64RemU => { exec_ctx.visit_i64_rem_u()?; }
-                Instr::I64And => { exec_ctx.visit_i64_and()?; }
-                Instr::I64Or => { exec_ctx.visit_i64_or()?; }
-                Instr::I64Xor => { exec_ctx.visit_i64_xor()?; }
-                Instr::I64Shl => { exec_ctx.visit_i64_shl()?; }
-                Instr::I64ShrS => { exec_ctx.visit_i64_shr_s()?; }
-                Instr::I64ShrU => { exec_ctx.visit_i64_shr_u()?; }
-                Instr::I64Rotl => { exec_ctx.visit_i64_rotl()?; }
-                Instr::I64Rotr => { exec_ctx.visit_i64_rotr()?; }
-                Instr::F32Abs => { exec_ctx.visit_f32_abs()?; }
-                Instr::F32Neg => { exec_ctx.visit_f32_neg()?; }
-                Instr::F32Ceil => { exec_ctx.visit_f32_ceil()?; }
-                Instr::F32Floor => { exec_ctx.visit_f32_floor()?; }
-                Instr::F32Trunc => { exec_ctx.visit_f32_trunc()?; }
-                Instr::F32Nearest => { exec_ctx.visit_f32_nearest()?; }
-                Instr::F32Sqrt => { exec_ctx.visit_f32_sqrt()?; }
-                Instr::F32Add => { exec_ctx.visit_f32_add()?; }
-                Instr::F32Sub => { exec_ctx.visit_f32_sub()?; }
-                Instr::F32Mul => { exec_ctx.visit_f32_mul()?; }
-                Instr::F32Div => { exec_ctx.visit_f32_div()?; }
-                Instr::F32Min => { exec_ctx.visit_f32_min()?; }
-                Instr::F32Max => { exec_ctx.visit_f32_max()?; }
-                Instr::F32Copysign => { exec_ctx.visit_f32_copysign()?; }
-                Instr::F64Abs => { exec_ctx.visit_f64_abs()?; }
-                Instr::F64Neg => { exec_ctx.visit_f64_neg()?; }
-                Instr::F64Ceil => { exec_ctx.visit_f64_ceil()?; }
-                Instr::F64Floor => { exec_ctx.visit_f64_floor()?; }
-                Instr::F64Trunc => { exec_ctx.visit_f64_trunc()?; }
-                Instr::F64Nearest => { exec_ctx.visit_f64_nearest()?; }
-                Instr::F64Sqrt => { exec_ctx.visit_f64_sqrt()?; }
-                Instr::F64Add => { exec_ctx.visit_f64_add()?; }
-                Instr::F64Sub => { exec_ctx.visit_f64_sub()?; }
-                Instr::F64Mul => { exec_ctx.visit_f64_mul()?; }
-                Instr::F64Div => { exec_ctx.visit_f64_div()?; }
-                Instr::F64Min => { exec_ctx.visit_f64_min()?; }
-                Instr::F64Max => { exec_ctx.visit_f64_max()?; }
-                Instr::F64Copysign => { exec_ctx.visit_f64_copysign()?; }
-                Instr::I32WrapI64 => { exec_ctx.visit_i32_wrap_i64()?; }
-                Instr::I32TruncSF32 => { exec_ctx.visit_i32_trunc_f32()?; }
-                Instr::I32TruncUF32 => { exec_ctx.visit_u32_trunc_f32()?; }
-                Instr::I32TruncSF64 => { exec_ctx.visit_i32_trunc_f64()?; }
-                Instr::I32TruncUF64 => { exec_ctx.visit_u32_trunc_f64()?; }
-                Instr::I64ExtendSI32 => { exec_ctx.visit_i64_extend_i32()?; }
-                Instr::I64ExtendUI32 => { exec_ctx.visit_i64_extend_u32()?; }
-                Instr::I64TruncSF32 => { exec_ctx.visit_i64_trunc_f32()?; }
-                Instr::I64TruncUF32 => { exec_ctx.visit_u64_trunc_f32()?; }
-                Instr::I64TruncSF64 => { exec_ctx.visit_i64_trunc_f64()?; }
-                Instr::I64TruncUF64 => { exec_ctx.visit_u64_trunc_f64()?; }
-                Instr::F32ConvertSI32 => { exec_ctx.visit_f32_convert_i32()?; }
-                Instr::F32ConvertUI32 => { exec_ctx.visit_f32_convert_u32()?; }
-                Instr::F32ConvertSI64 => { exec_ctx.visit_f32_convert_i64()?; }
-                Instr::F32ConvertUI64 => { exec_ctx.visit_f32_convert_u64()?; }
-                Instr::F32DemoteF64 => { exec_ctx.visit_f32_demote_f64()?; }
-                Instr::F64ConvertSI32 => { exec_ctx.visit_f64_convert_i32()?; }
-                Instr::F64ConvertUI32 => { exec_ctx.visit_f64_convert_u32()?; }
-                Instr::F64ConvertSI64 => { exec_ctx.visit_f64_convert_i64()?; }
-                Instr::F64ConvertUI64 => { exec_ctx.visit_f64_convert_u64()?; }
-                Instr::F64PromoteF32 => { exec_ctx.visit_f64_promote_f32()?; }
-                Instr::I32ReinterpretF32 => { exec_ctx.visit_i32_reinterpret_f32()?; }
-                Instr::I64ReinterpretF64 => { exec_ctx.visit_i64_reinterpret_f64()?; }
-                Instr::F32ReinterpretI32 => { exec_ctx.visit_f32_reinterpret_i32()?; }
-                Instr::F64ReinterpretI64 => { exec_ctx.visit_f64_reinterpret_i64()?; }
-                Instr::I32TruncSatF32S => { exec_ctx.visit_i32_trunc_sat_f32()?; }
-                Instr::I32TruncSatF32U => { exec_ctx.visit_u32_trunc_sat_f32()?; }
-                Instr::I32TruncSatF64S => { exec_ctx.visit_i32_trunc_sat_f64()?; }
-                Instr::I32TruncSatF64U => { exec_ctx.visit_u32_trunc_sat_f64()?; }
-                Instr::I64TruncSatF32S => { exec_ctx.visit_i64_trunc_sat_f32()?; }
-                Instr::I64TruncSatF32U => { exec_ctx.visit_u64_trunc_sat_f32()?; }
-                Instr::I64TruncSatF64S => { exec_ctx.visit_i64_trunc_sat_f64()?; }
-                Instr::I64TruncSatF64U => { exec_ctx.visit_u64_trunc_sat_f64()?; }
-                Instr::I32Extend8S => { exec_ctx.visit_i32_sign_extend8()?; }
-                Instr::I32Extend16S => { exec_ctx.visit_i32_sign_extend16()?; }
-                Instr::I64Extend8S => { exec_ctx.visit_i64_sign_extend8()?; }
-                Instr::I64Extend16S => { exec_ctx.visit_i64_sign_extend16()?; }
-                Instr::I64Extend32S => { exec_ctx.visit_i64_sign_extend32()?; }
+                Instr::GetGlobal { result, global } => { exec_ctx.visit_get_global(*result, *global)?; }
+                Instr::SetGlobal { global, input } => { exec_ctx.visit_set_global(*global, *input)?; }
+                Instr::I32Load { result, ptr, offset } => { exec_ctx.visit_i32_load(*result, *ptr, *offset)?; }
+                Instr::I64Load { result, ptr, offset } => { exec_ctx.visit_i64_load(*result, *ptr, *offset)?; }
+                Instr::F32Load { result, ptr, offset } => { exec_ctx.visit_f32_load(*result, *ptr, *offset)?; }
+                Instr::F64Load { result, ptr, offset } => { exec_ctx.visit_f64_load(*result, *ptr, *offset)?; }
+                Instr::I32Load8S { result, ptr, offset } => { exec_ctx.visit_i32_load_i8(*result, *ptr, *offset)?; }
+                Instr::I32Load8U { result, ptr, offset } => { exec_ctx.visit_i32_load_u8(*result, *ptr, *offset)?; }
+                Instr::I32Load16S { result, ptr, offset } => { exec_ctx.visit_i32_load_i16(*result, *ptr, *offset)?; }
+                Instr::I32Load16U { result, ptr, offset } => { exec_ctx.visit_i32_load_u16(*result, *ptr, *offset)?; }
+                Instr::I64Load8S { result, ptr, offset } => { exec_ctx.visit_i64_load_i8(*result, *ptr, *offset)?; }
+                Instr::I64Load8U { result, ptr, offset } => { exec_ctx.visit_i64_load_u8(*result, *ptr, *offset)?; }
+                Instr::I64Load16S { result, ptr, offset } => { exec_ctx.visit_i64_load_i16(*result, *ptr, *offset)?; }
+                Instr::I64Load16U { result, ptr, offset } => { exec_ctx.visit_i64_load_u16(*result, *ptr, *offset)?; }
+                Instr::I64Load32S { result, ptr, offset } => { exec_ctx.visit_i64_load_i32(*result, *ptr, *offset)?; }
+                Instr::I64Load32U { result, ptr, offset } => { exec_ctx.visit_i64_load_u32(*result, *ptr, *offset)?; }
+                Instr::I32Store { ptr, value, offset } => { exec_ctx.visit_i32_store(*ptr, *value, *offset)?; }
+                Instr::I64Store { ptr, value, offset } => { exec_ctx.visit_i64_store(*ptr, *value, *offset)?; }
+                Instr::F32Store { ptr, value, offset } => { exec_ctx.visit_f32_store(*ptr, *value, *offset)?; }
+                Instr::F64Store { ptr, value, offset } => { exec_ctx.visit_f64_store(*ptr, *value, *offset)?; }
+                Instr::I32Store8 { ptr, value, offset } => { exec_ctx.visit_i32_store_8(*ptr, *value, *offset)?; }
+                Instr::I32Store16 { ptr, value, offset } => { exec_ctx.visit_i32_store_16(*ptr, *value, *offset)?; }
+                Instr::I64Store8 { ptr, value, offset } => { exec_ctx.visit_i64_store_8(*ptr, *value, *offset)?; }
+                Instr::I64Store16 { ptr, value, offset } => { exec_ctx.visit_i64_store_16(*ptr, *value, *offset)?; }
+                Instr::I64Store32 { ptr, value, offset } => { exec_ctx.visit_i64_store_32(*ptr, *value, *offset)?; }
+                Instr::CurrentMemory { result } => { exec_ctx.visit_current_memory(*result)?; }
+                Instr::GrowMemory { result, delta } => { exec_ctx.visit_grow_memory(*result, *delta)?; }
+                Instr::MemoryCopy { dst, src, len } => { exec_ctx.visit_memory_copy(*dst, *src, *len)?; }
+                Instr::MemoryFill { dst, value, len } => { exec_ctx.visit_memory_fill(*dst, *value, *len)?; }
+                Instr::MemoryInit { segment, operands } => { exec_ctx.visit_memory_init(*segment, *operands)?; }
+                Instr::DataDrop { segment } => { exec_ctx.visit_data_drop(*segment)?; }
+                Instr::TableCopy { dst, src, len } => { exec_ctx.visit_table_copy(*dst, *src, *len)?; }
+                Instr::TableInit { segment, operands } => { exec_ctx.visit_table_init(*segment, *operands)?; }
+                Instr::ElemDrop { segment } => { exec_ctx.visit_elem_drop(*segment)?; }
+                Instr::Const { result, value } => { exec_ctx.visit_const(*result, *value)?; }
+                Instr::I32Eqz { result, input } => { exec_ctx.visit_i32_eqz(*result, *input)?; }
+                Instr::I32Eq { result, lhs, rhs } => { exec_ctx.visit_i32_eq(*result, *lhs, *rhs)?; }
+                Instr::I32Ne { result, lhs, rhs } => { exec_ctx.visit_i32_ne(*result, *lhs, *rhs)?; }
+                Instr::I32LtS { result, lhs, rhs } => { exec_ctx.visit_i32_lt_s(*result, *lhs, *rhs)?; }
+                Instr::I32LtU { result, lhs, rhs } => { exec_ctx.visit_i32_lt_u(*result, *lhs, *rhs)?; }
+                Instr::I32GtS { result, lhs, rhs } => { exec_ctx.visit_i32_gt_s(*result, *lhs, *rhs)?; }
+                Instr::I32GtU { result, lhs, rhs } => { exec_ctx.visit_i32_gt_u(*result, *lhs, *rhs)?; }
+                Instr::I32LeS { result, lhs, rhs } => { exec_ctx.visit_i32_le_s(*result, *lhs, *rhs)?; }
+                Instr::I32LeU { result, lhs, rhs } => { exec_ctx.visit_i32_le_u(*result, *lhs, *rhs)?; }
+                Instr::I32GeS { result, lhs, rhs } => { exec_ctx.visit_i32_ge_s(*result, *lhs, *rhs)?; }
+                Instr::I32GeU { result, lhs, rhs } => { exec_ctx.visit_i32_ge_u(*result, *lhs, *rhs)?; }
+                Instr::I64Eqz { result, input } => { exec_ctx.visit_i64_eqz(*result, *input)?; }
+                Instr::I64Eq { result, lhs, rhs } => { exec_ctx.visit_i64_eq(*result, *lhs, *rhs)?; }
+                Instr::I64Ne { result, lhs, rhs } => { exec_ctx.visit_i64_ne(*result, *lhs, *rhs)?; }
+                Instr::I64LtS { result, lhs, rhs } => { exec_ctx.visit_i64_lt_s(*result, *lhs, *rhs)?; }
+                Instr::I64LtU { result, lhs, rhs } => { exec_ctx.visit_i64_lt_u(*result, *lhs, *rhs)?; }
+                Instr::I64GtS { result, lhs, rhs } => { exec_ctx.visit_i64_gt_s(*result, *lhs, *rhs)?; }
+                Instr::I64GtU { result, lhs, rhs } => { exec_ctx.visit_i64_gt_u(*result, *lhs, *rhs)?; }
+                Instr::I64LeS { result, lhs, rhs } => { exec_ctx.visit_i64_le_s(*result, *lhs, *rhs)?; }
+                Instr::I64LeU { result, lhs, rhs } => { exec_ctx.visit_i64_le_u(*result, *lhs, *rhs)?; }
+                Instr::I64GeS { result, lhs, rhs } => { exec_ctx.visit_i64_ge_s(*result, *lhs, *rhs)?; }
+                Instr::I64GeU { result, lhs, rhs } => { exec_ctx.visit_i64_ge_u(*result, *lhs, *rhs)?; }
+                Instr::F32Eq { result, lhs, rhs } => { exec_ctx.visit_f32_eq(*result, *lhs, *rhs)?; }
+                Instr::F32Ne { result, lhs, rhs } => { exec_ctx.visit_f32_ne(*result, *lhs, *rhs)?; }
+                Instr::F32Lt { result, lhs, rhs } => { exec_ctx.visit_f32_lt(*result, *lhs, *rhs)?; }
+                Instr::F32Gt { result, lhs, rhs } => { exec_ctx.visit_f32_gt(*result, *lhs, *rhs)?; }
+                Instr::F32Le { result, lhs, rhs } => { exec_ctx.visit_f32_le(*result, *lhs, *rhs)?; }
+                Instr::F32Ge { result, lhs, rhs } => { exec_ctx.visit_f32_ge(*result, *lhs, *rhs)?; }
+                Instr::F64Eq { result, lhs, rhs } => { exec_ctx.visit_f64_eq(*result, *lhs, *rhs)?; }
+                Instr::F64Ne { result, lhs, rhs } => { exec_ctx.visit_f64_ne(*result, *lhs, *rhs)?; }
+                Instr::F64Lt { result, lhs, rhs } => { exec_ctx.visit_f64_lt(*result, *lhs, *rhs)?; }
+                Instr::F64Gt { result, lhs, rhs } => { exec_ctx.visit_f64_gt(*result, *lhs, *rhs)?; }
+                Instr::F64Le { result, lhs, rhs } => { exec_ctx.visit_f64_le(*result, *lhs, *rhs)?; }
+                Instr::F64Ge { result, lhs, rhs } => { exec_ctx.visit_f64_ge(*result, *lhs, *rhs)?; }
+                Instr::I32Clz { result, input } => { exec_ctx.visit_i32_clz(*result, *input)?; }
+                Instr::I32Ctz { result, input } => { exec_ctx.visit_i32_ctz(*result, *input)?; }
+                Instr::I32Popcnt { result, input } => { exec_ctx.visit_i32_popcnt(*result, *input)?; }
+                Instr::I32Add { result, lhs, rhs } => { exec_ctx.visit_i32_add(*result, *lhs, *rhs)?; }
+                Instr::I32Sub { result, lhs, rhs } => { exec_ctx.visit_i32_sub(*result, *lhs, *rhs)?; }
+                Instr::I32AddImm { result, input, value } => { exec_ctx.visit_i32_add_imm(*result, *input, *value)?; }
+                Instr::I32Mul { result, lhs, rhs } => { exec_ctx.visit_i32_mul(*result, *lhs, *rhs)?; }
+                Instr::I32DivS { result, lhs, rhs } => { exec_ctx.visit_i32_div_s(*result, *lhs, *rhs)?; }
+                Instr::I32DivU { result, lhs, rhs } => { exec_ctx.visit_i32_div_u(*result, *lhs, *rhs)?; }
+                Instr::I32RemS { result, lhs, rhs } => { exec_ctx.visit_i32_rem_s(*result, *lhs, *rhs)?; }
+                Instr::I32RemU { result, lhs, rhs } => { exec_ctx.visit_i32_rem_u(*result, *lhs, *rhs)?; }
+                Instr::I32And { result, lhs, rhs } => { exec_ctx.visit_i32_and(*result, *lhs, *rhs)?; }
+                Instr::I32Or { result, lhs, rhs } => { exec_ctx.visit_i32_or(*result, *lhs, *rhs)?; }
+                Instr::I32Xor { result, lhs, rhs } => { exec_ctx.visit_i32_xor(*result, *lhs, *rhs)?; }
+                Instr::I32Shl { result, lhs, rhs } => { exec_ctx.visit_i32_shl(*result, *lhs, *rhs)?; }
+                Instr::I32ShrS { result, lhs, rhs } => { exec_ctx.visit_i32_shr_s(*result, *lhs, *rhs)?; }
+                Instr::I32ShrU { result, lhs, rhs } => { exec_ctx.visit_i32_shr_u(*result, *lhs, *rhs)?; }
+                Instr::I32Rotl { result, lhs, rhs } => { exec_ctx.visit_i32_rotl(*result, *lhs, *rhs)?; }
+                Instr::I32Rotr { result, lhs, rhs } => { exec_ctx.visit_i32_rotr(*result, *lhs, *rhs)?; }
+                Instr::I64Clz { result, input } => { exec_ctx.visit_i64_clz(*result, *input)?; }
+                Instr::I64Ctz { result, input } => { exec_ctx.visit_i64_ctz(*result, *input)?; }
+                Instr::I64Popcnt { result, input } => { exec_ctx.visit_i64_popcnt(*result, *input)?; }
+                Instr::I64Add { result, lhs, rhs } => { exec_ctx.visit_i64_add(*result, *lhs, *rhs)?; }
+                Instr::I64Sub { result, lhs, rhs } => { exec_ctx.visit_i64_sub(*result, *lhs, *rhs)?; }
+                Instr::I64AddImm { result, input, value } => { exec_ctx.visit_i64_add_imm(*result, *input, *value)?; }
+                Instr::I64Mul { result, lhs, rhs } => { exec_ctx.visit_i64_mul(*result, *lhs, *rhs)?; }
+                Instr::I64DivS { result, lhs, rhs } => { exec_ctx.visit_i64_div_s(*result, *lhs, *rhs)?; }
+                Instr::I64DivU { result, lhs, rhs } => { exec_ctx.visit_i64_div_u(*result, *lhs, *rhs)?; }
+                Instr::I64RemS { result, lhs, rhs } => { exec_ctx.visit_i64_rem_s(*result, *lhs, *rhs)?; }
+                Instr::I64RemU { result, lhs, rhs } => { exec_ctx.visit_i64_rem_u(*result, *lhs, *rhs)?; }
+                Instr::I64And { result, lhs, rhs } => { exec_ctx.visit_i64_and(*result, *lhs, *rhs)?; }
+                Instr::I64Or { result, lhs, rhs } => { exec_ctx.visit_i64_or(*result, *lhs, *rhs)?; }
+                Instr::I64Xor { result, lhs, rhs } => { exec_ctx.visit_i64_xor(*result, *lhs, *rhs)?; }
+                Instr::I64Shl { result, lhs, rhs } => { exec_ctx.visit_i64_shl(*result, *lhs, *rhs)?; }
+                Instr::I64ShrS { result, lhs, rhs } => { exec_ctx.visit_i64_shr_s(*result, *lhs, *rhs)?; }
+                Instr::I64ShrU { result, lhs, rhs } => { exec_ctx.visit_i64_shr_u(*result, *lhs, *rhs)?; }
+                Instr::I64Rotl { result, lhs, rhs } => { exec_ctx.visit_i64_rotl(*result, *lhs, *rhs)?; }
+                Instr::I64Rotr { result, lhs, rhs } => { exec_ctx.visit_i64_rotr(*result, *lhs, *rhs)?; }
+                Instr::F32Abs { result, input } => { exec_ctx.visit_f32_abs(*result, *input)?; }
+                Instr::F32Neg { result, input } => { exec_ctx.visit_f32_neg(*result, *input)?; }
+                Instr::F32Ceil { result, input } => { exec_ctx.visit_f32_ceil(*result, *input)?; }
+                Instr::F32Floor { result, input } => { exec_ctx.visit_f32_floor(*result, *input)?; }
+                Instr::F32Trunc { result, input } => { exec_ctx.visit_f32_trunc(*result, *input)?; }
+                Instr::F32Nearest { result, input } => { exec_ctx.visit_f32_nearest(*result, *input)?; }
+                Instr::F32Sqrt { result, input } => { exec_ctx.visit_f32_sqrt(*result, *input)?; }
+                Instr::F32Add { result, lhs, rhs } => { exec_ctx.visit_f32_add(*result, *lhs, *rhs)?; }
+                Instr::F32Sub { result, lhs, rhs } => { exec_ctx.visit_f32_sub(*result, *lhs, *rhs)?; }
+                Instr::F32Mul { result, lhs, rhs } => { exec_ctx.visit_f32_mul(*result, *lhs, *rhs)?; }
+                Instr::F32Div { result, lhs, rhs } => { exec_ctx.visit_f32_div(*result, *lhs, *rhs)?; }
+                Instr::F32Min { result, lhs, rhs } => { exec_ctx.visit_f32_min(*result, *lhs, *rhs)?; }
+                Instr::F32Max { result, lhs, rhs } => { exec_ctx.visit_f32_max(*result, *lhs, *rhs)?; }
+                Instr::F32Copysign { result, lhs, rhs } => { exec_ctx.visit_f32_copysign(*result, *lhs, *rhs)?; }
+                Instr::F64Abs { result, input } => { exec_ctx.visit_f64_abs(*result, *input)?; }
+                Instr::F64Neg { result, input } => { exec_ctx.visit_f64_neg(*result, *input)?; }
+                Instr::F64Ceil { result, input } => { exec_ctx.visit_f64_ceil(*result, *input)?; }
+                Instr::F64Floor { result, input } => { exec_ctx.visit_f64_floor(*result, *input)?; }
+                Instr::F64Trunc { result, input } => { exec_ctx.visit_f64_trunc(*result, *input)?; }
+                Instr::F64Nearest { result, input } => { exec_ctx.visit_f64_nearest(*result, *input)?; }
+                Instr::F64Sqrt { result, input } => { exec_ctx.visit_f64_sqrt(*result, *input)?; }
+                Instr::F64Add { result, lhs, rhs } => { exec_ctx.visit_f64_add(*result, *lhs, *rhs)?; }
+                Instr::F64Sub { result, lhs, rhs } => { exec_ctx.visit_f64_sub(*result, *lhs, *rhs)?; }
+                Instr::F64Mul { result, lhs, rhs } => { exec_ctx.visit_f64_mul(*result, *lhs, *rhs)?; }
+                Instr::F64Div { result, lhs, rhs } => { exec_ctx.visit_f64_div(*result, *lhs, *rhs)?; }
+                Instr::F64Min { result, lhs, rhs } => { exec_ctx.visit_f64_min(*result, *lhs, *rhs)?; }
+                Instr::F64Max { result, lhs, rhs } => { exec_ctx.visit_f64_max(*result, *lhs, *rhs)?; }
+                Instr::F64Copysign { result, lhs, rhs } => { exec_ctx.visit_f64_copysign(*result, *lhs, *rhs)?; }
+                Instr::I32WrapI64 { result, input } => { exec_ctx.visit_i32_wrap_i64(*result, *input)?; }
+                Instr::I32TruncSF32 { result, input } => { exec_ctx.visit_i32_trunc_f32(*result, *input)?; }
+                Instr::I32TruncUF32 { result, input } => { exec_ctx.visit_u32_trunc_f32(*result, *input)?; }
+                Instr::I32TruncSF64 { result, input } => { exec_ctx.visit_i32_trunc_f64(*result, *input)?; }
+                Instr::I32TruncUF64 { result, input } => { exec_ctx.visit_u32_trunc_f64(*result, *input)?; }
+                Instr::I64ExtendSI32 { result, input } => { exec_ctx.visit_i64_extend_i32(*result, *input)?; }
+                Instr::I64ExtendUI32 { result, input } => { exec_ctx.visit_i64_extend_u32(*result, *input)?; }
+                Instr::I64TruncSF32 { result, input } => { exec_ctx.visit_i64_trunc_f32(*result, *input)?; }
+                Instr::I64TruncUF32 { result, input } => { exec_ctx.visit_u64_trunc_f32(*result, *input)?; }
+                Instr::I64TruncSF64 { result, input } => { exec_ctx.visit_i64_trunc_f64(*result, *input)?; }
+                Instr::I64TruncUF64 { result, input } => { exec_ctx.visit_u64_trunc_f64(*result, *input)?; }
+                Instr::F32ConvertSI32 { result, input } => { exec_ctx.visit_f32_convert_i32(*result, *input)?; }
+                Instr::F32ConvertUI32 { result, input } => { exec_ctx.visit_f32_convert_u32(*result, *input)?; }
+                Instr::F32ConvertSI64 { result, input } => { exec_ctx.visit_f32_convert_i64(*result, *input)?; }
+                Instr::F32ConvertUI64 { result, input } => { exec_ctx.visit_f32_convert_u64(*result, *input)?; }
+                Instr::F32DemoteF64 { result, input } => { exec_ctx.visit_f32_demote_f64(*result, *input)?; }
+                Instr::F64ConvertSI32 { result, input } => { exec_ctx.visit_f64_convert_i32(*result, *input)?; }
+                Instr::F64ConvertUI32 { result, input } => { exec_ctx.visit_f64_convert_u32(*result, *input)?; }
+                Instr::F64ConvertSI64 { result, input } => { exec_ctx.visit_f64_convert_i64(*result, *input)?; }
+                Instr::F64ConvertUI64 { result, input } => { exec_ctx.visit_f64_convert_u64(*result, *input)?; }
+                Instr::F64PromoteF32 { result, input } => { exec_ctx.visit_f64_promote_f32(*result, *input)?; }
+                Instr::I32Extend8S { result, input } => { exec_ctx.visit_i32_sign_extend8(*result, *input)?; }
+                Instr::I32Extend16S { result, input } => { exec_ctx.visit_i32_sign_extend16(*result, *input)?; }
+                Instr::I64Extend8S { result, input } => { exec_ctx.visit_i64_sign_extend8(*result, *input)?; }
+                Instr::I64Extend16S { result, input } => { exec_ctx.visit_i64_sign_extend16(*result, *input)?; }
+                Instr::I64Extend32S { result, input } => { exec_ctx.visit_i64_sign_extend32(*result, *input)?; }
+                Instr::I32TruncSatF32S { result, input } => { exec_ctx.visit_i32_trunc_sat_f32(*result, *input)?; }
+                Instr::I32TruncSatF32U { result, input } => { exec_ctx.visit_u32_trunc_sat_f32(*result, *input)?; }
+                Instr::I32TruncSatF64S { result, input } => { exec_ctx.visit_i32_trunc_sat_f64(*result, *input)?; }
+                Instr::I32TruncSatF64U { result, input } => { exec_ctx.visit_u32_trunc_sat_f64(*result, *input)?; }
+                Instr::I64TruncSatF32S { result, input } => { exec_ctx.visit_i64_trunc_sat_f32(*result, *input)?; }
+                Instr::I64TruncSatF32U { result, input } => { exec_ctx.visit_u64_trunc_sat_f32(*result, *input)?; }
+                Instr::I64TruncSatF64S { result, input } => { exec_ctx.visit_i64_trunc_sat_f64(*result, *input)?; }
+                Instr::I64TruncSatF64U { result, input } => { exec_ctx.visit_u64_trunc_sat_f64(*result, *input)?; }
                 Instr::FuncBodyStart { .. } | Instruction::FuncBodyEnd => {
                     if cfg!(debug) {
                         unreachable!(
@@ -283,6 +388,8 @@ impl<'engine, 'func> FunctionExecutor<'engine, 'func> {
 struct ExecutionContext<'engine, 'func, Ctx> {
     /// The program counter.
     pc: usize,
+    /// The index of the first register of the executed function frame on the value stack.
+    base: usize,
     /// Stores the value stack of live values on the Wasm stack.
     value_stack: &'engine mut ValueStack,
     /// The function frame that is being executed.
@@ -291,6 +398,10 @@ struct ExecutionContext<'engine, 'func, Ctx> {
     ///
     /// [`Store`]: [`crate::v1::Store`]
     ctx: Ctx,
+    /// The fuel cost schedule if fuel metering is enabled.
+    fuel_costs: Option<FuelCosts>,
+    /// Is `true` if NaN results of float instructions are canonicalized.
+    nan_canonicalization: bool,
 }
 
 impl<'engine, 'func, Ctx> ExecutionContext<'engine, 'func, Ctx>
@@ -303,15 +414,112 @@ where
         frame: &'func mut FunctionFrame,
         ctx: Ctx,
         pc: usize,
+        fuel_costs: Option<FuelCosts>,
+        nan_canonicalization: bool,
     ) -> Self {
+        let base = frame.base();
         Self {
             value_stack,
             frame,
             ctx,
             pc,
+            base,
+            fuel_costs,
+            nan_canonicalization,
+        }
+    }
+
+    /// Consumes `delta` units of fuel from the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough fuel remaining in the [`Store`].
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[inline]
+    fn consume_fuel(&mut self, delta: u64) -> Result<(), TrapCode> {
+        self.ctx.as_context_mut().store.fuel_mut().consume(delta)
+    }
+
+    /// Invokes the tracing hook of the [`Store`] if any before `instr` is executed.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[cfg(feature = "trace")]
+    #[inline]
+    fn trace(&mut self, func_body: ResolvedFuncBody, instr: &Instruction) {
+        let ctx = self.ctx.as_context_mut();
+        if let Some(hook) = ctx.store.trace_hook_mut() {
+            let registers = self
+                .value_stack
+                .as_slice(self.base, self.base + func_body.max_stack_height());
+            let event = TraceEvent::new(
+                self.frame.func,
+                self.frame.func_index(),
+                self.pc,
+                instr,
+                registers,
+                func_body.len_params() + func_body.len_locals(),
+            );
+            hook.trace(&event);
         }
     }
 
+    /// Returns the [`PauseReason`] if the debug execution within the [`Store`] shall pause at the `pc`.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[cfg(feature = "debugger")]
+    #[inline]
+    fn should_pause(&mut self, func_body: ResolvedFuncBody) -> Option<PauseReason> {
+        let pc = self.pc;
+        self.ctx.as_context_mut().store.debugger_mut().should_pause(
+            self.frame.func_index(),
+            func_body.wasm_offset(pc),
+            func_body.starts_wasm_instr(pc),
+        )
+    }
+
+    /// Counts the executed instruction if profiling is enabled for the [`Store`].
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[cfg(feature = "profiler")]
+    #[inline]
+    fn count_instruction(&mut self) {
+        if let Some(profiler) = self.ctx.as_context_mut().store.profiler_mut() {
+            profiler.count_instruction();
+        }
+    }
+
+    /// Records the execution of the instruction at the `pc` if coverage is enabled for the [`Store`].
+    ///
+    /// The `last_pc` is the `pc` of the previously executed instruction of the function frame if any.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[cfg(feature = "coverage")]
+    #[inline]
+    fn cover(&mut self, last_pc: Option<usize>) {
+        let func_body = self.frame.func_body;
+        let pc = self.pc;
+        if let Some(coverage) = self.ctx.as_context_mut().store.coverage_mut() {
+            coverage.hit(func_body, pc, last_pc);
+        }
+    }
+
+    /// Traps if an interrupt has been requested for the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is checked at loop back-edges and function calls so that
+    /// every long running execution eventually observes the interrupt.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[inline]
+    fn check_interrupt(&self) -> Result<(), TrapCode> {
+        if self.ctx.as_context().store.take_interrupt() {
+            return Err(TrapCode::Interrupted);
+        }
+        Ok(())
+    }
+
     /// Returns the default linear memory.
     ///
     /// # Panics
@@ -342,10 +550,82 @@ where
             .unwrap_or_else(|| panic!("missing global at index {:?}", global_index))
     }
 
-    /// Returns the local depth as `usize`.
-    fn convert_local_depth(local_depth: LocalIdx) -> usize {
-        // TODO: calculate the -1 offset at module compilation time.
-        (local_depth.into_inner() - 1) as usize
+    /// Returns the bytes of the data segment at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no data segment at the given index.
+    fn data_segment(&self, segment: DataSegmentIdx) -> Arc<[u8]> {
+        self.frame
+            .instance
+            .get_data_segment(self.ctx.as_context(), segment.into_inner())
+            .unwrap_or_else(|| panic!("missing data segment at index {:?}", segment))
+    }
+
+    /// Returns the functions of the element segment at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no element segment at the given index.
+    fn element_segment(&self, segment: ElementSegmentIdx) -> Arc<[Func]> {
+        self.frame
+            .instance
+            .get_element_segment(self.ctx.as_context(), segment.into_inner())
+            .unwrap_or_else(|| panic!("missing element segment at index {:?}", segment))
+    }
+
+    /// Returns the value of the `register` of the executed function frame.
+    #[inline]
+    fn get_register(&self, register: Register) -> UntypedValue {
+        self.value_stack.get(self.base + register.into_usize())
+    }
+
+    /// Returns the value of the `register` of the executed function frame as `T`.
+    #[inline]
+    fn get_register_as<T>(&self, register: Register) -> T
+    where
+        T: From<UntypedValue>,
+    {
+        T::from(self.get_register(register))
+    }
+
+    /// Sets the value of the `register` of the executed function frame to `value`.
+    #[inline]
+    fn set_register<T>(&mut self, register: Register, value: T)
+    where
+        T: Into<UntypedValue>,
+    {
+        self.value_stack
+            .set(self.base + register.into_usize(), value.into())
+    }
+
+    /// Returns the `dst`, `src` and `len` operands of a `memory.init` or `table.init`
+    /// instruction stored in the three registers starting at `operands`.
+    fn get_init_operands(&self, operands: Register) -> (u32, u32, u32) {
+        let register = |n: u32| Register::from(operands.into_inner() + n);
+        (
+            self.get_register_as(register(0)),
+            self.get_register_as(register(1)),
+            self.get_register_as(register(2)),
+        )
+    }
+
+    /// Consumes the fuel for processing `len` bytes or table elements by a bulk memory instruction.
+    fn consume_bulk_fuel(&mut self, len: u32) -> Result<(), TrapCode> {
+        if let Some(costs) = &self.fuel_costs {
+            self.consume_fuel(costs.bulk_cost(len))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the range of `len` bytes or elements starting at `offset`
+    /// if it is in bounds of a region of size `bounds`.
+    fn bulk_range(offset: u32, len: u32, bounds: usize) -> Option<Range<usize>> {
+        let start = offset as usize;
+        start
+            .checked_add(len as usize)
+            .filter(|&end| end <= bounds)
+            .map(|end| start..end)
     }
 
     /// Calculates the effective address of a linear memory access.
@@ -371,21 +651,25 @@ where
     /// - `i64.load`
     /// - `f32.load`
     /// - `f64.load`
-    fn execute_load<T>(&mut self, offset: Offset) -> Result<(), TrapCode>
+    fn execute_load<T>(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode>
     where
         UntypedValue: From<T>,
         T: LittleEndianConvert,
     {
         let memory = self.default_memory();
-        let entry = self.value_stack.last_mut();
-        let raw_address = u32::from(*entry);
+        let raw_address = self.get_register_as::<u32>(ptr);
         let address = Self::effective_address(offset, raw_address)?;
         let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
         memory
             .read(self.ctx.as_context(), address, bytes.as_mut())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         let value = <T as LittleEndianConvert>::from_le_bytes(bytes);
-        *entry = value.into();
+        self.set_register(result, value);
         self.next_instr()
     }
 
@@ -405,21 +689,25 @@ where
     /// - `i64.load_16u`
     /// - `i64.load_32s`
     /// - `i64.load_32u`
-    fn execute_load_extend<T, U>(&mut self, offset: Offset) -> Result<(), TrapCode>
+    fn execute_load_extend<T, U>(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode>
     where
         T: ExtendInto<U> + LittleEndianConvert,
         UntypedValue: From<U>,
     {
         let memory = self.default_memory();
-        let entry = self.value_stack.last_mut();
-        let raw_address = u32::from(*entry);
+        let raw_address = self.get_register_as::<u32>(ptr);
         let address = Self::effective_address(offset, raw_address)?;
         let mut bytes = <<T as LittleEndianConvert>::Bytes as Default>::default();
         memory
             .read(self.ctx.as_context(), address, bytes.as_mut())
             .map_err(|_| TrapCode::MemoryAccessOutOfBounds)?;
         let extended = <T as LittleEndianConvert>::from_le_bytes(bytes).extend_into();
-        *entry = extended.into();
+        self.set_register(result, extended);
         self.next_instr()
     }
 
@@ -433,12 +721,17 @@ where
     /// - `i64.store`
     /// - `f32.store`
     /// - `f64.store`
-    fn execute_store<T>(&mut self, offset: Offset) -> Result<(), TrapCode>
+    fn execute_store<T>(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode>
     where
         T: LittleEndianConvert + From<UntypedValue>,
     {
-        let stack_value = self.value_stack.pop_as::<T>();
-        let raw_address = self.value_stack.pop_as::<u32>();
+        let stack_value = self.get_register_as::<T>(value);
+        let raw_address = self.get_register_as::<u32>(ptr);
         let address = Self::effective_address(offset, raw_address)?;
         let memory = self.default_memory();
         let bytes = <T as LittleEndianConvert>::into_le_bytes(stack_value);
@@ -459,13 +752,18 @@ where
     /// - `i64.store8`
     /// - `i64.store16`
     /// - `i64.store32`
-    fn execute_store_wrap<T, U>(&mut self, offset: Offset) -> Result<(), TrapCode>
+    fn execute_store_wrap<T, U>(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode>
     where
         T: WrapInto<U> + From<UntypedValue>,
         U: LittleEndianConvert,
     {
-        let wrapped_value = self.value_stack.pop_as::<T>().wrap_into();
-        let raw_address = self.value_stack.pop_as::<u32>();
+        let wrapped_value = self.get_register_as::<T>(value).wrap_into();
+        let raw_address = self.get_register_as::<u32>(ptr);
         let address = Self::effective_address(offset, raw_address)?;
         let memory = self.default_memory();
         let bytes = <U as LittleEndianConvert>::into_le_bytes(wrapped_value);
@@ -475,71 +773,181 @@ where
         self.next_instr()
     }
 
-    fn execute_unary(&mut self, f: fn(UntypedValue) -> UntypedValue) -> Result<(), TrapCode> {
-        let entry = self.value_stack.last_mut();
-        *entry = f(*entry);
+    fn execute_unary(
+        &mut self,
+        result: Register,
+        input: Register,
+        f: fn(UntypedValue) -> UntypedValue,
+    ) -> Result<(), TrapCode> {
+        let value = f(self.get_register(input));
+        self.set_register(result, value);
         self.next_instr()
     }
 
     fn try_execute_unary(
         &mut self,
+        result: Register,
+        input: Register,
         f: fn(UntypedValue) -> Result<UntypedValue, TrapCode>,
     ) -> Result<(), TrapCode> {
-        let entry = self.value_stack.last_mut();
-        *entry = f(*entry)?;
+        let value = f(self.get_register(input))?;
+        self.set_register(result, value);
         self.next_instr()
     }
 
     fn execute_binary(
         &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
         f: fn(UntypedValue, UntypedValue) -> UntypedValue,
     ) -> Result<(), TrapCode> {
-        let right = self.value_stack.pop();
-        let entry = self.value_stack.last_mut();
-        let left = *entry;
-        *entry = f(left, right);
+        let value = f(self.get_register(lhs), self.get_register(rhs));
+        self.set_register(result, value);
         self.next_instr()
     }
 
     fn try_execute_binary(
         &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
         f: fn(UntypedValue, UntypedValue) -> Result<UntypedValue, TrapCode>,
     ) -> Result<(), TrapCode> {
-        let right = self.value_stack.pop();
-        let entry = self.value_stack.last_mut();
-        let left = *entry;
-        *entry = f(left, right)?;
+        let value = f(self.get_register(lhs), self.get_register(rhs))?;
+        self.set_register(result, value);
         self.next_instr()
     }
 
-    fn execute_reinterpret<T, U>(&mut self) -> Result<(), TrapCode>
-    where
-        UntypedValue: From<U>,
-        T: From<UntypedValue>,
-    {
-        // Nothing to do for `wasmi` bytecode.
+    /// Replaces a NaN `value` with the canonical Wasm NaN if NaN canonicalization is enabled.
+    ///
+    /// The `canonicalize` function replaces NaN values of the result type of the float instruction.
+    #[inline]
+    fn canonicalize_nan(
+        &self,
+        value: UntypedValue,
+        canonicalize: fn(UntypedValue) -> UntypedValue,
+    ) -> UntypedValue {
+        match self.nan_canonicalization {
+            true => canonicalize(value),
+            false => value,
+        }
+    }
+
+    /// Executes a unary float instruction whose NaN results are canonicalized via `canonicalize` if enabled.
+    fn execute_float_unary(
+        &mut self,
+        result: Register,
+        input: Register,
+        f: fn(UntypedValue) -> UntypedValue,
+        canonicalize: fn(UntypedValue) -> UntypedValue,
+    ) -> Result<(), TrapCode> {
+        let value = self.canonicalize_nan(f(self.get_register(input)), canonicalize);
+        self.set_register(result, value);
+        self.next_instr()
+    }
+
+    /// Executes a binary float instruction whose NaN results are canonicalized via `canonicalize` if enabled.
+    fn execute_float_binary(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+        f: fn(UntypedValue, UntypedValue) -> UntypedValue,
+        canonicalize: fn(UntypedValue) -> UntypedValue,
+    ) -> Result<(), TrapCode> {
+        let value = f(self.get_register(lhs), self.get_register(rhs));
+        let value = self.canonicalize_nan(value, canonicalize);
+        self.set_register(result, value);
+        self.next_instr()
+    }
+
+    /// Executes a fallible binary float instruction whose NaN results are canonicalized via `canonicalize` if enabled.
+    fn try_execute_float_binary(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+        f: fn(UntypedValue, UntypedValue) -> Result<UntypedValue, TrapCode>,
+        canonicalize: fn(UntypedValue) -> UntypedValue,
+    ) -> Result<(), TrapCode> {
+        let value = f(self.get_register(lhs), self.get_register(rhs))?;
+        let value = self.canonicalize_nan(value, canonicalize);
+        self.set_register(result, value);
         self.next_instr()
     }
 
+    /// Branches to `target` if `f` evaluates to `true` for the values of `lhs` and `rhs`.
+    fn execute_br_if_cmp(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+        f: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    ) -> Result<(), TrapCode> {
+        let condition: bool = f(self.get_register(lhs), self.get_register(rhs)).into();
+        if condition {
+            self.branch_to(target)
+        } else {
+            self.next_instr()
+        }
+    }
+
     fn next_instr(&mut self) -> Result<(), TrapCode> {
         self.pc += 1;
         Ok(())
     }
 
     fn branch_to(&mut self, target: Target) -> Result<(), TrapCode> {
-        self.value_stack.drop_keep(target.drop_keep());
-        self.pc = target.destination_pc().into_usize();
+        let destination_pc = target.destination_pc().into_usize();
+        if destination_pc <= self.pc {
+            // Loop back-edges are checked for interrupts.
+            self.check_interrupt()?;
+        }
+        self.pc = destination_pc;
         Ok(())
     }
 
-    fn call_func(&mut self, func: Func) -> Result<CallOutcome, TrapCode> {
+    /// Returns `func` if it has not been dropped from the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the instance of the Wasm function `func` has been dropped.
+    ///
+    /// [`Store`]: [`crate::v1::Store`]
+    #[inline]
+    fn live_func(&self, func: Func) -> Result<Func, TrapCode> {
+        match self.ctx.as_context().store.contains_func(func) {
+            true => Ok(func),
+            false => Err(TrapCode::DroppedInstance),
+        }
+    }
+
+    /// Prepares the value stack for calling `func` and returns the [`CallOutcome`].
+    ///
+    /// # Note
+    ///
+    /// The parameters of the call are the registers preceding `params_end`.
+    /// The value stack is shrunk so that they are the last values on it.
+    fn call_func(&mut self, func: Func, params_end: Register) -> Result<CallOutcome, TrapCode> {
+        self.check_interrupt()?;
+        self.value_stack
+            .truncate(self.base + params_end.into_usize());
         self.pc += 1;
         self.frame.update_pc(self.pc);
         Ok(CallOutcome::NestedCall(func))
     }
 
-    fn ret(&mut self, drop_keep: DropKeep) -> Result<(), TrapCode> {
-        self.value_stack.drop_keep(drop_keep);
+    /// Moves the returned values to the start of the function frame.
+    ///
+    /// # Note
+    ///
+    /// The returned values are the `keep` registers starting at register `drop`.
+    /// Afterwards they are the last values on the value stack.
+    fn ret(&mut self, results: DropKeep) -> Result<(), TrapCode> {
+        self.value_stack
+            .truncate(self.base + results.drop() + results.keep());
+        self.value_stack.drop_keep(results);
         Ok(())
     }
 }
@@ -562,8 +970,8 @@ where
         self.branch_to(target)
     }
 
-    fn visit_br_if_eqz(&mut self, target: Target) -> Result<(), TrapCode> {
-        let condition = self.value_stack.pop_as();
+    fn visit_br_if_eqz(&mut self, condition: Register, target: Target) -> Result<(), TrapCode> {
+        let condition = self.get_register_as(condition);
         if condition {
             self.next_instr()
         } else {
@@ -571,8 +979,8 @@ where
         }
     }
 
-    fn visit_br_if_nez(&mut self, target: Target) -> Result<(), TrapCode> {
-        let condition = self.value_stack.pop_as();
+    fn visit_br_if_nez(&mut self, condition: Register, target: Target) -> Result<(), TrapCode> {
+        let condition = self.get_register_as(condition);
         if condition {
             self.branch_to(target)
         } else {
@@ -580,10 +988,194 @@ where
         }
     }
 
-    fn visit_return_if_nez(&mut self, drop_keep: DropKeep) -> Result<MaybeReturn, TrapCode> {
-        let condition = self.value_stack.pop_as();
+    fn visit_br_if_i32_eq(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_eq)
+    }
+
+    fn visit_br_if_i32_ne(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_ne)
+    }
+
+    fn visit_br_if_i32_lt_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_lt_s)
+    }
+
+    fn visit_br_if_i32_lt_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_lt_u)
+    }
+
+    fn visit_br_if_i32_gt_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_gt_s)
+    }
+
+    fn visit_br_if_i32_gt_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_gt_u)
+    }
+
+    fn visit_br_if_i32_le_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_le_s)
+    }
+
+    fn visit_br_if_i32_le_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_le_u)
+    }
+
+    fn visit_br_if_i32_ge_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_ge_s)
+    }
+
+    fn visit_br_if_i32_ge_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i32_ge_u)
+    }
+
+    fn visit_br_if_i64_eq(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_eq)
+    }
+
+    fn visit_br_if_i64_ne(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_ne)
+    }
+
+    fn visit_br_if_i64_lt_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_lt_s)
+    }
+
+    fn visit_br_if_i64_lt_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_lt_u)
+    }
+
+    fn visit_br_if_i64_gt_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_gt_s)
+    }
+
+    fn visit_br_if_i64_gt_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_gt_u)
+    }
+
+    fn visit_br_if_i64_le_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_le_s)
+    }
+
+    fn visit_br_if_i64_le_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_le_u)
+    }
+
+    fn visit_br_if_i64_ge_s(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_ge_s)
+    }
+
+    fn visit_br_if_i64_ge_u(
+        &mut self,
+        lhs: Register,
+        rhs: Register,
+        target: Target,
+    ) -> Result<(), TrapCode> {
+        self.execute_br_if_cmp(lhs, rhs, target, UntypedValue::i64_ge_u)
+    }
+
+    fn visit_return_if_nez(
+        &mut self,
+        condition: Register,
+        results: DropKeep,
+    ) -> Result<MaybeReturn, TrapCode> {
+        let condition = self.get_register_as(condition);
         if condition {
-            self.ret(drop_keep)?;
+            self.ret(results)?;
             Ok(MaybeReturn::Return)
         } else {
             self.pc += 1;
@@ -591,8 +1183,8 @@ where
         }
     }
 
-    fn visit_br_table(&mut self, len_targets: usize) -> Result<(), TrapCode> {
-        let index: u32 = self.value_stack.pop_as();
+    fn visit_br_table(&mut self, index: Register, len_targets: usize) -> Result<(), TrapCode> {
+        let index: u32 = self.get_register_as(index);
         // The index of the default target which is the last target of the slice.
         let max_index = len_targets - 1;
         // A normalized index will always yield a target without panicking.
@@ -602,42 +1194,34 @@ where
         Ok(())
     }
 
-    fn visit_ret(&mut self, drop_keep: DropKeep) -> Result<(), TrapCode> {
-        self.ret(drop_keep)
-    }
-
-    fn visit_get_local(&mut self, local_depth: LocalIdx) -> Result<(), TrapCode> {
-        let local_depth = Self::convert_local_depth(local_depth);
-        let value = self.value_stack.peek(local_depth);
-        self.value_stack.push(value);
-        self.next_instr()
-    }
-
-    fn visit_set_local(&mut self, local_depth: LocalIdx) -> Result<(), TrapCode> {
-        let local_depth = Self::convert_local_depth(local_depth);
-        let new_value = self.value_stack.pop();
-        *self.value_stack.peek_mut(local_depth) = new_value;
-        self.next_instr()
+    fn visit_ret(&mut self, results: DropKeep) -> Result<(), TrapCode> {
+        self.ret(results)
     }
 
-    fn visit_tee_local(&mut self, local_depth: LocalIdx) -> Result<(), TrapCode> {
-        let local_depth = Self::convert_local_depth(local_depth);
-        let new_value = self.value_stack.last();
-        *self.value_stack.peek_mut(local_depth) = new_value;
+    fn visit_copy(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        let value = self.get_register(input);
+        self.set_register(result, value);
         self.next_instr()
     }
 
-    fn visit_get_global(&mut self, global_index: GlobalIdx) -> Result<(), TrapCode> {
+    fn visit_get_global(
+        &mut self,
+        result: Register,
+        global_index: GlobalIdx,
+    ) -> Result<(), TrapCode> {
         let global_value = self.global(global_index).get(self.ctx.as_context());
-        self.value_stack.push(global_value);
+        self.set_register(result, global_value);
         self.next_instr()
     }
 
-    fn visit_set_global(&mut self, global_index: GlobalIdx) -> Result<(), TrapCode> {
+    fn visit_set_global(
+        &mut self,
+        global_index: GlobalIdx,
+        input: Register,
+    ) -> Result<(), TrapCode> {
         let global = self.global(global_index);
         let new_value = self
-            .value_stack
-            .pop()
+            .get_register(input)
             .with_type(global.value_type(self.ctx.as_context()));
         global
             .set(self.ctx.as_context_mut(), new_value)
@@ -645,25 +1229,79 @@ where
         self.next_instr()
     }
 
-    fn visit_call(&mut self, func_index: FuncIdx) -> Result<CallOutcome, TrapCode> {
+    fn visit_call(
+        &mut self,
+        func_index: FuncIdx,
+        params_end: Register,
+    ) -> Result<CallOutcome, TrapCode> {
+        let func = self.resolve_func(func_index)?;
+        self.call_func(func, params_end)
+    }
+
+    fn visit_call_indirect(
+        &mut self,
+        signature_index: SignatureIdx,
+        index: Register,
+        params_end: Register,
+    ) -> Result<CallOutcome, TrapCode> {
+        let func = self.resolve_indirect_func(signature_index, index)?;
+        self.call_func(func, params_end)
+    }
+
+    fn visit_return_call(
+        &mut self,
+        func_index: FuncIdx,
+        params_end: Register,
+    ) -> Result<CallOutcome, TrapCode> {
+        let func = self.resolve_func(func_index)?;
+        self.call_func(func, params_end)?;
+        Ok(CallOutcome::TailCall(func))
+    }
+
+    fn visit_return_call_indirect(
+        &mut self,
+        signature_index: SignatureIdx,
+        index: Register,
+        params_end: Register,
+    ) -> Result<CallOutcome, TrapCode> {
+        let func = self.resolve_indirect_func(signature_index, index)?;
+        self.call_func(func, params_end)?;
+        Ok(CallOutcome::TailCall(func))
+    }
+
+    /// Returns the function at `func_index` of the instance of the function frame.
+    ///
+    /// # Errors
+    ///
+    /// If the instance of the function has been dropped.
+    fn resolve_func(&mut self, func_index: FuncIdx) -> Result<Func, TrapCode> {
         let func = self
             .frame
             .instance
             .get_func(self.ctx.as_context_mut(), func_index.into_inner())
             .unwrap_or_else(|| panic!("missing function at index {:?}", func_index));
-        self.call_func(func)
+        self.live_func(func)
     }
 
-    fn visit_call_indirect(
+    /// Returns the function stored in the default table at the value of the `index` register.
+    ///
+    /// # Errors
+    ///
+    /// - If the table access is out of bounds or the table element is uninitialized.
+    /// - If the instance of the function has been dropped.
+    /// - If the signature of the function does not match `signature_index`.
+    fn resolve_indirect_func(
         &mut self,
         signature_index: SignatureIdx,
-    ) -> Result<CallOutcome, TrapCode> {
-        let func_index: u32 = self.value_stack.pop_as();
+        index: Register,
+    ) -> Result<Func, TrapCode> {
+        let func_index: u32 = self.get_register_as(index);
         let table = self.default_table();
         let func = table
             .get(self.ctx.as_context(), func_index as usize)
             .map_err(|_| TrapCode::TableAccessOutOfBounds)?
             .ok_or(TrapCode::ElemUninitialized)?;
+        let func = self.live_func(func)?;
         let actual_signature = func.signature(self.ctx.as_context());
         let expected_signature = self
             .frame
@@ -678,683 +1316,1481 @@ where
         if actual_signature != expected_signature {
             return Err(TrapCode::UnexpectedSignature).map_err(Into::into);
         }
-        self.call_func(func)
+        Ok(func)
     }
 
-    fn visit_const(&mut self, bytes: UntypedValue) -> Result<(), TrapCode> {
-        self.value_stack.push(bytes);
+    fn visit_const(&mut self, result: Register, value: UntypedValue) -> Result<(), TrapCode> {
+        self.set_register(result, value);
         self.next_instr()
     }
 
-    fn visit_drop(&mut self) -> Result<(), TrapCode> {
-        let _ = self.value_stack.pop();
-        self.next_instr()
-    }
-
-    fn visit_select(&mut self) -> Result<(), TrapCode> {
-        self.value_stack.pop2_eval(|e1, e2, e3| {
-            let condition = <bool as From<UntypedValue>>::from(e3);
-            let result = if condition { *e1 } else { e2 };
-            *e1 = result;
-        });
+    fn visit_select(
+        &mut self,
+        result: Register,
+        condition: Register,
+        if_false: Register,
+    ) -> Result<(), TrapCode> {
+        let condition: bool = self.get_register_as(condition);
+        if !condition {
+            let value = self.get_register(if_false);
+            self.set_register(result, value);
+        }
         self.next_instr()
     }
 
-    fn visit_current_memory(&mut self) -> Result<(), TrapCode> {
+    fn visit_current_memory(&mut self, result: Register) -> Result<(), TrapCode> {
         let memory = self.default_memory();
-        let result = memory.current_pages(self.ctx.as_context()).0 as u32;
-        self.value_stack.push(result);
+        let pages = memory.current_pages(self.ctx.as_context()).0 as u32;
+        self.set_register(result, pages);
         self.next_instr()
     }
 
-    fn visit_grow_memory(&mut self) -> Result<(), TrapCode> {
-        let pages: u32 = self.value_stack.pop_as();
+    fn visit_grow_memory(&mut self, result: Register, delta: Register) -> Result<(), TrapCode> {
+        let pages: u32 = self.get_register_as(delta);
+        if let Some(costs) = &self.fuel_costs {
+            self.consume_fuel(costs.memory_grow_cost(pages))?;
+        }
         let memory = self.default_memory();
         let new_size = match memory.grow(self.ctx.as_context_mut(), Pages(pages as usize)) {
             Ok(Pages(old_size)) => old_size as u32,
+            Err(MemoryError::ResourceLimiterTrap(trap_code)) => return Err(trap_code),
             Err(_) => {
                 // Note: The WebAssembly spec demands to return `0xFFFF_FFFF`
                 //       in case of failure for this instruction.
                 u32::MAX
             }
         };
-        self.value_stack.push(new_size);
+        self.set_register(result, new_size);
         self.next_instr()
     }
 
-    fn visit_i32_load(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load::<i32>(offset)
+    fn visit_memory_copy(
+        &mut self,
+        dst: Register,
+        src: Register,
+        len: Register,
+    ) -> Result<(), TrapCode> {
+        let dst: u32 = self.get_register_as(dst);
+        let src: u32 = self.get_register_as(src);
+        let len: u32 = self.get_register_as(len);
+        self.consume_bulk_fuel(len)?;
+        let memory = self.default_memory();
+        let bytes = memory.data_mut(self.ctx.as_context_mut());
+        let src =
+            Self::bulk_range(src, len, bytes.len()).ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        let dst =
+            Self::bulk_range(dst, len, bytes.len()).ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        bytes.copy_within(src, dst.start);
+        self.next_instr()
     }
 
-    fn visit_i64_load(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load::<i64>(offset)
+    fn visit_memory_fill(
+        &mut self,
+        dst: Register,
+        value: Register,
+        len: Register,
+    ) -> Result<(), TrapCode> {
+        let dst: u32 = self.get_register_as(dst);
+        let value: u32 = self.get_register_as(value);
+        let len: u32 = self.get_register_as(len);
+        self.consume_bulk_fuel(len)?;
+        let memory = self.default_memory();
+        let bytes = memory.data_mut(self.ctx.as_context_mut());
+        let dst =
+            Self::bulk_range(dst, len, bytes.len()).ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        bytes[dst].fill(value as u8);
+        self.next_instr()
     }
 
-    fn visit_f32_load(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load::<F32>(offset)
+    fn visit_memory_init(
+        &mut self,
+        segment: DataSegmentIdx,
+        operands: Register,
+    ) -> Result<(), TrapCode> {
+        let (dst, src, len) = self.get_init_operands(operands);
+        self.consume_bulk_fuel(len)?;
+        let data = self.data_segment(segment);
+        let memory = self.default_memory();
+        let bytes = memory.data_mut(self.ctx.as_context_mut());
+        let src =
+            Self::bulk_range(src, len, data.len()).ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        let dst =
+            Self::bulk_range(dst, len, bytes.len()).ok_or(TrapCode::MemoryAccessOutOfBounds)?;
+        bytes[dst].copy_from_slice(&data[src]);
+        self.next_instr()
     }
 
-    fn visit_f64_load(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load::<F64>(offset)
+    fn visit_data_drop(&mut self, segment: DataSegmentIdx) -> Result<(), TrapCode> {
+        self.frame
+            .instance
+            .drop_data_segment(self.ctx.as_context_mut(), segment.into_inner());
+        self.next_instr()
     }
 
-    fn visit_i32_load_i8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<i8, i32>(offset)
+    fn visit_table_copy(
+        &mut self,
+        dst: Register,
+        src: Register,
+        len: Register,
+    ) -> Result<(), TrapCode> {
+        let dst: u32 = self.get_register_as(dst);
+        let src: u32 = self.get_register_as(src);
+        let len: u32 = self.get_register_as(len);
+        self.consume_bulk_fuel(len)?;
+        let table = self.default_table();
+        table
+            .copy_within(
+                self.ctx.as_context_mut(),
+                dst as usize,
+                src as usize,
+                len as usize,
+            )
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        self.next_instr()
     }
 
-    fn visit_i32_load_u8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<u8, i32>(offset)
+    fn visit_table_init(
+        &mut self,
+        segment: ElementSegmentIdx,
+        operands: Register,
+    ) -> Result<(), TrapCode> {
+        let (dst, src, len) = self.get_init_operands(operands);
+        self.consume_bulk_fuel(len)?;
+        let funcs = self.element_segment(segment);
+        let table = self.default_table();
+        let src =
+            Self::bulk_range(src, len, funcs.len()).ok_or(TrapCode::TableAccessOutOfBounds)?;
+        table
+            .init(self.ctx.as_context_mut(), dst as usize, &funcs[src])
+            .map_err(|_| TrapCode::TableAccessOutOfBounds)?;
+        self.next_instr()
     }
 
-    fn visit_i32_load_i16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<i16, i32>(offset)
+    fn visit_elem_drop(&mut self, segment: ElementSegmentIdx) -> Result<(), TrapCode> {
+        self.frame
+            .instance
+            .drop_element_segment(self.ctx.as_context_mut(), segment.into_inner());
+        self.next_instr()
     }
 
-    fn visit_i32_load_u16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<u16, i32>(offset)
+    fn visit_i32_load(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load::<i32>(result, ptr, offset)
     }
 
-    fn visit_i64_load_i8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<i8, i64>(offset)
+    fn visit_i64_load(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load::<i64>(result, ptr, offset)
     }
 
-    fn visit_i64_load_u8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<u8, i64>(offset)
+    fn visit_f32_load(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load::<F32>(result, ptr, offset)
     }
 
-    fn visit_i64_load_i16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<i16, i64>(offset)
+    fn visit_f64_load(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load::<F64>(result, ptr, offset)
     }
 
-    fn visit_i64_load_u16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<u16, i64>(offset)
+    fn visit_i32_load_i8(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<i8, i32>(result, ptr, offset)
     }
 
-    fn visit_i64_load_i32(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<i32, i64>(offset)
+    fn visit_i32_load_u8(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<u8, i32>(result, ptr, offset)
     }
 
-    fn visit_i64_load_u32(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_load_extend::<u32, i64>(offset)
+    fn visit_i32_load_i16(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<i16, i32>(result, ptr, offset)
     }
 
-    fn visit_i32_store(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store::<i32>(offset)
+    fn visit_i32_load_u16(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<u16, i32>(result, ptr, offset)
     }
 
-    fn visit_i64_store(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store::<i64>(offset)
+    fn visit_i64_load_i8(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<i8, i64>(result, ptr, offset)
     }
 
-    fn visit_f32_store(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store::<F32>(offset)
+    fn visit_i64_load_u8(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<u8, i64>(result, ptr, offset)
     }
 
-    fn visit_f64_store(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store::<F64>(offset)
+    fn visit_i64_load_i16(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<i16, i64>(result, ptr, offset)
     }
 
-    fn visit_i32_store_8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store_wrap::<i32, i8>(offset)
+    fn visit_i64_load_u16(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<u16, i64>(result, ptr, offset)
     }
 
-    fn visit_i32_store_16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store_wrap::<i32, i16>(offset)
+    fn visit_i64_load_i32(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<i32, i64>(result, ptr, offset)
     }
 
-    fn visit_i64_store_8(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store_wrap::<i64, i8>(offset)
+    fn visit_i64_load_u32(
+        &mut self,
+        result: Register,
+        ptr: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_load_extend::<u32, i64>(result, ptr, offset)
     }
 
-    fn visit_i64_store_16(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store_wrap::<i64, i16>(offset)
+    fn visit_i32_store(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store::<i32>(ptr, value, offset)
     }
 
-    fn visit_i64_store_32(&mut self, offset: Offset) -> Result<(), TrapCode> {
-        self.execute_store_wrap::<i64, i32>(offset)
+    fn visit_i64_store(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store::<i64>(ptr, value, offset)
     }
 
-    fn visit_i32_eqz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_eqz)
+    fn visit_f32_store(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store::<F32>(ptr, value, offset)
     }
 
-    fn visit_i32_eq(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_eq)
+    fn visit_f64_store(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store::<F64>(ptr, value, offset)
     }
 
-    fn visit_i32_ne(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_ne)
+    fn visit_i32_store_8(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store_wrap::<i32, i8>(ptr, value, offset)
     }
 
-    fn visit_i32_lt_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_lt_s)
+    fn visit_i32_store_16(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store_wrap::<i32, i16>(ptr, value, offset)
     }
 
-    fn visit_i32_lt_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_lt_u)
+    fn visit_i64_store_8(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store_wrap::<i64, i8>(ptr, value, offset)
     }
 
-    fn visit_i32_gt_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_gt_s)
+    fn visit_i64_store_16(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store_wrap::<i64, i16>(ptr, value, offset)
     }
 
-    fn visit_i32_gt_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_gt_u)
+    fn visit_i64_store_32(
+        &mut self,
+        ptr: Register,
+        value: Register,
+        offset: Offset,
+    ) -> Result<(), TrapCode> {
+        self.execute_store_wrap::<i64, i32>(ptr, value, offset)
     }
 
-    fn visit_i32_le_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_le_s)
+    fn visit_i32_eqz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_eqz)
     }
 
-    fn visit_i32_le_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_le_u)
+    fn visit_i32_eq(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_eq)
     }
 
-    fn visit_i32_ge_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_ge_s)
+    fn visit_i32_ne(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_ne)
     }
 
-    fn visit_i32_ge_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_ge_u)
+    fn visit_i32_lt_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_lt_s)
     }
 
-    fn visit_i64_eqz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_eqz)
+    fn visit_i32_lt_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_lt_u)
     }
 
-    fn visit_i64_eq(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_eq)
+    fn visit_i32_gt_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_gt_s)
     }
 
-    fn visit_i64_ne(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_ne)
+    fn visit_i32_gt_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_gt_u)
     }
 
-    fn visit_i64_lt_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_lt_s)
+    fn visit_i32_le_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_le_s)
     }
 
-    fn visit_i64_lt_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_lt_u)
+    fn visit_i32_le_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_le_u)
     }
 
-    fn visit_i64_gt_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_gt_s)
+    fn visit_i32_ge_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_ge_s)
     }
 
-    fn visit_i64_gt_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_gt_u)
+    fn visit_i32_ge_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_ge_u)
     }
 
-    fn visit_i64_le_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_le_s)
+    fn visit_i64_eqz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_eqz)
     }
 
-    fn visit_i64_le_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_le_u)
+    fn visit_i64_eq(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_eq)
     }
 
-    fn visit_i64_ge_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_ge_s)
+    fn visit_i64_ne(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_ne)
     }
 
-    fn visit_i64_ge_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_ge_u)
+    fn visit_i64_lt_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_lt_s)
     }
 
-    fn visit_f32_eq(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_eq)
+    fn visit_i64_lt_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_lt_u)
     }
 
-    fn visit_f32_ne(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_ne)
+    fn visit_i64_gt_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_gt_s)
     }
 
-    fn visit_f32_lt(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_lt)
+    fn visit_i64_gt_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_gt_u)
     }
 
-    fn visit_f32_gt(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_gt)
+    fn visit_i64_le_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_le_s)
     }
 
-    fn visit_f32_le(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_le)
+    fn visit_i64_le_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_le_u)
     }
 
-    fn visit_f32_ge(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_ge)
+    fn visit_i64_ge_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_ge_s)
     }
 
-    fn visit_f64_eq(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_eq)
+    fn visit_i64_ge_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_ge_u)
     }
 
-    fn visit_f64_ne(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_ne)
+    fn visit_f32_eq(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_eq)
     }
 
-    fn visit_f64_lt(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_lt)
+    fn visit_f32_ne(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_ne)
     }
 
-    fn visit_f64_gt(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_gt)
+    fn visit_f32_lt(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_lt)
     }
 
-    fn visit_f64_le(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_le)
+    fn visit_f32_gt(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_gt)
     }
 
-    fn visit_f64_ge(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_ge)
+    fn visit_f32_le(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_le)
     }
 
-    fn visit_i32_clz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_clz)
+    fn visit_f32_ge(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_ge)
     }
 
-    fn visit_i32_ctz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_ctz)
+    fn visit_f64_eq(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_eq)
     }
 
-    fn visit_i32_popcnt(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_popcnt)
+    fn visit_f64_ne(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_ne)
     }
 
-    fn visit_i32_add(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_add)
+    fn visit_f64_lt(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_lt)
     }
 
-    fn visit_i32_sub(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_sub)
+    fn visit_f64_gt(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_gt)
     }
 
-    fn visit_i32_mul(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_mul)
+    fn visit_f64_le(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_le)
+    }
+
+    fn visit_f64_ge(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_ge)
+    }
+
+    fn visit_i32_clz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_clz)
+    }
+
+    fn visit_i32_ctz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_ctz)
+    }
+
+    fn visit_i32_popcnt(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_popcnt)
+    }
+
+    fn visit_i32_add(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_add)
     }
 
-    fn visit_i32_div_s(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i32_div_s)
+    fn visit_i32_add_imm(
+        &mut self,
+        result: Register,
+        input: Register,
+        value: i32,
+    ) -> Result<(), TrapCode> {
+        let input: i32 = self.get_register_as(input);
+        self.set_register(result, input.wrapping_add(value));
+        self.next_instr()
     }
 
-    fn visit_i32_div_u(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i32_div_u)
+    fn visit_i32_sub(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_sub)
     }
 
-    fn visit_i32_rem_s(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i32_rem_s)
+    fn visit_i32_mul(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_mul)
     }
 
-    fn visit_i32_rem_u(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i32_rem_u)
+    fn visit_i32_div_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i32_div_s)
     }
 
-    fn visit_i32_and(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_and)
+    fn visit_i32_div_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i32_div_u)
     }
 
-    fn visit_i32_or(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_or)
+    fn visit_i32_rem_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i32_rem_s)
     }
 
-    fn visit_i32_xor(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_xor)
+    fn visit_i32_rem_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i32_rem_u)
     }
 
-    fn visit_i32_shl(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_shl)
+    fn visit_i32_and(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_and)
     }
 
-    fn visit_i32_shr_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_shr_s)
+    fn visit_i32_or(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_or)
     }
 
-    fn visit_i32_shr_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_shr_u)
+    fn visit_i32_xor(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_xor)
     }
 
-    fn visit_i32_rotl(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_rotl)
+    fn visit_i32_shl(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_shl)
     }
 
-    fn visit_i32_rotr(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i32_rotr)
+    fn visit_i32_shr_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_shr_s)
     }
 
-    fn visit_i64_clz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_clz)
+    fn visit_i32_shr_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_shr_u)
     }
 
-    fn visit_i64_ctz(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_ctz)
+    fn visit_i32_rotl(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_rotl)
     }
 
-    fn visit_i64_popcnt(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_popcnt)
+    fn visit_i32_rotr(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i32_rotr)
     }
 
-    fn visit_i64_add(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_add)
+    fn visit_i64_clz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_clz)
     }
 
-    fn visit_i64_sub(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_sub)
+    fn visit_i64_ctz(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_ctz)
     }
 
-    fn visit_i64_mul(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_mul)
+    fn visit_i64_popcnt(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_popcnt)
     }
 
-    fn visit_i64_div_s(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i64_div_s)
+    fn visit_i64_add(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_add)
     }
 
-    fn visit_i64_div_u(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i64_div_u)
+    fn visit_i64_add_imm(
+        &mut self,
+        result: Register,
+        input: Register,
+        value: i32,
+    ) -> Result<(), TrapCode> {
+        let input: i64 = self.get_register_as(input);
+        self.set_register(result, input.wrapping_add(i64::from(value)));
+        self.next_instr()
     }
 
-    fn visit_i64_rem_s(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i64_rem_s)
+    fn visit_i64_sub(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_sub)
     }
 
-    fn visit_i64_rem_u(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::i64_rem_u)
+    fn visit_i64_mul(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_mul)
     }
 
-    fn visit_i64_and(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_and)
+    fn visit_i64_div_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i64_div_s)
     }
 
-    fn visit_i64_or(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_or)
+    fn visit_i64_div_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i64_div_u)
     }
 
-    fn visit_i64_xor(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_xor)
+    fn visit_i64_rem_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i64_rem_s)
     }
 
-    fn visit_i64_shl(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_shl)
+    fn visit_i64_rem_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_binary(result, lhs, rhs, UntypedValue::i64_rem_u)
     }
 
-    fn visit_i64_shr_s(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_shr_s)
+    fn visit_i64_and(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_and)
     }
 
-    fn visit_i64_shr_u(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_shr_u)
+    fn visit_i64_or(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_or)
     }
 
-    fn visit_i64_rotl(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_rotl)
+    fn visit_i64_xor(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_xor)
     }
 
-    fn visit_i64_rotr(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::i64_rotr)
+    fn visit_i64_shl(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_shl)
     }
 
-    fn visit_f32_abs(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_abs)
+    fn visit_i64_shr_s(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_shr_s)
     }
 
-    fn visit_f32_neg(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_neg)
+    fn visit_i64_shr_u(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_shr_u)
     }
 
-    fn visit_f32_ceil(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_ceil)
+    fn visit_i64_rotl(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_rotl)
     }
 
-    fn visit_f32_floor(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_floor)
+    fn visit_i64_rotr(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::i64_rotr)
     }
 
-    fn visit_f32_trunc(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_trunc)
+    fn visit_f32_abs(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_abs)
     }
 
-    fn visit_f32_nearest(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_nearest)
+    fn visit_f32_neg(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_neg)
     }
 
-    fn visit_f32_sqrt(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_sqrt)
+    fn visit_f32_ceil(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_ceil,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_add(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_add)
+    fn visit_f32_floor(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_floor,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_sub(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_sub)
+    fn visit_f32_trunc(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_trunc,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_mul(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_mul)
+    fn visit_f32_nearest(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_nearest,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_div(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::f32_div)
+    fn visit_f32_sqrt(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_sqrt,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_min(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_min)
+    fn visit_f32_add(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_add,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_max(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_max)
+    fn visit_f32_sub(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_sub,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f32_copysign(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f32_copysign)
+    fn visit_f32_mul(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_mul,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_abs(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_abs)
+    fn visit_f32_div(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_div,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_neg(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_neg)
+    fn visit_f32_min(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_min,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_ceil(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_ceil)
+    fn visit_f32_max(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f32_max,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_floor(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_floor)
+    fn visit_f32_copysign(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f32_copysign)
     }
 
-    fn visit_f64_trunc(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_trunc)
+    fn visit_f64_abs(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_abs)
     }
 
-    fn visit_f64_nearest(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_nearest)
+    fn visit_f64_neg(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_neg)
     }
 
-    fn visit_f64_sqrt(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_sqrt)
+    fn visit_f64_ceil(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_ceil,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_add(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_add)
+    fn visit_f64_floor(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_floor,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_sub(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_sub)
+    fn visit_f64_trunc(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_trunc,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_mul(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_mul)
+    fn visit_f64_nearest(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_nearest,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_div(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_binary(UntypedValue::f64_div)
+    fn visit_f64_sqrt(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_sqrt,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_min(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_min)
+    fn visit_f64_add(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_add,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_max(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_max)
+    fn visit_f64_sub(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_sub,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_copysign(&mut self) -> Result<(), TrapCode> {
-        self.execute_binary(UntypedValue::f64_copysign)
+    fn visit_f64_mul(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_mul,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_i32_wrap_i64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_wrap_i64)
+    fn visit_f64_div(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.try_execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_div,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_i32_trunc_f32(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i32_trunc_f32_s)
+    fn visit_f64_min(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_min,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_u32_trunc_f32(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i32_trunc_f32_u)
+    fn visit_f64_max(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_float_binary(
+            result,
+            lhs,
+            rhs,
+            UntypedValue::f64_max,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_i32_trunc_f64(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i32_trunc_f64_s)
+    fn visit_f64_copysign(
+        &mut self,
+        result: Register,
+        lhs: Register,
+        rhs: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_binary(result, lhs, rhs, UntypedValue::f64_copysign)
     }
 
-    fn visit_u32_trunc_f64(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i32_trunc_f64_u)
+    fn visit_i32_wrap_i64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_wrap_i64)
     }
 
-    fn visit_i64_extend_i32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_extend_i32_s)
+    fn visit_i32_trunc_f32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i32_trunc_f32_s)
     }
 
-    fn visit_i64_extend_u32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_extend_i32_u)
+    fn visit_u32_trunc_f32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i32_trunc_f32_u)
     }
 
-    fn visit_i64_trunc_f32(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i64_trunc_f32_s)
+    fn visit_i32_trunc_f64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i32_trunc_f64_s)
     }
 
-    fn visit_u64_trunc_f32(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i64_trunc_f32_u)
+    fn visit_u32_trunc_f64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i32_trunc_f64_u)
     }
 
-    fn visit_i64_trunc_f64(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i64_trunc_f64_s)
+    fn visit_i64_extend_i32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_extend_i32_s)
     }
 
-    fn visit_u64_trunc_f64(&mut self) -> Result<(), TrapCode> {
-        self.try_execute_unary(UntypedValue::i64_trunc_f64_u)
+    fn visit_i64_extend_u32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_extend_i32_u)
     }
 
-    fn visit_f32_convert_i32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_convert_i32_s)
+    fn visit_i64_trunc_f32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i64_trunc_f32_s)
     }
 
-    fn visit_f32_convert_u32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_convert_i32_u)
+    fn visit_u64_trunc_f32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i64_trunc_f32_u)
     }
 
-    fn visit_f32_convert_i64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_convert_i64_s)
+    fn visit_i64_trunc_f64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i64_trunc_f64_s)
     }
 
-    fn visit_f32_convert_u64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_convert_i64_u)
+    fn visit_u64_trunc_f64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.try_execute_unary(result, input, UntypedValue::i64_trunc_f64_u)
     }
 
-    fn visit_f32_demote_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f32_demote_f64)
+    fn visit_f32_convert_i32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_convert_i32_s)
     }
 
-    fn visit_f64_convert_i32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_convert_i32_s)
+    fn visit_f32_convert_u32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_convert_i32_u)
     }
 
-    fn visit_f64_convert_u32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_convert_i32_u)
+    fn visit_f32_convert_i64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_convert_i64_s)
     }
 
-    fn visit_f64_convert_i64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_convert_i64_s)
+    fn visit_f32_convert_u64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f32_convert_i64_u)
     }
 
-    fn visit_f64_convert_u64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_convert_i64_u)
+    fn visit_f32_demote_f64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f32_demote_f64,
+            UntypedValue::f32_canonicalize_nan,
+        )
     }
 
-    fn visit_f64_promote_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::f64_promote_f32)
+    fn visit_f64_convert_i32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_convert_i32_s)
     }
 
-    fn visit_i32_reinterpret_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_reinterpret::<F32, i32>()
+    fn visit_f64_convert_u32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_convert_i32_u)
     }
 
-    fn visit_i64_reinterpret_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_reinterpret::<F64, i64>()
+    fn visit_f64_convert_i64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_convert_i64_s)
     }
 
-    fn visit_f32_reinterpret_i32(&mut self) -> Result<(), TrapCode> {
-        self.execute_reinterpret::<i32, F32>()
+    fn visit_f64_convert_u64(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::f64_convert_i64_u)
     }
 
-    fn visit_f64_reinterpret_i64(&mut self) -> Result<(), TrapCode> {
-        self.execute_reinterpret::<i64, F64>()
+    fn visit_f64_promote_f32(&mut self, result: Register, input: Register) -> Result<(), TrapCode> {
+        self.execute_float_unary(
+            result,
+            input,
+            UntypedValue::f64_promote_f32,
+            UntypedValue::f64_canonicalize_nan,
+        )
     }
 
-    fn visit_i32_sign_extend8(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_extend8_s)
+    fn visit_i32_sign_extend8(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_extend8_s)
     }
 
-    fn visit_i32_sign_extend16(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_extend16_s)
+    fn visit_i32_sign_extend16(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_extend16_s)
     }
 
-    fn visit_i64_sign_extend8(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_extend8_s)
+    fn visit_i64_sign_extend8(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_extend8_s)
     }
 
-    fn visit_i64_sign_extend16(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_extend16_s)
+    fn visit_i64_sign_extend16(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_extend16_s)
     }
 
-    fn visit_i64_sign_extend32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_extend32_s)
+    fn visit_i64_sign_extend32(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_extend32_s)
     }
 
-    fn visit_i32_trunc_sat_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_trunc_sat_f32_s)
+    fn visit_i32_trunc_sat_f32(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_trunc_sat_f32_s)
     }
 
-    fn visit_u32_trunc_sat_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_trunc_sat_f32_u)
+    fn visit_u32_trunc_sat_f32(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_trunc_sat_f32_u)
     }
 
-    fn visit_i32_trunc_sat_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_trunc_sat_f64_s)
+    fn visit_i32_trunc_sat_f64(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_trunc_sat_f64_s)
     }
 
-    fn visit_u32_trunc_sat_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i32_trunc_sat_f64_u)
+    fn visit_u32_trunc_sat_f64(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i32_trunc_sat_f64_u)
     }
 
-    fn visit_i64_trunc_sat_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_trunc_sat_f32_s)
+    fn visit_i64_trunc_sat_f32(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_trunc_sat_f32_s)
     }
 
-    fn visit_u64_trunc_sat_f32(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_trunc_sat_f32_u)
+    fn visit_u64_trunc_sat_f32(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_trunc_sat_f32_u)
     }
 
-    fn visit_i64_trunc_sat_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_trunc_sat_f64_s)
+    fn visit_i64_trunc_sat_f64(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_trunc_sat_f64_s)
     }
 
-    fn visit_u64_trunc_sat_f64(&mut self) -> Result<(), TrapCode> {
-        self.execute_unary(UntypedValue::i64_trunc_sat_f64_u)
+    fn visit_u64_trunc_sat_f64(
+        &mut self,
+        result: Register,
+        input: Register,
+    ) -> Result<(), TrapCode> {
+        self.execute_unary(result, input, UntypedValue::i64_trunc_sat_f64_u)
     }
 }
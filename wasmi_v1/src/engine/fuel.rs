@@ -0,0 +1,165 @@
+use super::bytecode::Instruction;
+use core::{fmt, fmt::Display};
+use wasmi_core::TrapCode;
+
+/// The fuel cost schedule used for fuel metering.
+///
+/// # Note
+///
+/// - Every executed `wasmi` bytecode [`Instruction`] consumes the amount of
+///   fuel returned by the instruction cost function.
+/// - Growing a linear memory via `memory.grow` additionally consumes fuel
+///   for every requested page.
+/// - Bulk memory instructions such as `memory.copy` or `table.init` additionally
+///   consume fuel for every processed byte or table element.
+/// - Calling a host function additionally consumes a fixed amount of fuel.
+///
+/// By default every [`Instruction`] costs 1 unit of fuel and there are no
+/// additional costs for `memory.grow`, bulk memory instructions or host function calls.
+#[derive(Debug, Copy, Clone)]
+pub struct FuelCosts {
+    /// Returns the fuel cost of executing the given [`Instruction`].
+    instruction: fn(&Instruction) -> u64,
+    /// The additional fuel cost per page requested by `memory.grow`.
+    memory_grow_page: u64,
+    /// The additional fuel cost per byte or table element processed by bulk memory instructions.
+    bulk_element: u64,
+    /// The additional fuel cost of calling a host function.
+    host_call: u64,
+}
+
+impl Default for FuelCosts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FuelCosts {
+    /// Creates the default [`FuelCosts`].
+    pub const fn new() -> Self {
+        Self {
+            instruction: Self::default_instruction_cost,
+            memory_grow_page: 0,
+            bulk_element: 0,
+            host_call: 0,
+        }
+    }
+
+    /// The default fuel cost of executing a `wasmi` bytecode [`Instruction`].
+    fn default_instruction_cost(_instr: &Instruction) -> u64 {
+        1
+    }
+
+    /// Sets the function that determines the fuel cost per [`Instruction`].
+    pub const fn with_instruction_costs(mut self, costs: fn(&Instruction) -> u64) -> Self {
+        self.instruction = costs;
+        self
+    }
+
+    /// Sets the additional fuel cost per page requested by `memory.grow`.
+    pub const fn with_memory_grow_page_cost(mut self, cost: u64) -> Self {
+        self.memory_grow_page = cost;
+        self
+    }
+
+    /// Sets the additional fuel cost per byte or table element processed by bulk memory instructions.
+    pub const fn with_bulk_element_cost(mut self, cost: u64) -> Self {
+        self.bulk_element = cost;
+        self
+    }
+
+    /// Sets the additional fuel cost of calling a host function.
+    pub const fn with_host_call_cost(mut self, cost: u64) -> Self {
+        self.host_call = cost;
+        self
+    }
+
+    /// Returns the fuel cost of executing the given [`Instruction`].
+    #[inline]
+    pub fn instruction_cost(&self, instr: &Instruction) -> u64 {
+        (self.instruction)(instr)
+    }
+
+    /// Returns the fuel cost for growing a linear memory by the given amount of pages.
+    pub fn memory_grow_cost(&self, pages: u32) -> u64 {
+        self.memory_grow_page.saturating_mul(u64::from(pages))
+    }
+
+    /// Returns the fuel cost for processing the given amount of bytes or table elements
+    /// by a bulk memory instruction.
+    pub fn bulk_cost(&self, len: u32) -> u64 {
+        self.bulk_element.saturating_mul(u64::from(len))
+    }
+
+    /// Returns the additional fuel cost of calling a host function.
+    pub fn host_call_cost(&self) -> u64 {
+        self.host_call
+    }
+}
+
+/// An error that may occur upon operating on the fuel of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FuelError {
+    /// Occurs when fuel metering is used but has not been enabled in the [`Config`].
+    ///
+    /// [`Config`]: [`crate::Config`]
+    FuelMeteringDisabled,
+    /// Occurs when trying to consume more fuel than there is remaining.
+    OutOfFuel,
+}
+
+impl Display for FuelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FuelMeteringDisabled => write!(f, "fuel metering is disabled"),
+            Self::OutOfFuel => write!(f, "all fuel consumed"),
+        }
+    }
+}
+
+/// The fuel state of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Fuel {
+    /// The remaining fuel.
+    remaining: u64,
+    /// The total amount of fuel consumed so far.
+    consumed: u64,
+}
+
+impl Fuel {
+    /// Adds `delta` units of fuel.
+    pub fn add(&mut self, delta: u64) {
+        self.remaining = self.remaining.saturating_add(delta);
+    }
+
+    /// Returns the remaining fuel.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the total amount of fuel consumed so far.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Consumes `delta` units of fuel.
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough fuel remaining to consume `delta` units.
+    /// In this case no fuel is consumed.
+    #[inline]
+    pub fn consume(&mut self, delta: u64) -> Result<(), TrapCode> {
+        self.remaining = self
+            .remaining
+            .checked_sub(delta)
+            .ok_or(TrapCode::OutOfFuel)?;
+        self.consumed = self.consumed.saturating_add(delta);
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Abstractions to build up instructions forming Wasm function bodies.
 
-use crate::engine::{Engine, FuncBody, Instruction};
+use crate::{
+    engine::{Instruction, TranslatedFuncBody},
+    module::FuncIdx,
+};
 use alloc::vec::Vec;
 use core::mem;
 
@@ -74,7 +77,7 @@ pub struct LabelIdx(pub(crate) usize);
 /// A relocation entry that specifies.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Reloc {
-    /// Patch the target of the `br`, `br_eqz` or `br_nez` instruction.
+    /// Patch the target of the `br`, `br_eqz`, `br_nez` or compare-and-branch instruction.
     Br { inst_idx: InstructionIdx },
     /// Patch the specified target index inside of a Wasm `br_table` instruction.
     BrTable {
@@ -110,9 +113,35 @@ pub struct InstructionsBuilder {
     insts: Vec<Instruction>,
     /// All labels and their uses.
     labels: Vec<Label>,
+    /// The original Wasm offsets of the instructions in `insts`.
+    ///
+    /// # Note
+    ///
+    /// Used to map `wasmi` bytecode back to Wasm offsets for backtraces.
+    offsets: Vec<u32>,
+    /// The Wasm offset of the currently translated Wasm operator.
+    wasm_offset: u32,
+    /// The instruction index at which a label has been resolved most recently.
+    ///
+    /// # Note
+    ///
+    /// Used to detect if the current instruction position might be reached by branches.
+    last_resolved_pc: Option<InstructionIdx>,
 }
 
 impl InstructionsBuilder {
+    /// Updates the Wasm offset of the currently translated Wasm operator.
+    ///
+    /// All instructions pushed afterwards are associated to this offset.
+    pub fn update_wasm_offset(&mut self, offset: usize) {
+        self.wasm_offset = offset.try_into().unwrap_or_else(|error| {
+            panic!(
+                "encountered out of bounds Wasm offset {}: {}",
+                offset, error
+            )
+        });
+    }
+
     /// Returns the current instruction pointer as index.
     pub fn current_pc(&self) -> InstructionIdx {
         InstructionIdx::from_usize(self.insts.len())
@@ -163,6 +192,7 @@ impl InstructionsBuilder {
     /// If the label has already been resolved.
     pub fn resolve_label(&mut self, label: LabelIdx) {
         let dst_pc = self.current_pc();
+        self.last_resolved_pc = Some(dst_pc);
         let old_label = mem::replace(&mut self.labels[label.0], Label::Resolved(dst_pc));
         match old_label {
             Label::Resolved(idx) => panic!(
@@ -201,19 +231,57 @@ impl InstructionsBuilder {
     pub fn push_inst(&mut self, inst: Instruction) -> InstructionIdx {
         let idx = self.current_pc();
         self.insts.push(inst);
+        self.offsets.push(self.wasm_offset);
         idx
     }
 
+    /// Returns an exclusive reference to the last pushed instruction.
+    ///
+    /// Returns `None` if there is no such instruction or if branches might
+    /// target the current instruction position. In the latter case the last
+    /// pushed instruction is not the only way to reach the current position.
+    pub fn last_inst_mut(&mut self) -> Option<&mut Instruction> {
+        if self.last_resolved_pc == Some(self.current_pc()) {
+            return None;
+        }
+        self.insts.last_mut()
+    }
+
+    /// Returns the last `n` pushed instructions.
+    ///
+    /// Returns `None` if there are less than `n` instructions or if branches might
+    /// target any of them except the first or the current instruction position.
+    /// In the latter case the instructions are not always executed in sequence.
+    pub fn last_insts(&self, n: usize) -> Option<&[Instruction]> {
+        let start = self.insts.len().checked_sub(n)?;
+        if let Some(pc) = self.last_resolved_pc {
+            if pc.into_usize() > start {
+                return None;
+            }
+        }
+        Some(&self.insts[start..])
+    }
+
+    /// Removes the last pushed instruction and returns it.
+    ///
+    /// # Note
+    ///
+    /// This is used to replace the last pushed instruction by a fused instruction.
+    /// Callers must make sure that the removed instruction is not the target of
+    /// any branch, e.g. by using [`InstructionsBuilder::last_inst_mut`] beforehand.
+    pub fn pop_inst(&mut self) -> Option<Instruction> {
+        self.offsets.pop();
+        self.insts.pop()
+    }
+
     /// Allows to patch the branch target of branch instructions.
     pub fn patch_relocation(&mut self, reloc: Reloc, dst_pc: InstructionIdx) {
         match reloc {
-            Reloc::Br { inst_idx } => match &mut self.insts[inst_idx.into_usize()] {
-                Instruction::Br(target)
-                | Instruction::BrIfEqz(target)
-                | Instruction::BrIfNez(target) => {
+            Reloc::Br { inst_idx } => match self.insts[inst_idx.into_usize()].branch_target_mut() {
+                Some(target) => {
                     target.update_destination_pc(dst_pc);
                 }
-                _ => panic!(
+                None => panic!(
                     "branch relocation points to a non-branch instruction: {:?}",
                     reloc
                 ),
@@ -237,17 +305,27 @@ impl InstructionsBuilder {
     ///
     /// # Note
     ///
-    /// This feeds the built-up instructions of the function body
-    /// into the [`Engine`] so that the [`Engine`] is
-    /// aware of the Wasm function existance. Returns a `FuncBody`
-    /// reference that allows to retrieve the instructions.
+    /// The returned [`TranslatedFuncBody`] still has to be allocated
+    /// to the [`Engine`] so that the [`Engine`] is aware of the Wasm
+    /// function existance.
+    ///
+    /// [`Engine`]: [`crate::Engine`]
     #[must_use]
     pub fn finish(
         &mut self,
-        engine: &Engine,
+        func: FuncIdx,
+        len_params: usize,
         len_locals: usize,
         max_stack_height: usize,
-    ) -> FuncBody {
-        engine.alloc_func_body(len_locals, max_stack_height, self.insts.drain(..))
+    ) -> TranslatedFuncBody {
+        self.last_resolved_pc = None;
+        TranslatedFuncBody::new(
+            func.into_u32(),
+            len_params,
+            len_locals,
+            max_stack_height,
+            mem::take(&mut self.insts),
+            mem::take(&mut self.offsets),
+        )
     }
 }
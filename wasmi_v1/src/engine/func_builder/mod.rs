@@ -18,9 +18,15 @@ use self::{
     },
     control_stack::ControlFlowStack,
     locals_registry::LocalsRegistry,
-    value_stack::ValueStack,
+    value_stack::{Provider, ValueStack},
+};
+use super::{
+    bytecode::{DataSegmentIdx, ElementSegmentIdx, Register},
+    DropKeep,
+    Instruction,
+    Target,
+    TranslatedFuncBody,
 };
-use super::{DropKeep, FuncBody, Instruction, Target};
 use crate::{
     engine::bytecode::Offset,
     module::{
@@ -38,7 +44,8 @@ use crate::{
     ModuleError,
     Mutability,
 };
-use wasmi_core::{Value, ValueType, F32, F64};
+use core::ops::Range;
+use wasmi_core::{UntypedValue, Value, ValueType, F32, F64};
 
 /// The interface to translate a `wasmi` bytecode function using Wasm bytecode.
 #[derive(Debug)]
@@ -71,6 +78,22 @@ pub struct FunctionBuilder<'engine, 'parser> {
     /// Visiting the Wasm `Else` or `End` control flow operator resets
     /// reachability to `true` again.
     reachable: bool,
+    /// Is `true` if translation-time optimizations are enabled.
+    ///
+    /// # Note
+    ///
+    /// This caches the respective [`Config`] option of the [`Engine`].
+    ///
+    /// [`Config`]: [`crate::Config`]
+    optimize: bool,
+    /// Is `true` if NaN results of float instructions are canonicalized.
+    ///
+    /// # Note
+    ///
+    /// This caches the respective [`Config`] option of the [`Engine`].
+    ///
+    /// [`Config`]: [`crate::Config`]
+    nan_canonicalization: bool,
 }
 
 impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
@@ -91,6 +114,8 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             inst_builder,
             locals,
             reachable: true,
+            optimize: engine.config().optimizations(),
+            nan_canonicalization: engine.config().nan_canonicalization(),
         }
     }
 
@@ -178,15 +203,27 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         len_params_locals - len_params
     }
 
-    /// Finishes constructing the function and returns its [`FuncBody`].
-    pub fn finish(mut self) -> FuncBody {
+    /// Finishes constructing the function and returns its [`TranslatedFuncBody`].
+    pub fn finish(mut self) -> TranslatedFuncBody {
+        let len_params = self.func_type().params().len();
         self.inst_builder.finish(
-            self.engine,
+            self.func,
+            len_params,
             self.len_locals(),
             self.value_stack.max_stack_height() as usize,
         )
     }
 
+    /// Updates the Wasm offset of the Wasm operator that is translated next.
+    ///
+    /// # Note
+    ///
+    /// The Wasm offsets are used to map `wasmi` bytecode back to the original
+    /// Wasm bytecode, e.g. for backtraces upon traps.
+    pub fn update_wasm_offset(&mut self, offset: usize) {
+        self.inst_builder.update_wasm_offset(offset);
+    }
+
     /// Returns `true` if the code at the current translation position is reachable.
     fn is_reachable(&self) -> bool {
         self.reachable
@@ -207,83 +244,272 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         Ok(())
     }
 
-    /// Computes how many values should be dropped and kept for the specific branch.
+    /// Returns the [`Register`] of the local variable at `local_idx`.
+    fn local_register(local_idx: u32) -> Register {
+        Register::from(local_idx)
+    }
+
+    /// Returns the [`Register`] that belongs to the value stack position at `height`.
     ///
-    /// # Panics
+    /// # Note
     ///
-    /// If underflow of the value stack is detected.
-    fn compute_drop_keep(&self, depth: u32) -> DropKeep {
-        debug_assert!(self.is_reachable());
-        let frame = self.control_frames.nth_back(depth);
-        // Find out how many values we need to keep (copy to the new stack location after the drop).
-        let keep = match frame.kind() {
-            ControlFrameKind::Block | ControlFrameKind::If => {
-                frame.block_type().len_results(self.engine)
-            }
-            ControlFrameKind::Loop => frame.block_type().len_params(self.engine),
+    /// The registers of the value stack positions follow after
+    /// the registers of the function parameters and local variables.
+    fn temp_register(&self, height: u32) -> Register {
+        let len_params_locals = self.locals.len_registered();
+        Register::from(len_params_locals + height)
+    }
+
+    /// Returns the [`Register`] that currently stores the value stack entry at `height`.
+    fn register_at(&self, height: u32) -> Register {
+        match self.value_stack.provider(height) {
+            Provider::Temp => self.temp_register(height),
+            Provider::Local(local_idx) => Self::local_register(local_idx),
+        }
+    }
+
+    /// Returns the [`Register`] that currently stores the top most value stack entry.
+    fn top_register(&self) -> Register {
+        let height = self.value_stack.len() - 1;
+        self.register_at(height)
+    }
+
+    /// Pops the top most value stack entry of type `expected` and returns its [`Register`].
+    fn pop_register(&mut self, expected: ValueType) -> Register {
+        let register = self.top_register();
+        let actual = self.value_stack.pop1();
+        debug_assert_eq!(actual, expected);
+        register
+    }
+
+    /// Pushes a new value stack entry of type `value_type` and returns its [`Register`].
+    fn push_register(&mut self, value_type: ValueType) -> Register {
+        let register = self.temp_register(self.value_stack.len());
+        self.value_stack.push(value_type);
+        register
+    }
+
+    /// Pushes an instruction copying the `input` register into the `result` register.
+    ///
+    /// Does nothing if both registers are the same.
+    fn copy_register(&mut self, result: Register, input: Register) {
+        if result != input {
+            self.inst_builder
+                .push_inst(Instruction::Copy { result, input });
+        }
+    }
+
+    /// Returns the value of the top most value stack entry if it has been
+    /// computed by a constant instruction that has been pushed last.
+    fn last_constant(&mut self) -> Option<UntypedValue> {
+        let height = self.value_stack.len().checked_sub(1)?;
+        if self.value_stack.provider(height) != Provider::Temp {
+            return None;
+        }
+        let register = self.temp_register(height);
+        match self.inst_builder.last_inst_mut()? {
+            Instruction::Const { result, value } if *result == register => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Evaluates `inst` computing the `result` register if all of its inputs
+    /// have been computed by the constant instructions pushed last.
+    ///
+    /// Returns the evaluated value and the number of those constant instructions.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if optimizations are disabled, if `inst` is not a pure
+    /// computation or if its evaluation traps. In the latter case `inst` is kept
+    /// so that the trap still occurs at its original position during execution.
+    fn evaluate_constant(
+        &self,
+        result: Register,
+        inst: &Instruction,
+    ) -> Option<(usize, UntypedValue)> {
+        if !self.optimize {
+            return None;
+        }
+        let operation = inst.operation()?;
+        let len_inputs = operation.len_inputs();
+        let consts = self.inst_builder.last_insts(len_inputs)?;
+        let value_of = |register| {
+            consts.iter().rev().find_map(|inst| match *inst {
+                // Note: Only registers of value stack positions that are no longer
+                //       in use after `inst` can have their constant instructions removed.
+                Instruction::Const {
+                    result: const_result,
+                    value,
+                } if const_result == register && const_result >= result => Some(value),
+                _ => None,
+            })
         };
-        // Find out how many values we need to drop.
-        let current_height = self.value_stack.len();
-        let origin_height = frame.stack_height();
-        assert!(
-            origin_height <= current_height,
-            "encountered value stack underflow: current height {}, original height {}",
-            current_height,
-            origin_height,
-        );
-        let height_diff = current_height - origin_height;
-        assert!(
-            keep <= height_diff,
-            "tried to keep {} values while having only {} values available on the frame",
-            keep,
-            height_diff,
-        );
-        let drop = height_diff - keep;
-        DropKeep::new32(drop, keep)
+        let mut value = operation.evaluate(value_of)?.ok()?;
+        if self.nan_canonicalization {
+            if let Some(canonicalize) = inst.nan_canonicalization() {
+                value = canonicalize(value);
+            }
+        }
+        Some((len_inputs, value))
     }
 
-    /// Compute [`DropKeep`] for the return statement.
+    /// Pushes the instruction `inst` computing the `result` register.
     ///
-    /// # Panics
+    /// # Note
     ///
-    /// - If the control flow frame stack is empty.
-    /// - If the value stack is underflown.
-    fn drop_keep_return(&self) -> DropKeep {
-        debug_assert!(self.is_reachable());
-        assert!(
-            !self.control_frames.is_empty(),
-            "drop_keep_return cannot be called with the frame stack empty"
-        );
-        let max_depth = self
-            .control_frames
-            .len()
-            .checked_sub(1)
-            .expect("control flow frame stack must not be empty") as u32;
-        let drop_keep = self.compute_drop_keep(max_depth);
-        let len_params_locals = self.locals.len_registered() as usize;
-        DropKeep::new(
-            // Drop all local variables and parameters upon exit.
-            drop_keep.drop() + len_params_locals,
-            drop_keep.keep(),
-        )
+    /// If `inst` can be evaluated at translation time the constant instructions
+    /// computing its inputs are replaced by a single constant instruction instead.
+    fn push_computation(&mut self, result: Register, inst: Instruction) {
+        match self.evaluate_constant(result, &inst) {
+            Some((len_consts, value)) => {
+                for _ in 0..len_consts {
+                    self.inst_builder.pop_inst();
+                }
+                self.inst_builder
+                    .push_inst(Instruction::Const { result, value });
+            }
+            None => {
+                self.inst_builder.push_inst(inst);
+            }
+        }
+    }
+
+    /// Removes the pure instructions pushed last that compute registers
+    /// of value stack positions above the current value stack height.
+    ///
+    /// # Note
+    ///
+    /// The results of those instructions can no longer be used
+    /// since their values have been dropped from the value stack.
+    fn remove_dead_computations(&mut self) {
+        let dead = self.temp_register(self.value_stack.len());
+        while let Some([last]) = self.inst_builder.last_insts(1) {
+            let mut last = *last;
+            let is_dead = last.is_pure()
+                && last
+                    .result_mut()
+                    .map(|result| *result >= dead)
+                    .unwrap_or(false);
+            if !is_dead {
+                break;
+            }
+            self.inst_builder.pop_inst();
+        }
+    }
+
+    /// Copies the value stack entries at `heights` that are stored in registers of
+    /// local variables into the registers that belong to their value stack positions.
+    ///
+    /// # Note
+    ///
+    /// This is required whenever the values might be used by code that
+    /// assumes them to be stored in the registers of their stack positions
+    /// or when the local variables might be changed in the meantime.
+    fn materialize(&mut self, heights: Range<u32>) {
+        for height in heights {
+            if let Provider::Local(local_idx) = self.value_stack.provider(height) {
+                let result = self.temp_register(height);
+                self.copy_register(result, Self::local_register(local_idx));
+                self.value_stack.set_provider(height, Provider::Temp);
+            }
+        }
+    }
+
+    /// Materializes all value stack entries that are stored in the local variable at `local_idx`.
+    ///
+    /// # Note
+    ///
+    /// This is required before the local variable is changed.
+    fn materialize_local(&mut self, local_idx: u32) {
+        for height in 0..self.value_stack.len() {
+            if self.value_stack.provider(height) == Provider::Local(local_idx) {
+                self.materialize(height..height + 1);
+            }
+        }
+    }
+
+    /// Materializes all values of the emulated value stack.
+    ///
+    /// # Note
+    ///
+    /// This is required upon entering a new control flow frame so that
+    /// all values outside of the control flow frame stay in the registers
+    /// of their value stack positions.
+    fn materialize_all(&mut self) {
+        self.materialize(0..self.value_stack.len());
+    }
+
+    /// Returns `true` if the top `len` values are not stored in the
+    /// `len` consecutive registers that belong to the stack positions
+    /// starting at `height`.
+    fn requires_copies(&self, height: u32, len: u32) -> bool {
+        let start = self.value_stack.len() - len;
+        (0..len).any(|n| self.register_at(start + n) != self.temp_register(height + n))
+    }
+
+    /// Copies the top `len` values into the `len` consecutive registers that
+    /// belong to the value stack positions starting at `height`.
+    ///
+    /// # Note
+    ///
+    /// - This is used to pass values to the destination of a branch.
+    /// - This does not alter the emulated value stack.
+    fn copy_values_to(&mut self, height: u32, len: u32) {
+        let start = self.value_stack.len() - len;
+        debug_assert!(height <= start);
+        // Note: Copying in ascending order never overwrites values that are yet to be copied
+        //       since the destination registers are below the source registers.
+        for n in 0..len {
+            let result = self.temp_register(height + n);
+            let input = self.register_at(start + n);
+            self.copy_register(result, input);
+        }
+    }
+
+    /// Returns the amount of values returned by the function under construction.
+    fn len_results(&self) -> u32 {
+        self.control_frames
+            .first()
+            .block_type()
+            .len_results(self.engine)
     }
 
-    /// Returns the relative depth on the stack of the local variable.
+    /// Returns `true` if the top most values returned by the function
+    /// are stored in consecutive registers.
+    fn results_are_consecutive(&self) -> bool {
+        let len_results = self.len_results();
+        if len_results == 0 {
+            return true;
+        }
+        let height = self.value_stack.len() - len_results;
+        let first = self.register_at(height).into_inner();
+        (0..len_results).all(|n| self.register_at(height + n).into_inner() == first + n)
+    }
+
+    /// Returns the [`DropKeep`] for returning the top most values from the function.
     ///
     /// # Note
     ///
-    /// See stack layout definition in `isa.rs`.
-    fn relative_local_depth(&self, local_idx: u32) -> u32 {
+    /// Pushes copy instructions if the returned values are not stored
+    /// in consecutive registers. This does not alter the emulated value stack.
+    fn prepare_return(&mut self) -> DropKeep {
         debug_assert!(self.is_reachable());
-        let stack_height = self.value_stack.len();
-        let len_params_locals = self.locals.len_registered();
-        stack_height
-            .checked_add(len_params_locals)
-            .and_then(|x| x.checked_sub(local_idx))
-            .unwrap_or_else(|| panic!("cannot convert local index into local depth: {}", local_idx))
+        let len_results = self.len_results();
+        if len_results == 0 {
+            return DropKeep::new32(0, 0);
+        }
+        let height = self.value_stack.len() - len_results;
+        let first = if self.results_are_consecutive() {
+            self.register_at(height)
+        } else {
+            self.copy_values_to(height, len_results);
+            self.temp_register(height)
+        };
+        DropKeep::new32(first.into_inner(), len_results)
     }
 
-    /// Returns the target at the given `depth` together with its [`DropKeep`].
+    /// Returns the target at the given `depth`.
     ///
     /// # Panics
     ///
@@ -292,15 +518,28 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     fn acquire_target(&self, relative_depth: u32) -> AquiredTarget {
         debug_assert!(self.is_reachable());
         if self.control_frames.is_root(relative_depth) {
-            let drop_keep = self.drop_keep_return();
-            AquiredTarget::Return(drop_keep)
-        } else {
-            let label = self
-                .control_frames
-                .nth_back(relative_depth)
-                .branch_destination();
-            let drop_keep = self.compute_drop_keep(relative_depth);
-            AquiredTarget::Branch(label, drop_keep)
+            return AquiredTarget::Return;
+        }
+        let frame = self.control_frames.nth_back(relative_depth);
+        let label = frame.branch_destination();
+        // Find out how many values we need to pass to the branch destination.
+        let len_values = match frame.kind() {
+            ControlFrameKind::Block | ControlFrameKind::If => {
+                frame.block_type().len_results(self.engine)
+            }
+            ControlFrameKind::Loop => frame.block_type().len_params(self.engine),
+        };
+        let height = frame.stack_height();
+        assert!(
+            height + len_values <= self.value_stack.len(),
+            "encountered value stack underflow: current height {}, expected at least {}",
+            self.value_stack.len(),
+            height + len_values,
+        );
+        AquiredTarget::Branch {
+            label,
+            height,
+            len_values,
         }
     }
 }
@@ -311,7 +550,14 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
 #[derive(Debug)]
 pub enum AquiredTarget {
     /// The branch jumps to the label.
-    Branch(LabelIdx, DropKeep),
+    ///
+    /// The top `len_values` values are passed to the registers belonging
+    /// to the value stack positions starting at `height`.
+    Branch {
+        label: LabelIdx,
+        height: u32,
+        len_values: u32,
+    },
     /// The branch returns to the caller.
     ///
     /// # Note
@@ -319,7 +565,7 @@ pub enum AquiredTarget {
     /// This is returned if the `relative_depth` points to the outmost
     /// function body `block`. WebAssembly defines branches to this control
     /// flow frame as equivalent to returning from the function.
-    Return(DropKeep),
+    Return,
 }
 
 impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
@@ -361,6 +607,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_block(&mut self, block_type: BlockType) -> Result<(), ModuleError> {
         let stack_height = self.frame_stack_height(block_type);
         if self.is_reachable() {
+            self.materialize_all();
             let end_label = self.inst_builder.new_label();
             self.control_frames.push_frame(BlockControlFrame::new(
                 block_type,
@@ -381,6 +628,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_loop(&mut self, block_type: BlockType) -> Result<(), ModuleError> {
         let stack_height = self.frame_stack_height(block_type);
         if self.is_reachable() {
+            self.materialize_all();
             let header = self.inst_builder.new_label();
             self.inst_builder.resolve_label(header);
             self.control_frames
@@ -398,9 +646,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translates a Wasm `if` control flow operator.
     pub fn translate_if(&mut self, block_type: BlockType) -> Result<(), ModuleError> {
         if self.is_reachable() {
-            let condition = self.value_stack.pop1();
-            debug_assert_eq!(condition, ValueType::I32);
+            let condition = self.pop_register(ValueType::I32);
+            // Note: The copies of `materialize_all` never overwrite the inputs of a
+            //       fused condition since they only write to the registers of value
+            //       stack positions below the condition.
+            let branch = self.conditional_branch(condition, true);
             let stack_height = self.frame_stack_height(block_type);
+            self.materialize_all();
             let else_label = self.inst_builder.new_label();
             let end_label = self.inst_builder.new_label();
             self.control_frames.push_frame(IfControlFrame::new(
@@ -409,10 +661,7 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
                 else_label,
                 stack_height,
             ));
-            let dst_pc = self.try_resolve_label(else_label, |pc| Reloc::Br { inst_idx: pc });
-            let branch_target = Target::new(dst_pc, DropKeep::new(0, 0));
-            self.inst_builder
-                .push_inst(Instruction::BrIfEqz(branch_target));
+            self.push_branch(branch, else_label);
         } else {
             let stack_height = self.frame_stack_height(block_type);
             self.control_frames.push_frame(UnreachableControlFrame::new(
@@ -424,6 +673,17 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         Ok(())
     }
 
+    /// Copies the results of the control flow `frame` into the registers
+    /// that belong to their value stack positions after the `frame`.
+    ///
+    /// # Note
+    ///
+    /// This is required at the reachable end of a control flow frame.
+    fn copy_frame_results(&mut self, frame: &ControlFrame) {
+        let len_results = frame.block_type().len_results(self.engine);
+        self.copy_values_to(frame.stack_height(), len_results);
+    }
+
     /// Translates a Wasm `else` control flow operator.
     pub fn translate_else(&mut self) -> Result<(), ModuleError> {
         let mut if_frame = match self.control_frames.pop_frame() {
@@ -451,10 +711,11 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         // Create the jump from the end of the `then` block to the `if`
         // block's end label in case the end of `then` is reachable.
         if reachable {
+            self.copy_frame_results(&ControlFrame::If(if_frame));
             let dst_pc =
                 self.try_resolve_label(if_frame.end_label(), |pc| Reloc::Br { inst_idx: pc });
-            let target = Target::new(dst_pc, DropKeep::new(0, 0));
-            self.inst_builder.push_inst(Instruction::Br(target));
+            self.inst_builder
+                .push_inst(Instruction::Br(Target::new(dst_pc)));
         }
         // Now resolve labels for the instructions of the `else` block
         self.inst_builder.resolve_label(if_frame.else_label());
@@ -473,7 +734,15 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
 
     /// Translates a Wasm `end` control flow operator.
     pub fn translate_end(&mut self) -> Result<(), ModuleError> {
-        let frame = self.control_frames.last();
+        let frame = *self.control_frames.last();
+        if self.is_reachable() && self.control_frames.len() != 1 {
+            // The results of the ended control flow frame need to be
+            // stored where the branches to its end store them as well.
+            //
+            // Note: This must happen before resolving any labels since
+            //       the copies only apply to the fallthrough path.
+            self.copy_frame_results(&frame);
+        }
         if let ControlFrame::If(if_frame) = &frame {
             // At this point we can resolve the `Else` label.
             //
@@ -508,18 +777,87 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         Ok(())
     }
 
+    /// Returns the conditional branch on the value of the `condition` register.
+    ///
+    /// The returned instruction branches if the value of the `condition` register
+    /// is zero in case `branch_if_zero` is `true` and if it is not zero otherwise.
+    /// Its branch target is set by [`FunctionBuilder::push_branch`].
+    ///
+    /// # Note
+    ///
+    /// If the last pushed instruction computes the `condition` it is removed
+    /// and fused into the returned instruction if possible. This turns integer
+    /// comparisons followed by a conditional branch into a single compare-and-branch
+    /// instruction and eliminates `i32.eqz` by inverting the branch condition.
+    fn conditional_branch(&mut self, condition: Register, branch_if_zero: bool) -> Instruction {
+        if let Some(fused) = self.fuse_conditional_branch(condition, branch_if_zero) {
+            return fused;
+        }
+        let target = Target::new(InstructionIdx::INVALID);
+        if branch_if_zero {
+            Instruction::BrIfEqz { condition, target }
+        } else {
+            Instruction::BrIfNez { condition, target }
+        }
+    }
+
+    /// Removes the last pushed instruction if it computes the `condition` and
+    /// returns the conditional branch that it has been fused into.
+    ///
+    /// Returns `None` if fusion is not possible.
+    fn fuse_conditional_branch(
+        &mut self,
+        condition: Register,
+        branch_if_zero: bool,
+    ) -> Option<Instruction> {
+        if condition != self.temp_register(self.value_stack.len()) {
+            // The condition is used by other values of the emulated value stack.
+            return None;
+        }
+        let mut last = *self.inst_builder.last_inst_mut()?;
+        if last.result_mut().copied() != Some(condition) {
+            return None;
+        }
+        if let Instruction::I32Eqz { input, .. } = last {
+            self.inst_builder.pop_inst();
+            return Some(self.conditional_branch(input, !branch_if_zero));
+        }
+        let fused = last.fuse_br_if(branch_if_zero, Target::new(InstructionIdx::INVALID))?;
+        self.inst_builder.pop_inst();
+        Some(fused)
+    }
+
+    /// Pushes the `branch` instruction after setting its branch target to `label`.
+    fn push_branch(&mut self, mut branch: Instruction, label: LabelIdx) {
+        let dst_pc = self.try_resolve_label(label, |pc| Reloc::Br { inst_idx: pc });
+        match branch.branch_target_mut() {
+            Some(target) => target.update_destination_pc(dst_pc),
+            None => panic!("expected a branch instruction but found: {:?}", branch),
+        }
+        self.inst_builder.push_inst(branch);
+    }
+
+    /// Pushes the instructions to branch to the `label` passing the top
+    /// `len_values` values to the value stack positions starting at `height`.
+    fn branch_to(&mut self, label: LabelIdx, height: u32, len_values: u32) {
+        self.copy_values_to(height, len_values);
+        let dst_pc = self.try_resolve_label(label, |pc| Reloc::Br { inst_idx: pc });
+        self.inst_builder
+            .push_inst(Instruction::Br(Target::new(dst_pc)));
+    }
+
     /// Translates a Wasm `br` control flow operator.
     pub fn translate_br(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             match builder.acquire_target(relative_depth) {
-                AquiredTarget::Branch(end_label, drop_keep) => {
-                    let dst_pc =
-                        builder.try_resolve_label(end_label, |pc| Reloc::Br { inst_idx: pc });
-                    builder
-                        .inst_builder
-                        .push_inst(Instruction::Br(Target::new(dst_pc, drop_keep)));
+                AquiredTarget::Branch {
+                    label,
+                    height,
+                    len_values,
+                } => {
+                    builder.branch_to(label, height, len_values);
                 }
-                AquiredTarget::Return(drop_keep) => {
+                AquiredTarget::Return => {
                     // In this case the `br` can be directly translated as `return`.
                     builder.translate_return()?;
                 }
@@ -532,20 +870,31 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translates a Wasm `br_if` control flow operator.
     pub fn translate_br_if(&mut self, relative_depth: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let condition = builder.value_stack.pop1();
-            debug_assert_eq!(condition, ValueType::I32);
+            let condition = builder.pop_register(ValueType::I32);
             match builder.acquire_target(relative_depth) {
-                AquiredTarget::Branch(end_label, drop_keep) => {
-                    let dst_pc =
-                        builder.try_resolve_label(end_label, |pc| Reloc::Br { inst_idx: pc });
-                    builder
-                        .inst_builder
-                        .push_inst(Instruction::BrIfNez(Target::new(dst_pc, drop_keep)));
+                AquiredTarget::Branch {
+                    label,
+                    height,
+                    len_values,
+                } => {
+                    if builder.requires_copies(height, len_values) {
+                        // The values passed to the branch destination need to be
+                        // copied only if the branch is taken.
+                        let skip = builder.inst_builder.new_label();
+                        let branch = builder.conditional_branch(condition, true);
+                        builder.push_branch(branch, skip);
+                        builder.branch_to(label, height, len_values);
+                        builder.inst_builder.resolve_label(skip);
+                    } else {
+                        let branch = builder.conditional_branch(condition, false);
+                        builder.push_branch(branch, label);
+                    }
                 }
-                AquiredTarget::Return(drop_keep) => {
+                AquiredTarget::Return => {
+                    let results = builder.prepare_return();
                     builder
                         .inst_builder
-                        .push_inst(Instruction::ReturnIfNez(drop_keep));
+                        .push_inst(Instruction::ReturnIfNez { condition, results });
                 }
             }
             Ok(())
@@ -562,41 +911,88 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         T: IntoIterator<Item = RelativeDepth>,
     {
         self.translate_if_reachable(|builder| {
-            let case = builder.value_stack.pop1();
-            debug_assert_eq!(case, ValueType::I32);
+            let index = builder.pop_register(ValueType::I32);
+
+            /// A branch of the `br_table` that requires copies before branching.
+            ///
+            /// These branches are redirected to a stub following the branch table.
+            /// Branches with the same relative depth share the same stub.
+            struct Stub {
+                depth: u32,
+                label: LabelIdx,
+                target: AquiredTarget,
+            }
 
             fn compute_inst(
                 builder: &mut FunctionBuilder,
+                stubs: &mut Vec<Stub>,
                 n: usize,
                 depth: RelativeDepth,
             ) -> Instruction {
-                match builder.acquire_target(depth.into_u32()) {
-                    AquiredTarget::Branch(label_idx, drop_keep) => {
-                        let dst_pc = builder.try_resolve_label(label_idx, |pc| Reloc::BrTable {
-                            inst_idx: pc,
-                            target_idx: n,
-                        });
-                        Instruction::Br(Target::new(dst_pc, drop_keep))
+                let target = builder.acquire_target(depth.into_u32());
+                let label = match target {
+                    AquiredTarget::Branch {
+                        label,
+                        height,
+                        len_values,
+                    } if !builder.requires_copies(height, len_values) => label,
+                    AquiredTarget::Return if builder.results_are_consecutive() => {
+                        return Instruction::Return(builder.prepare_return());
                     }
-                    AquiredTarget::Return(drop_keep) => Instruction::Return(drop_keep),
-                }
+                    target => {
+                        let depth = depth.into_u32();
+                        match stubs.iter().find(|stub| stub.depth == depth) {
+                            Some(stub) => stub.label,
+                            None => {
+                                let label = builder.inst_builder.new_label();
+                                stubs.push(Stub {
+                                    depth,
+                                    label,
+                                    target,
+                                });
+                                label
+                            }
+                        }
+                    }
+                };
+                let dst_pc = builder.try_resolve_label(label, |pc| Reloc::BrTable {
+                    inst_idx: pc,
+                    target_idx: n,
+                });
+                Instruction::Br(Target::new(dst_pc))
             }
 
+            let mut stubs = Vec::new();
             let branches = targets
                 .into_iter()
                 .enumerate()
-                .map(|(n, depth)| compute_inst(builder, n, depth))
+                .map(|(n, depth)| compute_inst(builder, &mut stubs, n, depth))
                 .collect::<Vec<_>>();
             // We include the default target in `len_branches`.
             let len_branches = branches.len();
-            let default_branch = compute_inst(builder, len_branches, default);
+            let default_branch = compute_inst(builder, &mut stubs, len_branches, default);
             builder.inst_builder.push_inst(Instruction::BrTable {
+                index,
                 len_targets: len_branches + 1,
             });
             for branch in branches {
                 builder.inst_builder.push_inst(branch);
             }
             builder.inst_builder.push_inst(default_branch);
+            for stub in stubs {
+                builder.inst_builder.resolve_label(stub.label);
+                match stub.target {
+                    AquiredTarget::Branch {
+                        label,
+                        height,
+                        len_values,
+                    } => builder.branch_to(label, height, len_values),
+                    AquiredTarget::Return => {
+                        let results = builder.prepare_return();
+                        builder.inst_builder.push_inst(Instruction::Return(results));
+                    }
+                }
+            }
             builder.reachable = false;
             Ok(())
         })
@@ -605,18 +1001,28 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translates a Wasm `return` control flow operator.
     pub fn translate_return(&mut self) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let drop_keep = builder.drop_keep_return();
-            builder
-                .inst_builder
-                .push_inst(Instruction::Return(drop_keep));
+            let results = builder.prepare_return();
+            builder.inst_builder.push_inst(Instruction::Return(results));
             builder.reachable = false;
             Ok(())
         })
     }
 
     /// Adjusts the emulated [`ValueStack`] given the [`FuncType`] of the call.
-    fn adjust_value_stack_for_call(&mut self, func_type: &FuncType) {
+    ///
+    /// Returns the register following the registers of the call parameters.
+    ///
+    /// # Note
+    ///
+    /// The call parameters are materialized so that they are stored in
+    /// consecutive registers. The call results are stored in the registers
+    /// starting at the register of the first call parameter.
+    fn adjust_value_stack_for_call(&mut self, func_type: &FuncType) -> Register {
         let (params, results) = func_type.params_results();
+        let len_params = params.len() as u32;
+        let params_end = self.value_stack.len();
+        let params_start = params_end - len_params;
+        self.materialize(params_start..params_end);
         for param in params.iter().rev() {
             let popped = self.value_stack.pop1();
             debug_assert_eq!(popped, *param);
@@ -624,15 +1030,19 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         for result in results {
             self.value_stack.push(*result);
         }
+        self.temp_register(params_end)
     }
 
     /// Translates a Wasm `call` instruction.
     pub fn translate_call(&mut self, func_idx: FuncIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             let func_type = builder.func_type_of(func_idx);
-            builder.adjust_value_stack_for_call(&func_type);
-            let func_idx = func_idx.into_u32().into();
-            builder.inst_builder.push_inst(Instruction::Call(func_idx));
+            let params_end = builder.adjust_value_stack_for_call(&func_type);
+            let func_index = func_idx.into_u32().into();
+            builder.inst_builder.push_inst(Instruction::Call {
+                func_index,
+                params_end,
+            });
             Ok(())
         })
     }
@@ -647,14 +1057,68 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
             /// The default Wasm MVP table index.
             const DEFAULT_TABLE_INDEX: u32 = 0;
             assert_eq!(table_idx.into_u32(), DEFAULT_TABLE_INDEX);
-            let func_type_offset = builder.value_stack.pop1();
-            debug_assert_eq!(func_type_offset, ValueType::I32);
+            let index = builder.pop_register(ValueType::I32);
+            let func_type = builder.func_type_at(func_type_idx);
+            let params_end = builder.adjust_value_stack_for_call(&func_type);
+            let signature = func_type_idx.into_u32().into();
+            builder.inst_builder.push_inst(Instruction::CallIndirect {
+                signature,
+                index,
+                params_end,
+            });
+            Ok(())
+        })
+    }
+
+    /// Pushes the [`Instruction::Return`] following a tail call instruction.
+    ///
+    /// # Note
+    ///
+    /// The returned values are the results of a tail called host function.
+    /// Tail called Wasm functions return to the caller directly instead.
+    fn return_after_tail_call(&mut self) {
+        let results = self.prepare_return();
+        self.inst_builder.push_inst(Instruction::Return(results));
+        self.reachable = false;
+    }
+
+    /// Translates a Wasm `return_call` instruction.
+    pub fn translate_return_call(&mut self, func_idx: FuncIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let func_type = builder.func_type_of(func_idx);
+            let params_end = builder.adjust_value_stack_for_call(&func_type);
+            let func_index = func_idx.into_u32().into();
+            builder.inst_builder.push_inst(Instruction::ReturnCall {
+                func_index,
+                params_end,
+            });
+            builder.return_after_tail_call();
+            Ok(())
+        })
+    }
+
+    /// Translates a Wasm `return_call_indirect` instruction.
+    pub fn translate_return_call_indirect(
+        &mut self,
+        func_type_idx: FuncTypeIdx,
+        table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            /// The default Wasm MVP table index.
+            const DEFAULT_TABLE_INDEX: u32 = 0;
+            assert_eq!(table_idx.into_u32(), DEFAULT_TABLE_INDEX);
+            let index = builder.pop_register(ValueType::I32);
             let func_type = builder.func_type_at(func_type_idx);
-            builder.adjust_value_stack_for_call(&func_type);
-            let func_type_idx = func_type_idx.into_u32().into();
+            let params_end = builder.adjust_value_stack_for_call(&func_type);
+            let signature = func_type_idx.into_u32().into();
             builder
                 .inst_builder
-                .push_inst(Instruction::CallIndirect(func_type_idx));
+                .push_inst(Instruction::ReturnCallIndirect {
+                    signature,
+                    index,
+                    params_end,
+                });
+            builder.return_after_tail_call();
             Ok(())
         })
     }
@@ -663,7 +1127,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_drop(&mut self) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             builder.value_stack.pop1();
-            builder.inst_builder.push_inst(Instruction::Drop);
+            if builder.optimize {
+                builder.remove_dead_computations();
+            }
             Ok(())
         })
     }
@@ -671,44 +1137,80 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translates a Wasm `select` instruction.
     pub fn translate_select(&mut self) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let (v0, v1, selector) = builder.value_stack.pop3();
-            debug_assert_eq!(selector, ValueType::I32);
+            let condition = builder.pop_register(ValueType::I32);
+            let if_false = builder.top_register();
+            let v1 = builder.value_stack.pop1();
+            let if_true = builder.top_register();
+            let v0 = builder.value_stack.pop1();
             debug_assert_eq!(v0, v1);
-            builder.value_stack.push(v0);
-            builder.inst_builder.push_inst(Instruction::Select);
+            let result = builder.push_register(v0);
+            builder.copy_register(result, if_true);
+            builder.inst_builder.push_inst(Instruction::Select {
+                result,
+                condition,
+                if_false,
+            });
             Ok(())
         })
     }
 
     /// Translate a Wasm `local.get` instruction.
+    ///
+    /// # Note
+    ///
+    /// This does not push any instructions since the value is
+    /// used directly from the register of the local variable.
     pub fn translate_local_get(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let local_depth = builder.relative_local_depth(local_idx);
-            builder
-                .inst_builder
-                .push_inst(Instruction::local_get(local_depth));
             let value_type = builder
                 .locals
                 .resolve_local(local_idx)
                 .unwrap_or_else(|| panic!("failed to resolve local {}", local_idx));
-            builder.value_stack.push(value_type);
+            builder
+                .value_stack
+                .push_provider(value_type, Provider::Local(local_idx));
             Ok(())
         })
     }
 
+    /// Stores the top most value into the local variable at `local_idx` and pops it.
+    ///
+    /// # Note
+    ///
+    /// If possible the instruction that computed the value is altered to
+    /// store its result directly into the register of the local variable.
+    fn store_local(&mut self, local_idx: u32) {
+        let expected = self
+            .locals
+            .resolve_local(local_idx)
+            .unwrap_or_else(|| panic!("failed to resolve local {}", local_idx));
+        let input = self.pop_register(expected);
+        let local = Self::local_register(local_idx);
+        if input == local {
+            // Nothing to do in this case.
+            return;
+        }
+        // Values on the stack that refer to the local variable need
+        // to be preserved before the local variable is changed.
+        self.materialize_local(local_idx);
+        if input == self.temp_register(self.value_stack.len()) {
+            if let Some(result) = self
+                .inst_builder
+                .last_inst_mut()
+                .and_then(Instruction::result_mut)
+                .filter(|result| **result == input)
+            {
+                *result = local;
+                return;
+            }
+        }
+        self.copy_register(local, input);
+    }
+
     /// Translate a Wasm `local.set` instruction.
     pub fn translate_local_set(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let actual = builder.value_stack.pop1();
-            let local_depth = builder.relative_local_depth(local_idx);
-            builder
-                .inst_builder
-                .push_inst(Instruction::local_set(local_depth));
-            let expected = builder
-                .locals
-                .resolve_local(local_idx)
-                .unwrap_or_else(|| panic!("failed to resolve local {}", local_idx));
-            debug_assert_eq!(actual, expected);
+            builder.store_local(local_idx);
             Ok(())
         })
     }
@@ -716,16 +1218,11 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// Translate a Wasm `local.tee` instruction.
     pub fn translate_local_tee(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let local_depth = builder.relative_local_depth(local_idx);
+            let value_type = builder.value_stack.top();
+            builder.store_local(local_idx);
             builder
-                .inst_builder
-                .push_inst(Instruction::local_tee(local_depth));
-            let expected = builder
-                .locals
-                .resolve_local(local_idx)
-                .unwrap_or_else(|| panic!("failed to resolve local {}", local_idx));
-            let actual = builder.value_stack.top();
-            debug_assert_eq!(actual, expected);
+                .value_stack
+                .push_provider(value_type, Provider::Local(local_idx));
             Ok(())
         })
     }
@@ -734,11 +1231,11 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_global_get(&mut self, global_idx: GlobalIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             let global_type = builder.res.get_type_of_global(global_idx);
-            builder.value_stack.push(global_type.value_type());
-            let global_idx = global_idx.into_u32().into();
+            let result = builder.push_register(global_type.value_type());
+            let global = global_idx.into_u32().into();
             builder
                 .inst_builder
-                .push_inst(Instruction::GetGlobal(global_idx));
+                .push_inst(Instruction::GetGlobal { result, global });
             Ok(())
         })
     }
@@ -748,13 +1245,11 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         self.translate_if_reachable(|builder| {
             let global_type = builder.res.get_type_of_global(global_idx);
             debug_assert_eq!(global_type.mutability(), Mutability::Mutable);
-            let expected = global_type.value_type();
-            let actual = builder.value_stack.pop1();
-            debug_assert_eq!(actual, expected);
-            let global_idx = global_idx.into_u32().into();
+            let input = builder.pop_register(global_type.value_type());
+            let global = global_idx.into_u32().into();
             builder
                 .inst_builder
-                .push_inst(Instruction::SetGlobal(global_idx));
+                .push_inst(Instruction::SetGlobal { global, input });
             Ok(())
         })
     }
@@ -784,15 +1279,16 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
         loaded_type: ValueType,
-        make_inst: fn(Offset) -> Instruction,
+        make_inst: fn(Register, Register, Offset) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            let pointer = builder.value_stack.pop1();
-            debug_assert_eq!(pointer, ValueType::I32);
-            builder.value_stack.push(loaded_type);
+            let ptr = builder.pop_register(ValueType::I32);
+            let result = builder.push_register(loaded_type);
             let offset = Offset::from(offset);
-            builder.inst_builder.push_inst(make_inst(offset));
+            builder
+                .inst_builder
+                .push_inst(make_inst(result, ptr, offset));
             Ok(())
         })
     }
@@ -803,7 +1299,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load)
+        self.translate_load(memory_idx, offset, ValueType::I32, |result, ptr, offset| {
+            Instruction::I32Load {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load` instruction.
@@ -812,7 +1314,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `f32.load` instruction.
@@ -821,7 +1329,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::F32, Instruction::F32Load)
+        self.translate_load(memory_idx, offset, ValueType::F32, |result, ptr, offset| {
+            Instruction::F32Load {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `f64.load` instruction.
@@ -830,7 +1344,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::F64, Instruction::F64Load)
+        self.translate_load(memory_idx, offset, ValueType::F64, |result, ptr, offset| {
+            Instruction::F64Load {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i32.load_i8` instruction.
@@ -839,7 +1359,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load8S)
+        self.translate_load(memory_idx, offset, ValueType::I32, |result, ptr, offset| {
+            Instruction::I32Load8S {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i32.load_u8` instruction.
@@ -848,7 +1374,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load8U)
+        self.translate_load(memory_idx, offset, ValueType::I32, |result, ptr, offset| {
+            Instruction::I32Load8U {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i32.load_i16` instruction.
@@ -857,7 +1389,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load16S)
+        self.translate_load(memory_idx, offset, ValueType::I32, |result, ptr, offset| {
+            Instruction::I32Load16S {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i32.load_u16` instruction.
@@ -866,7 +1404,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I32, Instruction::I32Load16U)
+        self.translate_load(memory_idx, offset, ValueType::I32, |result, ptr, offset| {
+            Instruction::I32Load16U {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_i8` instruction.
@@ -875,7 +1419,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load8S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load8S {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_u8` instruction.
@@ -884,7 +1434,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load8U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load8U {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_i16` instruction.
@@ -893,7 +1449,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load16S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load16S {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_u16` instruction.
@@ -902,7 +1464,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load16U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load16U {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_i32` instruction.
@@ -911,7 +1479,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load32S)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load32S {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `i64.load_u32` instruction.
@@ -920,7 +1494,13 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_load(memory_idx, offset, ValueType::I64, Instruction::I64Load32U)
+        self.translate_load(memory_idx, offset, ValueType::I64, |result, ptr, offset| {
+            Instruction::I64Load32U {
+                result,
+                ptr,
+                offset,
+            }
+        })
     }
 
     /// Translate a Wasm `<ty>.store` instruction.
@@ -943,15 +1523,16 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
         stored_value: ValueType,
-        make_inst: fn(Offset) -> Instruction,
+        make_inst: fn(Register, Register, Offset) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            let (pointer, stored) = builder.value_stack.pop2();
-            debug_assert_eq!(pointer, ValueType::I32);
-            assert_eq!(stored_value, stored);
+            let value = builder.pop_register(stored_value);
+            let ptr = builder.pop_register(ValueType::I32);
             let offset = Offset::from(offset);
-            builder.inst_builder.push_inst(make_inst(offset));
+            builder
+                .inst_builder
+                .push_inst(make_inst(ptr, value, offset));
             Ok(())
         })
     }
@@ -962,7 +1543,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store)
+        self.translate_store(memory_idx, offset, ValueType::I32, |ptr, value, offset| {
+            Instruction::I32Store { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store` instruction.
@@ -971,7 +1554,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store)
+        self.translate_store(memory_idx, offset, ValueType::I64, |ptr, value, offset| {
+            Instruction::I64Store { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `f32.store` instruction.
@@ -980,7 +1565,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::F32, Instruction::F32Store)
+        self.translate_store(memory_idx, offset, ValueType::F32, |ptr, value, offset| {
+            Instruction::F32Store { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `f64.store` instruction.
@@ -989,7 +1576,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::F64, Instruction::F64Store)
+        self.translate_store(memory_idx, offset, ValueType::F64, |ptr, value, offset| {
+            Instruction::F64Store { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i32.store_i8` instruction.
@@ -998,7 +1587,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store8)
+        self.translate_store(memory_idx, offset, ValueType::I32, |ptr, value, offset| {
+            Instruction::I32Store8 { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i32.store_i16` instruction.
@@ -1007,7 +1598,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I32, Instruction::I32Store16)
+        self.translate_store(memory_idx, offset, ValueType::I32, |ptr, value, offset| {
+            Instruction::I32Store16 { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i8` instruction.
@@ -1016,7 +1609,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store8)
+        self.translate_store(memory_idx, offset, ValueType::I64, |ptr, value, offset| {
+            Instruction::I64Store8 { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i16` instruction.
@@ -1025,7 +1620,9 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store16)
+        self.translate_store(memory_idx, offset, ValueType::I64, |ptr, value, offset| {
+            Instruction::I64Store16 { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `i64.store_i32` instruction.
@@ -1034,15 +1631,19 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
         memory_idx: MemoryIdx,
         offset: u32,
     ) -> Result<(), ModuleError> {
-        self.translate_store(memory_idx, offset, ValueType::I64, Instruction::I64Store32)
+        self.translate_store(memory_idx, offset, ValueType::I64, |ptr, value, offset| {
+            Instruction::I64Store32 { ptr, value, offset }
+        })
     }
 
     /// Translate a Wasm `memory.size` instruction.
     pub fn translate_memory_size(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(Instruction::CurrentMemory);
+            let result = builder.push_register(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::CurrentMemory { result });
             Ok(())
         })
     }
@@ -1051,8 +1652,136 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_memory_grow(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
             debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
-            debug_assert_eq!(builder.value_stack.top(), ValueType::I32);
-            builder.inst_builder.push_inst(Instruction::GrowMemory);
+            let delta = builder.pop_register(ValueType::I32);
+            let result = builder.push_register(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::GrowMemory { result, delta });
+            Ok(())
+        })
+    }
+
+    /// Pops the `dst`, `src` and `len` operands of a `memory.init` or `table.init` instruction.
+    ///
+    /// # Note
+    ///
+    /// The operands are materialized into the three consecutive registers
+    /// of their value stack positions of which the first is returned.
+    fn pop_init_operands(&mut self) -> Register {
+        let operands_end = self.value_stack.len();
+        let operands_start = operands_end - 3;
+        self.materialize(operands_start..operands_end);
+        for _ in operands_start..operands_end {
+            let popped = self.value_stack.pop1();
+            debug_assert_eq!(popped, ValueType::I32);
+        }
+        self.temp_register(operands_start)
+    }
+
+    /// Translate a Wasm `memory.copy` instruction.
+    pub fn translate_memory_copy(
+        &mut self,
+        dst_memory_idx: MemoryIdx,
+        src_memory_idx: MemoryIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            debug_assert_eq!(dst_memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
+            debug_assert_eq!(src_memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
+            let len = builder.pop_register(ValueType::I32);
+            let src = builder.pop_register(ValueType::I32);
+            let dst = builder.pop_register(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::MemoryCopy { dst, src, len });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.fill` instruction.
+    pub fn translate_memory_fill(&mut self, memory_idx: MemoryIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
+            let len = builder.pop_register(ValueType::I32);
+            let value = builder.pop_register(ValueType::I32);
+            let dst = builder.pop_register(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::MemoryFill { dst, value, len });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `memory.init` instruction.
+    pub fn translate_memory_init(
+        &mut self,
+        segment: DataSegmentIdx,
+        memory_idx: MemoryIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            debug_assert_eq!(memory_idx.into_u32(), DEFAULT_MEMORY_INDEX);
+            let operands = builder.pop_init_operands();
+            builder
+                .inst_builder
+                .push_inst(Instruction::MemoryInit { segment, operands });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `data.drop` instruction.
+    pub fn translate_data_drop(&mut self, segment: DataSegmentIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder
+                .inst_builder
+                .push_inst(Instruction::DataDrop { segment });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `table.copy` instruction.
+    pub fn translate_table_copy(
+        &mut self,
+        dst_table_idx: TableIdx,
+        src_table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            /// The default Wasm MVP table index.
+            const DEFAULT_TABLE_INDEX: u32 = 0;
+            assert_eq!(dst_table_idx.into_u32(), DEFAULT_TABLE_INDEX);
+            assert_eq!(src_table_idx.into_u32(), DEFAULT_TABLE_INDEX);
+            let len = builder.pop_register(ValueType::I32);
+            let src = builder.pop_register(ValueType::I32);
+            let dst = builder.pop_register(ValueType::I32);
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableCopy { dst, src, len });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `table.init` instruction.
+    pub fn translate_table_init(
+        &mut self,
+        segment: ElementSegmentIdx,
+        table_idx: TableIdx,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            /// The default Wasm MVP table index.
+            const DEFAULT_TABLE_INDEX: u32 = 0;
+            assert_eq!(table_idx.into_u32(), DEFAULT_TABLE_INDEX);
+            let operands = builder.pop_init_operands();
+            builder
+                .inst_builder
+                .push_inst(Instruction::TableInit { segment, operands });
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm `elem.drop` instruction.
+    pub fn translate_elem_drop(&mut self, segment: ElementSegmentIdx) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            builder
+                .inst_builder
+                .push_inst(Instruction::ElemDrop { segment });
             Ok(())
         })
     }
@@ -1073,8 +1802,10 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     {
         self.translate_if_reachable(|builder| {
             let value = value.into();
-            builder.value_stack.push(value.value_type());
-            builder.inst_builder.push_inst(Instruction::constant(value));
+            let result = builder.push_register(value.value_type());
+            builder
+                .inst_builder
+                .push_inst(Instruction::constant(result, value));
             Ok(())
         })
     }
@@ -1110,20 +1841,17 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     fn translate_unary_cmp(
         &mut self,
         input_type: ValueType,
-        inst: Instruction,
+        make_inst: fn(Register, Register) -> Instruction,
     ) -> Result<(), ModuleError> {
-        self.translate_if_reachable(|builder| {
-            let condition = builder.value_stack.pop1();
-            debug_assert_eq!(condition, input_type);
-            builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(inst);
-            Ok(())
-        })
+        self.translate_conversion(input_type, ValueType::I32, make_inst)
     }
 
     /// Translate a Wasm `i32.eqz` instruction.
     pub fn translate_i32_eqz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_cmp(ValueType::I32, Instruction::I32Eqz)
+        self.translate_unary_cmp(ValueType::I32, |result, input| Instruction::I32Eqz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm binary comparison instruction.
@@ -1141,181 +1869,311 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     fn translate_binary_cmp(
         &mut self,
         input_type: ValueType,
-        inst: Instruction,
+        make_inst: fn(Register, Register, Register) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let (v0, v1) = builder.value_stack.pop2();
-            debug_assert_eq!(v0, v1);
-            debug_assert_eq!(v0, input_type);
-            builder.value_stack.push(ValueType::I32);
-            builder.inst_builder.push_inst(inst);
+            let rhs = builder.pop_register(input_type);
+            let lhs = builder.pop_register(input_type);
+            let result = builder.push_register(ValueType::I32);
+            builder.push_computation(result, make_inst(result, lhs, rhs));
             Ok(())
         })
     }
 
     /// Translate a Wasm `i32.eq` instruction.
     pub fn translate_i32_eq(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32Eq)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32Eq {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.ne` instruction.
     pub fn translate_i32_ne(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32Ne)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32Ne {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.lt` instruction.
     pub fn translate_i32_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LtS)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32LtS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.lt` instruction.
     pub fn translate_u32_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LtU)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32LtU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.gt` instruction.
     pub fn translate_i32_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GtS)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32GtS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.gt` instruction.
     pub fn translate_u32_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GtU)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32GtU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.le` instruction.
     pub fn translate_i32_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LeS)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32LeS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.le` instruction.
     pub fn translate_u32_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32LeU)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32LeU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.ge` instruction.
     pub fn translate_i32_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GeS)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32GeS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.ge` instruction.
     pub fn translate_u32_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I32, Instruction::I32GeU)
+        self.translate_binary_cmp(ValueType::I32, |result, lhs, rhs| Instruction::I32GeU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.eqz` instruction.
     pub fn translate_i64_eqz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_cmp(ValueType::I64, Instruction::I64Eqz)
+        self.translate_unary_cmp(ValueType::I64, |result, input| Instruction::I64Eqz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.eq` instruction.
     pub fn translate_i64_eq(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64Eq)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64Eq {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.ne` instruction.
     pub fn translate_i64_ne(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64Ne)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64Ne {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.lt` instruction.
     pub fn translate_i64_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LtS)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64LtS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.lt` instruction.
     pub fn translate_u64_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LtU)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64LtU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.gt` instruction.
     pub fn translate_i64_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GtS)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64GtS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.gt` instruction.
     pub fn translate_u64_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GtU)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64GtU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.le` instruction.
     pub fn translate_i64_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LeS)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64LeS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.le` instruction.
     pub fn translate_u64_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64LeU)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64LeU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.ge` instruction.
     pub fn translate_i64_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GeS)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64GeS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.ge` instruction.
     pub fn translate_u64_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::I64, Instruction::I64GeU)
+        self.translate_binary_cmp(ValueType::I64, |result, lhs, rhs| Instruction::I64GeU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.eq` instruction.
     pub fn translate_f32_eq(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Eq)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Eq {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.ne` instruction.
     pub fn translate_f32_ne(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Ne)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Ne {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.lt` instruction.
     pub fn translate_f32_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Lt)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Lt {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.gt` instruction.
     pub fn translate_f32_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Gt)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Gt {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.le` instruction.
     pub fn translate_f32_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Le)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Le {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.ge` instruction.
     pub fn translate_f32_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F32, Instruction::F32Ge)
+        self.translate_binary_cmp(ValueType::F32, |result, lhs, rhs| Instruction::F32Ge {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.eq` instruction.
     pub fn translate_f64_eq(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Eq)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Eq {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.ne` instruction.
     pub fn translate_f64_ne(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Ne)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Ne {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.lt` instruction.
     pub fn translate_f64_lt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Lt)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Lt {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.gt` instruction.
     pub fn translate_f64_gt(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Gt)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Gt {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.le` instruction.
     pub fn translate_f64_le(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Le)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Le {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.ge` instruction.
     pub fn translate_f64_ge(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_cmp(ValueType::F64, Instruction::F64Ge)
+        self.translate_binary_cmp(ValueType::F64, |result, lhs, rhs| Instruction::F64Ge {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a unary Wasm instruction.
@@ -1337,29 +2195,33 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_unary_operation(
         &mut self,
         value_type: ValueType,
-        inst: Instruction,
+        make_inst: fn(Register, Register) -> Instruction,
     ) -> Result<(), ModuleError> {
-        self.translate_if_reachable(|builder| {
-            let actual_type = builder.value_stack.top();
-            debug_assert_eq!(actual_type, value_type);
-            builder.inst_builder.push_inst(inst);
-            Ok(())
-        })
+        self.translate_conversion(value_type, value_type, make_inst)
     }
 
     /// Translate a Wasm `i32.clz` instruction.
     pub fn translate_i32_clz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I32, Instruction::I32Clz)
+        self.translate_unary_operation(ValueType::I32, |result, input| Instruction::I32Clz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i32.ctz` instruction.
     pub fn translate_i32_ctz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I32, Instruction::I32Ctz)
+        self.translate_unary_operation(ValueType::I32, |result, input| Instruction::I32Ctz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i32.popcnt` instruction.
     pub fn translate_i32_popcnt(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I32, Instruction::I32Popcnt)
+        self.translate_unary_operation(ValueType::I32, |result, input| Instruction::I32Popcnt {
+            result,
+            input,
+        })
     }
 
     /// Translate a binary Wasm instruction.
@@ -1386,321 +2248,629 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     pub fn translate_binary_operation(
         &mut self,
         value_type: ValueType,
-        inst: Instruction,
+        make_inst: fn(Register, Register, Register) -> Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
-            let (v0, v1) = builder.value_stack.pop2();
-            debug_assert_eq!(v0, v1);
-            debug_assert_eq!(v0, value_type);
-            builder.value_stack.push(value_type);
-            builder.inst_builder.push_inst(inst);
+            let rhs = builder.pop_register(value_type);
+            let lhs = builder.pop_register(value_type);
+            let result = builder.push_register(value_type);
+            builder.push_computation(result, make_inst(result, lhs, rhs));
             Ok(())
         })
     }
 
+    /// Translate a binary Wasm instruction that can take a constant `rhs` operand.
+    ///
+    /// # Note
+    ///
+    /// If the `rhs` operand has been computed by a constant instruction pushed
+    /// last, the constant is fused into the instruction returned by `make_imm`.
+    /// Falls back to `make_inst` if `make_imm` returns `None`.
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
+    /// - `{i32, i64}.add`
+    /// - `{i32, i64}.sub`
+    fn translate_binary_operation_imm(
+        &mut self,
+        value_type: ValueType,
+        make_inst: fn(Register, Register, Register) -> Instruction,
+        make_imm: fn(Register, Register, UntypedValue) -> Option<Instruction>,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let height = builder.value_stack.len() - 2;
+            let result = builder.temp_register(height);
+            let lhs = builder.register_at(height);
+            let rhs = builder.register_at(height + 1);
+            if builder
+                .evaluate_constant(result, &make_inst(result, lhs, rhs))
+                .is_some()
+            {
+                // Both operands are constant so the whole operation can be folded.
+                return builder.translate_binary_operation(value_type, make_inst);
+            }
+            let fused = builder
+                .last_constant()
+                .and_then(|rhs| make_imm(result, lhs, rhs));
+            match fused {
+                Some(
+                    Instruction::I32AddImm { value: 0, .. }
+                    | Instruction::I64AddImm { value: 0, .. },
+                ) if builder.optimize => {
+                    // Adding zero leaves the `lhs` operand unchanged on the value stack.
+                    builder.inst_builder.pop_inst();
+                    builder.pop_register(value_type);
+                    Ok(())
+                }
+                Some(inst) => {
+                    // Replace the constant instruction with the fused instruction.
+                    builder.inst_builder.pop_inst();
+                    builder.pop_register(value_type);
+                    builder.pop_register(value_type);
+                    builder.push_register(value_type);
+                    builder.inst_builder.push_inst(inst);
+                    Ok(())
+                }
+                None => builder.translate_binary_operation(value_type, make_inst),
+            }
+        })
+    }
+
     /// Translate a Wasm `i32.add` instruction.
     pub fn translate_i32_add(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Add)
+        self.translate_binary_operation_imm(
+            ValueType::I32,
+            |result, lhs, rhs| Instruction::I32Add { result, lhs, rhs },
+            |result, input, rhs| {
+                let value = i32::from(rhs);
+                Some(Instruction::I32AddImm {
+                    result,
+                    input,
+                    value,
+                })
+            },
+        )
     }
 
     /// Translate a Wasm `i32.sub` instruction.
     pub fn translate_i32_sub(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Sub)
+        self.translate_binary_operation_imm(
+            ValueType::I32,
+            |result, lhs, rhs| Instruction::I32Sub { result, lhs, rhs },
+            |result, input, rhs| {
+                let value = i32::from(rhs).wrapping_neg();
+                Some(Instruction::I32AddImm {
+                    result,
+                    input,
+                    value,
+                })
+            },
+        )
     }
 
     /// Translate a Wasm `i32.mul` instruction.
     pub fn translate_i32_mul(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Mul)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Mul {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.div` instruction.
     pub fn translate_i32_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32DivS)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32DivS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.div` instruction.
     pub fn translate_u32_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32DivU)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32DivU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.rem` instruction.
     pub fn translate_i32_rem(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32RemS)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32RemS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.rem` instruction.
     pub fn translate_u32_rem(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32RemU)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32RemU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.and` instruction.
     pub fn translate_i32_and(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32And)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32And {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.or` instruction.
     pub fn translate_i32_or(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Or)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Or {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.xor` instruction.
     pub fn translate_i32_xor(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Xor)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Xor {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.shl` instruction.
     pub fn translate_i32_shl(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Shl)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Shl {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.shr` instruction.
     pub fn translate_i32_shr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32ShrS)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32ShrS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u32.shr` instruction.
     pub fn translate_u32_shr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32ShrU)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32ShrU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.rotl` instruction.
     pub fn translate_i32_rotl(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Rotl)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Rotl {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i32.rotr` instruction.
     pub fn translate_i32_rotr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I32, Instruction::I32Rotr)
+        self.translate_binary_operation(ValueType::I32, |result, lhs, rhs| Instruction::I32Rotr {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.clz` instruction.
     pub fn translate_i64_clz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Clz)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Clz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.ctz` instruction.
     pub fn translate_i64_ctz(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Ctz)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Ctz {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.popcnt` instruction.
     pub fn translate_i64_popcnt(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Popcnt)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Popcnt {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.add` instruction.
     pub fn translate_i64_add(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Add)
+        self.translate_binary_operation_imm(
+            ValueType::I64,
+            |result, lhs, rhs| Instruction::I64Add { result, lhs, rhs },
+            |result, input, rhs| {
+                let value = i32::try_from(i64::from(rhs)).ok()?;
+                Some(Instruction::I64AddImm {
+                    result,
+                    input,
+                    value,
+                })
+            },
+        )
     }
 
     /// Translate a Wasm `i64.sub` instruction.
     pub fn translate_i64_sub(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Sub)
+        self.translate_binary_operation_imm(
+            ValueType::I64,
+            |result, lhs, rhs| Instruction::I64Sub { result, lhs, rhs },
+            |result, input, rhs| {
+                let value = i64::from(rhs).checked_neg()?;
+                let value = i32::try_from(value).ok()?;
+                Some(Instruction::I64AddImm {
+                    result,
+                    input,
+                    value,
+                })
+            },
+        )
     }
 
     /// Translate a Wasm `i64.mul` instruction.
     pub fn translate_i64_mul(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Mul)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Mul {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.div` instruction.
     pub fn translate_i64_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64DivS)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64DivS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.div` instruction.
     pub fn translate_u64_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64DivU)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64DivU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.rem` instruction.
     pub fn translate_i64_rem(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64RemS)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64RemS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.rem` instruction.
     pub fn translate_u64_rem(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64RemU)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64RemU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.and` instruction.
     pub fn translate_i64_and(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64And)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64And {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.or` instruction.
     pub fn translate_i64_or(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Or)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Or {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.xor` instruction.
     pub fn translate_i64_xor(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Xor)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Xor {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.shl` instruction.
     pub fn translate_i64_shl(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Shl)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Shl {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.shr` instruction.
     pub fn translate_i64_shr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64ShrS)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64ShrS {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `u64.shr` instruction.
     pub fn translate_u64_shr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64ShrU)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64ShrU {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.rotl` instruction.
     pub fn translate_i64_rotl(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Rotl)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Rotl {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `i64.rotr` instruction.
     pub fn translate_i64_rotr(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::I64, Instruction::I64Rotr)
+        self.translate_binary_operation(ValueType::I64, |result, lhs, rhs| Instruction::I64Rotr {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.abs` instruction.
     pub fn translate_f32_abs(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Abs)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Abs {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.neg` instruction.
     pub fn translate_f32_neg(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Neg)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Neg {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.ceil` instruction.
     pub fn translate_f32_ceil(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Ceil)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Ceil {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.floor` instruction.
     pub fn translate_f32_floor(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Floor)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Floor {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.trunc` instruction.
     pub fn translate_f32_trunc(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Trunc)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Trunc {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.nearest` instruction.
     pub fn translate_f32_nearest(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Nearest)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Nearest {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.sqrt` instruction.
     pub fn translate_f32_sqrt(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F32, Instruction::F32Sqrt)
+        self.translate_unary_operation(ValueType::F32, |result, input| Instruction::F32Sqrt {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f32.add` instruction.
     pub fn translate_f32_add(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Add)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Add {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.sub` instruction.
     pub fn translate_f32_sub(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Sub)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Sub {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.mul` instruction.
     pub fn translate_f32_mul(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Mul)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Mul {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.div` instruction.
     pub fn translate_f32_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Div)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Div {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.min` instruction.
     pub fn translate_f32_min(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Min)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Min {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.max` instruction.
     pub fn translate_f32_max(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Max)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| Instruction::F32Max {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f32.copysign` instruction.
     pub fn translate_f32_copysign(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F32, Instruction::F32Copysign)
+        self.translate_binary_operation(ValueType::F32, |result, lhs, rhs| {
+            Instruction::F32Copysign { result, lhs, rhs }
+        })
     }
 
     /// Translate a Wasm `f64.abs` instruction.
     pub fn translate_f64_abs(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Abs)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Abs {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.neg` instruction.
     pub fn translate_f64_neg(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Neg)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Neg {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.ceil` instruction.
     pub fn translate_f64_ceil(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Ceil)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Ceil {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.floor` instruction.
     pub fn translate_f64_floor(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Floor)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Floor {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.trunc` instruction.
     pub fn translate_f64_trunc(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Trunc)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Trunc {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.nearest` instruction.
     pub fn translate_f64_nearest(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Nearest)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Nearest {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.sqrt` instruction.
     pub fn translate_f64_sqrt(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::F64, Instruction::F64Sqrt)
+        self.translate_unary_operation(ValueType::F64, |result, input| Instruction::F64Sqrt {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `f64.add` instruction.
     pub fn translate_f64_add(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Add)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Add {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.sub` instruction.
     pub fn translate_f64_sub(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Sub)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Sub {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.mul` instruction.
     pub fn translate_f64_mul(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Mul)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Mul {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.div` instruction.
     pub fn translate_f64_div(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Div)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Div {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.min` instruction.
     pub fn translate_f64_min(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Min)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Min {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.max` instruction.
     pub fn translate_f64_max(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Max)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| Instruction::F64Max {
+            result,
+            lhs,
+            rhs,
+        })
     }
 
     /// Translate a Wasm `f64.copysign` instruction.
     pub fn translate_f64_copysign(&mut self) -> Result<(), ModuleError> {
-        self.translate_binary_operation(ValueType::F64, Instruction::F64Copysign)
+        self.translate_binary_operation(ValueType::F64, |result, lhs, rhs| {
+            Instruction::F64Copysign { result, lhs, rhs }
+        })
     }
 
     /// Translate a Wasm conversion instruction.
@@ -1715,228 +2885,308 @@ impl<'engine, 'parser> FunctionBuilder<'engine, 'parser> {
     /// - `f32.demote_f64`
     /// - `f64.convert_{i32, u32, i64, u64}`
     /// - `f64.promote_f32`
+    pub fn translate_conversion(
+        &mut self,
+        input_type: ValueType,
+        output_type: ValueType,
+        make_inst: fn(Register, Register) -> Instruction,
+    ) -> Result<(), ModuleError> {
+        self.translate_if_reachable(|builder| {
+            let input = builder.pop_register(input_type);
+            let result = builder.push_register(output_type);
+            builder.push_computation(result, make_inst(result, input));
+            Ok(())
+        })
+    }
+
+    /// Translate a Wasm reinterpret instruction.
+    ///
+    /// # Note
+    ///
+    /// This does not push any instructions since reinterpretations
+    /// do not alter the bits of the value stored in the register.
+    ///
+    /// This is used to translate the following Wasm instructions:
+    ///
     /// - `i32.reinterpret_f32`
     /// - `i64.reinterpret_f64`
     /// - `f32.reinterpret_i32`
     /// - `f64.reinterpret_i64`
-    pub fn translate_conversion(
+    pub fn translate_reinterpret(
         &mut self,
         input_type: ValueType,
         output_type: ValueType,
-        inst: Instruction,
     ) -> Result<(), ModuleError> {
         self.translate_if_reachable(|builder| {
+            let height = builder.value_stack.len() - 1;
+            let provider = builder.value_stack.provider(height);
             let input = builder.value_stack.pop1();
             debug_assert_eq!(input, input_type);
-            builder.value_stack.push(output_type);
-            builder.inst_builder.push_inst(inst);
+            builder.value_stack.push_provider(output_type, provider);
             Ok(())
         })
     }
 
     /// Translate a Wasm `i32.wrap_i64` instruction.
     pub fn translate_i32_wrap_i64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I64, ValueType::I32, Instruction::I32WrapI64)
+        self.translate_conversion(ValueType::I64, ValueType::I32, |result, input| {
+            Instruction::I32WrapI64 { result, input }
+        })
     }
 
     /// Translate a Wasm `i32.trunc_f32` instruction.
     pub fn translate_i32_trunc_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I32, Instruction::I32TruncSF32)
+        self.translate_conversion(ValueType::F32, ValueType::I32, |result, input| {
+            Instruction::I32TruncSF32 { result, input }
+        })
     }
 
     /// Translate a Wasm `u32.trunc_f32` instruction.
     pub fn translate_u32_trunc_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I32, Instruction::I32TruncUF32)
+        self.translate_conversion(ValueType::F32, ValueType::I32, |result, input| {
+            Instruction::I32TruncUF32 { result, input }
+        })
     }
 
     /// Translate a Wasm `i32.trunc_f64` instruction.
     pub fn translate_i32_trunc_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I32, Instruction::I32TruncSF64)
+        self.translate_conversion(ValueType::F64, ValueType::I32, |result, input| {
+            Instruction::I32TruncSF64 { result, input }
+        })
     }
 
     /// Translate a Wasm `u32.trunc_f64` instruction.
     pub fn translate_u32_trunc_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I32, Instruction::I32TruncUF64)
+        self.translate_conversion(ValueType::F64, ValueType::I32, |result, input| {
+            Instruction::I32TruncUF64 { result, input }
+        })
     }
 
     /// Translate a Wasm `i64.extend_i32` instruction.
     pub fn translate_i64_extend_i32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::I64, Instruction::I64ExtendSI32)
+        self.translate_conversion(ValueType::I32, ValueType::I64, |result, input| {
+            Instruction::I64ExtendSI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `u64.extend_i32` instruction.
     pub fn translate_u64_extend_i32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::I64, Instruction::I64ExtendUI32)
+        self.translate_conversion(ValueType::I32, ValueType::I64, |result, input| {
+            Instruction::I64ExtendUI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `i64.trunc_f32` instruction.
     pub fn translate_i64_trunc_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I64, Instruction::I64TruncSF32)
+        self.translate_conversion(ValueType::F32, ValueType::I64, |result, input| {
+            Instruction::I64TruncSF32 { result, input }
+        })
     }
 
     /// Translate a Wasm `u64.trunc_f32` instruction.
     pub fn translate_u64_trunc_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I64, Instruction::I64TruncUF32)
+        self.translate_conversion(ValueType::F32, ValueType::I64, |result, input| {
+            Instruction::I64TruncUF32 { result, input }
+        })
     }
 
     /// Translate a Wasm `i64.trunc_f64` instruction.
     pub fn translate_i64_trunc_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I64, Instruction::I64TruncSF64)
+        self.translate_conversion(ValueType::F64, ValueType::I64, |result, input| {
+            Instruction::I64TruncSF64 { result, input }
+        })
     }
 
     /// Translate a Wasm `u64.trunc_f64` instruction.
     pub fn translate_u64_trunc_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I64, Instruction::I64TruncUF64)
+        self.translate_conversion(ValueType::F64, ValueType::I64, |result, input| {
+            Instruction::I64TruncUF64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f32.convert_i32` instruction.
     pub fn translate_f32_convert_i32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::F32, Instruction::F32ConvertSI32)
+        self.translate_conversion(ValueType::I32, ValueType::F32, |result, input| {
+            Instruction::F32ConvertSI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `f32.convert_u32` instruction.
     pub fn translate_f32_convert_u32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::F32, Instruction::F32ConvertUI32)
+        self.translate_conversion(ValueType::I32, ValueType::F32, |result, input| {
+            Instruction::F32ConvertUI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `f32.convert_i64` instruction.
     pub fn translate_f32_convert_i64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I64, ValueType::F32, Instruction::F32ConvertSI64)
+        self.translate_conversion(ValueType::I64, ValueType::F32, |result, input| {
+            Instruction::F32ConvertSI64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f32.convert_u64` instruction.
     pub fn translate_f32_convert_u64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I64, ValueType::F32, Instruction::F32ConvertUI64)
+        self.translate_conversion(ValueType::I64, ValueType::F32, |result, input| {
+            Instruction::F32ConvertUI64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f32.demote_f64` instruction.
     pub fn translate_f32_demote_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::F32, Instruction::F32DemoteF64)
+        self.translate_conversion(ValueType::F64, ValueType::F32, |result, input| {
+            Instruction::F32DemoteF64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f64.convert_i32` instruction.
     pub fn translate_f64_convert_i32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::F64, Instruction::F64ConvertSI32)
+        self.translate_conversion(ValueType::I32, ValueType::F64, |result, input| {
+            Instruction::F64ConvertSI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `f64.convert_u32` instruction.
     pub fn translate_f64_convert_u32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I32, ValueType::F64, Instruction::F64ConvertUI32)
+        self.translate_conversion(ValueType::I32, ValueType::F64, |result, input| {
+            Instruction::F64ConvertUI32 { result, input }
+        })
     }
 
     /// Translate a Wasm `f64.convert_i64` instruction.
     pub fn translate_f64_convert_i64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I64, ValueType::F64, Instruction::F64ConvertSI64)
+        self.translate_conversion(ValueType::I64, ValueType::F64, |result, input| {
+            Instruction::F64ConvertSI64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f64.convert_u64` instruction.
     pub fn translate_f64_convert_u64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::I64, ValueType::F64, Instruction::F64ConvertUI64)
+        self.translate_conversion(ValueType::I64, ValueType::F64, |result, input| {
+            Instruction::F64ConvertUI64 { result, input }
+        })
     }
 
     /// Translate a Wasm `f64.promote_f32` instruction.
     pub fn translate_f64_promote_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::F64, Instruction::F64PromoteF32)
+        self.translate_conversion(ValueType::F32, ValueType::F64, |result, input| {
+            Instruction::F64PromoteF32 { result, input }
+        })
     }
 
     /// Translate a Wasm `i32.reinterpret_f32` instruction.
     pub fn translate_i32_reinterpret_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(
-            ValueType::F32,
-            ValueType::I32,
-            Instruction::I32ReinterpretF32,
-        )
+        self.translate_reinterpret(ValueType::F32, ValueType::I32)
     }
 
     /// Translate a Wasm `i64.reinterpret_f64` instruction.
     pub fn translate_i64_reinterpret_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(
-            ValueType::F64,
-            ValueType::I64,
-            Instruction::I64ReinterpretF64,
-        )
+        self.translate_reinterpret(ValueType::F64, ValueType::I64)
     }
 
     /// Translate a Wasm `f32.reinterpret_i32` instruction.
     pub fn translate_f32_reinterpret_i32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(
-            ValueType::I32,
-            ValueType::F32,
-            Instruction::F32ReinterpretI32,
-        )
+        self.translate_reinterpret(ValueType::I32, ValueType::F32)
     }
 
     /// Translate a Wasm `f64.reinterpret_i64` instruction.
     pub fn translate_f64_reinterpret_i64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(
-            ValueType::I64,
-            ValueType::F64,
-            Instruction::F64ReinterpretI64,
-        )
+        self.translate_reinterpret(ValueType::I64, ValueType::F64)
     }
 
     /// Translate a Wasm `i32.extend_8s` instruction.
     pub fn translate_i32_sign_extend8(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I32, Instruction::I32Extend8S)
+        self.translate_unary_operation(ValueType::I32, |result, input| Instruction::I32Extend8S {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i32.extend_16s` instruction.
     pub fn translate_i32_sign_extend16(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I32, Instruction::I32Extend16S)
+        self.translate_unary_operation(ValueType::I32, |result, input| Instruction::I32Extend16S {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.extend_8s` instruction.
     pub fn translate_i64_sign_extend8(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Extend8S)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Extend8S {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.extend_16s` instruction.
     pub fn translate_i64_sign_extend16(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Extend16S)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Extend16S {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i64.extend_32s` instruction.
     pub fn translate_i64_sign_extend32(&mut self) -> Result<(), ModuleError> {
-        self.translate_unary_operation(ValueType::I64, Instruction::I64Extend32S)
+        self.translate_unary_operation(ValueType::I64, |result, input| Instruction::I64Extend32S {
+            result,
+            input,
+        })
     }
 
     /// Translate a Wasm `i32.truncate_sat_f32` instruction.
     pub fn translate_i32_truncate_saturate_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I32, Instruction::I32TruncSatF32S)
+        self.translate_conversion(ValueType::F32, ValueType::I32, |result, input| {
+            Instruction::I32TruncSatF32S { result, input }
+        })
     }
 
     /// Translate a Wasm `u32.truncate_sat_f32` instruction.
     pub fn translate_u32_truncate_saturate_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I32, Instruction::I32TruncSatF32U)
+        self.translate_conversion(ValueType::F32, ValueType::I32, |result, input| {
+            Instruction::I32TruncSatF32U { result, input }
+        })
     }
 
     /// Translate a Wasm `i32.truncate_sat_f64` instruction.
     pub fn translate_i32_truncate_saturate_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I32, Instruction::I32TruncSatF64S)
+        self.translate_conversion(ValueType::F64, ValueType::I32, |result, input| {
+            Instruction::I32TruncSatF64S { result, input }
+        })
     }
 
     /// Translate a Wasm `u32.truncate_sat_f64` instruction.
     pub fn translate_u32_truncate_saturate_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I32, Instruction::I32TruncSatF64U)
+        self.translate_conversion(ValueType::F64, ValueType::I32, |result, input| {
+            Instruction::I32TruncSatF64U { result, input }
+        })
     }
 
     /// Translate a Wasm `i64.truncate_sat_f32` instruction.
     pub fn translate_i64_truncate_saturate_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I64, Instruction::I64TruncSatF32S)
+        self.translate_conversion(ValueType::F32, ValueType::I64, |result, input| {
+            Instruction::I64TruncSatF32S { result, input }
+        })
     }
 
     /// Translate a Wasm `u64.truncate_sat_f32` instruction.
     pub fn translate_u64_truncate_saturate_f32(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F32, ValueType::I32, Instruction::I64TruncSatF32U)
+        self.translate_conversion(ValueType::F32, ValueType::I64, |result, input| {
+            Instruction::I64TruncSatF32U { result, input }
+        })
     }
 
     /// Translate a Wasm `i64.truncate_sat_f64` instruction.
     pub fn translate_i64_truncate_saturate_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I64, Instruction::I64TruncSatF64S)
+        self.translate_conversion(ValueType::F64, ValueType::I64, |result, input| {
+            Instruction::I64TruncSatF64S { result, input }
+        })
     }
 
     /// Translate a Wasm `u64.truncate_sat_f64` instruction.
     pub fn translate_u64_truncate_saturate_f64(&mut self) -> Result<(), ModuleError> {
-        self.translate_conversion(ValueType::F64, ValueType::I32, Instruction::I64TruncSatF64U)
+        self.translate_conversion(ValueType::F64, ValueType::I64, |result, input| {
+            Instruction::I64TruncSatF64U { result, input }
+        })
     }
 }
@@ -2,11 +2,26 @@ use core::cmp;
 
 use wasmi_core::ValueType;
 
+/// Describes where the value of an entry of the emulated value stack is stored.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Provider {
+    /// The value is stored in the register that belongs to its value stack position.
+    Temp,
+    /// The value is stored in the register of the local variable at the index.
+    ///
+    /// # Note
+    ///
+    /// This allows to use the values of local variables without copying them.
+    Local(u32),
+}
+
 /// The value stack that is emulated during Wasm to `wasmi` bytecode translation.
 #[derive(Debug, Default)]
 pub struct ValueStack {
     /// The values of the emulated value stack.
     values: Vec<ValueType>,
+    /// The providers of the values of the emulated value stack.
+    providers: Vec<Provider>,
     /// The maximum height of the emulated value stack of the translated function.
     ///
     /// # Note
@@ -38,10 +53,34 @@ impl ValueStack {
     ///
     /// [`Value`]: [`wasmi_core::Value`]
     pub fn push(&mut self, value_type: ValueType) {
+        self.push_provider(value_type, Provider::Temp);
+    }
+
+    /// Pushes the [`ValueType`] provided by `provider` to the emulated [`ValueStack`].
+    pub fn push_provider(&mut self, value_type: ValueType, provider: Provider) {
         self.values.push(value_type);
+        self.providers.push(provider);
         self.update_max_height();
     }
 
+    /// Returns the [`Provider`] of the entry at the given `height`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no entry at the given `height`.
+    pub fn provider(&self, height: u32) -> Provider {
+        self.providers[height as usize]
+    }
+
+    /// Sets the [`Provider`] of the entry at the given `height`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no entry at the given `height`.
+    pub fn set_provider(&mut self, height: u32, provider: Provider) {
+        self.providers[height as usize] = provider;
+    }
+
     /// Returns the top most [`ValueType`] form the emulated [`ValueStack`].
     pub fn top(&self) -> ValueType {
         *self
@@ -56,6 +95,7 @@ impl ValueStack {
     ///
     /// If the emulated [`ValueStack`] is empty.
     pub fn pop1(&mut self) -> ValueType {
+        self.providers.pop();
         self.values
             .pop()
             .expect("tried to pop value from an empty emulated value stack")
@@ -116,5 +156,6 @@ impl ValueStack {
             )
         });
         self.values.resize(new_height, ValueType::I32);
+        self.providers.resize(new_height, Provider::Temp);
     }
 }
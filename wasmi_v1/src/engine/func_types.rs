@@ -7,6 +7,7 @@ use crate::{
     Index,
     Store,
 };
+use alloc::vec::Vec;
 
 /// A raw index to a function signature entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -53,7 +54,7 @@ impl DedupFuncType {
     }
 
     /// Creates a new function signature to the store.
-    pub fn new<T, E, I, O>(ctx: &mut Store<T, E>, inputs: I, outputs: O) -> Self
+    pub fn new<T, I, O>(ctx: &mut Store<T>, inputs: I, outputs: O) -> Self
     where
         I: IntoIterator<Item = ValueType>,
         O: IntoIterator<Item = ValueType>,
@@ -87,6 +88,12 @@ pub struct FuncTypeRegistry {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: DedupArena<DedupFuncTypeIdx, FuncType>,
+    /// The number of allocations of each deduplicated function type.
+    ///
+    /// # Note
+    ///
+    /// A function type is deallocated once all of its allocations have been released.
+    refcounts: Vec<usize>,
 }
 
 impl FuncTypeRegistry {
@@ -95,6 +102,7 @@ impl FuncTypeRegistry {
         Self {
             engine_idx,
             func_types: DedupArena::default(),
+            refcounts: Vec::new(),
         }
     }
 
@@ -116,11 +124,49 @@ impl FuncTypeRegistry {
     }
 
     /// Allocates a new function type to the engine.
+    ///
+    /// # Note
+    ///
+    /// Every allocation must be released via [`FuncTypeRegistry::release_func_type`]
+    /// in order for the function type to be deallocated eventually.
     pub(crate) fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
-        DedupFuncType::from_inner(Guarded::new(
-            self.engine_idx,
-            self.func_types.alloc(func_type),
-        ))
+        let entity_index = self.func_types.alloc(func_type);
+        let index = entity_index.into_usize();
+        if index >= self.refcounts.len() {
+            self.refcounts.resize(index + 1, 0);
+        }
+        self.refcounts[index] += 1;
+        DedupFuncType::from_inner(Guarded::new(self.engine_idx, entity_index))
+    }
+
+    /// Releases an allocation of the deduplicated function type.
+    ///
+    /// # Note
+    ///
+    /// The function type is deallocated once all of its allocations have been released.
+    /// Afterwards all [`DedupFuncType`] referring to it are invalid.
+    ///
+    /// # Panics
+    ///
+    /// - If the deduplicated function type is not owned by the engine.
+    /// - If the deduplicated function type has already been deallocated.
+    pub(crate) fn release_func_type(&mut self, func_type: DedupFuncType) {
+        let entity_index = self.unwrap_index(func_type.into_inner());
+        let refcount = self
+            .refcounts
+            .get_mut(entity_index.into_usize())
+            .filter(|refcount| **refcount != 0)
+            .unwrap_or_else(|| panic!("failed to release function type: {:?}", entity_index));
+        *refcount -= 1;
+        if *refcount == 0 {
+            self.func_types.dealloc(entity_index);
+        }
+    }
+
+    /// Returns the number of allocated deduplicated function types.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.func_types.len()
     }
 
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
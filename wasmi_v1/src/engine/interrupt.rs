@@ -0,0 +1,38 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A handle to interrupt the execution of Wasm functions within a [`Store`].
+///
+/// # Note
+///
+/// - The [`InterruptHandle`] is `Send + Sync` and cheap to clone so that
+///   it can be used to interrupt execution from another thread, e.g. upon
+///   a wall-clock timeout or a user cancellation.
+/// - Interrupted executions trap with [`TrapCode::Interrupted`] at the next
+///   loop back-edge or function call. The [`Store`] remains usable afterwards.
+/// - A requested interrupt is consumed by the execution that traps because of it.
+///   If an interrupt is requested while no execution is in progress the next
+///   execution traps once it reaches a loop back-edge or function call.
+///
+/// [`Store`]: [`crate::Store`]
+/// [`TrapCode::Interrupted`]: [`crate::core::TrapCode::Interrupted`]
+#[derive(Debug, Default, Clone)]
+pub struct InterruptHandle {
+    /// Is `true` if an interrupt has been requested.
+    requested: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Requests to interrupt the execution of Wasm functions within the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn interrupt(&self) {
+        self.requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if an interrupt has been requested and resets the request.
+    #[inline]
+    pub(crate) fn take_interrupt(&self) -> bool {
+        self.requested.load(Ordering::Relaxed) && self.requested.swap(false, Ordering::Relaxed)
+    }
+}
@@ -3,46 +3,93 @@
 pub mod bytecode;
 pub mod call_stack;
 pub mod code_map;
+#[cfg(feature = "coverage")]
+mod coverage;
+#[cfg(feature = "debugger")]
+mod debugger;
 pub mod exec_context;
+mod fuel;
 mod func_args;
 mod func_builder;
 mod func_types;
+mod interrupt;
+mod module_code;
+#[cfg(feature = "profiler")]
+mod profiler;
+mod resumable;
+mod stack;
+#[cfg(feature = "trace")]
+mod trace;
 mod traits;
 pub mod value_stack;
 
-pub(crate) use self::func_args::{FuncParams, FuncResults};
+#[cfg(feature = "coverage")]
+pub(crate) use self::coverage::CoverageRecorder;
+#[cfg(feature = "coverage")]
+pub use self::coverage::{BranchCoverage, Coverage, FuncCoverage, OffsetCoverage};
+#[cfg(feature = "debugger")]
+pub(crate) use self::debugger::DebugState;
+#[cfg(feature = "debugger")]
+pub use self::debugger::{DebugCall, DebugFrame, PauseReason, PausedInvocation, StepMode};
+#[cfg(feature = "profiler")]
+pub(crate) use self::profiler::{func_name, Profiler};
+#[cfg(feature = "profiler")]
+pub use self::profiler::{FuncProfile, Profile};
+#[cfg(feature = "trace")]
+pub use self::trace::TraceEvent;
+#[cfg(feature = "trace")]
+pub(crate) use self::trace::TraceHook;
 use self::{
     bytecode::Instruction,
-    call_stack::{CallStack, FunctionFrame},
+    call_stack::FunctionFrame,
     code_map::{CodeMap, ResolvedFuncBody},
     exec_context::FunctionExecutor,
     func_types::FuncTypeRegistry,
+    stack::StackBase,
     value_stack::ValueStack,
 };
 pub use self::{
     bytecode::{DropKeep, Target},
-    code_map::FuncBody,
+    code_map::{FuncBody, TranslatedFuncBody},
+    fuel::{FuelCosts, FuelError},
     func_builder::{FunctionBuilder, InstructionIdx, LabelIdx, RelativeDepth, Reloc},
+    interrupt::InterruptHandle,
+    resumable::{ResumableCall, ResumableInvocation},
     traits::{CallParams, CallResults},
 };
+pub(crate) use self::{
+    fuel::Fuel,
+    func_args::{FuncParams, FuncResults},
+    module_code::ModuleCode,
+    stack::Stack,
+};
 use super::{func::FuncEntityInternal, AsContext, AsContextMut, Func};
+#[cfg(feature = "coverage")]
+use crate::module::ModuleNames;
 use crate::{
     arena::{GuardedEntity, Index},
     func::HostFuncEntity,
+    module::UncompiledFuncBody,
     Error,
+    FrameInfo,
     FuncType,
     Instance,
+    Trap,
+    WasmBacktrace,
 };
+#[cfg(feature = "coverage")]
+use alloc::collections::BTreeMap;
 use alloc::sync::Arc;
 use core::{
-    cmp,
+    iter,
+    mem,
     sync::atomic::{AtomicUsize, Ordering},
 };
 pub use func_types::DedupFuncType;
-use spin::mutex::Mutex;
-use wasmi_core::TrapCode;
+use spin::RwLock;
+use wasmi_core::{CanResume, TrapCode, Value};
 
-/// Maximum number of bytes on the value stack.
+/// Maximum number of values on the value stack.
 pub const DEFAULT_VALUE_STACK_LIMIT: usize = 1024 * 1024;
 
 /// Maximum number of levels on the call stack.
@@ -55,6 +102,64 @@ pub enum CallOutcome {
     Return,
     /// The function called another function.
     NestedCall(Func),
+    /// The function tail called another function.
+    ///
+    /// # Note
+    ///
+    /// The parameters of the call are the last values on the value stack
+    /// the same as for [`CallOutcome::NestedCall`].
+    TailCall(Func),
+    /// The debug execution paused before executing the next instruction.
+    #[cfg(feature = "debugger")]
+    Pause(PauseReason),
+}
+
+/// An error that occurred during the execution of Wasm functions.
+#[derive(Debug)]
+enum ExecutionError {
+    /// A [`Trap`] that occurred during the execution of Wasm functions.
+    Wasm(Trap),
+    /// A [`Trap`] returned by a host function called from Wasm.
+    Host {
+        /// The host function that returned the [`Trap`].
+        host_func: Func,
+        /// The [`Trap`] returned by the host function.
+        error: Trap,
+    },
+    /// The debug execution paused at a breakpoint or after a step.
+    #[cfg(feature = "debugger")]
+    Paused(PauseReason),
+}
+
+impl ExecutionError {
+    /// Creates an [`ExecutionError`] from the given Wasm `trap`.
+    fn trap(trap: TrapCode) -> Self {
+        Self::Wasm(Trap::from(trap))
+    }
+
+    /// Attaches the [`WasmBacktrace`] to the underlying [`Trap`] if it has none so far.
+    fn with_backtrace(self, backtrace: impl FnOnce() -> WasmBacktrace) -> Self {
+        match self {
+            Self::Wasm(trap) => Self::Wasm(trap.with_backtrace(backtrace)),
+            Self::Host { host_func, error } => Self::Host {
+                host_func,
+                error: error.with_backtrace(backtrace),
+            },
+            #[cfg(feature = "debugger")]
+            Self::Paused(reason) => Self::Paused(reason),
+        }
+    }
+
+    /// Returns the underlying [`Trap`] as [`Error`].
+    fn into_error(self) -> Error {
+        match self {
+            Self::Wasm(trap) | Self::Host { error: trap, .. } => Error::Trap(trap),
+            #[cfg(feature = "debugger")]
+            Self::Paused(_) => {
+                panic!("encountered paused execution outside of a debug execution")
+            }
+        }
+    }
 }
 
 /// A unique engine index.
@@ -95,9 +200,15 @@ type Guarded<Idx> = GuardedEntity<EngineIdx, Idx>;
 /// - The current `wasmi` engine implements a bytecode interpreter.
 /// - This structure is intentionally cheap to copy.
 ///   Most of its API has a `&self` receiver, so can be shared easily.
+/// - The [`Engine`] only stores compiled Wasm code and function types.
+///   The execution stacks are owned by every [`Store`] so that multiple
+///   [`Store`] instances sharing the same [`Engine`] can execute Wasm
+///   functions concurrently, e.g. on different threads.
+///
+/// [`Store`]: [`crate::Store`]
 #[derive(Debug, Clone)]
 pub struct Engine {
-    inner: Arc<Mutex<EngineInner>>,
+    inner: Arc<RwLock<EngineInner>>,
 }
 
 /// Configuration for an [`Engine`].
@@ -149,6 +260,60 @@ pub struct Config {
     ///
     /// [`multi-value`]: https://github.com/WebAssembly/multi-value
     multi_value: bool,
+    /// Is `true` if the [`bulk-memory`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Enabled by default.
+    ///
+    /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
+    bulk_memory: bool,
+    /// Is `true` if the [`tail-call`] Wasm proposal is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    ///
+    /// [`tail-call`]: https://github.com/WebAssembly/tail-call
+    tail_call: bool,
+    /// Is `true` if fuel metering is enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    fuel_metering: bool,
+    /// The fuel cost schedule used if fuel metering is enabled.
+    fuel_costs: FuelCosts,
+    /// Is `true` if translation-time optimizations are enabled.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    optimizations: bool,
+    /// Is `true` if Wasm function bodies are translated upon their first execution.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    lazy_compilation: bool,
+    /// Is `true` if Wasm function bodies are validated and translated on multiple threads.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default. Only supported on `std` targets.
+    parallel_compilation: bool,
+    /// Is `true` if NaN results of float instructions are canonicalized.
+    ///
+    /// # Note
+    ///
+    /// Disabled by default.
+    nan_canonicalization: bool,
+    /// Is `true` if floating point types and instructions are supported.
+    ///
+    /// # Note
+    ///
+    /// Enabled by default.
+    floating_point: bool,
 }
 
 impl Default for Config {
@@ -160,6 +325,15 @@ impl Default for Config {
             sign_extension: true,
             saturating_float_to_int: true,
             multi_value: true,
+            bulk_memory: true,
+            tail_call: false,
+            fuel_metering: false,
+            fuel_costs: FuelCosts::new(),
+            optimizations: false,
+            lazy_compilation: false,
+            parallel_compilation: false,
+            nan_canonicalization: false,
+            floating_point: true,
         }
     }
 }
@@ -178,9 +352,52 @@ impl Config {
             sign_extension: false,
             saturating_float_to_int: false,
             multi_value: false,
+            bulk_memory: false,
+            tail_call: false,
+            fuel_metering: false,
+            fuel_costs: FuelCosts::new(),
+            optimizations: false,
+            lazy_compilation: false,
+            parallel_compilation: false,
+            nan_canonicalization: false,
+            floating_point: true,
         }
     }
 
+    /// Sets the maximum number of values on the value stack.
+    ///
+    /// # Note
+    ///
+    /// - Defaults to [`DEFAULT_VALUE_STACK_LIMIT`].
+    /// - Reaching this limit during execution of a Wasm function will
+    ///   cause a stack overflow trap.
+    pub const fn set_value_stack_limit(mut self, limit: usize) -> Self {
+        self.value_stack_limit = limit;
+        self
+    }
+
+    /// Returns the maximum number of values on the value stack.
+    pub const fn value_stack_limit(&self) -> usize {
+        self.value_stack_limit
+    }
+
+    /// Sets the maximum number of function frames on the call stack.
+    ///
+    /// # Note
+    ///
+    /// - Defaults to [`DEFAULT_CALL_STACK_LIMIT`].
+    /// - Reaching this limit during execution of a Wasm function will
+    ///   cause a stack overflow trap.
+    pub const fn set_call_stack_limit(mut self, limit: usize) -> Self {
+        self.call_stack_limit = limit;
+        self
+    }
+
+    /// Returns the maximum number of function frames on the call stack.
+    pub const fn call_stack_limit(&self) -> usize {
+        self.call_stack_limit
+    }
+
     /// Enables the `mutable-global` Wasm proposal.
     pub const fn enable_mutable_global(mut self, enable: bool) -> Self {
         self.mutable_global = enable;
@@ -224,6 +441,186 @@ impl Config {
     pub const fn multi_value(&self) -> bool {
         self.multi_value
     }
+
+    /// Enables the `bulk-memory` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// If enabled, passive data and element segments as well as the
+    /// `memory.copy`, `memory.fill`, `memory.init`, `data.drop`,
+    /// `table.copy`, `table.init` and `elem.drop` instructions are supported.
+    pub const fn enable_bulk_memory(mut self, enable: bool) -> Self {
+        self.bulk_memory = enable;
+        self
+    }
+
+    /// Returns `true` if the `bulk-memory` Wasm proposal is enabled.
+    pub const fn bulk_memory(&self) -> bool {
+        self.bulk_memory
+    }
+
+    /// Enables the `tail-call` Wasm proposal.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the `return_call` and `return_call_indirect` instructions
+    /// replace the function frame of the caller with the frame of the called
+    /// Wasm function so that tail recursion does not grow the call stack.
+    pub const fn enable_tail_call(mut self, enable: bool) -> Self {
+        self.tail_call = enable;
+        self
+    }
+
+    /// Returns `true` if the `tail-call` Wasm proposal is enabled.
+    pub const fn tail_call(&self) -> bool {
+        self.tail_call
+    }
+
+    /// Enables fuel metering.
+    ///
+    /// # Note
+    ///
+    /// If enabled, execution consumes fuel from the [`Store`] according to the
+    /// [`FuelCosts`] of the [`Config`] and traps once the fuel is used up.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub const fn enable_fuel_metering(mut self, enable: bool) -> Self {
+        self.fuel_metering = enable;
+        self
+    }
+
+    /// Returns `true` if fuel metering is enabled.
+    pub const fn fuel_metering(&self) -> bool {
+        self.fuel_metering
+    }
+
+    /// Sets the [`FuelCosts`] used if fuel metering is enabled.
+    pub const fn with_fuel_costs(mut self, costs: FuelCosts) -> Self {
+        self.fuel_costs = costs;
+        self
+    }
+
+    /// Returns the [`FuelCosts`] used if fuel metering is enabled.
+    pub const fn fuel_costs(&self) -> &FuelCosts {
+        &self.fuel_costs
+    }
+
+    /// Enables translation-time optimizations.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the translation of Wasm function bodies folds operations
+    /// on constant inputs, removes pure computations whose results are
+    /// dropped and rewrites trivial patterns such as additions of zero.
+    ///
+    /// Operations that would trap for their constant inputs are never folded
+    /// so that traps still occur at their original positions.
+    pub const fn enable_optimizations(mut self, enable: bool) -> Self {
+        self.optimizations = enable;
+        self
+    }
+
+    /// Returns `true` if translation-time optimizations are enabled.
+    pub const fn optimizations(&self) -> bool {
+        self.optimizations
+    }
+
+    /// Enables lazy compilation of Wasm function bodies.
+    ///
+    /// # Note
+    ///
+    /// If enabled, Wasm function bodies are still validated when creating a
+    /// [`Module`] but their translation into `wasmi` bytecode is deferred until
+    /// they are executed for the first time. This reduces the startup time and
+    /// memory usage for modules of which only a few functions are ever executed.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    pub const fn enable_lazy_compilation(mut self, enable: bool) -> Self {
+        self.lazy_compilation = enable;
+        self
+    }
+
+    /// Returns `true` if lazy compilation of Wasm function bodies is enabled.
+    pub const fn lazy_compilation(&self) -> bool {
+        self.lazy_compilation
+    }
+
+    /// Enables parallel compilation of Wasm function bodies.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the Wasm function bodies of the code section are validated
+    /// and translated into `wasmi` bytecode on multiple threads. The translated
+    /// function bodies are still allocated to the [`Engine`] in the order of the
+    /// code section so that the result does not depend on thread scheduling.
+    ///
+    /// Lazy compilation takes precedence over parallel compilation if both are enabled.
+    #[cfg(feature = "std")]
+    pub const fn enable_parallel_compilation(mut self, enable: bool) -> Self {
+        self.parallel_compilation = enable;
+        self
+    }
+
+    /// Returns `true` if parallel compilation of Wasm function bodies is enabled.
+    pub const fn parallel_compilation(&self) -> bool {
+        self.parallel_compilation
+    }
+
+    /// Enables canonicalization of NaN results of float instructions.
+    ///
+    /// # Note
+    ///
+    /// By default NaN results preserve the payloads of their NaN inputs which
+    /// may lead to different results on different platforms. If enabled, the
+    /// NaN results of the following instructions are replaced with the
+    /// canonical Wasm NaN so that execution is deterministic:
+    ///
+    /// - `add`, `sub`, `mul`, `div`, `min`, `max` and `sqrt`
+    /// - `ceil`, `floor`, `trunc` and `nearest`
+    /// - `f32.demote_f64` and `f64.promote_f32`
+    ///
+    /// The `abs`, `neg` and `copysign` instructions only operate on the sign bit
+    /// and conversions from integers never produce NaN results.
+    pub const fn enable_nan_canonicalization(mut self, enable: bool) -> Self {
+        self.nan_canonicalization = enable;
+        self
+    }
+
+    /// Returns `true` if NaN results of float instructions are canonicalized.
+    pub const fn nan_canonicalization(&self) -> bool {
+        self.nan_canonicalization
+    }
+
+    /// Enables support for floating point types and instructions.
+    ///
+    /// # Note
+    ///
+    /// If disabled, creating a [`Module`] fails with a [`ModuleError`] that names
+    /// the offending function and offset if the Wasm module uses floating point
+    /// types in function signatures, local or global variables or block types,
+    /// or if it uses any floating point instruction.
+    ///
+    /// Enabled by default.
+    ///
+    /// [`Module`]: [`crate::Module`]
+    /// [`ModuleError`]: [`crate::ModuleError`]
+    pub const fn enable_floating_point(mut self, enable: bool) -> Self {
+        self.floating_point = enable;
+        self
+    }
+
+    /// Returns `true` if floating point types and instructions are supported.
+    pub const fn floating_point(&self) -> bool {
+        self.floating_point
+    }
+
+    /// Returns the [`FuelCosts`] if fuel metering is enabled.
+    pub(crate) fn metered_fuel_costs(&self) -> Option<&FuelCosts> {
+        if self.fuel_metering {
+            return Some(&self.fuel_costs);
+        }
+        None
+    }
 }
 
 impl Default for Engine {
@@ -240,18 +637,18 @@ impl Engine {
     /// Users should ues [`Engine::default`] to construct a default [`Engine`].
     pub fn new(config: &Config) -> Self {
         Self {
-            inner: Arc::new(Mutex::new(EngineInner::new(config))),
+            inner: Arc::new(RwLock::new(EngineInner::new(config))),
         }
     }
 
     /// Returns a shared reference to the [`Config`] of the [`Engine`].
     pub fn config(&self) -> Config {
-        *self.inner.lock().config()
+        *self.inner.read().config()
     }
 
     /// Allocates a new function type to the engine.
     pub(super) fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
-        self.inner.lock().func_types.alloc_func_type(func_type)
+        self.inner.write().func_types.alloc_func_type(func_type)
     }
 
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
@@ -265,25 +662,102 @@ impl Engine {
         F: FnOnce(&FuncType) -> R,
     {
         // Note: The clone operation on FuncType is intentionally cheap.
-        f(self.inner.lock().func_types.resolve_func_type(func_type))
+        f(self.inner.read().func_types.resolve_func_type(func_type))
     }
 
-    /// Allocates the instructions of a Wasm function body to the [`Engine`].
+    /// Allocates the translated Wasm function body to the [`Engine`].
     ///
     /// Returns a [`FuncBody`] reference to the allocated function body.
-    pub(super) fn alloc_func_body<I>(
-        &self,
-        len_locals: usize,
-        max_stack_height: usize,
-        insts: I,
-    ) -> FuncBody
-    where
-        I: IntoIterator<Item = Instruction>,
-        I::IntoIter: ExactSizeIterator,
-    {
+    pub(super) fn alloc_func_body(&self, func_body: TranslatedFuncBody) -> FuncBody {
+        self.inner.write().alloc_func_body(func_body)
+    }
+
+    /// Allocates a lazily translated Wasm function body to the [`Engine`].
+    ///
+    /// Returns a [`FuncBody`] reference to the function body that is
+    /// translated into `wasmi` bytecode upon its first execution.
+    pub(super) fn alloc_uncompiled_func_body(&self, func_body: UncompiledFuncBody) -> FuncBody {
+        self.inner.write().code_map.alloc_lazy(func_body)
+    }
+
+    /// Frees the function bodies and releases the function types of a Wasm module.
+    ///
+    /// # Note
+    ///
+    /// This is called once a Wasm module and all of its instances have been dropped.
+    /// See [`ModuleCode`] for more information.
+    fn free_module_code(&self, func_types: &[DedupFuncType], func_bodies: &[FuncBody]) {
+        let mut inner = self.inner.write();
+        for &func_body in func_bodies {
+            inner.code_map.free(func_body);
+        }
+        for &func_type in func_types {
+            inner.func_types.release_func_type(func_type);
+        }
+    }
+
+    /// Returns the number of instructions and deduplicated function types allocated to the [`Engine`].
+    #[cfg(test)]
+    pub(crate) fn len_allocated(&self) -> (usize, usize) {
+        let inner = self.inner.read();
+        (inner.code_map.len_insts(), inner.func_types.len())
+    }
+
+    /// Returns the translated function body of `func_body`.
+    ///
+    /// # Note
+    ///
+    /// - This translates lazily translated function bodies into `wasmi`
+    ///   bytecode if they have not yet been translated.
+    /// - No lock on the [`EngineInner`] must be held by the caller since
+    ///   the translation allocates the translated function body.
+    fn compile_lazy(&self, func_body: FuncBody) -> FuncBody {
+        let uncompiled = match self.inner.read().code_map.compiled(func_body) {
+            Ok(compiled) => return compiled,
+            Err(uncompiled) => uncompiled.clone(),
+        };
+        let compiled = uncompiled.translate(self);
         self.inner
-            .lock()
-            .alloc_func_body(len_locals, max_stack_height, insts)
+            .write()
+            .code_map
+            .set_compiled(func_body, compiled)
+    }
+
+    /// Returns a copy of the translated function body of `func_body`.
+    ///
+    /// # Note
+    ///
+    /// Lazily translated function bodies are translated if necessary.
+    pub(crate) fn translated_func_body(&self, func_body: FuncBody) -> TranslatedFuncBody {
+        let func_body = self.compile_lazy(func_body);
+        self.inner.read().code_map.translated(func_body)
+    }
+
+    /// Returns the [`Coverage`] recorded by the `recorder` for the given Wasm function bodies.
+    ///
+    /// # Note
+    ///
+    /// - The Wasm function bodies are paired with the [`ModuleNames`] of their Wasm module.
+    /// - Lazily translated function bodies are translated if necessary so that
+    ///   functions that have never been executed are reported as well.
+    /// - Function bodies shared by multiple instances are reported once.
+    #[cfg(feature = "coverage")]
+    pub(crate) fn coverage<'a>(
+        &self,
+        recorder: &CoverageRecorder,
+        func_bodies: impl IntoIterator<Item = (FuncBody, &'a ModuleNames)>,
+    ) -> Coverage {
+        let func_bodies = func_bodies
+            .into_iter()
+            .map(|(func_body, names)| (self.compile_lazy(func_body), names))
+            .map(|(func_body, names)| (func_body.into_usize(), (func_body, names)))
+            .collect::<BTreeMap<_, _>>();
+        let inner = self.inner.read();
+        let funcs = func_bodies
+            .into_values()
+            .map(|(func_body, names)| recorder.func_coverage(&inner.code_map, func_body, names))
+            .collect();
+        Coverage::new(funcs)
     }
 
     /// Resolves the [`FuncBody`] to the underlying `wasmi` bytecode instructions.
@@ -300,7 +774,7 @@ impl Engine {
     #[cfg(test)]
     pub(crate) fn resolve_inst(&self, func_body: FuncBody, index: usize) -> Option<Instruction> {
         self.inner
-            .lock()
+            .read()
             .code_map
             .resolve(func_body)
             .get(index)
@@ -311,285 +785,775 @@ impl Engine {
     ///
     /// # Note
     ///
-    /// This API assumes that the `params` and `results` are well typed and
-    /// therefore won't perform type checks.
-    /// Those checks are usually done at the [`Func::call`] API or when creating
-    /// a new [`TypedFunc`] instance via [`Func::typed`].
+    /// - This API assumes that the `params` and `results` are well typed and
+    ///   therefore won't perform type checks.
+    ///   Those checks are usually done at the [`Func::call`] API or when creating
+    ///   a new [`TypedFunc`] instance via [`Func::typed`].
+    /// - The execution uses the [`Stack`] of the [`Store`] so that multiple
+    ///   [`Store`] instances sharing the same [`Engine`] can execute concurrently.
+    /// - The [`Engine`] is re-entrant so host functions may call back into Wasm
+    ///   using their [`Caller`]. Nested executions share the [`Stack`] of the
+    ///   [`Store`] so the configured stack limits apply to all of them.
     ///
     /// # Errors
     ///
-    /// - If the given `func` is not a Wasm function, e.g. if it is a host function.
     /// - If the given arguments `params` do not match the expected parameters of `func`.
     /// - If the given `results` do not match the the length of the expected results of `func`.
     /// - When encountering a Wasm trap during the execution of `func`.
     ///
     /// [`TypedFunc`]: [`crate::TypedFunc`]
+    /// [`Caller`]: [`crate::Caller`]
+    /// [`Store`]: [`crate::Store`]
     pub(crate) fn execute_func<C, Params, Results>(
-        &mut self,
-        ctx: C,
+        &self,
+        mut ctx: C,
         func: Func,
         params: Params,
         results: Results,
-    ) -> Result<<Results as CallResults>::Results, C::Error>
+    ) -> Result<<Results as CallResults>::Results, Error>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
         Params: CallParams,
         Results: CallResults,
     {
-        self.inner.lock().execute_func(ctx, func, params, results)
+        let signature = func.signature(&ctx);
+        let mut stack = take_stack(&mut ctx);
+        let results = match stack.initialize_args(params) {
+            Ok(base) => match self.execute_func_impl(&mut ctx, &mut stack, func, base) {
+                Ok(()) => Ok(self.resolve_func_type(signature, |func_type| {
+                    stack.write_results_back(func_type.results(), base, results)
+                })),
+                Err((error, _frame)) => {
+                    stack.reset(base);
+                    Err(error.into_error())
+                }
+            },
+            Err(trap) => Err(Error::from(trap)),
+        };
+        restore_stack(&mut ctx, stack);
+        results
     }
-}
 
-/// The internal state of the `wasmi` engine.
-#[derive(Debug)]
-pub struct EngineInner {
-    /// The configuration with which the [`Engine`] has been created.
-    config: Config,
-    /// Stores the value stack of live values on the Wasm stack.
-    value_stack: ValueStack,
-    /// Stores the call stack of live function invocations.
-    call_stack: CallStack,
-    /// Stores all Wasm function bodies that the interpreter is aware of.
-    code_map: CodeMap,
-    /// Deduplicated function types.
+    /// Executes the given [`Func`] in a resumable way using the given arguments `params`.
+    ///
+    /// The results are written into `results` once the execution has finished.
     ///
     /// # Note
     ///
-    /// The engine deduplicates function types to make the equality
-    /// comparison very fast. This helps to speed up indirect calls.
-    func_types: FuncTypeRegistry,
-}
-
-impl EngineInner {
-    /// Creates a new [`EngineInner`] with the given [`Config`].
-    pub fn new(config: &Config) -> Self {
-        let engine_idx = EngineIdx::new();
-        Self {
-            config: *config,
-            value_stack: ValueStack::new(64, config.value_stack_limit),
-            call_stack: CallStack::new(config.call_stack_limit),
-            code_map: CodeMap::default(),
-            func_types: FuncTypeRegistry::new(engine_idx),
-        }
+    /// This API assumes that the `params` and `results` are well typed and
+    /// therefore won't perform type checks.
+    /// Those checks are usually done at the [`Func::call_resumable`] API.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a host function returns an error that is not resumable.
+    pub(crate) fn execute_func_resumable<C>(
+        &self,
+        mut ctx: C,
+        func: Func,
+        params: &[Value],
+        results: &mut [Value],
+    ) -> Result<ResumableCall, Error>
+    where
+        C: AsContextMut,
+    {
+        let mut stack = take_stack(&mut ctx);
+        let outcome = match stack.initialize_args(params) {
+            Ok(base) => {
+                let outcome = self.execute_func_impl(&mut ctx, &mut stack, func, base);
+                self.finish_resumable(&ctx, &mut stack, func, base, outcome, results)
+            }
+            Err(trap) => Err(Error::from(trap)),
+        };
+        restore_stack(&mut ctx, stack);
+        outcome
     }
 
-    /// Returns a shared reference to the [`Config`] of the [`Engine`].
-    pub fn config(&self) -> &Config {
-        &self.config
+    /// Resumes the given suspended `invocation` using the host function results `params`.
+    ///
+    /// The results are written into `results` once the execution has finished.
+    ///
+    /// # Note
+    ///
+    /// This API assumes that the `params` and `results` are well typed and
+    /// therefore won't perform type checks.
+    /// Those checks are usually done at the [`ResumableInvocation::resume`] API.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a host function returns an error that is not resumable.
+    pub(crate) fn resume_func<C>(
+        &self,
+        mut ctx: C,
+        invocation: ResumableInvocation,
+        params: &[Value],
+        results: &mut [Value],
+    ) -> Result<ResumableCall, Error>
+    where
+        C: AsContextMut,
+    {
+        let func = invocation.func();
+        let len_host_inputs = invocation.host_func().func_type(&ctx).params().len();
+        let state = invocation.into_state();
+        let mut stack = take_stack(&mut ctx);
+        let outcome = match stack.restore_suspended(len_host_inputs, state, params) {
+            Ok((base, mut frame)) => {
+                #[cfg(feature = "profiler")]
+                let profile_depth = self
+                    .inner
+                    .read()
+                    .profile_resume(&mut ctx, &stack, base, &frame);
+                let outcome = self.execute_wasm_func(&mut ctx, &mut stack, &mut frame, base);
+                #[cfg(feature = "profiler")]
+                if outcome.is_err() {
+                    profile_unwind(&mut ctx, profile_depth);
+                }
+                let outcome = outcome.map_err(|error| (error, Some(frame)));
+                self.finish_resumable(&ctx, &mut stack, func, base, outcome, results)
+            }
+            Err(trap) => Err(Error::from(trap)),
+        };
+        restore_stack(&mut ctx, stack);
+        outcome
     }
 
-    /// Allocates the instructions of a Wasm function body to the [`Engine`].
+    /// Finishes a resumable execution of `func` given its `outcome`.
     ///
-    /// Returns a [`FuncBody`] reference to the allocated function body.
-    pub fn alloc_func_body<I>(
-        &mut self,
-        len_locals: usize,
-        max_stack_height: usize,
-        insts: I,
-    ) -> FuncBody
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a host function returns an error that is not resumable.
+    fn finish_resumable<C>(
+        &self,
+        ctx: C,
+        stack: &mut Stack,
+        func: Func,
+        base: StackBase,
+        outcome: Result<(), (ExecutionError, Option<FunctionFrame>)>,
+        results: &mut [Value],
+    ) -> Result<ResumableCall, Error>
     where
-        I: IntoIterator<Item = Instruction>,
-        I::IntoIter: ExactSizeIterator,
+        C: AsContext,
     {
-        self.code_map.alloc(len_locals, max_stack_height, insts)
+        match outcome {
+            Ok(()) => {
+                let signature = func.signature(&ctx);
+                self.resolve_func_type(signature, |func_type| {
+                    stack.write_results_back(func_type.results(), base, results)
+                });
+                Ok(ResumableCall::Finished)
+            }
+            Err((ExecutionError::Host { host_func, error }, Some(frame))) if error.can_resume() => {
+                let state = stack.suspend(base, frame);
+                Ok(ResumableCall::Resumable(ResumableInvocation::new(
+                    func, host_func, error, state,
+                )))
+            }
+            Err((error, _frame)) => {
+                stack.reset(base);
+                Err(error.into_error())
+            }
+        }
     }
 
-    /// Executes the given [`Func`] using the given arguments `args` and stores the result into `results`.
+    /// Executes the given [`Func`] with the debugger using the given arguments `params`.
+    ///
+    /// The results are written into `results` once the execution has finished.
+    ///
+    /// # Note
+    ///
+    /// This API assumes that the `params` and `results` are well typed and
+    /// therefore won't perform type checks.
+    /// Those checks are usually done at the [`Func::call_debug`] API.
     ///
     /// # Errors
     ///
-    /// - If the given arguments `args` do not match the expected parameters of `func`.
-    /// - If the given `results` do not match the the length of the expected results of `func`.
     /// - When encountering a Wasm trap during the execution of `func`.
-    pub fn execute_func<C, Params, Results>(
-        &mut self,
+    /// - When a host function returns an error.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn execute_func_debug<C>(
+        &self,
         mut ctx: C,
         func: Func,
-        params: Params,
-        results: Results,
-    ) -> Result<<Results as CallResults>::Results, C::Error>
+        params: &[Value],
+        results: &mut [Value],
+        mode: StepMode,
+    ) -> Result<DebugCall, Error>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
-        Params: CallParams,
-        Results: CallResults,
     {
-        self.initialize_args(params);
-        let signature = match func.as_internal(&ctx) {
-            FuncEntityInternal::Wasm(wasm_func) => {
-                let signature = wasm_func.signature();
-                self.execute_wasm_func(&mut ctx, func)?;
-                signature
+        let mut stack = take_stack(&mut ctx);
+        let outcome = match stack.initialize_args(params) {
+            Ok(base) => {
+                // The called function is executed on top of the frames of enclosing executions.
+                ctx.as_context_mut()
+                    .store
+                    .debugger_mut()
+                    .start(mode, base.len_frames(), false);
+                let outcome = self.execute_func_impl(&mut ctx, &mut stack, func, base);
+                self.finish_debug(&mut ctx, &mut stack, func, base, outcome, results)
             }
-            FuncEntityInternal::Host(host_func) => {
-                let signature = host_func.signature();
-                let host_func = host_func.clone();
-                self.execute_host_func(&mut ctx, host_func, None)?;
-                signature
+            Err(trap) => Err(Error::from(trap)),
+        };
+        restore_stack(&mut ctx, stack);
+        outcome
+    }
+
+    /// Resumes the given paused `invocation` according to the [`StepMode`].
+    ///
+    /// The results are written into `results` once the execution has finished.
+    ///
+    /// # Note
+    ///
+    /// This API assumes that the `results` are well typed and therefore won't perform type checks.
+    /// Those checks are usually done at the [`PausedInvocation::resume`] API.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a host function returns an error.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn resume_func_debug<C>(
+        &self,
+        mut ctx: C,
+        invocation: PausedInvocation,
+        mode: StepMode,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Error>
+    where
+        C: AsContextMut,
+    {
+        let func = invocation.func();
+        let state = invocation.into_state();
+        let len_callers = state.frames.len();
+        let no_params: &[Value] = &[];
+        let mut stack = take_stack(&mut ctx);
+        let outcome = match stack.restore_suspended(0, state, no_params) {
+            Ok((base, mut frame)) => {
+                ctx.as_context_mut().store.debugger_mut().start(
+                    mode,
+                    base.len_frames() + len_callers,
+                    true,
+                );
+                #[cfg(feature = "profiler")]
+                let profile_depth = self
+                    .inner
+                    .read()
+                    .profile_resume(&mut ctx, &stack, base, &frame);
+                let outcome = self.execute_wasm_func(&mut ctx, &mut stack, &mut frame, base);
+                #[cfg(feature = "profiler")]
+                if outcome.is_err() {
+                    profile_unwind(&mut ctx, profile_depth);
+                }
+                let outcome = outcome.map_err(|error| (error, Some(frame)));
+                self.finish_debug(&mut ctx, &mut stack, func, base, outcome, results)
             }
+            Err(trap) => Err(Error::from(trap)),
         };
-        let results = self.write_results_back(signature, results);
-        Ok(results)
+        restore_stack(&mut ctx, stack);
+        outcome
     }
 
-    /// Initializes the value stack with the given arguments `params`.
-    fn initialize_args<Params>(&mut self, params: Params)
+    /// Finishes a debug execution of `func` given its `outcome`.
+    ///
+    /// # Errors
+    ///
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a host function returns an error.
+    #[cfg(feature = "debugger")]
+    fn finish_debug<C>(
+        &self,
+        mut ctx: C,
+        stack: &mut Stack,
+        func: Func,
+        base: StackBase,
+        outcome: Result<(), (ExecutionError, Option<FunctionFrame>)>,
+        results: &mut [Value],
+    ) -> Result<DebugCall, Error>
     where
-        Params: CallParams,
+        C: AsContextMut,
     {
-        self.value_stack.clear();
-        self.call_stack.clear();
-        for param in params.feed_params() {
-            self.value_stack.push(param);
+        ctx.as_context_mut().store.debugger_mut().stop();
+        match outcome {
+            Ok(()) => {
+                let signature = func.signature(&ctx);
+                self.resolve_func_type(signature, |func_type| {
+                    stack.write_results_back(func_type.results(), base, results)
+                });
+                Ok(DebugCall::Finished)
+            }
+            Err((ExecutionError::Paused(reason), Some(frame))) => {
+                let state = stack.suspend(base, frame);
+                Ok(DebugCall::Paused(PausedInvocation::new(
+                    func, reason, state,
+                )))
+            }
+            Err((error, _frame)) => {
+                stack.reset(base);
+                Err(error.into_error())
+            }
         }
     }
 
-    /// Writes the results of the function execution back into the `results` buffer.
+    /// Executes the given [`Func`] with its parameters already on the value stack.
     ///
     /// # Note
     ///
-    /// The value stack is empty after this operation.
+    /// In case of an error this also returns the Wasm function frame
+    /// that was executing when the error occurred if any.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// - If the `results` buffer length does not match the remaining amount of stack values.
-    fn write_results_back<Results>(
-        &mut self,
-        func_type: DedupFuncType,
-        results: Results,
-    ) -> <Results as CallResults>::Results
+    /// - When encountering a Wasm trap during the execution of `func`.
+    /// - When a called host function returns an error.
+    #[allow(clippy::result_large_err)]
+    fn execute_func_impl<C>(
+        &self,
+        mut ctx: C,
+        stack: &mut Stack,
+        func: Func,
+        base: StackBase,
+    ) -> Result<(), (ExecutionError, Option<FunctionFrame>)>
     where
-        Results: CallResults,
+        C: AsContextMut,
     {
-        let result_types = self.func_types.resolve_func_type(func_type).results();
-        assert_eq!(
-            self.value_stack.len(),
-            results.len_results(),
-            "expected {} values on the stack after function execution but found {}",
-            results.len_results(),
-            self.value_stack.len(),
-        );
-        assert_eq!(results.len_results(), result_types.len());
-        results.feed_results(
-            self.value_stack
-                .drain()
-                .iter()
-                .zip(result_types)
-                .map(|(raw_value, value_type)| raw_value.with_type(*value_type)),
-        )
+        match func.as_internal(&ctx) {
+            FuncEntityInternal::Wasm(wasm_func) => {
+                let mut frame = FunctionFrame::new_wasm(func, wasm_func);
+                if frame.func_body.is_lazy() {
+                    frame.func_body = self.compile_lazy(frame.func_body);
+                }
+                #[cfg(feature = "profiler")]
+                let profile_depth = profile_depth(&mut ctx);
+                #[cfg(feature = "profiler")]
+                self.inner.read().profile_enter(&mut ctx, &frame, false);
+                let outcome = self.execute_wasm_func(&mut ctx, stack, &mut frame, base);
+                #[cfg(feature = "profiler")]
+                if outcome.is_err() {
+                    profile_unwind(&mut ctx, profile_depth);
+                }
+                outcome.map_err(|error| (error, Some(frame)))
+            }
+            FuncEntityInternal::Host(host_func) => {
+                // Note: Host functions called directly from the host side
+                //       are never resumable since there is no Wasm execution
+                //       that could be suspended and therefore no frame.
+                let host_func = host_func.clone();
+                self.execute_host_func(&mut ctx, stack, func, host_func, None)
+                    .map_err(|error| (error, None))
+            }
+        }
     }
 
-    /// Executes the given Wasm [`Func`] using the given arguments `args` and stores the result into `results`.
+    /// Executes the given Wasm `frame` and all of its nested calls until completion.
     ///
     /// # Note
     ///
-    /// The caller is required to ensure that the given `func` actually is a Wasm function.
+    /// - The lock on the [`EngineInner`] is only held while executing a single
+    ///   function frame up to its next call or return. This way the [`Engine`]
+    ///   can compile or free code while Wasm functions are executing and host
+    ///   functions can compile or call back into Wasm.
+    /// - If a host function called from Wasm returns an error the `frame`
+    ///   refers to the Wasm function frame that called the host function.
     ///
     /// # Errors
     ///
-    /// - If the given arguments `args` do not match the expected parameters of `func`.
-    /// - If the given `results` do not match the the length of the expected results of `func`.
-    /// - When encountering a Wasm trap during the execution of `func`.
-    fn execute_wasm_func<C>(&mut self, mut ctx: C, func: Func) -> Result<(), C::Error>
+    /// - When encountering a Wasm trap during the execution.
+    /// - When a called host function returns an error.
+    fn execute_wasm_func<C>(
+        &self,
+        mut ctx: C,
+        stack: &mut Stack,
+        frame: &mut FunctionFrame,
+        base: StackBase,
+    ) -> Result<(), ExecutionError>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
     {
-        let mut function_frame = FunctionFrame::new(&ctx, func);
-        'outer: loop {
-            match self
-                .execute_frame(&mut ctx, &mut function_frame)
-                .map_err(Into::into)?
-            {
-                CallOutcome::Return => match self.call_stack.pop() {
-                    Some(frame) => {
-                        function_frame = frame;
-                        continue 'outer;
+        loop {
+            let outcome = {
+                let inner = self.inner.read();
+                match inner.execute_frame(&mut ctx, stack, frame) {
+                    Ok(outcome) => outcome,
+                    Err(trap) => {
+                        // The `pc` of the executed frame refers to the trapping instruction.
+                        let pc = frame.pc();
+                        return Err(inner.trap_with_backtrace(&ctx, stack, frame, pc, trap));
                     }
-                    None => return Ok(()),
-                },
-                CallOutcome::NestedCall(func) => match func.as_internal(&ctx) {
-                    FuncEntityInternal::Wasm(wasm_func) => {
-                        let nested_frame = FunctionFrame::new_wasm(func, wasm_func);
-                        self.call_stack.push(function_frame).map_err(Into::into)?;
-                        function_frame = nested_frame;
+                }
+            };
+            match outcome {
+                CallOutcome::Return => {
+                    #[cfg(feature = "profiler")]
+                    profile_exit(&mut ctx);
+                    if stack.frames.len() == base.len_frames() {
+                        return Ok(());
                     }
-                    FuncEntityInternal::Host(host_func) => {
-                        let instance = function_frame.instance();
-                        let host_func = host_func.clone();
-                        self.execute_host_func(&mut ctx, host_func, Some(instance))?;
+                    *frame = stack.frames.pop().unwrap_or_else(|| {
+                        panic!("encountered missing function frame on the call stack")
+                    });
+                }
+                #[cfg(feature = "debugger")]
+                CallOutcome::Pause(reason) => return Err(ExecutionError::Paused(reason)),
+                CallOutcome::NestedCall(func) | CallOutcome::TailCall(func) => {
+                    match func.as_internal(&ctx) {
+                        FuncEntityInternal::Wasm(wasm_func) => {
+                            let mut nested_frame = FunctionFrame::new_wasm(func, wasm_func);
+                            if nested_frame.func_body.is_lazy() {
+                                nested_frame.func_body = self.compile_lazy(nested_frame.func_body);
+                            }
+                            let inner = self.inner.read();
+                            if let CallOutcome::TailCall(_) = outcome {
+                                // The tail called frame replaces the calling frame so that
+                                // tail calls do not grow the call stack. Therefore its
+                                // parameters are moved to the start of the calling frame.
+                                let len_params =
+                                    inner.code_map.resolve(nested_frame.func_body).len_params();
+                                let len_values = stack.values.len() - frame.base();
+                                stack
+                                    .values
+                                    .drop_keep(DropKeep::new(len_values - len_params, len_params));
+                                #[cfg(feature = "profiler")]
+                                profile_exit(&mut ctx);
+                            } else if let Err(trap) = stack.frames.push(*frame) {
+                                // The `pc` of the calling frame refers to the instruction
+                                // following the call instruction.
+                                let pc = frame.pc() - 1;
+                                return Err(inner.trap_with_backtrace(&ctx, stack, frame, pc, trap));
+                            }
+                            *frame = nested_frame;
+                            #[cfg(feature = "profiler")]
+                            inner.profile_enter(&mut ctx, frame, false);
+                        }
+                        FuncEntityInternal::Host(host_func) => {
+                            // Note: Tail called host functions are called the same as
+                            //       called host functions. Their results are returned by
+                            //       the instruction following the tail call instruction.
+                            let instance = frame.instance();
+                            let host_func = host_func.clone();
+                            // The calling frame stays on the call stack during the host
+                            // function call so that nested executions respect the
+                            // recursion limit.
+                            if let Err(trap) = stack.frames.push(*frame) {
+                                let pc = frame.pc() - 1;
+                                return Err(self
+                                    .inner
+                                    .read()
+                                    .trap_with_backtrace(&ctx, stack, frame, pc, trap));
+                            }
+                            let outcome = self.execute_host_func(
+                                &mut ctx,
+                                stack,
+                                func,
+                                host_func,
+                                Some(instance),
+                            );
+                            *frame = stack.frames.pop().unwrap_or_else(|| {
+                                panic!("encountered missing function frame on the call stack")
+                            });
+                            if let Err(error) = outcome {
+                                // Traps of host functions are attributed to the calling frame.
+                                let pc = frame.pc() - 1;
+                                return Err(error.with_backtrace(|| {
+                                    self.inner.read().backtrace(&ctx, stack, frame, pc)
+                                }));
+                            }
+                        }
                     }
-                },
+                }
             }
         }
     }
 
-    /// Executes the given function frame and returns the outcome.
+    /// Executes the given host function.
     ///
-    /// # Errors
+    /// # Note
     ///
-    /// If the function frame execution trapped.
-    #[inline(always)]
-    fn execute_frame(
-        &mut self,
-        mut ctx: impl AsContextMut,
-        frame: &mut FunctionFrame,
-    ) -> Result<CallOutcome, TrapCode> {
-        FunctionExecutor::new(self, frame)?.execute_frame(&mut ctx)
-    }
-
-    /// Executes the given host function.
+    /// - The parameters of the host function call are the last values on the
+    ///   value stack and are replaced by its results after the call.
+    /// - The `stack` is moved back into the [`Store`] during the call so that
+    ///   the host function can call back into Wasm using the same [`Stack`].
     ///
     /// # Errors
     ///
     /// - If the host function returns a host side error or trap.
     /// - If the value stack overflowed upon pushing parameters or results.
+    /// - If fuel metering is enabled and there is not enough fuel for the call.
+    ///
+    /// [`Store`]: [`crate::Store`]
     #[inline(never)]
     fn execute_host_func<C>(
-        &mut self,
+        &self,
         mut ctx: C,
-        host_func: HostFuncEntity<<C as AsContext>::UserState, <C as AsContext>::Error>,
+        stack: &mut Stack,
+        func: Func,
+        host_func: HostFuncEntity<<C as AsContext>::UserState>,
         instance: Option<Instance>,
-    ) -> Result<(), C::Error>
+    ) -> Result<(), ExecutionError>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
     {
-        // The host function signature is required for properly
-        // adjusting, inspecting and manipulating the value stack.
-        let (input_types, output_types) = self
-            .func_types
-            .resolve_func_type(host_func.signature())
-            .params_results();
-        // In case the host function returns more values than it takes
-        // we are required to extend the value stack.
-        let len_inputs = input_types.len();
-        let len_outputs = output_types.len();
-        let max_inout = cmp::max(len_inputs, len_outputs);
-        self.value_stack.reserve(max_inout).map_err(Into::into)?;
-        if len_outputs > len_inputs {
-            let delta = len_outputs - len_inputs;
-            self.value_stack.extend_zeros(delta).map_err(Into::into)?;
+        let (len_inputs, len_outputs, fuel_cost) = {
+            let inner = self.inner.read();
+            let (input_types, output_types) = inner
+                .func_types
+                .resolve_func_type(host_func.signature())
+                .params_results();
+            let fuel_cost = inner
+                .config
+                .metered_fuel_costs()
+                .map(FuelCosts::host_call_cost);
+            (input_types.len(), output_types.len(), fuel_cost)
+        };
+        if let Some(fuel_cost) = fuel_cost {
+            ctx.as_context_mut()
+                .store
+                .fuel_mut()
+                .consume(fuel_cost)
+                .map_err(ExecutionError::trap)?;
         }
-        let params_results = FuncParams::new(
-            self.value_stack.peek_as_slice_mut(max_inout),
-            len_inputs,
-            len_outputs,
-        );
-        // Now we are ready to perform the host function call.
-        // Note: We need to clone the host function due to some borrowing issues.
-        //       This should not be a big deal since host functions usually are cheap to clone.
-        host_func.call(ctx.as_context_mut(), instance, params_results)?;
-        // If the host functions returns fewer results than it receives parameters
-        // the value stack needs to be shrinked for the delta.
-        if len_outputs < len_inputs {
-            let delta = len_inputs - len_outputs;
-            self.value_stack.drop(delta);
+        // The host function parameters are copied into a separate buffer
+        // so that the stack can be moved back into the store during the call.
+        let mut params_results = stack.take_host_buffer(len_inputs, len_outputs);
+        let params = FuncParams::new(&mut params_results, len_inputs, len_outputs);
+        restore_stack(&mut ctx, mem::replace(stack, Stack::empty()));
+        // Executions of the host function calling back into Wasm do not pause.
+        #[cfg(feature = "debugger")]
+        let debugging = ctx.as_context_mut().store.debugger_mut().stop();
+        #[cfg(feature = "profiler")]
+        ctx.as_context_mut().store.profile_enter(func, None, false);
+        let outcome = host_func.call(&mut ctx, instance, params);
+        #[cfg(feature = "profiler")]
+        profile_exit(&mut ctx);
+        #[cfg(feature = "debugger")]
+        ctx.as_context_mut().store.debugger_mut().restore(debugging);
+        *stack = take_stack(&mut ctx);
+        let result = outcome
+            .map_err(|error| ExecutionError::Host {
+                host_func: func,
+                error,
+            })
+            .and_then(|_| {
+                // At this point the host function has been called and has directly
+                // written its results into the buffer so that we can replace the
+                // parameters on the value stack with the results of the call.
+                stack.values.drop(len_inputs);
+                stack
+                    .values
+                    .extend_from_slice(&params_results[..len_outputs])
+                    .map_err(ExecutionError::trap)
+            });
+        stack.return_host_buffer(params_results);
+        result
+    }
+}
+
+/// Takes the [`Stack`] out of the [`Store`] of `ctx` for an execution.
+///
+/// # Note
+///
+/// The [`Stack`] must be put back via [`restore_stack`] after the execution.
+///
+/// [`Store`]: [`crate::Store`]
+fn take_stack(mut ctx: impl AsContextMut) -> Stack {
+    mem::replace(ctx.as_context_mut().store.stack_mut(), Stack::empty())
+}
+
+/// Puts the [`Stack`] back into the [`Store`] of `ctx` after an execution.
+///
+/// [`Store`]: [`crate::Store`]
+fn restore_stack(mut ctx: impl AsContextMut, stack: Stack) {
+    *ctx.as_context_mut().store.stack_mut() = stack;
+}
+
+/// Returns the number of active profiled calls within the [`Store`] of `ctx` if profiling is enabled.
+///
+/// [`Store`]: [`crate::Store`]
+#[cfg(feature = "profiler")]
+fn profile_depth(mut ctx: impl AsContextMut) -> Option<usize> {
+    ctx.as_context_mut()
+        .store
+        .profiler_mut()
+        .map(|profiler| profiler.depth())
+}
+
+/// Exits the innermost active profiled call within the [`Store`] of `ctx` if profiling is enabled.
+///
+/// [`Store`]: [`crate::Store`]
+#[cfg(feature = "profiler")]
+fn profile_exit(mut ctx: impl AsContextMut) {
+    if let Some(profiler) = ctx.as_context_mut().store.profiler_mut() {
+        profiler.exit();
+    }
+}
+
+/// Exits the active profiled calls within the [`Store`] of `ctx` down to the given `depth`.
+///
+/// # Note
+///
+/// This is required if a Wasm execution fails or is suspended.
+/// The `depth` is `None` if profiling was disabled when the execution started.
+///
+/// [`Store`]: [`crate::Store`]
+#[cfg(feature = "profiler")]
+fn profile_unwind(mut ctx: impl AsContextMut, depth: Option<usize>) {
+    if let (Some(profiler), Some(depth)) = (ctx.as_context_mut().store.profiler_mut(), depth) {
+        profiler.unwind(depth);
+    }
+}
+
+/// The internal state of the `wasmi` engine.
+#[derive(Debug)]
+pub struct EngineInner {
+    /// The configuration with which the [`Engine`] has been created.
+    config: Config,
+    /// Stores all Wasm function bodies that the interpreter is aware of.
+    code_map: CodeMap,
+    /// Deduplicated function types.
+    ///
+    /// # Note
+    ///
+    /// The engine deduplicates function types to make the equality
+    /// comparison very fast. This helps to speed up indirect calls.
+    func_types: FuncTypeRegistry,
+}
+
+impl EngineInner {
+    /// Creates a new [`EngineInner`] with the given [`Config`].
+    pub fn new(config: &Config) -> Self {
+        let engine_idx = EngineIdx::new();
+        Self {
+            config: *config,
+            code_map: CodeMap::default(),
+            func_types: FuncTypeRegistry::new(engine_idx),
+        }
+    }
+
+    /// Returns a shared reference to the [`Config`] of the [`Engine`].
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Allocates the translated Wasm function body to the [`Engine`].
+    ///
+    /// Returns a [`FuncBody`] reference to the allocated function body.
+    pub fn alloc_func_body(&mut self, func_body: TranslatedFuncBody) -> FuncBody {
+        self.code_map.alloc(func_body)
+    }
+
+    /// Creates an [`ExecutionError`] for the `trap` with a [`WasmBacktrace`].
+    ///
+    /// See [`EngineInner::backtrace`] for more information.
+    fn trap_with_backtrace(
+        &self,
+        ctx: impl AsContext,
+        stack: &Stack,
+        frame: &FunctionFrame,
+        pc: usize,
+        trap: TrapCode,
+    ) -> ExecutionError {
+        ExecutionError::trap(trap).with_backtrace(|| self.backtrace(ctx, stack, frame, pc))
+    }
+
+    /// Creates the [`WasmBacktrace`] of the executed `frame` at the given `pc`.
+    ///
+    /// # Note
+    ///
+    /// The [`WasmBacktrace`] consists of the executed `frame` at the given
+    /// `pc` followed by all function frames on the call stack including
+    /// those of enclosing executions of host functions calling into Wasm.
+    fn backtrace(
+        &self,
+        ctx: impl AsContext,
+        stack: &Stack,
+        frame: &FunctionFrame,
+        pc: usize,
+    ) -> WasmBacktrace {
+        let store = ctx.as_context().store;
+        let frame_info = |frame: &FunctionFrame, pc: usize| {
+            let instance = frame.instance();
+            let names = store.resolve_instance(instance).names();
+            let func_index = self.code_map.func_index(frame.func_body);
+            FrameInfo::new(
+                instance,
+                names.module().cloned(),
+                func_index,
+                names.func(func_index).cloned(),
+                self.code_map.wasm_offset(frame.func_body, pc),
+            )
+        };
+        // The frames on the call stack have already executed their call
+        // instruction and thus their `pc` refers to the following instruction.
+        let frames = iter::once(frame_info(frame, pc))
+            .chain(
+                stack
+                    .frames
+                    .iter()
+                    .rev()
+                    .map(|frame| frame_info(frame, frame.pc() - 1)),
+            )
+            .collect();
+        WasmBacktrace::new(frames)
+    }
+
+    /// Enters a profiled call of the Wasm function of `frame` if profiling is enabled.
+    ///
+    /// If `resumed` is `true` the call is re-entered after its execution has been suspended.
+    #[cfg(feature = "profiler")]
+    fn profile_enter(&self, mut ctx: impl AsContextMut, frame: &FunctionFrame, resumed: bool) {
+        let store = ctx.as_context_mut().store;
+        if store.profiler_mut().is_some() {
+            let func_index = self.code_map.func_index(frame.func_body);
+            store.profile_enter(frame.func, Some((frame.instance(), func_index)), resumed);
+        }
+    }
+
+    /// Re-enters the profiled calls of a resumed execution if profiling is enabled.
+    ///
+    /// The resumed function frames are the frames on the call stack above the
+    /// [`StackBase`] followed by the `frame` that is executed next.
+    ///
+    /// Returns the number of active profiled calls before re-entering.
+    #[cfg(feature = "profiler")]
+    fn profile_resume(
+        &self,
+        mut ctx: impl AsContextMut,
+        stack: &Stack,
+        base: StackBase,
+        frame: &FunctionFrame,
+    ) -> Option<usize> {
+        let depth = profile_depth(&mut ctx);
+        for frame in stack.frames.iter().skip(base.len_frames()).chain([frame]) {
+            self.profile_enter(&mut ctx, frame, true);
+        }
+        depth
+    }
+
+    /// Executes the given function frame and returns the outcome.
+    ///
+    /// # Errors
+    ///
+    /// If the function frame execution trapped.
+    #[inline(always)]
+    fn execute_frame(
+        &self,
+        mut ctx: impl AsContextMut,
+        stack: &mut Stack,
+        frame: &mut FunctionFrame,
+    ) -> Result<CallOutcome, TrapCode> {
+        #[cfg(feature = "debugger")]
+        ctx.as_context_mut()
+            .store
+            .debugger_mut()
+            .enter_frame(stack.frames.len());
+        #[cfg(feature = "coverage")]
+        if !frame.instantiated {
+            if let Some(coverage) = ctx.as_context_mut().store.coverage_mut() {
+                coverage.enter(frame.func_body);
+            }
         }
-        // At this point the host function has been called and has directly
-        // written its results into the value stack so that the last entries
-        // in the value stack are the result values of the host function call.
-        Ok(())
+        FunctionExecutor::new(self, &mut stack.values, frame)?.execute_frame(&mut ctx)
     }
 }
@@ -0,0 +1,52 @@
+//! Ownership of the function bodies and function types of Wasm modules.
+
+use super::{DedupFuncType, Engine, FuncBody};
+use crate::FuncType;
+use alloc::vec::Vec;
+
+/// The function bodies and deduplicated function types allocated to an [`Engine`] for a Wasm module.
+///
+/// # Note
+///
+/// This is shared by the Wasm module and all of its instances.
+/// The function bodies and function types are freed from the [`Engine`]
+/// once the Wasm module and all of its instances have been dropped.
+#[derive(Debug)]
+pub struct ModuleCode {
+    /// The [`Engine`] that the function bodies and function types are allocated to.
+    engine: Engine,
+    /// The allocated deduplicated function types.
+    func_types: Vec<DedupFuncType>,
+    /// The allocated function bodies.
+    func_bodies: Vec<FuncBody>,
+}
+
+impl ModuleCode {
+    /// Creates a new empty [`ModuleCode`] for the `engine`.
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            func_types: Vec::new(),
+            func_bodies: Vec::new(),
+        }
+    }
+
+    /// Allocates the function type to the [`Engine`] and takes ownership of the allocation.
+    pub fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
+        let func_type = self.engine.alloc_func_type(func_type);
+        self.func_types.push(func_type);
+        func_type
+    }
+
+    /// Takes ownership of the function body allocated to the [`Engine`].
+    pub fn push_func_body(&mut self, func_body: FuncBody) {
+        self.func_bodies.push(func_body);
+    }
+}
+
+impl Drop for ModuleCode {
+    fn drop(&mut self) {
+        self.engine
+            .free_module_code(&self.func_types, &self.func_bodies);
+    }
+}
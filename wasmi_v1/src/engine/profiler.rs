@@ -0,0 +1,491 @@
+//! Call and instruction profiling of Wasm and host functions.
+
+use crate::{
+    arena::{GenerationalIdx, Index},
+    func::FuncIdx,
+};
+use std::{
+    collections::BTreeMap,
+    format,
+    io,
+    string::String,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+/// The profile of a single Wasm or host function.
+#[derive(Debug, Clone)]
+pub struct FuncProfile {
+    /// The name of the profiled function.
+    name: String,
+    /// The number of calls to the function.
+    calls: u64,
+    /// The number of `wasmi` bytecode instructions executed by the function.
+    instructions: u64,
+    /// The time spent in the function including its callees.
+    inclusive: Duration,
+    /// The time spent in the function excluding its callees.
+    exclusive: Duration,
+}
+
+impl FuncProfile {
+    /// Creates a new empty [`FuncProfile`] for the function with the given `name`.
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            calls: 0,
+            instructions: 0,
+            inclusive: Duration::ZERO,
+            exclusive: Duration::ZERO,
+        }
+    }
+
+    /// Returns the name of the profiled function.
+    ///
+    /// # Note
+    ///
+    /// - Wasm functions are named `module::func` using the Wasm name section
+    ///   if available and `func[index]` otherwise.
+    /// - Host functions are named `host[index]` using their index within the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of calls to the function.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the number of `wasmi` bytecode instructions executed by the function.
+    ///
+    /// # Note
+    ///
+    /// This is always zero for host functions.
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Returns the time spent in the function including the time spent in its callees.
+    ///
+    /// # Note
+    ///
+    /// Time spent in recursive calls of the function is only counted once.
+    pub fn inclusive_time(&self) -> Duration {
+        self.inclusive
+    }
+
+    /// Returns the time spent in the function excluding the time spent in its callees.
+    pub fn exclusive_time(&self) -> Duration {
+        self.exclusive
+    }
+}
+
+/// The profile of all functions executed while profiling was enabled for a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The profiles of all executed functions in the order of their first call.
+    funcs: Vec<FuncProfile>,
+    /// The exclusive time of all distinct call stacks.
+    ///
+    /// # Note
+    ///
+    /// A call stack is given as indices into `funcs` starting at the outermost call.
+    stacks: Vec<(Vec<usize>, Duration)>,
+}
+
+impl Profile {
+    /// Returns the profiles of all executed functions in the order of their first call.
+    pub fn funcs(&self) -> &[FuncProfile] {
+        &self.funcs
+    }
+
+    /// Writes the exclusive time of every executed call stack in folded stacks format.
+    ///
+    /// # Note
+    ///
+    /// Every line consists of the function names of a call stack starting at the outermost
+    /// call separated by `;` followed by the exclusive time of the innermost call in nanoseconds.
+    /// This format is understood by flamegraph tools such as `flamegraph.pl` or `inferno`.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the `writer` fails.
+    pub fn write_folded(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for (stack, time) in &self.stacks {
+            let mut names = stack
+                .iter()
+                .map(|&func| folded_name(&self.funcs[func].name));
+            if let Some(first) = names.next() {
+                write!(writer, "{first}")?;
+            }
+            for name in names {
+                write!(writer, ";{name}")?;
+            }
+            writeln!(writer, " {}", time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name of a profiled function.
+///
+/// Wasm functions are identified by their `module` name, their `func_index` and their
+/// `name` given by the Wasm name section while host functions are identified by their
+/// index within the [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub(crate) fn func_name(
+    idx: GenerationalIdx<FuncIdx>,
+    wasm: Option<(Option<&str>, u32, Option<&str>)>,
+) -> String {
+    let (module, func_index, name) = match wasm {
+        Some(wasm) => wasm,
+        None => return format!("host[{}]", idx.index().into_usize()),
+    };
+    let name = match name {
+        Some(name) => String::from(name),
+        None => format!("func[{func_index}]"),
+    };
+    match module {
+        Some(module) => format!("{module}::{name}"),
+        None => name,
+    }
+}
+
+/// Returns the function `name` with characters that are reserved by the folded stacks format replaced.
+fn folded_name(name: &str) -> String {
+    name.replace(|c: char| c == ';' || c.is_whitespace(), "_")
+}
+
+/// A node of the call tree recorded by the [`Profiler`].
+#[derive(Debug, Default)]
+struct CallNode {
+    /// The calls made from this node mapped from the called function to their node.
+    callees: BTreeMap<usize, usize>,
+    /// The time spent in this node excluding its callees.
+    exclusive: Duration,
+}
+
+/// An active function call recorded by the [`Profiler`].
+#[derive(Debug)]
+struct Activation {
+    /// The index of the called function within the [`Profiler`].
+    func: usize,
+    /// The call tree node of the call.
+    node: usize,
+    /// The point in time at which the call has been entered.
+    start: Instant,
+    /// The time spent in callees of the call so far.
+    callees: Duration,
+}
+
+/// Records calls, executed instructions and timings of the functions executed within a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+#[derive(Debug)]
+pub(crate) struct Profiler {
+    /// The index of every profiled function mapped from its index within the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    indices: BTreeMap<GenerationalIdx<FuncIdx>, usize>,
+    /// The profiles of all profiled functions.
+    funcs: Vec<FuncProfile>,
+    /// The number of active calls of every profiled function.
+    active: Vec<usize>,
+    /// The call tree of all recorded calls.
+    ///
+    /// # Note
+    ///
+    /// The first node is the root of the call tree and does not refer to a function.
+    nodes: Vec<CallNode>,
+    /// The stack of active function calls.
+    stack: Vec<Activation>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            indices: BTreeMap::new(),
+            funcs: Vec::new(),
+            active: Vec::new(),
+            nodes: Vec::from([CallNode::default()]),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl Profiler {
+    /// The index of the root node of the call tree.
+    const ROOT: usize = 0;
+
+    /// Returns `true` if the function at `idx` has already been profiled.
+    pub fn contains(&self, idx: GenerationalIdx<FuncIdx>) -> bool {
+        self.indices.contains_key(&idx)
+    }
+
+    /// Registers the function at `idx` with the given `name` for profiling.
+    pub fn register(&mut self, idx: GenerationalIdx<FuncIdx>, name: String) {
+        let func = self.funcs.len();
+        self.indices.insert(idx, func);
+        self.funcs.push(FuncProfile::new(name));
+        self.active.push(0);
+    }
+
+    /// Returns the number of active function calls.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Enters a call to the registered function at `idx`.
+    ///
+    /// # Note
+    ///
+    /// If `resumed` is `true` the call has already been entered before the
+    /// execution has been suspended and thus is not counted again.
+    pub fn enter(&mut self, idx: GenerationalIdx<FuncIdx>, resumed: bool) {
+        let func = self.indices[&idx];
+        let parent = self
+            .stack
+            .last()
+            .map(|activation| activation.node)
+            .unwrap_or(Self::ROOT);
+        let next_node = self.nodes.len();
+        let node = *self.nodes[parent].callees.entry(func).or_insert(next_node);
+        if node == next_node {
+            self.nodes.push(CallNode::default());
+        }
+        if !resumed {
+            self.funcs[func].calls += 1;
+        }
+        self.active[func] += 1;
+        self.stack.push(Activation {
+            func,
+            node,
+            start: Instant::now(),
+            callees: Duration::ZERO,
+        });
+    }
+
+    /// Exits the innermost active function call if any.
+    pub fn exit(&mut self) {
+        let activation = match self.stack.pop() {
+            Some(activation) => activation,
+            None => return,
+        };
+        let elapsed = activation.start.elapsed();
+        let exclusive = elapsed.saturating_sub(activation.callees);
+        let func = activation.func;
+        self.funcs[func].exclusive += exclusive;
+        self.nodes[activation.node].exclusive += exclusive;
+        self.active[func] -= 1;
+        if self.active[func] == 0 {
+            self.funcs[func].inclusive += elapsed;
+        }
+        if let Some(caller) = self.stack.last_mut() {
+            caller.callees += elapsed;
+        }
+    }
+
+    /// Exits active function calls until only `depth` active calls remain.
+    ///
+    /// # Note
+    ///
+    /// This is used when an execution traps or is suspended.
+    pub fn unwind(&mut self, depth: usize) {
+        while self.stack.len() > depth {
+            self.exit();
+        }
+    }
+
+    /// Counts an executed `wasmi` bytecode instruction for the innermost active function call.
+    #[inline]
+    pub fn count_instruction(&mut self) {
+        if let Some(activation) = self.stack.last() {
+            self.funcs[activation.func].instructions += 1;
+        }
+    }
+
+    /// Returns the [`Profile`] of all function calls recorded so far.
+    ///
+    /// # Note
+    ///
+    /// Active function calls are not included.
+    pub fn profile(&self) -> Profile {
+        let mut stacks = Vec::new();
+        let mut path = Vec::new();
+        self.collect_stacks(Self::ROOT, &mut path, &mut stacks);
+        Profile {
+            funcs: self.funcs.clone(),
+            stacks,
+        }
+    }
+
+    /// Collects the call stacks of all callees of the call tree `node` at the given `path`.
+    fn collect_stacks(
+        &self,
+        node: usize,
+        path: &mut Vec<usize>,
+        stacks: &mut Vec<(Vec<usize>, Duration)>,
+    ) {
+        for (&func, &callee) in &self.nodes[node].callees {
+            path.push(func);
+            stacks.push((path.clone(), self.nodes[callee].exclusive));
+            self.collect_stacks(callee, path, stacks);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FuncProfile, Profile};
+    use crate::{core::Value, Engine, Extern, Func, Linker, Module, Store};
+    use std::{string::String, vec::Vec};
+
+    const WAT: &str = r#"
+        (module $test
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func $run (export "run") (param $n i32) (result i32)
+                (call $host (call $fac (local.get $n)))
+            )
+            (func $fac (param $n i32) (result i32)
+                (if (result i32) (i32.eqz (local.get $n))
+                    (then (i32.const 1))
+                    (else
+                        (i32.mul
+                            (local.get $n)
+                            (call $fac (i32.sub (local.get $n) (i32.const 1)))
+                        )
+                    )
+                )
+            )
+            (func (export "trap") (result i32)
+                (call $fac (i32.const 2))
+                (unreachable)
+            )
+        )
+    "#;
+
+    /// Instantiates the [`WAT`] module and returns the exported function getter.
+    fn setup() -> (Store<()>, impl Fn(&Store<()>, &str) -> Func) {
+        let wasm = wat::parse_str(WAT).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let host = Func::wrap(&mut store, |value: i32| value + 1);
+        let mut linker = <Linker<()>>::new();
+        linker.define("env", "host", host).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let get = move |store: &Store<()>, name: &str| {
+            instance
+                .get_export(store, name)
+                .and_then(Extern::into_func)
+                .unwrap()
+        };
+        (store, get)
+    }
+
+    /// Returns the [`FuncProfile`] of the function with the given `name`.
+    fn func<'a>(profile: &'a Profile, name: &str) -> &'a FuncProfile {
+        profile
+            .funcs()
+            .iter()
+            .find(|func| func.name() == name)
+            .unwrap_or_else(|| panic!("missing profile of {name}"))
+    }
+
+    /// Returns the call stacks of the `profile` in folded stacks format without their times.
+    fn folded_stacks(profile: &Profile) -> Vec<String> {
+        let mut folded = Vec::new();
+        profile.write_folded(&mut folded).unwrap();
+        String::from_utf8(folded)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let (stack, time) = line.rsplit_once(' ').unwrap();
+                time.parse::<u128>().unwrap();
+                String::from(stack)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn profiler_is_disabled_by_default() {
+        let (mut store, get) = setup();
+        let run = get(&store, "run");
+        let mut results = [Value::I32(0)];
+        run.call(&mut store, &[Value::I32(3)], &mut results)
+            .unwrap();
+        assert!(store.profile().is_none());
+        store.enable_profiler();
+        assert!(store.profile().unwrap().funcs().is_empty());
+        store.disable_profiler();
+        assert!(store.profile().is_none());
+    }
+
+    #[test]
+    fn profiler_counts_calls_and_instructions() {
+        let (mut store, get) = setup();
+        let run = get(&store, "run");
+        store.enable_profiler();
+        let mut results = [Value::I32(0)];
+        run.call(&mut store, &[Value::I32(3)], &mut results)
+            .unwrap();
+        assert_eq!(results, [Value::I32(7)]);
+        let profile = store.profile().unwrap();
+        assert_eq!(profile.funcs().len(), 3);
+        let run = func(&profile, "test::run");
+        let fac = func(&profile, "test::fac");
+        let host = &profile.funcs()[2];
+        assert!(host.name().starts_with("host["));
+        assert_eq!((run.calls(), fac.calls(), host.calls()), (1, 4, 1));
+        assert!(run.instructions() > 0);
+        assert!(fac.instructions() > run.instructions());
+        assert_eq!(host.instructions(), 0);
+        // Recursive calls are only counted once for the inclusive time.
+        assert!(run.inclusive_time() >= fac.inclusive_time() + host.inclusive_time());
+        for func in profile.funcs() {
+            assert!(func.inclusive_time() >= func.exclusive_time());
+        }
+        assert_eq!(
+            folded_stacks(&profile),
+            [
+                "test::run",
+                "test::run;test::fac",
+                "test::run;test::fac;test::fac",
+                "test::run;test::fac;test::fac;test::fac",
+                "test::run;test::fac;test::fac;test::fac;test::fac",
+                "test::run;host[0]",
+            ]
+        );
+    }
+
+    #[test]
+    fn profiler_unwinds_traps() {
+        let (mut store, get) = setup();
+        let run = get(&store, "run");
+        let trap = get(&store, "trap");
+        store.enable_profiler();
+        let mut results = [Value::I32(0)];
+        trap.call(&mut store, &[], &mut results).unwrap_err();
+        run.call(&mut store, &[Value::I32(0)], &mut results)
+            .unwrap();
+        let profile = store.profile().unwrap();
+        assert_eq!(func(&profile, "test::func[3]").calls(), 1);
+        assert_eq!(func(&profile, "test::fac").calls(), 4);
+        // The call stack of `run` does not start within the trapped `trap`.
+        let stacks = folded_stacks(&profile);
+        assert!(stacks.iter().any(|stack| stack == "test::run;test::fac"));
+        assert!(stacks
+            .iter()
+            .all(|stack| !stack.starts_with("test::func[3];test::run")));
+    }
+}
@@ -0,0 +1,213 @@
+use super::FunctionFrame;
+use crate::{
+    core::{TrapCode, UntypedValue, Value},
+    errors::FuncError,
+    func::cancel_pending_calls,
+    AsContext,
+    AsContextMut,
+    Error,
+    Func,
+    Trap,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Returned by calling a [`Func`] in a resumable way.
+///
+/// See [`Func::call_resumable`] for more information.
+#[derive(Debug)]
+pub enum ResumableCall {
+    /// The resumable call has finished properly and returned its results.
+    Finished,
+    /// The resumable call was suspended by a host function returning a resumable error.
+    ///
+    /// The suspended call can be resumed via [`ResumableInvocation::resume`].
+    Resumable(ResumableInvocation),
+}
+
+/// State required to resume a suspended [`Func`] invocation.
+///
+/// # Note
+///
+/// This holds the suspended parts of the value and call stacks of the invocation
+/// so that the [`Engine`] can be used for other executions in the meantime.
+///
+/// [`Engine`]: [`crate::Engine`]
+pub struct ResumableInvocation {
+    /// The [`Func`] that was originally called.
+    func: Func,
+    /// The host [`Func`] that returned the resumable error.
+    host_func: Func,
+    /// The resumable [`Trap`] returned by the host function.
+    host_error: Trap,
+    /// The suspended state of the invocation.
+    state: SuspendedState,
+}
+
+/// The suspended execution state of a [`ResumableInvocation`].
+#[derive(Debug)]
+pub(super) struct SuspendedState {
+    /// The values of the suspended invocation on the value stack.
+    ///
+    /// # Note
+    ///
+    /// The parameters of the host function call are the last values.
+    pub values: Vec<UntypedValue>,
+    /// The function frames of the suspended invocation on the call stack.
+    pub frames: Vec<FunctionFrame>,
+    /// The Wasm function frame that called the host function.
+    pub frame: FunctionFrame,
+}
+
+impl SuspendedState {
+    /// Ensures that the instances of all suspended function frames have not been dropped.
+    ///
+    /// # Errors
+    ///
+    /// If the instance of any suspended function frame has been dropped
+    /// from the [`Store`] while the invocation was suspended.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn ensure_alive(&self, ctx: impl AsContext) -> Result<(), TrapCode> {
+        let store = ctx.as_context().store;
+        let alive = self
+            .frames
+            .iter()
+            .chain(Some(&self.frame))
+            .all(|frame| store.contains_instance(frame.instance()));
+        match alive {
+            true => Ok(()),
+            false => Err(TrapCode::DroppedInstance),
+        }
+    }
+}
+
+impl fmt::Debug for ResumableInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableInvocation")
+            .field("func", &self.func)
+            .field("host_func", &self.host_func)
+            .field("host_error", &self.host_error)
+            .finish()
+    }
+}
+
+impl ResumableInvocation {
+    /// Creates a new [`ResumableInvocation`].
+    pub(super) fn new(
+        func: Func,
+        host_func: Func,
+        host_error: Trap,
+        state: SuspendedState,
+    ) -> Self {
+        Self {
+            func,
+            host_func,
+            host_error,
+            state,
+        }
+    }
+
+    /// Returns the [`Func`] that was originally called.
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the host [`Func`] that returned the resumable error.
+    ///
+    /// # Note
+    ///
+    /// The results of this host function must be provided upon resumption.
+    pub fn host_func(&self) -> Func {
+        self.host_func
+    }
+
+    /// Returns a shared reference to the resumable [`Trap`] returned by the host function.
+    pub fn host_error(&self) -> &Trap {
+        &self.host_error
+    }
+
+    /// Consumes `self` and returns the resumable [`Trap`] returned by the host function.
+    ///
+    /// # Note
+    ///
+    /// This drops the suspended invocation which can no longer be resumed.
+    pub fn into_host_error(self) -> Trap {
+        self.host_error
+    }
+
+    /// Returns the suspended state of the [`ResumableInvocation`].
+    pub(super) fn into_state(self) -> SuspendedState {
+        self.state
+    }
+
+    /// Resumes the suspended invocation.
+    ///
+    /// The `inputs` are the results of the host function that returned the
+    /// resumable error and must match its result types.
+    /// The results of the original [`Func`] are written into the `outputs`
+    /// buffer once the invocation has finished.
+    ///
+    /// # Errors
+    ///
+    /// - If the types of the `inputs` do not match the result types of the
+    ///   host function that suspended the invocation.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of the original [`Func`].
+    /// - If the resumed execution traps or a host function returns an error
+    ///   that is not resumable.
+    /// - If an instance of the suspended invocation has been dropped from the
+    ///   [`Store`] in the meantime.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn resume<C>(
+        self,
+        ctx: C,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error>
+    where
+        C: AsContextMut,
+    {
+        cancel_pending_calls(self.resume_impl(ctx, inputs, outputs))
+    }
+
+    /// Implementation of [`ResumableInvocation::resume`] that keeps
+    /// pending calls of asynchronous host functions resumable.
+    ///
+    /// # Note
+    ///
+    /// This is used by [`Func::call_async`] to await asynchronous host functions.
+    ///
+    /// [`Func::call_async`]: [`crate::Func::call_async`]
+    pub(crate) fn resume_impl<C>(
+        self,
+        mut ctx: C,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error>
+    where
+        C: AsContextMut,
+    {
+        self.state.ensure_alive(&ctx)?;
+        let host_func_type = self.host_func.func_type(&ctx);
+        let actual_inputs = inputs.iter().map(|value| value.value_type());
+        if host_func_type.results().iter().copied().ne(actual_inputs) {
+            return Err(Error::Func(FuncError::MismatchingParameters {
+                func: self.host_func,
+            }));
+        }
+        if self.func.func_type(&ctx).results().len() != outputs.len() {
+            return Err(Error::Func(FuncError::MismatchingResults {
+                func: self.func,
+            }));
+        }
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().resume_func(
+            ctx.as_context_mut(),
+            self,
+            inputs,
+            outputs,
+        )
+    }
+}
@@ -0,0 +1,257 @@
+//! The execution stacks of a [`Store`].
+//!
+//! [`Store`]: [`crate::Store`]
+
+use super::{
+    call_stack::CallStack,
+    resumable::SuspendedState,
+    value_stack::ValueStack,
+    CallParams,
+    CallResults,
+    Config,
+    FunctionFrame,
+};
+use crate::core::{TrapCode, UntypedValue, ValueType};
+use alloc::vec::Vec;
+use core::cmp;
+
+/// The value and call stacks used to execute Wasm functions.
+///
+/// # Note
+///
+/// Every [`Store`] owns its own [`Stack`] so that multiple [`Store`]
+/// instances that share the same [`Engine`] can execute Wasm functions
+/// concurrently. Nested executions of host functions calling back into
+/// Wasm use the [`Stack`] of the same [`Store`] and therefore share
+/// its stack limits.
+///
+/// [`Store`]: [`crate::Store`]
+/// [`Engine`]: [`crate::Engine`]
+#[derive(Debug)]
+pub struct Stack {
+    /// Stores the value stack of live values on the Wasm stack.
+    pub(super) values: ValueStack,
+    /// Stores the call stack of live function invocations.
+    pub(super) frames: CallStack,
+    /// Stores reusable buffers for the parameters and results of host function calls.
+    ///
+    /// # Note
+    ///
+    /// Every active host function call uses its own buffer since host
+    /// functions may call back into Wasm which may call host functions again.
+    host_buffers: Vec<Vec<UntypedValue>>,
+}
+
+/// The lengths of the value and call stacks before an execution.
+///
+/// # Note
+///
+/// Executions push their values and function frames on top of the stacks
+/// which allows for nested executions. After an execution the stacks
+/// are reset to the lengths stored in the [`StackBase`].
+#[derive(Debug, Copy, Clone)]
+pub(super) struct StackBase {
+    /// The length of the value stack before the execution.
+    len_values: usize,
+    /// The length of the call stack before the execution.
+    len_frames: usize,
+}
+
+impl StackBase {
+    /// Returns the length of the call stack before the execution.
+    pub fn len_frames(&self) -> usize {
+        self.len_frames
+    }
+}
+
+impl Stack {
+    /// Creates a new [`Stack`] with the stack limits of the given [`Config`].
+    pub fn new(config: &Config) -> Self {
+        Self {
+            values: ValueStack::new(64, config.value_stack_limit()),
+            frames: CallStack::new(config.call_stack_limit()),
+            host_buffers: Vec::new(),
+        }
+    }
+
+    /// Creates an empty [`Stack`] that does not allocate.
+    ///
+    /// # Note
+    ///
+    /// This is used as a placeholder in the [`Store`] while its [`Stack`]
+    /// is in use by an execution and cannot be used to execute Wasm functions.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub fn empty() -> Self {
+        Self {
+            values: ValueStack::empty(),
+            frames: CallStack::new(0),
+            host_buffers: Vec::new(),
+        }
+    }
+
+    /// Takes a buffer for the parameters and results of a host function call.
+    ///
+    /// The buffer holds the `len_inputs` parameters on top of the value stack
+    /// and has room for at least `len_outputs` results.
+    ///
+    /// # Note
+    ///
+    /// The buffer must be returned via [`Stack::return_host_buffer`] after the
+    /// call so that host function calls do not allocate in the common case.
+    pub(super) fn take_host_buffer(
+        &mut self,
+        len_inputs: usize,
+        len_outputs: usize,
+    ) -> Vec<UntypedValue> {
+        let mut buffer = self.host_buffers.pop().unwrap_or_default();
+        buffer.clear();
+        buffer.extend_from_slice(self.values.peek_as_slice(len_inputs));
+        buffer.resize(cmp::max(len_inputs, len_outputs), UntypedValue::default());
+        buffer
+    }
+
+    /// Returns a `buffer` taken via [`Stack::take_host_buffer`] for reuse.
+    pub(super) fn return_host_buffer(&mut self, buffer: Vec<UntypedValue>) {
+        self.host_buffers.push(buffer);
+    }
+
+    /// Returns the current [`StackBase`].
+    fn base(&self) -> StackBase {
+        StackBase {
+            len_values: self.values.len(),
+            len_frames: self.frames.len(),
+        }
+    }
+
+    /// Pushes the given arguments `params` onto the value stack.
+    ///
+    /// Returns the [`StackBase`] of the execution.
+    ///
+    /// # Errors
+    ///
+    /// If the value stack cannot fit all arguments `params`.
+    pub(super) fn initialize_args<Params>(&mut self, params: Params) -> Result<StackBase, TrapCode>
+    where
+        Params: CallParams,
+    {
+        let base = self.base();
+        self.values.reserve(params.len_params())?;
+        for param in params.feed_params() {
+            self.values.push(param);
+        }
+        Ok(base)
+    }
+
+    /// Restores the suspended `state` on top of the value and call stacks.
+    ///
+    /// The `len_host_inputs` parameters of the suspended host function call
+    /// are replaced with the given `params` that are the results of the host function.
+    ///
+    /// Returns the [`StackBase`] of the resumed execution together with the
+    /// Wasm function frame that was executing when the execution was suspended.
+    ///
+    /// # Errors
+    ///
+    /// If the suspended state does not fit into the value or call stack.
+    pub(super) fn restore_suspended<Params>(
+        &mut self,
+        len_host_inputs: usize,
+        state: SuspendedState,
+        params: Params,
+    ) -> Result<(StackBase, FunctionFrame), TrapCode>
+    where
+        Params: CallParams,
+    {
+        let base = self.base();
+        let SuspendedState {
+            values,
+            mut frames,
+            mut frame,
+        } = state;
+        // The registers of the suspended frames are relative to the start
+        // of the suspended values and need to be relocated.
+        for frame in frames.iter_mut().chain(Some(&mut frame)) {
+            frame.relocate(0, base.len_values);
+        }
+        let restored = self
+            .values
+            .extend_from_slice(&values)
+            .and_then(|_| self.frames.extend(frames))
+            .and_then(|_| {
+                self.values.drop(len_host_inputs);
+                self.values.reserve(params.len_params())
+            });
+        if let Err(trap) = restored {
+            self.reset(base);
+            return Err(trap);
+        }
+        for param in params.feed_params() {
+            self.values.push(param);
+        }
+        Ok((base, frame))
+    }
+
+    /// Splits off the values and function frames above the [`StackBase`].
+    ///
+    /// The `frame` is the Wasm function frame that was executing when
+    /// the execution was suspended.
+    pub(super) fn suspend(&mut self, base: StackBase, mut frame: FunctionFrame) -> SuspendedState {
+        let values = self.values.split_off(base.len_values);
+        let mut frames = self.frames.split_off(base.len_frames);
+        // The registers of the suspended frames are stored relative
+        // to the start of the suspended values.
+        for frame in frames.iter_mut().chain(Some(&mut frame)) {
+            frame.relocate(base.len_values, 0);
+        }
+        SuspendedState {
+            values,
+            frames,
+            frame,
+        }
+    }
+
+    /// Resets the value and call stacks to the given [`StackBase`].
+    pub(super) fn reset(&mut self, base: StackBase) {
+        self.values.truncate(base.len_values);
+        self.frames.truncate(base.len_frames);
+    }
+
+    /// Writes the results of the function execution back into the `results` buffer.
+    ///
+    /// # Note
+    ///
+    /// The stacks are reset to the given [`StackBase`] after this operation.
+    ///
+    /// # Panics
+    ///
+    /// - If the `results` buffer length does not match the remaining amount of stack values.
+    pub(super) fn write_results_back<Results>(
+        &mut self,
+        result_types: &[ValueType],
+        base: StackBase,
+        results: Results,
+    ) -> <Results as CallResults>::Results
+    where
+        Results: CallResults,
+    {
+        let len_results = self.values.len() - base.len_values;
+        assert_eq!(
+            len_results,
+            results.len_results(),
+            "expected {} values on the stack after function execution but found {}",
+            results.len_results(),
+            len_results,
+        );
+        assert_eq!(results.len_results(), result_types.len());
+        let results = results.feed_results(
+            self.values
+                .peek_as_slice(len_results)
+                .iter()
+                .zip(result_types)
+                .map(|(raw_value, value_type)| raw_value.with_type(*value_type)),
+        );
+        self.reset(base);
+        results
+    }
+}
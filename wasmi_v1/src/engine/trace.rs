@@ -0,0 +1,215 @@
+use super::bytecode::Instruction;
+use crate::Func;
+use alloc::boxed::Box;
+use core::{fmt, fmt::Debug};
+use wasmi_core::UntypedValue;
+
+/// A snapshot of the execution state right before a `wasmi` bytecode [`Instruction`] is executed.
+///
+/// # Note
+///
+/// Values are provided untyped since `wasmi` registers do not carry type information.
+/// Use the [`Func`] type and the [`Instruction`] to interpret them.
+#[derive(Debug, Copy, Clone)]
+pub struct TraceEvent<'a> {
+    /// The executed Wasm function.
+    func: Func,
+    /// The index of the executed Wasm function within its module.
+    func_index: u32,
+    /// The program counter of the `instr` within its function body.
+    pc: usize,
+    /// The instruction that is about to be executed.
+    instr: &'a Instruction,
+    /// The registers of the executed function frame.
+    registers: &'a [UntypedValue],
+    /// The amount of registers storing function parameters and local variables.
+    len_locals: usize,
+}
+
+impl<'a> TraceEvent<'a> {
+    /// Creates a new [`TraceEvent`].
+    pub(crate) fn new(
+        func: Func,
+        func_index: u32,
+        pc: usize,
+        instr: &'a Instruction,
+        registers: &'a [UntypedValue],
+        len_locals: usize,
+    ) -> Self {
+        Self {
+            func,
+            func_index,
+            pc,
+            instr,
+            registers,
+            len_locals,
+        }
+    }
+
+    /// Returns the executed Wasm [`Func`].
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the index of the executed Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the program counter of the [`Instruction`] within its function body.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the [`Instruction`] that is about to be executed.
+    pub fn instruction(&self) -> &'a Instruction {
+        self.instr
+    }
+
+    /// Returns the function parameters followed by the local variables of the executed function.
+    pub fn locals(&self) -> &'a [UntypedValue] {
+        &self.registers[..self.len_locals]
+    }
+
+    /// Returns all registers of the executed function frame.
+    ///
+    /// # Note
+    ///
+    /// The registers start with the function parameters and local
+    /// variables followed by the live temporary values.
+    pub fn registers(&self) -> &'a [UntypedValue] {
+        self.registers
+    }
+}
+
+/// A tracing callback that is invoked before every executed `wasmi` bytecode [`Instruction`].
+pub(crate) struct TraceHook {
+    hook: Box<dyn FnMut(&TraceEvent) + Send + Sync>,
+}
+
+impl Debug for TraceHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TraceHook").finish_non_exhaustive()
+    }
+}
+
+impl TraceHook {
+    /// Creates a new [`TraceHook`] from the given closure.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: FnMut(&TraceEvent) + Send + Sync + 'static,
+    {
+        Self {
+            hook: Box::new(hook),
+        }
+    }
+
+    /// Invokes the tracing callback for the given [`TraceEvent`].
+    #[inline]
+    pub fn trace(&mut self, event: &TraceEvent) {
+        (self.hook)(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytecode::Instruction, core::Value, Engine, Extern, Func, Linker, Module, Store};
+    use alloc::{sync::Arc, vec::Vec};
+    use spin::Mutex;
+
+    /// A traced instruction: the function index, the pc, the instruction and the locals.
+    type Traced = (u32, usize, Instruction, Vec<u64>);
+
+    /// Instantiates the Wasm module in `.wat` format and returns its exported function `run`.
+    fn setup(wat: &str) -> (Store<()>, Func) {
+        let wasm = wat::parse_str(wat).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let host = Func::wrap(&mut store, |value: i32| value);
+        let mut linker = <Linker<()>>::new();
+        linker.define("env", "host", host).unwrap();
+        let run = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_export(&store, "run")
+            .and_then(Extern::into_func)
+            .unwrap();
+        (store, run)
+    }
+
+    /// Sets a tracing hook on the `store` that records all [`TraceEvent`].
+    fn record(store: &mut Store<()>) -> Arc<Mutex<Vec<Traced>>> {
+        let traced = Arc::new(Mutex::new(Vec::new()));
+        let recorder = traced.clone();
+        store.set_trace_hook(move |event| {
+            let locals = event.locals().iter().map(|value| value.to_bits()).collect();
+            recorder
+                .lock()
+                .push((event.func_index(), event.pc(), *event.instruction(), locals));
+        });
+        traced
+    }
+
+    const WAT: &str = r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (func (export "run") (param $n i32) (result i32)
+                (local $x i32)
+                (local.set $x (call $host (local.get $n)))
+                (call $double (local.get $x))
+            )
+            (func $double (param i32) (result i32)
+                (i32.add (local.get 0) (local.get 0))
+            )
+        )
+    "#;
+
+    #[test]
+    fn trace_hook_observes_instructions() {
+        let (mut store, run) = setup(WAT);
+        let traced = record(&mut store);
+        let mut results = [Value::I32(0)];
+        run.call(&mut store, &[Value::I32(21)], &mut results)
+            .unwrap();
+        assert_eq!(results, [Value::I32(42)]);
+        let traced = traced.lock();
+        // The first instruction of `run` observes its parameter and zeroed local.
+        let (func_index, pc, _, locals) = &traced[0];
+        assert_eq!((*func_index, *pc), (1, 0));
+        assert_eq!(locals, &[21, 0]);
+        // Host functions are not traced while `$double` observes its parameter.
+        let (_, pc, _, locals) = traced
+            .iter()
+            .find(|(func_index, ..)| *func_index == 2)
+            .unwrap();
+        assert_eq!(*pc, 0);
+        assert_eq!(locals, &[21]);
+        assert!(traced.iter().all(|(func_index, ..)| *func_index != 0));
+        // Execution of `run` resumes after returning from `$double` and returns.
+        let (func_index, _, instr, _) = traced.last().unwrap();
+        assert_eq!(*func_index, 1);
+        assert!(matches!(instr, Instruction::Return(_)));
+        // Program counters increase within a function without branches.
+        let pcs = traced
+            .iter()
+            .filter(|(func_index, ..)| *func_index == 2)
+            .map(|(_, pc, ..)| *pc)
+            .collect::<Vec<_>>();
+        assert!(pcs.windows(2).all(|pcs| pcs[0] + 1 == pcs[1]));
+    }
+
+    #[test]
+    fn clear_trace_hook_works() {
+        let (mut store, run) = setup(WAT);
+        let traced = record(&mut store);
+        store.clear_trace_hook();
+        let mut results = [Value::I32(0)];
+        run.call(&mut store, &[Value::I32(1)], &mut results)
+            .unwrap();
+        assert_eq!(results, [Value::I32(2)]);
+        assert!(traced.lock().is_empty());
+    }
+}
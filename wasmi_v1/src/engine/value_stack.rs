@@ -95,6 +95,19 @@ impl ValueStack {
         }
     }
 
+    /// Creates an empty [`ValueStack`] that does not allocate.
+    ///
+    /// # Note
+    ///
+    /// The returned [`ValueStack`] cannot fit any values.
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+            stack_ptr: 0,
+            maximum_len: 0,
+        }
+    }
+
     /// Extends the value stack by the `additional` amount of zeros.
     ///
     /// # Errors
@@ -141,113 +154,62 @@ impl ValueStack {
         self.stack_ptr -= drop;
     }
 
-    /// Returns the last stack entry of the [`ValueStack`].
-    ///
-    /// # Note
-    ///
-    /// This has the same effect as [`ValueStack::peek`]`(0)`.
-    pub fn last(&self) -> UntypedValue {
-        self.entries[self.stack_ptr - 1]
-    }
-
-    /// Returns the last stack entry of the [`ValueStack`].
-    ///
-    /// # Note
-    ///
-    /// This has the same effect as [`ValueStack::peek`]`(0)`.
-    pub fn last_mut(&mut self) -> &mut UntypedValue {
-        &mut self.entries[self.stack_ptr - 1]
+    pub fn drop(&mut self, depth: usize) {
+        self.stack_ptr -= depth;
     }
 
-    /// Peeks the entry at the given depth from the last entry.
+    /// Pushes the [`UntypedValue`] to the end of the [`ValueStack`].
     ///
     /// # Note
     ///
-    /// Given a `depth` of 0 has the same effect as [`ValueStack::last`].
-    pub fn peek(&self, depth: usize) -> UntypedValue {
-        self.entries[self.stack_ptr - depth - 1]
+    /// - This operation heavily relies on the prior validation of
+    ///   the executed WebAssembly bytecode for correctness.
+    /// - Especially the stack-depth analysis during compilation with
+    ///   a manual stack extension before function call prevents this
+    ///   procedure from panicking.
+    pub fn push<T>(&mut self, entry: T)
+    where
+        T: Into<UntypedValue>,
+    {
+        self.entries[self.stack_ptr] = entry.into();
+        self.stack_ptr += 1;
     }
 
-    /// Peeks the `&mut` entry at the given depth from the last entry.
+    /// Returns the value of the entry at `index`.
     ///
     /// # Note
     ///
-    /// Given a `depth` of 0 has the same effect as [`ValueStack::last_mut`].
-    pub fn peek_mut(&mut self, depth: usize) -> &mut UntypedValue {
-        &mut self.entries[self.stack_ptr - depth - 1]
+    /// This is used to read the registers of the executed function frame.
+    #[inline]
+    pub fn get(&self, index: usize) -> UntypedValue {
+        self.entries[index]
     }
 
-    /// Pops the last [`UntypedValue`] from the [`ValueStack`].
+    /// Sets the value of the entry at `index` to `value`.
     ///
     /// # Note
     ///
-    /// This operation heavily relies on the prior validation of
-    /// the executed WebAssembly bytecode for correctness.
-    pub fn pop(&mut self) -> UntypedValue {
-        self.stack_ptr -= 1;
-        self.entries[self.stack_ptr]
-    }
-
-    pub fn drop(&mut self, depth: usize) {
-        self.stack_ptr -= depth;
-    }
-
-    /// Pops the last [`UntypedValue`] from the [`ValueStack`] as `T`.
-    pub fn pop_as<T>(&mut self) -> T
-    where
-        T: From<UntypedValue>,
-    {
-        T::from(self.pop())
+    /// This is used to write the registers of the executed function frame.
+    #[inline]
+    pub fn set(&mut self, index: usize, value: UntypedValue) {
+        self.entries[index] = value;
     }
 
-    /// Pops the last pair of [`UntypedValue`] from the [`ValueStack`].
+    /// Resizes the [`ValueStack`] to the given `len`.
     ///
     /// # Note
     ///
-    /// - This operation is slightly more efficient than using
-    ///   [`ValueStack::pop`] twice.
-    /// - This operation heavily relies on the prior validation of
-    ///   the executed WebAssembly bytecode for correctness.
-    pub fn pop2(&mut self) -> (UntypedValue, UntypedValue) {
-        self.stack_ptr -= 2;
-        (
-            self.entries[self.stack_ptr],
-            self.entries[self.stack_ptr + 1],
-        )
-    }
-
-    /// Evaluates `f` on the top three stack entries.
-    ///
-    /// In summary this procedure does the following:
+    /// Entries that are added by growing the [`ValueStack`] have unspecified values.
     ///
-    /// - Pop entry `e3`.
-    /// - Pop entry `e2`.
-    /// - Peek entry `&mut e1_ptr`.
-    /// - Evaluate `f(e1_ptr, e2, e3)`.
-    pub fn pop2_eval<F>(&mut self, f: F)
-    where
-        F: FnOnce(&mut UntypedValue, UntypedValue, UntypedValue),
-    {
-        let (e2, e3) = self.pop2();
-        let e1 = self.last_mut();
-        f(e1, e2, e3)
-    }
-
-    /// Pushes the [`UntypedValue`] to the end of the [`ValueStack`].
-    ///
-    /// # Note
+    /// # Errors
     ///
-    /// - This operation heavily relies on the prior validation of
-    ///   the executed WebAssembly bytecode for correctness.
-    /// - Especially the stack-depth analysis during compilation with
-    ///   a manual stack extension before function call prevents this
-    ///   procedure from panicking.
-    pub fn push<T>(&mut self, entry: T)
-    where
-        T: Into<UntypedValue>,
-    {
-        self.entries[self.stack_ptr] = entry.into();
-        self.stack_ptr += 1;
+    /// If the value stack cannot fit `len` entries.
+    pub fn resize(&mut self, len: usize) -> Result<(), TrapCode> {
+        if let Some(additional) = len.checked_sub(self.len()) {
+            self.reserve(additional)?;
+        }
+        self.stack_ptr = len;
+        Ok(())
     }
 
     /// Returns the capacity of the [`ValueStack`].
@@ -288,35 +250,67 @@ impl ValueStack {
         Ok(())
     }
 
-    /// Drains the remaining value stack.
+    /// Returns a shared slice to the last `depth` entries in the value stack.
+    pub fn peek_as_slice(&self, depth: usize) -> &[UntypedValue] {
+        let start = self.stack_ptr - depth;
+        let end = self.stack_ptr;
+        &self.entries[start..end]
+    }
+
+    /// Returns a shared slice to the entries within `start..end` of the value stack.
     ///
-    /// # Note
+    /// # Panics
     ///
-    /// This API is mostly used when writing results back to the
-    /// caller after function execution has finished.
-    pub fn drain(&mut self) -> &[UntypedValue] {
-        let len = self.stack_ptr;
-        self.stack_ptr = 0;
-        &self.entries[0..len]
+    /// If `start..end` is out of bounds for the [`ValueStack`].
+    #[cfg(feature = "trace")]
+    pub fn as_slice(&self, start: usize, end: usize) -> &[UntypedValue] {
+        &self.entries[start..end]
     }
 
-    /// Returns an exclusive slice to the last `depth` entries in the value stack.
-    pub fn peek_as_slice_mut(&mut self, depth: usize) -> &mut [UntypedValue] {
-        let start = self.stack_ptr - depth;
-        let end = self.stack_ptr;
-        &mut self.entries[start..end]
+    /// Pushes all `values` to the end of the [`ValueStack`].
+    ///
+    /// # Errors
+    ///
+    /// If the value stack cannot fit all `values`.
+    pub fn extend_from_slice(&mut self, values: &[UntypedValue]) -> Result<(), TrapCode> {
+        self.reserve(values.len())?;
+        let len = values.len();
+        self.entries[self.stack_ptr..self.stack_ptr + len].copy_from_slice(values);
+        self.stack_ptr += len;
+        Ok(())
     }
 
-    /// Clears the [`ValueStack`] entirely.
+    /// Removes all entries starting at `len` from the [`ValueStack`] and returns them.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is greater than the current length of the [`ValueStack`].
+    pub fn split_off(&mut self, len: usize) -> Vec<UntypedValue> {
+        let values = self.entries[len..self.stack_ptr].to_vec();
+        self.truncate(len);
+        values
+    }
+
+    /// Shortens the [`ValueStack`] to the given `len`.
     ///
     /// # Note
     ///
     /// This is required since sometimes execution can halt in the middle of
     /// function execution which leaves the [`ValueStack`] in an unspecified
-    /// state. Therefore the [`ValueStack`] is required to be reset before
-    /// function execution happens.
-    pub fn clear(&mut self) {
-        self.stack_ptr = 0;
+    /// state. Therefore the [`ValueStack`] is required to be reset to the
+    /// length it had before the function execution.
+    ///
+    /// # Panics
+    ///
+    /// If `len` is greater than the current length of the [`ValueStack`].
+    pub fn truncate(&mut self, len: usize) {
+        assert!(
+            len <= self.stack_ptr,
+            "cannot truncate value stack of length {} to length {}",
+            self.stack_ptr,
+            len,
+        );
+        self.stack_ptr = len;
     }
 }
 
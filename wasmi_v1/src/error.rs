@@ -1,6 +1,7 @@
 use wasmi_core::TrapCode;
 
 use super::errors::{
+    FuelError,
     FuncError,
     GlobalError,
     InstantiationError,
@@ -9,6 +10,7 @@ use super::errors::{
     ModuleError,
     TableError,
 };
+use crate::{HostError, Trap};
 use core::{fmt, fmt::Display};
 
 /// An error that may occur upon operating on Wasm modules or module instances.
@@ -29,13 +31,17 @@ pub enum Error {
     Module(ModuleError),
     /// A function error.
     Func(FuncError),
-    /// Trap.
-    Trap(TrapCode),
+    /// A fuel metering error.
+    Fuel(FuelError),
+    /// A trap with an optional Wasm backtrace.
+    Trap(Trap),
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+impl HostError for Error {}
+
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -44,6 +50,7 @@ impl Display for Error {
             Self::Table(error) => Display::fmt(error, f),
             Self::Linker(error) => Display::fmt(error, f),
             Self::Func(error) => Display::fmt(error, f),
+            Self::Fuel(error) => Display::fmt(error, f),
             Self::Instantiation(error) => Display::fmt(error, f),
             Self::Module(error) => Display::fmt(error, f),
             Self::Trap(error) => Display::fmt(error, f),
@@ -53,7 +60,13 @@ impl Display for Error {
 
 impl From<TrapCode> for Error {
     fn from(e: TrapCode) -> Self {
-        Self::Trap(e)
+        Self::Trap(e.into())
+    }
+}
+
+impl From<Trap> for Error {
+    fn from(trap: Trap) -> Self {
+        Self::Trap(trap)
     }
 }
 
@@ -98,3 +111,9 @@ impl From<FuncError> for Error {
         Self::Func(error)
     }
 }
+
+impl From<FuelError> for Error {
+    fn from(error: FuelError) -> Self {
+        Self::Fuel(error)
+    }
+}
@@ -0,0 +1,218 @@
+use super::{
+    into_func::{WasmResults, WasmType, WasmTypeList},
+    HostFuncTrampoline,
+};
+use crate::{
+    core::Value,
+    engine::{FuncParams, FuncResults, ResumableCall},
+    Caller,
+    Error,
+    FuncType,
+    HostError,
+    Trap,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+    future::Future,
+    pin::Pin,
+};
+use spin::Mutex;
+
+/// Closures and functions that can be used as asynchronous host functions.
+///
+/// # Note
+///
+/// The returned [`Future`] must not borrow the [`Caller`] since the [`Store`]
+/// is not accessible while the future is pending.
+///
+/// [`Store`]: [`crate::Store`]
+pub trait IntoAsyncFunc<T, Params, Results>: Send + Sync + 'static {
+    /// The parameters of the host function.
+    #[doc(hidden)]
+    type Params: WasmTypeList;
+    /// The results of the host function.
+    #[doc(hidden)]
+    type Results: WasmTypeList;
+
+    /// Converts the function into its `wasmi` signature and its trampoline.
+    #[doc(hidden)]
+    fn into_async_func(self) -> (FuncType, HostFuncTrampoline<T>);
+}
+
+macro_rules! impl_into_async_func {
+    ( $n:literal $( $tuple:ident )* ) => {
+        impl<T, F, $($tuple,)* Fut> IntoAsyncFunc<T, ($($tuple,)*), Fut> for F
+        where
+            F: Fn($($tuple),*) -> Fut,
+            F: Send + Sync + 'static,
+            $(
+                $tuple: WasmType,
+            )*
+            Fut: Future + Send + 'static,
+            Fut::Output: WasmResults,
+        {
+            type Params = ($($tuple,)*);
+            type Results = <Fut::Output as WasmResults>::Ok;
+
+            #[allow(non_snake_case)]
+            fn into_async_func(self) -> (FuncType, HostFuncTrampoline<T>) {
+                IntoAsyncFunc::into_async_func(
+                    move |
+                        _: Caller<'_, T>,
+                        $(
+                            $tuple: $tuple,
+                        )*
+                    | {
+                        (self)($($tuple),*)
+                    }
+                )
+            }
+        }
+
+        impl<T, F, $($tuple,)* Fut> IntoAsyncFunc<T, (Caller<'_, T>, $($tuple),*), Fut> for F
+        where
+            F: Fn(Caller<T>, $($tuple),*) -> Fut,
+            F: Send + Sync + 'static,
+            $(
+                $tuple: WasmType,
+            )*
+            Fut: Future + Send + 'static,
+            Fut::Output: WasmResults,
+        {
+            type Params = ($($tuple,)*);
+            type Results = <Fut::Output as WasmResults>::Ok;
+
+            #[allow(non_snake_case)]
+            fn into_async_func(self) -> (FuncType, HostFuncTrampoline<T>) {
+                let signature = FuncType::new(
+                    <Self::Params as WasmTypeList>::value_types(),
+                    <Self::Results as WasmTypeList>::value_types(),
+                );
+                let trampoline = HostFuncTrampoline::new(
+                    move |caller: Caller<T>, params_results: FuncParams| -> Result<FuncResults, Trap> {
+                        let ($($tuple,)*): Self::Params = params_results.read_params();
+                        let future = (self)(caller, $($tuple),*);
+                        Err(Trap::host(PendingHostCall::new(async move {
+                            let results: Self::Results = future.await.into_fallible()?;
+                            Ok(<Self::Results as WasmTypeList>::values(results)
+                                .into_iter()
+                                .collect())
+                        })))
+                    },
+                );
+                (signature, trampoline)
+            }
+        }
+    };
+}
+for_each_tuple!(impl_into_async_func);
+
+/// The [`Future`] of a call to an asynchronous host function.
+type HostFuture = Pin<Box<dyn Future<Output = Result<Vec<Value>, Trap>> + Send>>;
+
+/// A call to an asynchronous host function whose [`Future`] is still pending.
+///
+/// # Note
+///
+/// Asynchronous host functions return this resumable host error so that
+/// the execution is suspended until [`Func::call_async`] has awaited the
+/// [`Future`] and resumes the execution with its results.
+///
+/// [`Func::call_async`]: [`crate::Func::call_async`]
+pub(crate) struct PendingHostCall {
+    future: Mutex<Option<HostFuture>>,
+}
+
+impl PendingHostCall {
+    /// Creates a new [`PendingHostCall`] for the given `future`.
+    fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = Result<Vec<Value>, Trap>> + Send + 'static,
+    {
+        Self {
+            future: Mutex::new(Some(Box::pin(future))),
+        }
+    }
+
+    /// Takes the [`Future`] of the host function call.
+    ///
+    /// Returns `None` if the [`Future`] has already been taken.
+    pub fn take_future(&self) -> Option<HostFuture> {
+        self.future.lock().take()
+    }
+
+    /// Cancels the [`PendingHostCall`] if the `trap` is one.
+    ///
+    /// # Note
+    ///
+    /// A cancelled [`PendingHostCall`] is no longer resumable.
+    fn cancel(trap: &Trap) {
+        if let Some(pending) = trap.downcast_ref::<Self>() {
+            pending.take_future();
+        }
+    }
+}
+
+/// Cancels the [`PendingHostCall`] of a non-asynchronous call that failed with `error`.
+///
+/// # Note
+///
+/// Only [`Func::call_async`] can await the [`Future`] of a [`PendingHostCall`].
+/// Cancelling it prevents a host function from passing it on as its own resumable
+/// error, in which case [`Func::call_async`] would resume the wrong host function.
+///
+/// [`Func::call_async`]: [`crate::Func::call_async`]
+pub(crate) fn cancel_pending_call(error: Error) -> Error {
+    if let Error::Trap(trap) = &error {
+        PendingHostCall::cancel(trap);
+    }
+    error
+}
+
+/// Cancels the [`PendingHostCall`] of a non-asynchronous resumable call.
+///
+/// # Note
+///
+/// Invocations suspended by a [`PendingHostCall`] are turned into
+/// non-resumable errors. See [`cancel_pending_call`] for more information.
+pub(crate) fn cancel_pending_calls(
+    call: Result<ResumableCall, Error>,
+) -> Result<ResumableCall, Error> {
+    match call {
+        Ok(ResumableCall::Resumable(invocation))
+            if invocation
+                .host_error()
+                .downcast_ref::<PendingHostCall>()
+                .is_some() =>
+        {
+            let trap = invocation.into_host_error();
+            PendingHostCall::cancel(&trap);
+            Err(Error::Trap(trap))
+        }
+        call => call.map_err(cancel_pending_call),
+    }
+}
+
+impl Debug for PendingHostCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PendingHostCall").finish_non_exhaustive()
+    }
+}
+
+impl Display for PendingHostCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "called an asynchronous host function outside of an asynchronous call"
+        )
+    }
+}
+
+impl HostError for PendingHostCall {
+    fn can_resume(&self) -> bool {
+        // Cancelled pending host calls have no future to await.
+        self.future.lock().is_some()
+    }
+}
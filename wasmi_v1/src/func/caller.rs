@@ -1,11 +1,11 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{Engine, Extern, Instance};
+use crate::{errors::FuelError, Engine, Extern, Instance};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
 /// [`Func::wrap`]: struct.Func.html#method.wrap
-pub struct Caller<'a, T, E> {
-    pub(crate) store: StoreContextMut<'a, T, E>,
+pub struct Caller<'a, T> {
+    pub(crate) store: StoreContextMut<'a, T>,
     /// The module instance associated to the call.
     /// This is `Some` if the host function was called from a Wasm function
     /// since all Wasm function are associated to a module instance.
@@ -13,11 +13,11 @@ pub struct Caller<'a, T, E> {
     instance: Option<Instance>,
 }
 
-impl<'a, T, E> Caller<'a, T, E> {
+impl<'a, T> Caller<'a, T> {
     /// Creates a new [`Caller`] from the given store context and [`Instance`] handle.
     pub fn new<C>(ctx: &'a mut C, instance: Option<Instance>) -> Self
     where
-        C: AsContextMut<UserState = T, Error = E>,
+        C: AsContextMut<UserState = T>,
     {
         Self {
             store: ctx.as_context_mut(),
@@ -48,24 +48,53 @@ impl<'a, T, E> Caller<'a, T, E> {
     pub fn engine(&self) -> &Engine {
         self.store.store.engine()
     }
+
+    /// Adds `delta` units of fuel to the [`Store`].
+    ///
+    /// For more information see [`Store::add_fuel`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`Store::add_fuel`]: [`crate::Store::add_fuel`]
+    pub fn add_fuel(&mut self, delta: u64) -> Result<(), FuelError> {
+        self.store.store.add_fuel(delta)
+    }
+
+    /// Returns the amount of fuel consumed by executions of the [`Store`] so far.
+    ///
+    /// For more information see [`Store::fuel_consumed`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`Store::fuel_consumed`]: [`crate::Store::fuel_consumed`]
+    pub fn fuel_consumed(&self) -> Option<u64> {
+        self.store.store.fuel_consumed()
+    }
+
+    /// Consumes `delta` units of fuel from the [`Store`].
+    ///
+    /// For more information see [`Store::consume_fuel`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`Store::consume_fuel`]: [`crate::Store::consume_fuel`]
+    pub fn consume_fuel(&mut self, delta: u64) -> Result<u64, FuelError> {
+        self.store.store.consume_fuel(delta)
+    }
 }
 
-impl<T, E> AsContext for Caller<'_, T, E> {
+impl<T> AsContext for Caller<'_, T> {
     type UserState = T;
-    type Error = E;
 
-    fn as_context(&self) -> StoreContext<'_, Self::UserState, Self::Error> {
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
         self.store.as_context()
     }
 }
 
-impl<T, E> AsContextMut for Caller<'_, T, E> {
-    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState, Self::Error> {
+impl<T> AsContextMut for Caller<'_, T> {
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState> {
         self.store.as_context_mut()
     }
 }
 
-impl<'a, T: AsContextMut> From<&'a mut T> for Caller<'a, T::UserState, T::Error> {
+impl<'a, T: AsContextMut> From<&'a mut T> for Caller<'a, T::UserState> {
     fn from(ctx: &'a mut T) -> Self {
         Self {
             store: ctx.as_context_mut(),
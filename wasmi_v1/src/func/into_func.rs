@@ -6,14 +6,14 @@ use crate::{
     core::{FromValue, Value, ValueType, F32, F64},
     foreach_tuple::for_each_tuple,
     Caller,
-    Error,
     FuncType,
+    Trap,
 };
 use core::{array, iter::FusedIterator};
-use wasmi_core::{DecodeUntypedSlice, EncodeUntypedSlice, TrapCode, UntypedValue};
+use wasmi_core::{DecodeUntypedSlice, EncodeUntypedSlice, UntypedValue};
 
 /// Closures and functions that can be used as host functions.
-pub trait IntoFunc<T, E, Params, Results>: Send + Sync + 'static {
+pub trait IntoFunc<T, Params, Results>: Send + Sync + 'static {
     /// The parameters of the host function.
     #[doc(hidden)]
     type Params: WasmTypeList;
@@ -23,14 +23,13 @@ pub trait IntoFunc<T, E, Params, Results>: Send + Sync + 'static {
 
     /// Converts the function into its `wasmi` signature and its trampoline.
     #[doc(hidden)]
-    fn into_func(self) -> (FuncType, HostFuncTrampoline<T, E>);
+    fn into_func(self) -> (FuncType, HostFuncTrampoline<T>);
 }
 
 macro_rules! impl_into_func {
     ( $n:literal $( $tuple:ident )* ) => {
-        impl<T, E, F, $($tuple,)* R> IntoFunc<T, E, ($($tuple,)*), R> for F
+        impl<T, F, $($tuple,)* R> IntoFunc<T, ($($tuple,)*), R> for F
         where
-            E: From<Error>,
             F: Fn($($tuple),*) -> R,
             F: Send + Sync + 'static,
             $(
@@ -42,10 +41,10 @@ macro_rules! impl_into_func {
             type Results = <R as WasmResults>::Ok;
 
             #[allow(non_snake_case)]
-            fn into_func(self) -> (FuncType, HostFuncTrampoline<T, E>) {
+            fn into_func(self) -> (FuncType, HostFuncTrampoline<T>) {
                 IntoFunc::into_func(
                     move |
-                        _: Caller<'_, T, E>,
+                        _: Caller<'_, T>,
                         $(
                             $tuple: $tuple,
                         )*
@@ -56,10 +55,9 @@ macro_rules! impl_into_func {
             }
         }
 
-        impl<T, E, F, $($tuple,)* R> IntoFunc<T, E, (Caller<'_, T, E>, $($tuple),*), R> for F
+        impl<T, F, $($tuple,)* R> IntoFunc<T, (Caller<'_, T>, $($tuple),*), R> for F
         where
-            E: From<Error>,
-            F: Fn(Caller<T, E>, $($tuple),*) -> R,
+            F: Fn(Caller<T>, $($tuple),*) -> R,
             F: Send + Sync + 'static,
             $(
                 $tuple: WasmType,
@@ -70,16 +68,16 @@ macro_rules! impl_into_func {
             type Results = <R as WasmResults>::Ok;
 
             #[allow(non_snake_case)]
-            fn into_func(self) -> (FuncType, HostFuncTrampoline<T, E>) {
+            fn into_func(self) -> (FuncType, HostFuncTrampoline<T>) {
                 let signature = FuncType::new(
                     <Self::Params as WasmTypeList>::value_types(),
                     <Self::Results as WasmTypeList>::value_types(),
                 );
                 let trampoline = HostFuncTrampoline::new(
-                    move |caller: Caller<T, E>, params_results: FuncParams| -> Result<FuncResults, E> {
+                    move |caller: Caller<T>, params_results: FuncParams| -> Result<FuncResults, Trap> {
                         let ($($tuple,)*): Self::Params = params_results.read_params();
                         let results: Self::Results =
-                            (self)(caller, $($tuple),*).into_fallible().map_err(Into::into)?;
+                            (self)(caller, $($tuple),*).into_fallible()?;
                         Ok(params_results.write_results(results))
                     },
                 );
@@ -96,7 +94,7 @@ pub trait WasmResults {
     type Ok: WasmTypeList;
 
     #[doc(hidden)]
-    fn into_fallible(self) -> Result<<Self as WasmResults>::Ok, TrapCode>;
+    fn into_fallible(self) -> Result<<Self as WasmResults>::Ok, Trap>;
 }
 
 impl<T1> WasmResults for T1
@@ -105,7 +103,7 @@ where
 {
     type Ok = T1;
 
-    fn into_fallible(self) -> Result<Self::Ok, TrapCode> {
+    fn into_fallible(self) -> Result<Self::Ok, Trap> {
         Ok(self)
     }
 }
@@ -120,12 +118,12 @@ macro_rules! impl_wasm_return_type {
         {
             type Ok = ($($tuple,)*);
 
-            fn into_fallible(self) -> Result<Self::Ok, TrapCode> {
+            fn into_fallible(self) -> Result<Self::Ok, Trap> {
                 Ok(self)
             }
         }
 
-        impl<$($tuple),*> WasmResults for Result<($($tuple,)*), TrapCode>
+        impl<$($tuple),*> WasmResults for Result<($($tuple,)*), Trap>
         where
             $(
                 $tuple: WasmType
@@ -133,7 +131,7 @@ macro_rules! impl_wasm_return_type {
         {
             type Ok = ($($tuple,)*);
 
-            fn into_fallible(self) -> Result<<Self as WasmResults>::Ok, TrapCode> {
+            fn into_fallible(self) -> Result<<Self as WasmResults>::Ok, Trap> {
                 self
             }
         }
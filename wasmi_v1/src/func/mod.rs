@@ -1,16 +1,20 @@
+mod async_func;
 mod caller;
 mod error;
 mod into_func;
 mod typed_func;
 
+pub(crate) use self::async_func::cancel_pending_calls;
+use self::async_func::{cancel_pending_call, PendingHostCall};
 pub use self::{
+    async_func::IntoAsyncFunc,
     caller::Caller,
     error::FuncError,
     into_func::IntoFunc,
     typed_func::{TypedFunc, WasmParams, WasmResults},
 };
 use super::{
-    engine::{DedupFuncType, FuncBody, FuncParams, FuncResults},
+    engine::{DedupFuncType, FuncBody, FuncParams, FuncResults, ResumableCall},
     AsContext,
     AsContextMut,
     Index,
@@ -18,8 +22,15 @@ use super::{
     StoreContext,
     Stored,
 };
-use crate::{core::Value, Error, FuncType};
-use alloc::sync::Arc;
+#[cfg(feature = "debugger")]
+use crate::engine::{DebugCall, StepMode};
+use crate::{
+    core::{TrapCode, Value},
+    Error,
+    FuncType,
+    Trap,
+};
+use alloc::{sync::Arc, vec::Vec};
 use core::{fmt, fmt::Debug};
 
 /// A raw index to a function entity.
@@ -41,14 +52,14 @@ impl Index for FuncIdx {
 
 /// A function instance.
 #[derive(Debug)]
-pub struct FuncEntity<T, E> {
+pub struct FuncEntity<T> {
     /// We wrap this enum in a struct so that we can make its
     /// variants private. This is advantageous since they are
     /// implementation details and not important to the user.
-    internal: FuncEntityInternal<T, E>,
+    internal: FuncEntityInternal<T>,
 }
 
-impl<T, E> Clone for FuncEntity<T, E> {
+impl<T> Clone for FuncEntity<T> {
     fn clone(&self) -> Self {
         Self {
             internal: self.internal.clone(),
@@ -56,7 +67,7 @@ impl<T, E> Clone for FuncEntity<T, E> {
     }
 }
 
-impl<T, E> FuncEntity<T, E> {
+impl<T> FuncEntity<T> {
     /// Creates a new Wasm function from the given raw parts.
     pub(crate) fn new_wasm(signature: DedupFuncType, body: FuncBody, instance: Instance) -> Self {
         Self {
@@ -64,23 +75,44 @@ impl<T, E> FuncEntity<T, E> {
         }
     }
 
+    /// Creates a new host function from the given dynamically typed closure.
+    pub fn new(
+        ctx: impl AsContextMut<UserState = T>,
+        func_type: FuncType,
+        func: impl Fn(Caller<T>, &[Value], &mut [Value]) -> Result<(), Trap> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            internal: FuncEntityInternal::Host(HostFuncEntity::new(ctx, func_type, func)),
+        }
+    }
+
     /// Creates a new host function from the given closure.
     pub fn wrap<Params, Results>(
         ctx: impl AsContextMut<UserState = T>,
-        func: impl IntoFunc<T, E, Params, Results>,
+        func: impl IntoFunc<T, Params, Results>,
     ) -> Self {
         Self {
             internal: FuncEntityInternal::Host(HostFuncEntity::wrap(ctx, func)),
         }
     }
 
+    /// Creates a new asynchronous host function from the given closure.
+    pub fn wrap_async<Params, Results>(
+        ctx: impl AsContextMut<UserState = T>,
+        func: impl IntoAsyncFunc<T, Params, Results>,
+    ) -> Self {
+        Self {
+            internal: FuncEntityInternal::Host(HostFuncEntity::wrap_async(ctx, func)),
+        }
+    }
+
     /// Returns the internal function entity.
     ///
     /// # Note
     ///
     /// This can be used to efficiently match against host or Wasm
     /// function entities and efficiently extract their properties.
-    pub(crate) fn as_internal(&self) -> &FuncEntityInternal<T, E> {
+    pub(crate) fn as_internal(&self) -> &FuncEntityInternal<T> {
         &self.internal
     }
 
@@ -97,14 +129,14 @@ impl<T, E> FuncEntity<T, E> {
 ///
 /// This can either be a host function or a Wasm function.
 #[derive(Debug)]
-pub(crate) enum FuncEntityInternal<T, E> {
+pub(crate) enum FuncEntityInternal<T> {
     /// A Wasm function instance.
     Wasm(WasmFuncEntity),
     /// A host function instance.
-    Host(HostFuncEntity<T, E>),
+    Host(HostFuncEntity<T>),
 }
 
-impl<T, E> Clone for FuncEntityInternal<T, E> {
+impl<T> Clone for FuncEntityInternal<T> {
     fn clone(&self) -> Self {
         match self {
             Self::Wasm(func) => Self::Wasm(func.clone()),
@@ -148,12 +180,12 @@ impl WasmFuncEntity {
 }
 
 /// A host function instance.
-pub(crate) struct HostFuncEntity<T, E> {
+pub(crate) struct HostFuncEntity<T> {
     signature: DedupFuncType,
-    trampoline: HostFuncTrampoline<T, E>,
+    trampoline: HostFuncTrampoline<T>,
 }
 
-impl<T, E> Clone for HostFuncEntity<T, E> {
+impl<T> Clone for HostFuncEntity<T> {
     fn clone(&self) -> Self {
         Self {
             signature: self.signature,
@@ -162,18 +194,18 @@ impl<T, E> Clone for HostFuncEntity<T, E> {
     }
 }
 
-type HostFuncTrampolineFn<T, E> =
-    dyn Fn(Caller<T, E>, FuncParams) -> Result<FuncResults, E> + Send + Sync + 'static;
+type HostFuncTrampolineFn<T> =
+    dyn Fn(Caller<T>, FuncParams) -> Result<FuncResults, Trap> + Send + Sync + 'static;
 
-pub struct HostFuncTrampoline<T, E> {
-    closure: Arc<HostFuncTrampolineFn<T, E>>,
+pub struct HostFuncTrampoline<T> {
+    closure: Arc<HostFuncTrampolineFn<T>>,
 }
 
-impl<T, E> HostFuncTrampoline<T, E> {
+impl<T> HostFuncTrampoline<T> {
     /// Creates a new [`HostFuncTrampoline`] from the given trampoline function.
     pub fn new<F>(trampoline: F) -> Self
     where
-        F: Fn(Caller<T, E>, FuncParams) -> Result<FuncResults, E>,
+        F: Fn(Caller<T>, FuncParams) -> Result<FuncResults, Trap>,
         F: Send + Sync + 'static,
     {
         Self {
@@ -182,7 +214,7 @@ impl<T, E> HostFuncTrampoline<T, E> {
     }
 }
 
-impl<T, E> Clone for HostFuncTrampoline<T, E> {
+impl<T> Clone for HostFuncTrampoline<T> {
     fn clone(&self) -> Self {
         Self {
             closure: self.closure.clone(),
@@ -190,17 +222,62 @@ impl<T, E> Clone for HostFuncTrampoline<T, E> {
     }
 }
 
-impl<T, E> Debug for HostFuncEntity<T, E> {
+impl<T> Debug for HostFuncEntity<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(&self.signature, f)
     }
 }
 
-impl<T, E> HostFuncEntity<T, E> {
+impl<T> HostFuncEntity<T> {
+    /// Creates a new host function from the given dynamically typed closure.
+    ///
+    /// # Note
+    ///
+    /// The closure receives the parameters of the call and writes its results into
+    /// a buffer with one default initialized value per result of the `func_type`.
+    /// Writing results with mismatching types causes an
+    /// [`TrapCode::UnexpectedSignature`] trap.
+    pub fn new(
+        mut ctx: impl AsContextMut,
+        func_type: FuncType,
+        func: impl Fn(Caller<T>, &[Value], &mut [Value]) -> Result<(), Trap> + Send + Sync + 'static,
+    ) -> Self {
+        let signature = ctx
+            .as_context_mut()
+            .store
+            .alloc_func_type(func_type.clone());
+        let trampoline = HostFuncTrampoline::new(
+            move |caller: Caller<T>, params_results: FuncParams| -> Result<FuncResults, Trap> {
+                let (param_types, result_types) = func_type.params_results();
+                let inputs = params_results
+                    .params()
+                    .iter()
+                    .zip(param_types)
+                    .map(|(param, param_type)| param.with_type(*param_type))
+                    .collect::<Vec<_>>();
+                let mut outputs = result_types
+                    .iter()
+                    .copied()
+                    .map(Value::default)
+                    .collect::<Vec<_>>();
+                func(caller, &inputs, &mut outputs)?;
+                let actual_results = outputs.iter().map(Value::value_type);
+                if result_types.iter().copied().ne(actual_results) {
+                    return Err(TrapCode::UnexpectedSignature.into());
+                }
+                Ok(params_results.write_untyped_results(outputs.into_iter().map(Into::into)))
+            },
+        );
+        Self {
+            signature,
+            trampoline,
+        }
+    }
+
     /// Creates a new host function from the given closure.
     pub fn wrap<Params, Results>(
         mut ctx: impl AsContextMut,
-        func: impl IntoFunc<T, E, Params, Results>,
+        func: impl IntoFunc<T, Params, Results>,
     ) -> Self {
         let (signature, trampoline) = func.into_func();
         let signature = ctx.as_context_mut().store.alloc_func_type(signature);
@@ -210,6 +287,19 @@ impl<T, E> HostFuncEntity<T, E> {
         }
     }
 
+    /// Creates a new asynchronous host function from the given closure.
+    pub fn wrap_async<Params, Results>(
+        mut ctx: impl AsContextMut,
+        func: impl IntoAsyncFunc<T, Params, Results>,
+    ) -> Self {
+        let (signature, trampoline) = func.into_async_func();
+        let signature = ctx.as_context_mut().store.alloc_func_type(signature);
+        Self {
+            signature,
+            trampoline,
+        }
+    }
+
     /// Returns the signature of the host function.
     pub fn signature(&self) -> DedupFuncType {
         self.signature
@@ -220,11 +310,11 @@ impl<T, E> HostFuncEntity<T, E> {
     /// The result is written back into the `outputs` buffer.
     pub fn call(
         &self,
-        mut ctx: impl AsContextMut<UserState = T, Error = E>,
+        mut ctx: impl AsContextMut<UserState = T>,
         instance: Option<Instance>,
         params: FuncParams,
-    ) -> Result<FuncResults, E> {
-        let caller = <Caller<T, E>>::new(&mut ctx, instance);
+    ) -> Result<FuncResults, Trap> {
+        let caller = <Caller<T>>::new(&mut ctx, instance);
         (self.trampoline.closure)(caller, params)
     }
 }
@@ -245,10 +335,34 @@ impl Func {
         self.0
     }
 
+    /// Creates a new host function from the given dynamically typed closure.
+    ///
+    /// The closure receives the parameters of the call and must write
+    /// its results into the given buffer according to the `func_type`.
+    ///
+    /// # Note
+    ///
+    /// Prefer [`Func::wrap`] for statically typed host functions since
+    /// it avoids the overhead of dynamic type checks upon calls.
+    pub fn new<C>(
+        mut ctx: C,
+        func_type: FuncType,
+        func: impl Fn(Caller<C::UserState>, &[Value], &mut [Value]) -> Result<(), Trap>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self
+    where
+        C: AsContextMut,
+    {
+        let func = FuncEntity::new(ctx.as_context_mut(), func_type, func);
+        ctx.as_context_mut().store.alloc_func(func)
+    }
+
     /// Creates a new host function from the given closure.
     pub fn wrap<C, Params, Results>(
         mut ctx: C,
-        func: impl IntoFunc<C::UserState, C::Error, Params, Results>,
+        func: impl IntoFunc<C::UserState, Params, Results>,
     ) -> Self
     where
         C: AsContextMut,
@@ -257,6 +371,33 @@ impl Func {
         ctx.as_context_mut().store.alloc_func(func)
     }
 
+    /// Creates a new asynchronous host function from the given closure.
+    ///
+    /// The closure returns a [`Future`] that resolves to the results of the host function.
+    ///
+    /// # Note
+    ///
+    /// - Asynchronous host functions must be called via [`Func::call_async`] or
+    ///   [`TypedFunc::call_async`] which suspend the execution while the [`Future`]
+    ///   is pending and resume it afterwards. Other calls of asynchronous host
+    ///   functions return an error.
+    /// - The [`Future`] cannot borrow the [`Caller`] since the [`Store`] is not
+    ///   accessible while the [`Future`] is pending. Data required by the [`Future`],
+    ///   for example the contents of a linear memory, must be read before creating it.
+    ///
+    /// [`Future`]: [`core::future::Future`]
+    /// [`Store`]: [`crate::Store`]
+    pub fn wrap_async<C, Params, Results>(
+        mut ctx: C,
+        func: impl IntoAsyncFunc<C::UserState, Params, Results>,
+    ) -> Self
+    where
+        C: AsContextMut,
+    {
+        let func = FuncEntity::wrap_async(ctx.as_context_mut(), func);
+        ctx.as_context_mut().store.alloc_func(func)
+    }
+
     /// Returns the signature of the function.
     pub(crate) fn signature(&self, ctx: impl AsContext) -> DedupFuncType {
         ctx.as_context().store.resolve_func(*self).signature()
@@ -276,22 +417,208 @@ impl Func {
     /// # Errors
     ///
     /// - If the function returned an error.
+    /// - If an asynchronous host function is called which requires [`Func::call_async`].
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    pub fn call<C>(&self, mut ctx: C, inputs: &[Value], outputs: &mut [Value]) -> Result<(), Error>
+    where
+        C: AsContextMut,
+    {
+        self.verify_inputs_outputs(&ctx, inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func(ctx.as_context_mut(), *self, inputs, outputs)
+            .map_err(cancel_pending_call)?;
+        Ok(())
+    }
+
+    /// Calls the Wasm or host function with the given inputs in a resumable way.
+    ///
+    /// The result is written back into the `outputs` buffer if the call finishes.
+    ///
+    /// # Note
+    ///
+    /// If a host function called from Wasm returns a [`Trap`] for which
+    /// [`HostError::can_resume`] returns `true` the execution is suspended and
+    /// [`ResumableCall::Resumable`] is returned. The suspended execution can then
+    /// be resumed with the results of the host function via
+    /// [`ResumableInvocation::resume`].
+    ///
+    /// [`HostError::can_resume`]: [`crate::HostError::can_resume`]
+    /// [`ResumableInvocation::resume`]: [`crate::ResumableInvocation::resume`]
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned an error that is not resumable.
+    /// - If an asynchronous host function is called which requires [`Func::call_async`].
     /// - If the types of the `inputs` do not match the expected types for the
     ///   function signature of `self`.
     /// - If the number of input values does not match the expected number of
     ///   inputs required by the function signature of `self`.
     /// - If the number of output values does not match the expected number of
     ///   outputs required by the function signature of `self`.
-    pub fn call<C>(
+    pub fn call_resumable<C>(
+        &self,
+        ctx: C,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error>
+    where
+        C: AsContextMut,
+    {
+        cancel_pending_calls(self.call_resumable_impl(ctx, inputs, outputs))
+    }
+
+    /// Implementation of [`Func::call_resumable`] that keeps [`PendingHostCall`]s resumable.
+    fn call_resumable_impl<C>(
         &self,
         mut ctx: C,
         inputs: &[Value],
         outputs: &mut [Value],
-    ) -> Result<(), C::Error>
+    ) -> Result<ResumableCall, Error>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
     {
+        self.verify_inputs_outputs(&ctx, inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func_resumable(ctx.as_context_mut(), *self, inputs, outputs)
+    }
+
+    /// Calls the Wasm or host function with the given inputs asynchronously.
+    ///
+    /// The result is written back into the `outputs` buffer.
+    ///
+    /// # Note
+    ///
+    /// The execution is suspended whenever an asynchronous host function created via
+    /// [`Func::wrap_async`] is called and resumed with its results once its [`Future`]
+    /// has been awaited.
+    ///
+    /// [`Future`]: [`core::future::Future`]
+    ///
+    /// # Errors
+    ///
+    /// - If the function or the [`Future`] of an asynchronous host function returned an error.
+    /// - If a host function returned a resumable error that does not belong
+    ///   to an asynchronous host function.
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    pub async fn call_async<C>(
+        &self,
+        mut ctx: C,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<(), Error>
+    where
+        C: AsContextMut,
+    {
+        let mut call = match self.call_resumable_impl(&mut ctx, inputs, outputs) {
+            Ok(call) => call,
+            Err(Error::Trap(trap)) => {
+                // Note: Host functions called directly from the host side are
+                //       never suspended so their futures are awaited right here.
+                let future = match trap.downcast_ref().and_then(PendingHostCall::take_future) {
+                    Some(future) => future,
+                    None => return Err(Error::Trap(trap)),
+                };
+                let results = future.await?;
+                outputs.copy_from_slice(&results);
+                return Ok(());
+            }
+            Err(error) => return Err(error),
+        };
+        loop {
+            let invocation = match call {
+                ResumableCall::Finished => return Ok(()),
+                ResumableCall::Resumable(invocation) => invocation,
+            };
+            let future = match invocation
+                .host_error()
+                .downcast_ref()
+                .and_then(PendingHostCall::take_future)
+            {
+                Some(future) => future,
+                None => return Err(Error::Trap(invocation.into_host_error())),
+            };
+            let results = future.await?;
+            call = invocation.resume_impl(&mut ctx, &results, outputs)?;
+        }
+    }
+
+    /// Calls the Wasm or host function with the given inputs with the debugger.
+    ///
+    /// The result is written back into the `outputs` buffer if the call finishes.
+    ///
+    /// # Note
+    ///
+    /// The execution pauses according to the [`StepMode`] or at breakpoints of the
+    /// [`Store`] and returns [`DebugCall::Paused`]. The paused execution can then be
+    /// inspected via [`PausedInvocation::frames`] and continued via [`PausedInvocation::resume`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    /// [`PausedInvocation::frames`]: [`crate::PausedInvocation::frames`]
+    /// [`PausedInvocation::resume`]: [`crate::PausedInvocation::resume`]
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned an error.
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of input values does not match the expected number of
+    ///   inputs required by the function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    #[cfg(feature = "debugger")]
+    pub fn call_debug<C>(
+        &self,
+        mut ctx: C,
+        inputs: &[Value],
+        outputs: &mut [Value],
+        mode: StepMode,
+    ) -> Result<DebugCall, Error>
+    where
+        C: AsContextMut,
+    {
+        self.verify_inputs_outputs(&ctx, inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func_debug(
+            ctx.as_context_mut(),
+            *self,
+            inputs,
+            outputs,
+            mode,
+        )
+    }
+
+    /// Verifies that the `inputs` and `outputs` match the function signature of `self`.
+    ///
+    /// # Errors
+    ///
+    /// - If the types of the `inputs` do not match the expected types for the
+    ///   function signature of `self`.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the function signature of `self`.
+    fn verify_inputs_outputs(
+        &self,
+        ctx: impl AsContext,
+        inputs: &[Value],
+        outputs: &[Value],
+    ) -> Result<(), Error> {
         // Since [`Func`] is a dynamically typed function instance there is
         // a need to verify that the given input parameters match the required
         // types and that the given output slice matches the expected length.
@@ -301,18 +628,13 @@ impl Func {
         let (expected_inputs, expected_outputs) = func_type.params_results();
         let actual_inputs = inputs.iter().map(|value| value.value_type());
         if expected_inputs.iter().copied().ne(actual_inputs) {
-            return Err(Error::Func(FuncError::MismatchingParameters { func: *self }).into());
+            return Err(Error::Func(FuncError::MismatchingParameters {
+                func: *self,
+            }));
         }
         if expected_outputs.len() != outputs.len() {
-            return Err(Error::Func(FuncError::MismatchingResults { func: *self }).into());
+            return Err(Error::Func(FuncError::MismatchingResults { func: *self }));
         }
-        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
-        ctx.as_context().store.engine().clone().execute_func(
-            ctx.as_context_mut(),
-            *self,
-            inputs,
-            outputs,
-        )?;
         Ok(())
     }
 
@@ -338,10 +660,10 @@ impl Func {
     ///
     /// This is intentionally a private API and mainly provided for efficient
     /// execution of the `wasmi` interpreter upon function dispatch.
-    pub(crate) fn as_internal<'a, T: 'a, E>(
+    pub(crate) fn as_internal<'a, T: 'a>(
         &self,
-        ctx: impl Into<StoreContext<'a, T, E>>,
-    ) -> &'a FuncEntityInternal<T, E> {
+        ctx: impl Into<StoreContext<'a, T>>,
+    ) -> &'a FuncEntityInternal<T> {
         ctx.into().store.resolve_func(*self).as_internal()
     }
 }
@@ -6,6 +6,7 @@ use crate::{
     AsContextMut,
     Error,
 };
+use alloc::vec::Vec;
 use core::{fmt, fmt::Debug, marker::PhantomData};
 
 /// A typed [`Func`] instance.
@@ -85,7 +86,7 @@ where
 
     /// Invokes this Wasm or host function with the specified parameters.
     ///
-    /// Returns either the results of the call, or a high level `C::Error` if one happened.
+    /// Returns either the results of the call, or an [`Error`] if one happened.
     ///
     /// For more information, see the [`Func::typed`] and [`Func::call`]
     /// documentation.
@@ -93,10 +94,9 @@ where
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`TypedFunc`].
-    pub fn call<C>(&self, mut ctx: C, params: Params) -> Result<Results, C::Error>
+    pub fn call<C>(&self, mut ctx: C, params: Params) -> Result<Results, Error>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
     {
         // Note: Cloning an [`Engine`] is intentionally a cheap operation.
         ctx.as_context().store.engine().clone().execute_func(
@@ -106,6 +106,33 @@ where
             <CallResultsTuple<Results>>::default(),
         )
     }
+
+    /// Invokes this Wasm or host function with the specified parameters asynchronously.
+    ///
+    /// Returns either the results of the call, or an [`Error`] if one happened.
+    ///
+    /// For more information, see the [`Func::typed`] and [`Func::call_async`]
+    /// documentation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedFunc`].
+    pub async fn call_async<C>(&self, ctx: C, params: Params) -> Result<Results, Error>
+    where
+        C: AsContextMut,
+    {
+        let inputs = <Params as WasmTypeList>::values(params);
+        let mut outputs = <Results as WasmTypeList>::value_types()
+            .into_iter()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        self.func
+            .call_async(ctx, inputs.as_ref(), &mut outputs)
+            .await?;
+        let results = <Results as WasmTypeList>::from_values(outputs.into_iter())
+            .expect("unable to construct typed results from value iterator");
+        Ok(results)
+    }
 }
 
 impl<Params> CallParams for Params
@@ -1,6 +1,8 @@
 use super::{
-    engine::DedupFuncType,
+    engine::{DedupFuncType, ModuleCode},
+    module::ModuleNames,
     AsContext,
+    AsContextMut,
     Extern,
     Func,
     Global,
@@ -13,6 +15,7 @@ use super::{
 use alloc::{
     collections::{btree_map, BTreeMap},
     string::{String, ToString},
+    sync::Arc,
     vec::Vec,
 };
 use core::{iter::FusedIterator, ops::Deref};
@@ -44,11 +47,42 @@ pub struct InstanceEntity {
     memories: Vec<Memory>,
     globals: Vec<Global>,
     exports: BTreeMap<String, Extern>,
+    /// The bytes of the data segments available to `memory.init` instructions.
+    ///
+    /// # Note
+    ///
+    /// Active and dropped data segments are empty.
+    data_segments: Vec<Arc<[u8]>>,
+    /// The functions of the element segments available to `table.init` instructions.
+    ///
+    /// # Note
+    ///
+    /// Active, declared and dropped element segments are empty.
+    element_segments: Vec<Arc<[Func]>>,
+    names: Arc<ModuleNames>,
+    /// The entities allocated to the [`Store`] for the instance.
+    ///
+    /// # Note
+    ///
+    /// In contrast to imported entities these are removed from
+    /// the [`Store`] together with the instance.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    owned: Vec<Extern>,
+    /// The entities of other instances or the host imported by the instance.
+    imported: Vec<Extern>,
+    /// Keeps the function bodies and function types of the instantiated module alive.
+    _code: Arc<ModuleCode>,
 }
 
 impl InstanceEntity {
-    /// Creates an uninitialized [`InstanceEntity`].
-    pub(crate) fn uninitialized() -> InstanceEntity {
+    /// Creates an uninitialized [`InstanceEntity`] for the `code` of the instantiated module.
+    ///
+    /// # Note
+    ///
+    /// The `code` is kept alive even if the instantiation is never finished since
+    /// its functions might already be referenced, e.g. by imported tables.
+    pub(crate) fn uninitialized(code: Arc<ModuleCode>) -> InstanceEntity {
         Self {
             initialized: false,
             func_types: Vec::new(),
@@ -57,11 +91,17 @@ impl InstanceEntity {
             memories: Vec::new(),
             globals: Vec::new(),
             exports: BTreeMap::new(),
+            data_segments: Vec::new(),
+            element_segments: Vec::new(),
+            names: Arc::default(),
+            owned: Vec::new(),
+            imported: Vec::new(),
+            _code: code,
         }
     }
 
-    /// Creates a new [`InstanceEntityBuilder`].
-    pub(crate) fn build() -> InstanceEntityBuilder {
+    /// Creates a new [`InstanceEntityBuilder`] for the `code` of the instantiated module.
+    pub(crate) fn build(code: Arc<ModuleCode>) -> InstanceEntityBuilder {
         InstanceEntityBuilder {
             instance: Self {
                 initialized: false,
@@ -71,6 +111,12 @@ impl InstanceEntity {
                 memories: Vec::default(),
                 globals: Vec::default(),
                 exports: BTreeMap::default(),
+                data_segments: Vec::default(),
+                element_segments: Vec::default(),
+                names: Arc::default(),
+                owned: Vec::default(),
+                imported: Vec::default(),
+                _code: code,
             },
         }
     }
@@ -110,6 +156,55 @@ impl InstanceEntity {
         self.exports.get(name).copied()
     }
 
+    /// Returns the bytes of the data segment at the `index` if any.
+    pub(crate) fn get_data_segment(&self, index: u32) -> Option<Arc<[u8]>> {
+        self.data_segments.get(index as usize).cloned()
+    }
+
+    /// Returns the functions of the element segment at the `index` if any.
+    pub(crate) fn get_element_segment(&self, index: u32) -> Option<Arc<[Func]>> {
+        self.element_segments.get(index as usize).cloned()
+    }
+
+    /// Drops the data segment at the `index` so that it is empty afterwards.
+    ///
+    /// # Note
+    ///
+    /// Does nothing if there is no data segment at the `index`.
+    pub(crate) fn drop_data_segment(&mut self, index: u32) {
+        if let Some(segment) = self.data_segments.get_mut(index as usize) {
+            *segment = Arc::from(&[][..]);
+        }
+    }
+
+    /// Drops the element segment at the `index` so that it is empty afterwards.
+    ///
+    /// # Note
+    ///
+    /// Does nothing if there is no element segment at the `index`.
+    pub(crate) fn drop_element_segment(&mut self, index: u32) {
+        if let Some(segment) = self.element_segments.get_mut(index as usize) {
+            *segment = Arc::from(&[][..]);
+        }
+    }
+
+    /// Returns the entities allocated to the [`Store`] for the [`Instance`].
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub(crate) fn owned(&self) -> &[Extern] {
+        &self.owned
+    }
+
+    /// Returns the entities imported by the [`Instance`].
+    pub(crate) fn imported(&self) -> &[Extern] {
+        &self.imported
+    }
+
+    /// Returns the debug names of the module from which the [`Instance`] has been instantiated.
+    pub(crate) fn names(&self) -> &ModuleNames {
+        &self.names
+    }
+
     /// Returns an iterator over the exports of the [`Instance`].
     ///
     /// The order of the yielded exports is not specified.
@@ -166,6 +261,11 @@ pub struct InstanceEntityBuilder {
 }
 
 impl InstanceEntityBuilder {
+    /// Sets the debug names of the [`InstanceEntity`] under construction.
+    pub(crate) fn set_names(&mut self, names: Arc<ModuleNames>) {
+        self.instance.names = names;
+    }
+
     /// Pushes a new [`Memory`] to the [`InstanceEntity`] under construction.
     pub(crate) fn push_memory(&mut self, memory: Memory) {
         self.instance.memories.push(memory);
@@ -194,6 +294,28 @@ impl InstanceEntityBuilder {
         self.instance.func_types.push(func_type);
     }
 
+    /// Pushes the bytes of a new data segment to the [`InstanceEntity`] under construction.
+    pub(crate) fn push_data_segment(&mut self, data: Arc<[u8]>) {
+        self.instance.data_segments.push(data);
+    }
+
+    /// Pushes the functions of a new element segment to the [`InstanceEntity`] under construction.
+    pub(crate) fn push_element_segment(&mut self, items: Arc<[Func]>) {
+        self.instance.element_segments.push(items);
+    }
+
+    /// Marks the [`Extern`] as allocated to the [`Store`] for the [`InstanceEntity`] under construction.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    pub(crate) fn push_owned(&mut self, owned: Extern) {
+        self.instance.owned.push(owned);
+    }
+
+    /// Marks the [`Extern`] as imported by the [`InstanceEntity`] under construction.
+    pub(crate) fn push_imported(&mut self, imported: Extern) {
+        self.instance.imported.push(imported);
+    }
+
     /// Pushes a new [`Extern`] under the given `name` to the [`InstanceEntity`] under construction.
     ///
     /// # Panics
@@ -311,6 +433,62 @@ impl Instance {
             .get_signature(index)
     }
 
+    /// Returns the bytes of the data segment at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_data_segment(&self, store: impl AsContext, index: u32) -> Option<Arc<[u8]>> {
+        store
+            .as_context()
+            .store
+            .resolve_instance(*self)
+            .get_data_segment(index)
+    }
+
+    /// Returns the functions of the element segment at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_element_segment(
+        &self,
+        store: impl AsContext,
+        index: u32,
+    ) -> Option<Arc<[Func]>> {
+        store
+            .as_context()
+            .store
+            .resolve_instance(*self)
+            .get_element_segment(index)
+    }
+
+    /// Drops the data segment at the `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn drop_data_segment(&self, mut store: impl AsContextMut, index: u32) {
+        store
+            .as_context_mut()
+            .store
+            .resolve_instance_mut(*self)
+            .drop_data_segment(index)
+    }
+
+    /// Drops the element segment at the `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn drop_element_segment(&self, mut store: impl AsContextMut, index: u32) {
+        store
+            .as_context_mut()
+            .store
+            .resolve_instance_mut(*self)
+            .drop_element_segment(index)
+    }
+
     /// Returns the value exported to the given `name` if any.
     ///
     /// # Panics
@@ -331,10 +509,7 @@ impl Instance {
     /// # Panics
     ///
     /// Panics if `store` does not own this [`Instance`].
-    pub fn exports<'a, T: 'a, E: 'a>(
-        &self,
-        store: impl Into<StoreContext<'a, T, E>>,
-    ) -> ExportsIter<'a> {
+    pub fn exports<'a, T: 'a>(&self, store: impl Into<StoreContext<'a, T>>) -> ExportsIter<'a> {
         store.into().store.resolve_instance(*self).exports()
     }
 }
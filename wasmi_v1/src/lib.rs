@@ -21,11 +21,13 @@ mod func;
 mod func_type;
 mod global;
 mod instance;
+mod limits;
 mod linker;
 mod memory;
 mod module;
 mod store;
 mod table;
+mod trap;
 
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
@@ -34,19 +36,42 @@ pub use wasmi_core as core;
 /// Defines some errors that may occur upon interaction with `wasmi`.
 pub mod errors {
     pub use super::{
+        engine::FuelError,
         func::FuncError,
         global::GlobalError,
         linker::LinkerError,
         memory::MemoryError,
-        module::{InstantiationError, ModuleError},
+        module::{DeserializeError, InstantiationError, ModuleError},
         table::TableError,
     };
 }
 
+/// The `wasmi` bytecode which is required to define custom [`FuelCosts`].
+pub mod bytecode {
+    pub use super::engine::bytecode::{
+        DropKeep,
+        FuncIdx,
+        GlobalIdx,
+        Instruction,
+        Offset,
+        Register,
+        SignatureIdx,
+        Target,
+    };
+}
+
+#[cfg(feature = "trace")]
+pub use self::engine::TraceEvent;
+#[cfg(feature = "coverage")]
+pub use self::engine::{BranchCoverage, Coverage, FuncCoverage, OffsetCoverage};
+#[cfg(feature = "debugger")]
+pub use self::engine::{DebugCall, DebugFrame, PauseReason, PausedInvocation, StepMode};
+#[cfg(feature = "profiler")]
+pub use self::engine::{FuncProfile, Profile};
 use self::{
     arena::{GuardedEntity, Index},
     engine::FuncBody,
-    func::{FuncEntity, FuncEntityInternal, FuncIdx},
+    func::{FuncEntity, FuncIdx},
     global::{GlobalEntity, GlobalIdx},
     instance::{InstanceEntity, InstanceEntityBuilder, InstanceIdx},
     memory::{MemoryEntity, MemoryIdx},
@@ -54,16 +79,18 @@ use self::{
     table::{TableEntity, TableIdx},
 };
 pub use self::{
-    engine::{Config, Engine},
+    engine::{Config, Engine, FuelCosts, InterruptHandle, ResumableCall, ResumableInvocation},
     error::Error,
     external::Extern,
     func::{Caller, Func, TypedFunc, WasmParams, WasmResults},
     func_type::FuncType,
     global::{Global, GlobalType, Mutability},
     instance::{ExportsIter, Instance},
+    limits::ResourceLimiter,
     linker::Linker,
     memory::{Memory, MemoryType},
     module::{InstancePre, Module, ModuleError, Read},
     store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
     table::{Table, TableType},
+    trap::{FrameInfo, HostError, Trap, WasmBacktrace},
 };
@@ -0,0 +1,145 @@
+use crate::{
+    core::{memory_units::Pages, TrapCode},
+    errors::{InstantiationError, MemoryError, TableError},
+    Error,
+    Trap,
+};
+use alloc::boxed::Box;
+use core::{fmt, fmt::Debug};
+
+/// Limits the resources that the Wasm modules of a [`Store`] may allocate.
+///
+/// # Note
+///
+/// - The [`ResourceLimiter`] of a [`Store`] is consulted before linear memories
+///   and tables are created or grown and before module instances are created.
+/// - Every method either allows the request by returning `Ok(true)`, denies it
+///   by returning `Ok(false)` or traps by returning `Err` with a [`TrapCode`].
+/// - Denied `memory.grow` instructions return `-1` as if the linear memory
+///   could not grow beyond its maximum while traps abort the execution.
+///   All other denied requests and traps are returned as errors.
+/// - Requests that exceed the limits declared by the Wasm module are rejected
+///   without consulting the [`ResourceLimiter`].
+/// - The methods of the [`ResourceLimiter`] are called while executing Wasm
+///   functions for which the [`Engine`] of the [`Store`] stays locked.
+///   Therefore they must not call back into this [`Engine`], e.g. by creating
+///   a [`Module`], since this would never return.
+///
+/// [`Store`]: [`crate::Store`]
+/// [`Engine`]: [`crate::Engine`]
+/// [`Module`]: [`crate::Module`]
+pub trait ResourceLimiter {
+    /// Called before a linear memory is created or grown from `current` to `desired` pages.
+    ///
+    /// # Note
+    ///
+    /// - The `current` pages are zero upon creating a linear memory.
+    /// - The `maximum` pages are the maximum declared by the linear memory if any.
+    fn memory_growing(
+        &mut self,
+        current: Pages,
+        desired: Pages,
+        maximum: Option<Pages>,
+    ) -> Result<bool, TrapCode>;
+
+    /// Called before a table is created or grown from `current` to `desired` elements.
+    ///
+    /// # Note
+    ///
+    /// - The `current` elements are zero upon creating a table.
+    /// - The `maximum` elements are the maximum declared by the table if any.
+    fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<bool, TrapCode>;
+
+    /// Called before a module instance is created with `current` live instances in the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// By default all instance creations are allowed.
+    ///
+    /// [`Store`]: [`crate::Store`]
+    fn instance_creating(&mut self, _current: usize) -> Result<bool, TrapCode> {
+        Ok(true)
+    }
+}
+
+/// The [`ResourceLimiter`] of a [`Store`].
+///
+/// [`Store`]: [`crate::Store`]
+pub(crate) struct StoreLimiter {
+    limiter: Box<dyn ResourceLimiter + Send + Sync>,
+}
+
+impl Debug for StoreLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StoreLimiter").finish_non_exhaustive()
+    }
+}
+
+impl StoreLimiter {
+    /// Creates a new [`StoreLimiter`] from the given [`ResourceLimiter`].
+    pub fn new<L>(limiter: L) -> Self
+    where
+        L: ResourceLimiter + Send + Sync + 'static,
+    {
+        Self {
+            limiter: Box::new(limiter),
+        }
+    }
+
+    /// Consults the [`ResourceLimiter`] before a linear memory is created or grown.
+    ///
+    /// # Errors
+    ///
+    /// - [`MemoryError::ResourceLimitReached`] if the [`ResourceLimiter`] denies the growth.
+    /// - [`MemoryError::ResourceLimiterTrap`] if the [`ResourceLimiter`] traps.
+    pub fn memory_growing(
+        &mut self,
+        current: Pages,
+        desired: Pages,
+        maximum: Option<Pages>,
+    ) -> Result<(), MemoryError> {
+        match self.limiter.memory_growing(current, desired, maximum) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(MemoryError::ResourceLimitReached),
+            Err(trap_code) => Err(MemoryError::ResourceLimiterTrap(trap_code)),
+        }
+    }
+
+    /// Consults the [`ResourceLimiter`] before a table is created or grown.
+    ///
+    /// # Errors
+    ///
+    /// - [`TableError::ResourceLimitReached`] if the [`ResourceLimiter`] denies the growth.
+    /// - [`TableError::ResourceLimiterTrap`] if the [`ResourceLimiter`] traps.
+    pub fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<(), TableError> {
+        match self.limiter.table_growing(current, desired, maximum) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(TableError::ResourceLimitReached),
+            Err(trap_code) => Err(TableError::ResourceLimiterTrap(trap_code)),
+        }
+    }
+
+    /// Consults the [`ResourceLimiter`] before a module instance is created.
+    ///
+    /// # Errors
+    ///
+    /// - [`InstantiationError::ResourceLimitReached`] if the [`ResourceLimiter`] denies the creation.
+    /// - A [`Trap`] if the [`ResourceLimiter`] traps.
+    pub fn instance_creating(&mut self, current: usize) -> Result<(), Error> {
+        match self.limiter.instance_creating(current) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InstantiationError::ResourceLimitReached.into()),
+            Err(trap_code) => Err(Trap::from(trap_code).into()),
+        }
+    }
+}
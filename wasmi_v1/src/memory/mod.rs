@@ -9,7 +9,10 @@ mod byte_buffer;
 use self::byte_buffer::{ByteBuffer, VirtualMemoryError};
 use super::{AsContext, AsContextMut, Index, StoreContext, StoreContextMut, Stored};
 use core::{fmt, fmt::Display};
-use wasmi_core::memory_units::{Bytes, Pages};
+use wasmi_core::{
+    memory_units::{Bytes, Pages},
+    TrapCode,
+};
 
 /// A raw index to a linear memory entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -47,6 +50,10 @@ pub enum MemoryError {
         /// The required [`MemoryType`].
         required: MemoryType,
     },
+    /// Occurs when the resource limiter of the store denies to create or grow a linear memory.
+    ResourceLimitReached,
+    /// Occurs when the resource limiter of the store traps upon creating or growing a linear memory.
+    ResourceLimiterTrap(TrapCode),
 }
 
 impl Display for MemoryError {
@@ -72,6 +79,12 @@ impl Display for MemoryError {
                     unsatisfying, required,
                 )
             }
+            Self::ResourceLimitReached => {
+                write!(f, "the resource limiter denied the linear memory growth")
+            }
+            Self::ResourceLimiterTrap(trap_code) => {
+                write!(f, "the resource limiter trapped: {}", trap_code)
+            }
         }
     }
 }
@@ -184,6 +197,21 @@ impl MemoryEntity {
         self.current_pages
     }
 
+    /// Returns the amount of pages after growing the linear memory by `additional` pages.
+    ///
+    /// Returns `None` if the linear memory would grow beyond its maximum limit.
+    fn grown_pages(&self, additional: Pages) -> Option<Pages> {
+        let maximum_pages = self
+            .memory_type()
+            .maximum_pages()
+            .unwrap_or(Self::MAX_PAGES);
+        self.current_pages()
+            .0
+            .checked_add(additional.0)
+            .filter(|&new_pages| new_pages <= maximum_pages.0)
+            .map(Pages)
+    }
+
     /// Grows the linear memory by the given amount of new pages.
     ///
     /// Returns the amount of pages before the operation upon success.
@@ -198,15 +226,8 @@ impl MemoryEntity {
             // Nothing to do in this case. Bail out early.
             return Ok(current_pages);
         }
-        let maximum_pages = self
-            .memory_type()
-            .maximum_pages()
-            .unwrap_or(Self::MAX_PAGES);
-        let new_pages = current_pages
-            .0
-            .checked_add(additional.0)
-            .filter(|&new_pages| new_pages <= maximum_pages.0)
-            .map(Pages)
+        let new_pages = self
+            .grown_pages(additional)
             .ok_or(MemoryError::OutOfBoundsGrowth)?;
         // At this point it is okay to grow the underlying virtual memory
         // by the given amount of additional pages.
@@ -275,9 +296,22 @@ impl Memory {
     }
 
     /// Creates a new linear memory to the store.
+    ///
+    /// # Errors
+    ///
+    /// - If the linear memory cannot be allocated.
+    /// - If the [`ResourceLimiter`] of the store denies the creation or traps.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     pub fn new(mut ctx: impl AsContextMut, memory_type: MemoryType) -> Result<Self, MemoryError> {
+        let store = &mut ctx.as_context_mut().store;
+        store.memory_growing(
+            Pages(0),
+            memory_type.initial_pages(),
+            memory_type.maximum_pages(),
+        )?;
         let entity = MemoryEntity::new(memory_type)?;
-        let memory = ctx.as_context_mut().store.alloc_memory(entity);
+        let memory = store.alloc_memory(entity);
         Ok(memory)
     }
 
@@ -305,21 +339,30 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If the linear memory would grow beyond its maximum limit after
-    /// the grow operation.
+    /// - If the linear memory would grow beyond its maximum limit after
+    ///   the grow operation.
+    /// - If the [`ResourceLimiter`] of the store denies the growth or traps.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     pub fn grow(
         &self,
         mut ctx: impl AsContextMut,
         additional: Pages,
     ) -> Result<Pages, MemoryError> {
-        ctx.as_context_mut()
-            .store
-            .resolve_memory_mut(*self)
-            .grow(additional)
+        let store = &mut ctx.as_context_mut().store;
+        let entity = store.resolve_memory(*self);
+        if additional != Pages(0) {
+            if let Some(desired) = entity.grown_pages(additional) {
+                let current = entity.current_pages();
+                let maximum = entity.memory_type().maximum_pages();
+                store.memory_growing(current, desired, maximum)?;
+            }
+        }
+        store.resolve_memory_mut(*self).grow(additional)
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
@@ -327,7 +370,7 @@ impl Memory {
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
-    pub fn data<'a, T: 'a, E: 'a>(&self, ctx: impl Into<StoreContext<'a, T, E>>) -> &'a [u8] {
+    pub fn data<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> &'a [u8] {
         ctx.into().store.resolve_memory(*self).data()
     }
 
@@ -336,10 +379,7 @@ impl Memory {
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
-    pub fn data_mut<'a, T: 'a, E: 'a>(
-        &self,
-        ctx: impl Into<StoreContextMut<'a, T, E>>,
-    ) -> &'a mut [u8] {
+    pub fn data_mut<'a, T: 'a>(&self, ctx: impl Into<StoreContextMut<'a, T>>) -> &'a mut [u8] {
         ctx.into().store.resolve_memory_mut(*self).data_mut()
     }
 
@@ -11,9 +11,10 @@ use super::{
     ImportName,
     InitExpr,
     Module,
+    ModuleNames,
 };
 use crate::{
-    engine::{DedupFuncType, FuncBody},
+    engine::{DedupFuncType, FuncBody, ModuleCode},
     Engine,
     FuncType,
     GlobalType,
@@ -36,8 +37,10 @@ pub struct ModuleBuilder<'engine> {
     pub(super) exports: Vec<Export>,
     pub(super) start: Option<FuncIdx>,
     pub(super) func_bodies: Vec<FuncBody>,
+    pub(super) code: ModuleCode,
     pub(super) element_segments: Vec<ElementSegment>,
     pub(super) data_segments: Vec<DataSegment>,
+    pub(super) names: ModuleNames,
 }
 
 /// The import names of the [`Module`] imports.
@@ -52,33 +55,80 @@ pub struct ModuleImports {
 /// The resources of a [`Module`] required for translating function bodies.
 #[derive(Debug, Copy, Clone)]
 pub struct ModuleResources<'a> {
-    res: &'a ModuleBuilder<'a>,
+    engine: &'a Engine,
+    func_types: &'a [DedupFuncType],
+    funcs: &'a [DedupFuncType],
+    globals: &'a [GlobalType],
 }
 
 impl<'a> ModuleResources<'a> {
     /// Returns the [`Engine`] of the [`ModuleResources`].
-    pub fn engine(&'a self) -> &'a Engine {
-        self.res.engine
+    pub fn engine(&self) -> &'a Engine {
+        self.engine
     }
 
     /// Creates new [`ModuleResources`] from the given [`ModuleBuilder`].
     pub fn new(res: &'a ModuleBuilder) -> Self {
-        Self { res }
+        Self {
+            engine: res.engine,
+            func_types: &res.func_types,
+            funcs: &res.funcs,
+            globals: &res.globals,
+        }
     }
 
     /// Returns the [`FuncType`] at the given index.
     pub fn get_func_type(&self, func_type_idx: FuncTypeIdx) -> DedupFuncType {
-        self.res.func_types[func_type_idx.into_usize()]
+        self.func_types[func_type_idx.into_usize()]
     }
 
     /// Returns the [`FuncType`] of the indexed function.
     pub fn get_type_of_func(&self, func_idx: FuncIdx) -> DedupFuncType {
-        self.res.funcs[func_idx.into_usize()]
+        self.funcs[func_idx.into_usize()]
     }
 
     /// Returns the [`GlobalType`] the the indexed global variable.
     pub fn get_type_of_global(&self, global_idx: GlobalIdx) -> GlobalType {
-        self.res.globals[global_idx.into_usize()]
+        self.globals[global_idx.into_usize()]
+    }
+}
+
+/// The owned [`ModuleResources`] of a [`Module`].
+///
+/// # Note
+///
+/// Those are shared by all lazily translated function bodies of a [`Module`]
+/// so that they can still be translated after the [`ModuleBuilder`] is gone.
+#[derive(Debug)]
+pub struct SharedModuleResources {
+    func_types: Box<[DedupFuncType]>,
+    funcs: Box<[DedupFuncType]>,
+    globals: Box<[GlobalType]>,
+}
+
+impl SharedModuleResources {
+    /// Creates new [`SharedModuleResources`] from the given [`ModuleBuilder`].
+    ///
+    /// # Note
+    ///
+    /// This must only be used once all sections of the Wasm module
+    /// preceding the Wasm code section have been processed.
+    pub fn new(res: &ModuleBuilder) -> Self {
+        Self {
+            func_types: res.func_types.clone().into(),
+            funcs: res.funcs.clone().into(),
+            globals: res.globals.clone().into(),
+        }
+    }
+
+    /// Returns the [`ModuleResources`] for translating function bodies for the `engine`.
+    pub fn as_resources<'a>(&'a self, engine: &'a Engine) -> ModuleResources<'a> {
+        ModuleResources {
+            engine,
+            func_types: &self.func_types,
+            funcs: &self.funcs,
+            globals: &self.globals,
+        }
     }
 }
 
@@ -97,13 +147,15 @@ impl<'engine> ModuleBuilder<'engine> {
             exports: Vec::new(),
             start: None,
             func_bodies: Vec::new(),
+            code: ModuleCode::new(engine),
             element_segments: Vec::new(),
             data_segments: Vec::new(),
+            names: ModuleNames::default(),
         }
     }
 
     /// Returns a shared reference to the [`Engine`] of the [`Module`] under construction.
-    pub fn engine(&self) -> &Engine {
+    pub fn engine(&self) -> &'engine Engine {
         self.engine
     }
 
@@ -129,7 +181,7 @@ impl<'engine> ModuleBuilder<'engine> {
         self.func_types.reserve_exact(func_types.len());
         for func_type in func_types {
             let func_type = func_type?;
-            let dedup = self.engine.alloc_func_type(func_type);
+            let dedup = self.code.alloc_func_type(func_type);
             self.func_types.push(dedup)
         }
         Ok(())
@@ -373,6 +425,27 @@ impl<'engine> ModuleBuilder<'engine> {
         Ok(())
     }
 
+    /// Pushes the function body of the next internal function to the [`Module`] under construction.
+    ///
+    /// # Note
+    ///
+    /// The [`Module`] takes ownership of the function body so that it is
+    /// freed from the [`Engine`] once it is no longer in use.
+    pub fn push_func_body(&mut self, func_body: FuncBody) {
+        self.code.push_func_body(func_body);
+        self.func_bodies.push(func_body);
+    }
+
+    /// Sets the debug names of the [`Module`] under construction.
+    ///
+    /// # Note
+    ///
+    /// If the Wasm binary contains multiple `name` custom sections
+    /// the names of the last one are used.
+    pub fn set_names(&mut self, names: ModuleNames) {
+        self.names = names;
+    }
+
     /// Finishes construction of the WebAssembly [`Module`].
     pub fn finish(self) -> Module {
         Module::from_builder(self)
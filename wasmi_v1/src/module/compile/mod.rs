@@ -1,14 +1,25 @@
 pub use self::block_type::BlockType;
-use super::{utils::value_type_from_wasmparser, FuncIdx, ModuleResources};
+use super::{
+    builder::SharedModuleResources,
+    utils::value_type_from_wasmparser,
+    FuncIdx,
+    ModuleResources,
+};
 use crate::{
-    engine::{DropKeep, FuncBody, FunctionBuilder},
+    engine::{DropKeep, FuncBody, FunctionBuilder, TranslatedFuncBody},
     Engine,
     ModuleError,
 };
+use alloc::sync::Arc;
 use wasmparser::{FuncValidator, FunctionBody, Operator, ValidatorResources};
 
 mod block_type;
 mod operator;
+#[cfg(feature = "std")]
+mod parallel;
+
+#[cfg(feature = "std")]
+pub use self::parallel::{translate_parallel, PendingFuncBody};
 
 /// Translates the Wasm bytecode into `wasmi` bytecode.
 ///
@@ -30,7 +41,91 @@ pub fn translate<'parser>(
     validator: FuncValidator<ValidatorResources>,
     res: ModuleResources<'parser>,
 ) -> Result<FuncBody, ModuleError> {
-    FunctionTranslator::new(engine, func, func_body, validator, res).translate()
+    let func_body =
+        FunctionTranslator::new(engine, func, func_body, Some(validator), res).translate()?;
+    Ok(engine.alloc_func_body(func_body))
+}
+
+/// Validates the Wasm bytecode of a function body without translating it.
+///
+/// # Errors
+///
+/// If the function body fails to validate.
+pub fn validate(
+    func_body: FunctionBody,
+    mut validator: FuncValidator<ValidatorResources>,
+) -> Result<(), ModuleError> {
+    let mut reader = func_body.get_locals_reader()?;
+    let len_locals = reader.get_count();
+    for _ in 0..len_locals {
+        let offset = reader.original_position();
+        let (amount, value_type) = reader.read()?;
+        validator.define_locals(offset, amount, value_type)?;
+    }
+    let mut reader = func_body.get_operators_reader()?;
+    while !reader.eof() {
+        let (operator, offset) = reader.read_with_offset()?;
+        validator.op(offset, &operator)?;
+    }
+    reader.ensure_end()?;
+    validator.finish(reader.original_position())?;
+    Ok(())
+}
+
+/// A validated Wasm function body that is translated into `wasmi` bytecode upon its first execution.
+#[derive(Debug, Clone)]
+pub struct UncompiledFuncBody {
+    /// The index of the function.
+    func: FuncIdx,
+    /// The original Wasm offset of the function body.
+    offset: usize,
+    /// The Wasm bytecode of the function body.
+    bytes: Arc<[u8]>,
+    /// The resources of the module the function belongs to.
+    res: Arc<SharedModuleResources>,
+}
+
+impl UncompiledFuncBody {
+    /// Creates a new [`UncompiledFuncBody`] from the already validated `func_body`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes of the function body cannot be read.
+    pub fn new(
+        func: FuncIdx,
+        func_body: FunctionBody,
+        res: Arc<SharedModuleResources>,
+    ) -> Result<Self, ModuleError> {
+        let mut reader = func_body.get_binary_reader();
+        let offset = reader.original_position();
+        let bytes = reader.read_bytes(reader.bytes_remaining())?.into();
+        Ok(Self {
+            func,
+            offset,
+            bytes,
+            res,
+        })
+    }
+
+    /// Translates the function body into `wasmi` bytecode for the `engine`.
+    ///
+    /// # Panics
+    ///
+    /// If the translation fails which is not expected
+    /// since the function body has already been validated.
+    pub fn translate(&self, engine: &Engine) -> FuncBody {
+        let func_body = FunctionBody::new(self.offset, &self.bytes);
+        let res = self.res.as_resources(engine);
+        let func_body = FunctionTranslator::new(engine, self.func, func_body, None, res)
+            .translate()
+            .unwrap_or_else(|error| {
+                panic!(
+                    "failed to translate validated function body of {:?}: {}",
+                    self.func, error
+                )
+            });
+        engine.alloc_func_body(func_body)
+    }
 }
 
 /// Translates Wasm bytecode into `wasmi` bytecode for a single Wasm function.
@@ -44,7 +139,11 @@ struct FunctionTranslator<'engine, 'parser> {
     /// The interface to incrementally build up the `wasmi` bytecode function.
     func_builder: FunctionBuilder<'engine, 'parser>,
     /// The Wasm validator.
-    validator: FuncValidator<ValidatorResources>,
+    ///
+    /// # Note
+    ///
+    /// This is `None` if the function body has already been validated.
+    validator: Option<FuncValidator<ValidatorResources>>,
     /// The `wasmi` module resources.
     ///
     /// Provides immutable information about the translated Wasm module
@@ -58,7 +157,7 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         engine: &'engine Engine,
         func: FuncIdx,
         func_body: FunctionBody<'parser>,
-        validator: FuncValidator<ValidatorResources>,
+        validator: Option<FuncValidator<ValidatorResources>>,
         res: ModuleResources<'parser>,
     ) -> Self {
         let func_builder = FunctionBuilder::new(engine, func, res);
@@ -73,15 +172,15 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
     }
 
     /// Starts translation of the Wasm stream into `wasmi` bytecode.
-    fn translate(mut self) -> Result<FuncBody, ModuleError> {
+    fn translate(mut self) -> Result<TranslatedFuncBody, ModuleError> {
         self.translate_locals()?;
         self.translate_operators()?;
         let func_body = self.finish();
         Ok(func_body)
     }
 
-    /// Finishes construction of the function and returns its [`FuncBody`].
-    fn finish(self) -> FuncBody {
+    /// Finishes construction of the function and returns its [`TranslatedFuncBody`].
+    fn finish(self) -> TranslatedFuncBody {
         self.func_builder.finish()
     }
 
@@ -92,7 +191,9 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         for _ in 0..len_locals {
             let offset = reader.original_position();
             let (amount, value_type) = reader.read()?;
-            self.validator.define_locals(offset, amount, value_type)?;
+            if let Some(validator) = &mut self.validator {
+                validator.define_locals(offset, amount, value_type)?;
+            }
             let value_type = value_type_from_wasmparser(&value_type)?;
             self.func_builder.translate_locals(amount, value_type)?;
         }
@@ -104,11 +205,16 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         let mut reader = self.func_body.get_operators_reader()?;
         while !reader.eof() {
             let (operator, offset) = reader.read_with_offset()?;
-            self.validator.op(offset, &operator)?;
+            if let Some(validator) = &mut self.validator {
+                validator.op(offset, &operator)?;
+            }
+            self.func_builder.update_wasm_offset(offset);
             self.translate_operator(operator)?;
         }
         reader.ensure_end()?;
-        self.validator.finish(reader.original_position())?;
+        if let Some(validator) = &mut self.validator {
+            validator.finish(reader.original_position())?;
+        }
         Ok(())
     }
 
@@ -135,10 +241,11 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::CallIndirect { index, table_index } => {
                 self.translate_call_indirect(index, table_index)
             }
-            Operator::ReturnCall { .. }
-            | Operator::ReturnCallIndirect { .. }
-            | Operator::Delegate { .. }
-            | Operator::CatchAll => unsupported_error(),
+            Operator::ReturnCall { function_index } => self.translate_return_call(function_index),
+            Operator::ReturnCallIndirect { index, table_index } => {
+                self.translate_return_call_indirect(index, table_index)
+            }
+            Operator::Delegate { .. } | Operator::CatchAll => unsupported_error(),
             Operator::Drop => self.translate_drop(),
             Operator::Select => self.translate_select(),
             Operator::TypedSelect { ty: _ } => unsupported_error(),
@@ -315,14 +422,17 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
             Operator::I64Extend8S => self.translate_i64_sign_extend8(),
             Operator::I64Extend16S => self.translate_i64_sign_extend16(),
             Operator::I64Extend32S => self.translate_i64_sign_extend32(),
-            Operator::MemoryInit { .. }
-            | Operator::DataDrop { .. }
-            | Operator::MemoryCopy { .. }
-            | Operator::MemoryFill { .. }
-            | Operator::TableInit { .. }
-            | Operator::ElemDrop { .. }
-            | Operator::TableCopy { .. }
-            | Operator::TableFill { .. }
+            Operator::MemoryInit { segment, mem } => self.translate_memory_init(segment, mem),
+            Operator::DataDrop { segment } => self.translate_data_drop(segment),
+            Operator::MemoryCopy { src, dst } => self.translate_memory_copy(dst, src),
+            Operator::MemoryFill { mem } => self.translate_memory_fill(mem),
+            Operator::TableInit { segment, table } => self.translate_table_init(segment, table),
+            Operator::ElemDrop { segment } => self.translate_elem_drop(segment),
+            Operator::TableCopy {
+                dst_table,
+                src_table,
+            } => self.translate_table_copy(dst_table, src_table),
+            Operator::TableFill { .. }
             | Operator::TableGet { .. }
             | Operator::TableSet { .. }
             | Operator::TableGrow { .. }
@@ -84,6 +84,23 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         Ok(())
     }
 
+    /// Translate a Wasm `return_call` instruction.
+    pub fn translate_return_call(&mut self, func_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_return_call(FuncIdx(func_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `return_call_indirect` instruction.
+    pub fn translate_return_call_indirect(
+        &mut self,
+        func_type_idx: u32,
+        table_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_return_call_indirect(FuncTypeIdx(func_type_idx), TableIdx(table_idx))?;
+        Ok(())
+    }
+
     /// Translate a Wasm `local.get` instruction.
     pub fn translate_local_get(&mut self, local_idx: u32) -> Result<(), ModuleError> {
         self.func_builder.translate_local_get(local_idx)?;
@@ -406,6 +423,69 @@ impl<'engine, 'parser> FunctionTranslator<'engine, 'parser> {
         Ok(())
     }
 
+    /// Translate a Wasm `memory.init` instruction.
+    pub fn translate_memory_init(
+        &mut self,
+        segment_idx: u32,
+        memory_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_init(segment_idx.into(), MemoryIdx(memory_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `data.drop` instruction.
+    pub fn translate_data_drop(&mut self, segment_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_data_drop(segment_idx.into())?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `memory.copy` instruction.
+    pub fn translate_memory_copy(
+        &mut self,
+        dst_memory_idx: u32,
+        src_memory_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_copy(MemoryIdx(dst_memory_idx), MemoryIdx(src_memory_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `memory.fill` instruction.
+    pub fn translate_memory_fill(&mut self, memory_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_memory_fill(MemoryIdx(memory_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.init` instruction.
+    pub fn translate_table_init(
+        &mut self,
+        segment_idx: u32,
+        table_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_init(segment_idx.into(), TableIdx(table_idx))?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `elem.drop` instruction.
+    pub fn translate_elem_drop(&mut self, segment_idx: u32) -> Result<(), ModuleError> {
+        self.func_builder.translate_elem_drop(segment_idx.into())?;
+        Ok(())
+    }
+
+    /// Translate a Wasm `table.copy` instruction.
+    pub fn translate_table_copy(
+        &mut self,
+        dst_table_idx: u32,
+        src_table_idx: u32,
+    ) -> Result<(), ModuleError> {
+        self.func_builder
+            .translate_table_copy(TableIdx(dst_table_idx), TableIdx(src_table_idx))?;
+        Ok(())
+    }
+
     /// Translate a Wasm `i32.const` instruction.
     pub fn translate_i32_const(&mut self, value: i32) -> Result<(), ModuleError> {
         self.func_builder.translate_i32_const(value)?;
@@ -0,0 +1,122 @@
+//! Parallel validation and translation of Wasm function bodies.
+
+use super::FunctionTranslator;
+use crate::{
+    engine::TranslatedFuncBody,
+    module::{FuncIdx, ModuleResources},
+    Engine,
+    ModuleError,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::{panic, sync::Mutex, thread};
+use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
+
+/// A Wasm function body of the code section that is yet to be validated and translated.
+pub struct PendingFuncBody {
+    /// The index of the function.
+    func: FuncIdx,
+    /// The original Wasm offset of the function body.
+    offset: usize,
+    /// The Wasm bytecode of the function body.
+    bytes: Box<[u8]>,
+    /// The Wasm validator of the function body.
+    validator: FuncValidator<ValidatorResources>,
+}
+
+impl PendingFuncBody {
+    /// Creates a new [`PendingFuncBody`] for `func_body`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes of the function body cannot be read.
+    pub fn new(
+        func: FuncIdx,
+        func_body: FunctionBody,
+        validator: FuncValidator<ValidatorResources>,
+    ) -> Result<Self, ModuleError> {
+        let mut reader = func_body.get_binary_reader();
+        let offset = reader.original_position();
+        let bytes = reader.read_bytes(reader.bytes_remaining())?.into();
+        Ok(Self {
+            func,
+            offset,
+            bytes,
+            validator,
+        })
+    }
+
+    /// Validates and translates the function body into `wasmi` bytecode.
+    fn translate(
+        self,
+        engine: &Engine,
+        res: ModuleResources,
+    ) -> Result<TranslatedFuncBody, ModuleError> {
+        let func_body = FunctionBody::new(self.offset, &self.bytes);
+        FunctionTranslator::new(engine, self.func, func_body, Some(self.validator), res).translate()
+    }
+}
+
+/// Validates and translates the `func_bodies` on multiple threads.
+///
+/// Returns the translated function bodies in the order of `func_bodies`.
+///
+/// # Errors
+///
+/// If any of the function bodies fails to validate.
+/// In this case the error of the first invalid function body is returned.
+pub fn translate_parallel(
+    engine: &Engine,
+    func_bodies: Vec<PendingFuncBody>,
+    res: ModuleResources,
+) -> Result<Vec<TranslatedFuncBody>, ModuleError> {
+    let len_func_bodies = func_bodies.len();
+    let len_threads = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+        .min(len_func_bodies);
+    // Function bodies are handed out in the order of the code section so that
+    // all function bodies preceding an invalid function body are processed.
+    // This way the reported error does not depend on thread scheduling.
+    let queue = Mutex::new(func_bodies.into_iter().enumerate());
+    let failed = AtomicBool::new(false);
+    let next = || {
+        if failed.load(Ordering::Relaxed) {
+            return None;
+        }
+        queue
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .next()
+    };
+    let mut results = thread::scope(|scope| {
+        let workers = (0..len_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    while let Some((index, func_body)) = next() {
+                        let result = func_body.translate(engine, res);
+                        if result.is_err() {
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                        results.push((index, result));
+                    }
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|payload| panic::resume_unwind(payload))
+            })
+            .collect::<Vec<_>>()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -1,58 +1,72 @@
 use super::{InitExpr, MemoryIdx, ModuleError};
+use alloc::sync::Arc;
 
 /// A linear memory data segment within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
 #[derive(Debug)]
 pub struct DataSegment {
-    memory_index: MemoryIdx,
-    offset: InitExpr,
-    data: Box<[u8]>,
+    kind: DataSegmentKind,
+    data: Arc<[u8]>,
+}
+
+/// The kind of a [`DataSegment`].
+#[derive(Debug)]
+pub enum DataSegmentKind {
+    /// A passive data segment used by `memory.init` instructions.
+    Passive,
+    /// An active data segment initializing a linear memory upon instantiation.
+    Active {
+        /// The index of the initialized linear memory.
+        memory_index: MemoryIdx,
+        /// The offset expression at which the linear memory is initialized.
+        offset: InitExpr,
+    },
 }
 
 impl TryFrom<wasmparser::Data<'_>> for DataSegment {
     type Error = ModuleError;
 
     fn try_from(data: wasmparser::Data<'_>) -> Result<Self, Self::Error> {
-        let (memory_index, offset) = match data.kind {
+        let kind = match data.kind {
             wasmparser::DataKind::Active {
                 memory_index,
                 init_expr,
-            } => {
-                let memory_index = MemoryIdx(memory_index);
-                let offset = InitExpr::try_from(init_expr)?;
-                (memory_index, offset)
-            }
-            wasmparser::DataKind::Passive => {
-                return Err(ModuleError::unsupported(
-                    "encountered unsupported passive data segment",
-                ))
-            }
+            } => DataSegmentKind::Active {
+                memory_index: MemoryIdx(memory_index),
+                offset: InitExpr::try_from(init_expr)?,
+            },
+            wasmparser::DataKind::Passive => DataSegmentKind::Passive,
         };
         let data = data.data.into();
-        Ok(DataSegment {
-            memory_index,
-            offset,
-            data,
-        })
+        Ok(DataSegment { kind, data })
     }
 }
 
 impl DataSegment {
-    /// Returns the index of the [`Memory`] manipulated by the [`DataSegment`].
-    ///
-    /// [`Memory`]: [`crate::Memory`]
-    pub fn memory_index(&self) -> MemoryIdx {
-        self.memory_index
+    /// Creates a new [`DataSegment`] of the given `kind` with `data`.
+    pub fn new(kind: DataSegmentKind, data: Arc<[u8]>) -> Self {
+        Self { kind, data }
     }
 
-    /// Returns the offset expression of the [`DataSegment`].
-    pub fn offset(&self) -> &InitExpr {
-        &self.offset
+    /// Returns the [`DataSegmentKind`] of the [`DataSegment`].
+    pub fn kind(&self) -> &DataSegmentKind {
+        &self.kind
     }
 
-    /// Returns the element items of the [`DataSegment`].
+    /// Returns the bytes of the [`DataSegment`].
     pub fn data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    /// Returns a shared reference to the bytes of the [`DataSegment`].
+    ///
+    /// # Note
+    ///
+    /// Instances share the bytes of passive data segments with their [`Module`].
+    ///
+    /// [`Module`]: [`super::Module`]
+    pub fn shared_data(&self) -> Arc<[u8]> {
+        self.data.clone()
+    }
 }
@@ -7,11 +7,27 @@ use super::{FuncIdx, InitExpr, TableIdx};
 /// [`Module`]: [`super::Module`]
 #[derive(Debug)]
 pub struct ElementSegment {
-    table_index: TableIdx,
-    offset: InitExpr,
+    kind: ElementSegmentKind,
     items: Box<[FuncIdx]>,
 }
 
+/// The kind of an [`ElementSegment`].
+#[derive(Debug)]
+pub enum ElementSegmentKind {
+    /// A passive element segment used by `table.init` instructions.
+    Passive,
+    /// An active element segment initializing a table upon instantiation.
+    Active {
+        /// The index of the initialized table.
+        table_index: TableIdx,
+        /// The offset expression at which the table is initialized.
+        offset: InitExpr,
+    },
+    /// A declared element segment that is neither available at runtime
+    /// nor initializing a table.
+    Declared,
+}
+
 impl TryFrom<wasmparser::Element<'_>> for ElementSegment {
     type Error = ModuleError;
 
@@ -19,20 +35,16 @@ impl TryFrom<wasmparser::Element<'_>> for ElementSegment {
         if !matches!(element.ty, wasmparser::Type::FuncRef) {
             return Err(ModuleError::unsupported(element.ty));
         }
-        let (table_index, offset) = match element.kind {
+        let kind = match element.kind {
             wasmparser::ElementKind::Active {
                 table_index,
                 init_expr,
-            } => {
-                let table_index = TableIdx(table_index);
-                let offset = InitExpr::try_from(init_expr)?;
-                (table_index, offset)
-            }
-            wasmparser::ElementKind::Passive | wasmparser::ElementKind::Declared => {
-                return Err(ModuleError::unsupported(
-                    "encountered unsupported passive or declared element segment",
-                ))
-            }
+            } => ElementSegmentKind::Active {
+                table_index: TableIdx(table_index),
+                offset: InitExpr::try_from(init_expr)?,
+            },
+            wasmparser::ElementKind::Passive => ElementSegmentKind::Passive,
+            wasmparser::ElementKind::Declared => ElementSegmentKind::Declared,
         };
         let items = element
             .items
@@ -46,25 +58,19 @@ impl TryFrom<wasmparser::Element<'_>> for ElementSegment {
             })
             .collect::<Result<Vec<_>, ModuleError>>()?
             .into_boxed_slice();
-        Ok(ElementSegment {
-            table_index,
-            offset,
-            items,
-        })
+        Ok(ElementSegment { kind, items })
     }
 }
 
 impl ElementSegment {
-    /// Returns the index of the [`Table`] manipulated by the [`ElementSegment`].
-    ///
-    /// [`Table`]: [`crate::Table`]
-    pub fn table_index(&self) -> TableIdx {
-        self.table_index
+    /// Creates a new [`ElementSegment`] of the given `kind` with `items`.
+    pub fn new(kind: ElementSegmentKind, items: Box<[FuncIdx]>) -> Self {
+        Self { kind, items }
     }
 
-    /// Returns the offset expression of the [`ElementSegment`].
-    pub fn offset(&self) -> &InitExpr {
-        &self.offset
+    /// Returns the [`ElementSegmentKind`] of the [`ElementSegment`].
+    pub fn kind(&self) -> &ElementSegmentKind {
+        &self.kind
     }
 
     /// Returns the element items of the [`ElementSegment`].
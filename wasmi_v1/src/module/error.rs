@@ -1,4 +1,4 @@
-use super::ReadError;
+use super::{DeserializeError, FuncIdx, ReadError};
 use core::{
     fmt,
     fmt::{Debug, Display},
@@ -13,6 +13,18 @@ pub enum ModuleError {
     Parser(ParserError),
     /// Encountered when unsupported Wasm proposal definitions are used.
     Unsupported { message: String },
+    /// Encountered when a serialized module cannot be deserialized.
+    Deserialize(DeserializeError),
+    /// Encountered when floating point types or instructions are used
+    /// while floating point support is disabled.
+    FloatingPoint {
+        /// The index of the function using floating point if any.
+        func: Option<u32>,
+        /// The offset of the floating point definition within the Wasm binary.
+        offset: usize,
+        /// The description of the floating point definition.
+        definition: String,
+    },
 }
 
 impl ModuleError {
@@ -21,6 +33,18 @@ impl ModuleError {
             message: format!("{:?}", definition),
         }
     }
+
+    pub(crate) fn floating_point(
+        func: Option<FuncIdx>,
+        offset: usize,
+        definition: impl Into<String>,
+    ) -> Self {
+        Self::FloatingPoint {
+            func: func.map(FuncIdx::into_u32),
+            offset,
+            definition: definition.into(),
+        }
+    }
 }
 
 impl Display for ModuleError {
@@ -35,6 +59,18 @@ impl Display for ModuleError {
                     message
                 )
             }
+            ModuleError::Deserialize(error) => Display::fmt(error, f),
+            ModuleError::FloatingPoint {
+                func,
+                offset,
+                definition,
+            } => {
+                write!(f, "encountered denied floating point {}", definition)?;
+                if let Some(func) = func {
+                    write!(f, " in function {}", func)?;
+                }
+                write!(f, " at offset {:#x}", offset)
+            }
         }
     }
 }
@@ -50,3 +86,9 @@ impl From<ParserError> for ModuleError {
         Self::Parser(error)
     }
 }
+
+impl From<DeserializeError> for ModuleError {
+    fn from(error: DeserializeError) -> Self {
+        Self::Deserialize(error)
+    }
+}
@@ -77,6 +77,11 @@ impl TryFrom<wasmparser::Export<'_>> for Export {
 }
 
 impl Export {
+    /// Creates a new [`Export`] of the `external` item under the name `field`.
+    pub fn new(field: Box<str>, external: External) -> Self {
+        Self { field, external }
+    }
+
     /// Returns the field name of the [`Export`].
     pub fn field(&self) -> &str {
         &self.field
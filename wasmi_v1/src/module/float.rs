@@ -0,0 +1,242 @@
+//! Rejection of floating point types and instructions.
+//!
+//! # Note
+//!
+//! This is used if floating point support is disabled via the [`Config`].
+//! Every definition that uses floating point types or instructions is
+//! reported with the index of its function and its offset within the Wasm binary.
+//!
+//! [`Config`]: [`crate::Config`]
+
+use super::{FuncIdx, ModuleBuilder, ModuleError};
+use crate::{core::ValueType, engine::DedupFuncType};
+use wasmparser::{
+    FunctionBody,
+    FunctionSectionReader,
+    GlobalSectionReader,
+    ImportSectionEntryType,
+    ImportSectionReader,
+    Operator,
+    Type,
+    TypeOrFuncType,
+};
+
+/// Returns `true` if the Wasm value type is a floating point type.
+fn is_float(ty: Type) -> bool {
+    matches!(ty, Type::F32 | Type::F64)
+}
+
+/// Returns `true` if the function type at `type_idx` uses floating point types.
+///
+/// # Note
+///
+/// Returns `false` for invalid function type indices which are reported by Wasm validation.
+fn is_float_func_type(res: &ModuleBuilder, type_idx: u32) -> bool {
+    res.func_types
+        .get(type_idx as usize)
+        .map(|&func_type| uses_floats(res, func_type))
+        .unwrap_or(false)
+}
+
+/// Returns `true` if the [`DedupFuncType`] has floating point parameters or results.
+fn uses_floats(res: &ModuleBuilder, func_type: DedupFuncType) -> bool {
+    res.engine.resolve_func_type(func_type, |func_type| {
+        func_type
+            .params()
+            .iter()
+            .chain(func_type.results())
+            .any(|ty| matches!(ty, ValueType::F32 | ValueType::F64))
+    })
+}
+
+/// Returns `true` if the Wasm operator uses floating point types.
+fn is_float_operator(res: &ModuleBuilder, op: &Operator) -> bool {
+    match op {
+        Operator::Block { ty } | Operator::Loop { ty } | Operator::If { ty } => match *ty {
+            TypeOrFuncType::Type(ty) => is_float(ty),
+            TypeOrFuncType::FuncType(type_idx) => is_float_func_type(res, type_idx),
+        },
+        Operator::CallIndirect { index, .. } | Operator::ReturnCallIndirect { index, .. } => {
+            is_float_func_type(res, *index)
+        }
+        Operator::F32Load { .. }
+        | Operator::F64Load { .. }
+        | Operator::F32Store { .. }
+        | Operator::F64Store { .. }
+        | Operator::F32Const { .. }
+        | Operator::F64Const { .. }
+        | Operator::F32Eq
+        | Operator::F32Ne
+        | Operator::F32Lt
+        | Operator::F32Gt
+        | Operator::F32Le
+        | Operator::F32Ge
+        | Operator::F64Eq
+        | Operator::F64Ne
+        | Operator::F64Lt
+        | Operator::F64Gt
+        | Operator::F64Le
+        | Operator::F64Ge
+        | Operator::F32Abs
+        | Operator::F32Neg
+        | Operator::F32Ceil
+        | Operator::F32Floor
+        | Operator::F32Trunc
+        | Operator::F32Nearest
+        | Operator::F32Sqrt
+        | Operator::F32Add
+        | Operator::F32Sub
+        | Operator::F32Mul
+        | Operator::F32Div
+        | Operator::F32Min
+        | Operator::F32Max
+        | Operator::F32Copysign
+        | Operator::F64Abs
+        | Operator::F64Neg
+        | Operator::F64Ceil
+        | Operator::F64Floor
+        | Operator::F64Trunc
+        | Operator::F64Nearest
+        | Operator::F64Sqrt
+        | Operator::F64Add
+        | Operator::F64Sub
+        | Operator::F64Mul
+        | Operator::F64Div
+        | Operator::F64Min
+        | Operator::F64Max
+        | Operator::F64Copysign
+        | Operator::I32TruncF32S
+        | Operator::I32TruncF32U
+        | Operator::I32TruncF64S
+        | Operator::I32TruncF64U
+        | Operator::I64TruncF32S
+        | Operator::I64TruncF32U
+        | Operator::I64TruncF64S
+        | Operator::I64TruncF64U
+        | Operator::F32ConvertI32S
+        | Operator::F32ConvertI32U
+        | Operator::F32ConvertI64S
+        | Operator::F32ConvertI64U
+        | Operator::F32DemoteF64
+        | Operator::F64ConvertI32S
+        | Operator::F64ConvertI32U
+        | Operator::F64ConvertI64S
+        | Operator::F64ConvertI64U
+        | Operator::F64PromoteF32
+        | Operator::I32ReinterpretF32
+        | Operator::I64ReinterpretF64
+        | Operator::F32ReinterpretI32
+        | Operator::F64ReinterpretI64
+        | Operator::I32TruncSatF32S
+        | Operator::I32TruncSatF32U
+        | Operator::I32TruncSatF64S
+        | Operator::I32TruncSatF64U
+        | Operator::I64TruncSatF32S
+        | Operator::I64TruncSatF32U
+        | Operator::I64TruncSatF64S
+        | Operator::I64TruncSatF64U => true,
+        _ => false,
+    }
+}
+
+/// Rejects imported functions with floating point signatures and imported floating point globals.
+///
+/// # Errors
+///
+/// If an import uses floating point types.
+pub fn check_imports(
+    res: &ModuleBuilder,
+    mut section: ImportSectionReader,
+) -> Result<(), ModuleError> {
+    let mut func = res.imports.funcs.len() as u32;
+    for _ in 0..section.get_count() {
+        let offset = section.original_position();
+        match section.read()?.ty {
+            ImportSectionEntryType::Function(type_idx) => {
+                if is_float_func_type(res, type_idx) {
+                    return Err(ModuleError::floating_point(
+                        Some(FuncIdx(func)),
+                        offset,
+                        "signature",
+                    ));
+                }
+                func += 1;
+            }
+            ImportSectionEntryType::Global(global_type) if is_float(global_type.content_type) => {
+                return Err(ModuleError::floating_point(None, offset, "imported global"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Rejects internal functions with floating point signatures.
+///
+/// # Errors
+///
+/// If a function signature uses floating point types.
+pub fn check_funcs(
+    res: &ModuleBuilder,
+    mut section: FunctionSectionReader,
+) -> Result<(), ModuleError> {
+    let len_func_imports = res.imports.funcs.len() as u32;
+    for index in 0..section.get_count() {
+        let offset = section.original_position();
+        if is_float_func_type(res, section.read()?) {
+            return Err(ModuleError::floating_point(
+                Some(FuncIdx(len_func_imports + index)),
+                offset,
+                "signature",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Rejects floating point global variables.
+///
+/// # Errors
+///
+/// If a global variable is of floating point type.
+pub fn check_globals(mut section: GlobalSectionReader) -> Result<(), ModuleError> {
+    for _ in 0..section.get_count() {
+        let offset = section.original_position();
+        if is_float(section.read()?.ty.content_type) {
+            return Err(ModuleError::floating_point(None, offset, "global"));
+        }
+    }
+    Ok(())
+}
+
+/// Rejects floating point local variables and instructions of the function body of `func`.
+///
+/// # Errors
+///
+/// If the function body uses floating point types or instructions.
+pub fn check_func_body(
+    res: &ModuleBuilder,
+    func: FuncIdx,
+    func_body: &FunctionBody,
+) -> Result<(), ModuleError> {
+    let mut locals = func_body.get_locals_reader()?;
+    for _ in 0..locals.get_count() {
+        let offset = locals.original_position();
+        let (_, ty) = locals.read()?;
+        if is_float(ty) {
+            return Err(ModuleError::floating_point(Some(func), offset, "local"));
+        }
+    }
+    let mut operators = func_body.get_operators_reader()?;
+    while !operators.eof() {
+        let (op, offset) = operators.read_with_offset()?;
+        if is_float_operator(res, &op) {
+            return Err(ModuleError::floating_point(
+                Some(func),
+                offset,
+                format!("instruction {:?}", op),
+            ));
+        }
+    }
+    Ok(())
+}
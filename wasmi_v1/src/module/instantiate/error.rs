@@ -54,6 +54,8 @@ pub enum InstantiationError {
         /// The index of the found `start` function.
         index: u32,
     },
+    /// Caused when the resource limiter of the store denies the creation of the instance.
+    ResourceLimitReached,
 }
 
 #[cfg(feature = "std")]
@@ -97,6 +99,9 @@ impl Display for InstantiationError {
             }
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
+            Self::ResourceLimitReached => {
+                write!(f, "the resource limiter denied the instance creation")
+            }
         }
     }
 }
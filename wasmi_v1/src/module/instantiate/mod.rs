@@ -2,13 +2,15 @@ mod error;
 mod pre;
 
 pub use self::{error::InstantiationError, pre::InstancePre};
-use super::{export, InitExpr, Module, ModuleImportType};
+use super::{export, DataSegmentKind, ElementSegmentKind, InitExpr, Module, ModuleImportType};
 use crate::{
-    module::{init_expr::InitExprOperand, DEFAULT_MEMORY_INDEX},
+    errors::{MemoryError, TableError},
+    module::{init_expr::InitExprOperand, FuncIdx, DEFAULT_MEMORY_INDEX},
     AsContext,
     AsContextMut,
     Error,
     Extern,
+    Func,
     FuncEntity,
     FuncType,
     Global,
@@ -22,6 +24,7 @@ use crate::{
     Table,
     TableType,
 };
+use alloc::sync::Arc;
 use wasmi_core::{Value, ValueType, F32, F64};
 
 impl Module {
@@ -37,9 +40,17 @@ impl Module {
     ///
     /// # Errors
     ///
-    /// If the given `externals` do not satisfy the required imports, e.g. if an externally
-    /// provided [`Func`] has a different function signature than required by the module import.
+    /// - If the given `externals` do not satisfy the required imports, e.g. if an externally
+    ///   provided [`Func`] has a different function signature than required by the module import.
+    /// - If the initialization of table elements or linear memory data fails.
+    /// - If the [`ResourceLimiter`] of the [`Store`] denies the creation of the [`Instance`],
+    ///   its tables or linear memories or traps.
     ///
+    /// In all cases all entities allocated for the [`Instance`] are dropped from the [`Store`].
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    ///
+    /// [`Store`]: struct.Store.html
     /// [`Linker`]: struct.Linker.html
     /// [`Func`]: [`crate::v1::Func`]
     pub(crate) fn instantiate<I>(
@@ -50,25 +61,53 @@ impl Module {
     where
         I: IntoIterator<Item = Extern>,
     {
-        let handle = context.as_context_mut().store.alloc_instance();
-        let mut builder = InstanceEntity::build();
-
-        self.extract_func_types(&mut context, &mut builder);
-        self.extract_imports(&mut context, &mut builder, externals)?;
-        self.extract_functions(&mut context, &mut builder, handle);
-        self.extract_tables(&mut context, &mut builder);
-        self.extract_memories(&mut context, &mut builder);
-        self.extract_globals(&mut context, &mut builder);
-        self.extract_exports(&mut builder);
-
-        self.initialize_table_elements(&mut context, &mut builder)?;
-        self.initialize_memory_data(&mut context, &mut builder)?;
-
+        context.as_context_mut().store.instance_creating()?;
+        let handle = context
+            .as_context_mut()
+            .store
+            .alloc_instance(self.code.clone());
+        let mut builder = InstanceEntity::build(self.code.clone());
+        builder.set_names(self.names.clone());
+        if let Err(error) = self.build_instance(&mut context, &mut builder, handle, externals) {
+            context
+                .as_context_mut()
+                .store
+                .drop_uninitialized_instance(handle, &builder);
+            return Err(error);
+        }
         // At this point the module instantiation is nearly done.
         // The only thing that is missing is to run the `start` function.
         Ok(InstancePre::new(handle, self, builder))
     }
 
+    /// Allocates and initializes the entities of the [`Instance`] under construction.
+    ///
+    /// # Errors
+    ///
+    /// See [`Module::instantiate`].
+    fn build_instance<I>(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+        handle: Instance,
+        externals: I,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Extern>,
+    {
+        self.extract_func_types(context, builder);
+        self.extract_imports(context, builder, externals)?;
+        self.extract_functions(context, builder, handle);
+        self.extract_tables(context, builder)?;
+        self.extract_memories(context, builder)?;
+        self.extract_globals(context, builder);
+        self.extract_exports(builder);
+
+        self.initialize_table_elements(context, builder)?;
+        self.initialize_memory_data(context, builder)?;
+        Ok(())
+    }
+
     /// Extracts the Wasm function signatures from the
     /// module and stores them into the [`Store`].
     ///
@@ -136,16 +175,19 @@ impl Module {
                             expected: *expected_signature,
                         });
                     }
+                    builder.push_imported(Extern::Func(func));
                     builder.push_func(func);
                 }
                 (ModuleImportType::Table(required), Extern::Table(table)) => {
                     let imported = table.table_type(context.as_context());
                     imported.satisfies(required)?;
+                    builder.push_imported(Extern::Table(table));
                     builder.push_table(table);
                 }
                 (ModuleImportType::Memory(required), Extern::Memory(memory)) => {
                     let imported = memory.memory_type(context.as_context());
                     imported.satisfies(required)?;
+                    builder.push_imported(Extern::Memory(memory));
                     builder.push_memory(memory);
                 }
                 (ModuleImportType::Global(expected), Extern::Global(global)) => {
@@ -154,6 +196,7 @@ impl Module {
                     if expected != actual {
                         return Err(InstantiationError::GlobalTypeMismatch { expected, actual });
                     }
+                    builder.push_imported(Extern::Global(global));
                     builder.push_global(global);
                 }
                 (expected_import, actual_extern_val) => {
@@ -184,6 +227,7 @@ impl Module {
                 .as_context_mut()
                 .store
                 .alloc_func(FuncEntity::new_wasm(func_type, func_body, handle));
+            builder.push_owned(Extern::Func(func));
             builder.push_func(func);
         }
     }
@@ -192,33 +236,47 @@ impl Module {
     ///
     /// This also stores [`Table`] references into the [`Instance`] under construction.
     ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the [`Store`] denies the creation of a table or traps.
+    ///
     /// [`Store`]: struct.Store.html
-    fn extract_tables(&self, context: &mut impl AsContextMut, builder: &mut InstanceEntityBuilder) {
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    fn extract_tables(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+    ) -> Result<(), TableError> {
         for table_type in self.tables.iter().copied() {
-            builder.push_table(Table::new(context.as_context_mut(), table_type));
+            let table = Table::new(context.as_context_mut(), table_type)?;
+            builder.push_owned(Extern::Table(table));
+            builder.push_table(table);
         }
+        Ok(())
     }
 
     /// Extracts the Wasm linear memories from the module and stores them into the [`Store`].
     ///
     /// This also stores [`Memory`] references into the [`Instance`] under construction.
     ///
+    /// # Errors
+    ///
+    /// - If a linear memory cannot be allocated.
+    /// - If the [`ResourceLimiter`] of the [`Store`] denies the creation of a linear memory or traps.
+    ///
     /// [`Store`]: struct.Store.html
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     fn extract_memories(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
-    ) {
+    ) -> Result<(), MemoryError> {
         for memory_type in self.memories.iter().copied() {
-            let memory =
-                Memory::new(context.as_context_mut(), memory_type).unwrap_or_else(|error| {
-                    panic!(
-                        "encountered unexpected invalid memory type {:?} after Wasm validation: {}",
-                        memory_type, error,
-                    )
-                });
+            let memory = Memory::new(context.as_context_mut(), memory_type)?;
+            builder.push_owned(Extern::Memory(memory));
             builder.push_memory(memory);
         }
+        Ok(())
     }
 
     /// Extracts the Wasm global variables from the module and stores them into the [`Store`].
@@ -235,6 +293,7 @@ impl Module {
             let init_value = Self::eval_init_expr(context.as_context_mut(), builder, global_init);
             let mutability = global_type.mutability();
             let global = Global::new(context.as_context_mut(), init_value, mutability);
+            builder.push_owned(Extern::Global(global));
             builder.push_global(global);
         }
     }
@@ -322,13 +381,36 @@ impl Module {
     const DEFAULT_TABLE_INDEX: u32 = 0;
 
     /// Initializes the [`Instance`] tables with the Wasm element segments of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// Passive element segments are stored in the [`Instance`] for use by `table.init`
+    /// instructions whereas active and declared element segments count as dropped.
     fn initialize_table_elements(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
     ) -> Result<(), Error> {
         for element_segment in &self.element_segments[..] {
-            let offset_expr = element_segment.offset();
+            let offset_expr = match element_segment.kind() {
+                ElementSegmentKind::Active { offset, .. } => {
+                    builder.push_element_segment(Arc::from(&[][..]));
+                    offset
+                }
+                ElementSegmentKind::Passive => {
+                    let items = element_segment
+                        .items()
+                        .iter()
+                        .map(|func_index| Self::resolve_element_item(builder, *func_index))
+                        .collect::<Arc<[_]>>();
+                    builder.push_element_segment(items);
+                    continue;
+                }
+                ElementSegmentKind::Declared => {
+                    builder.push_element_segment(Arc::from(&[][..]));
+                    continue;
+                }
+            };
             let offset = Self::eval_init_expr(context.as_context_mut(), builder, offset_expr)
                 .try_into::<u32>()
                 .unwrap_or_else(|| {
@@ -360,27 +442,52 @@ impl Module {
             }
             // Finally do the actual initialization of the table elements.
             for (i, func_index) in element_segment.items().iter().enumerate() {
-                let func_index = func_index.into_u32();
-                let func = builder.get_func(func_index).unwrap_or_else(|| {
-                    panic!(
-                        "encountered missing function at index {} upon element initialization",
-                        func_index
-                    )
-                });
+                let func = Self::resolve_element_item(builder, *func_index);
                 table.set(context.as_context_mut(), offset + i, Some(func))?;
             }
         }
         Ok(())
     }
 
+    /// Returns the [`Func`] of the instance under construction referenced by an element item.
+    ///
+    /// # Panics
+    ///
+    /// If the referenced function is missing which is prevented by Wasm validation.
+    ///
+    /// [`Func`]: [`crate::Func`]
+    fn resolve_element_item(builder: &InstanceEntityBuilder, func_index: FuncIdx) -> Func {
+        let func_index = func_index.into_u32();
+        builder.get_func(func_index).unwrap_or_else(|| {
+            panic!(
+                "encountered missing function at index {} upon element initialization",
+                func_index
+            )
+        })
+    }
+
     /// Initializes the [`Instance`] linear memories with the Wasm data segments of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// Passive data segments are stored in the [`Instance`] for use by `memory.init`
+    /// instructions whereas active data segments count as dropped.
     fn initialize_memory_data(
         &self,
         context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
     ) -> Result<(), Error> {
         for data_segment in &self.data_segments[..] {
-            let offset_expr = data_segment.offset();
+            let offset_expr = match data_segment.kind() {
+                DataSegmentKind::Active { offset, .. } => {
+                    builder.push_data_segment(Arc::from(&[][..]));
+                    offset
+                }
+                DataSegmentKind::Passive => {
+                    builder.push_data_segment(data_segment.shared_data());
+                    continue;
+                }
+            };
             let offset = Self::eval_init_expr(context.as_context_mut(), builder, offset_expr)
                 .try_into::<u32>()
                 .unwrap_or_else(|| {
@@ -47,14 +47,16 @@ impl<'a> InstancePre<'a> {
     /// # Errors
     ///
     /// If executing the `start` function traps.
+    /// In this case the [`Instance`] is dropped from the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
     ///
     /// # Panics
     ///
     /// If the `start` function is invalid albeit successful validation.
-    pub fn start<C>(self, mut context: C) -> Result<Instance, C::Error>
+    pub fn start<C>(self, mut context: C) -> Result<Instance, Error>
     where
         C: AsContextMut,
-        C::Error: From<Error>,
     {
         let opt_start_index = self.start_fn();
         context
@@ -71,7 +73,10 @@ impl<'a> InstancePre<'a> {
                         start_index
                     )
                 });
-            start_func.call(context.as_context_mut(), &[], &mut [])?
+            if let Err(error) = start_func.call(context.as_context_mut(), &[], &mut []) {
+                context.as_context_mut().store.drop_instance(self.handle);
+                return Err(error);
+            }
         }
         Ok(self.handle)
     }
@@ -81,11 +86,18 @@ impl<'a> InstancePre<'a> {
     /// # Errors
     ///
     /// If a `start` function exists that needs to be called for conformant module instantiation.
+    /// In this case the [`Instance`] is dropped from the [`Store`].
+    ///
+    /// [`Store`]: [`crate::Store`]
     pub fn ensure_no_start(
         self,
         mut context: impl AsContextMut,
     ) -> Result<Instance, InstantiationError> {
         if let Some(index) = self.start_fn() {
+            context
+                .as_context_mut()
+                .store
+                .drop_uninitialized_instance(self.handle, &self.builder);
             return Err(InstantiationError::FoundStartFn { index });
         }
         context
@@ -6,40 +6,45 @@ mod data;
 mod element;
 mod error;
 mod export;
+mod float;
 mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+mod names;
 mod parser;
 mod read;
+mod serialize;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
-use self::{
-    builder::ModuleBuilder,
-    data::DataSegment,
-    element::ElementSegment,
-    export::Export,
-    global::Global,
-    import::{Import, ImportKind},
-    init_expr::{InitExpr, InitExprOperand},
-    parser::parse,
-    read::ReadError,
-};
+pub(crate) use self::names::ModuleNames;
 pub use self::{
     builder::ModuleResources,
-    compile::BlockType,
+    compile::{BlockType, UncompiledFuncBody},
     error::ModuleError,
-    export::{FuncIdx, MemoryIdx, TableIdx},
+    export::{External, FuncIdx, MemoryIdx, TableIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
     read::Read,
+    serialize::DeserializeError,
+};
+use self::{
+    builder::{ModuleBuilder, SharedModuleResources},
+    data::{DataSegment, DataSegmentKind},
+    element::{ElementSegment, ElementSegmentKind},
+    export::Export,
+    global::Global,
+    import::{Import, ImportKind},
+    init_expr::{InitExpr, InitExprOperand},
+    parser::parse,
+    read::ReadError,
 };
 use crate::{
-    engine::{DedupFuncType, FuncBody},
+    engine::{DedupFuncType, FuncBody, ModuleCode},
     Engine,
     Error,
     FuncType,
@@ -47,6 +52,7 @@ use crate::{
     MemoryType,
     TableType,
 };
+use alloc::sync::Arc;
 use core::{iter, slice::Iter as SliceIter};
 
 /// A parsed and validated WebAssembly module.
@@ -65,6 +71,9 @@ pub struct Module {
     func_bodies: Box<[FuncBody]>,
     element_segments: Box<[ElementSegment]>,
     data_segments: Box<[DataSegment]>,
+    names: Arc<ModuleNames>,
+    /// The function bodies and function types owned by the [`Module`] and its instances.
+    code: Arc<ModuleCode>,
 }
 
 /// The index of the default Wasm linear memory.
@@ -160,6 +169,8 @@ impl Module {
             func_bodies: builder.func_bodies.into(),
             element_segments: builder.element_segments.into(),
             data_segments: builder.data_segments.into(),
+            names: Arc::new(builder.names),
+            code: Arc::new(builder.code),
         }
     }
 
@@ -0,0 +1,86 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+use wasmparser::{Name, NameSectionReader};
+
+/// The debug names of a [`Module`] and its functions.
+///
+/// # Note
+///
+/// The names are read from the `name` custom section of the Wasm binary
+/// if present and are used to describe Wasm functions in backtraces.
+///
+/// [`Module`]: [`super::Module`]
+#[derive(Debug, Default)]
+pub struct ModuleNames {
+    /// The name of the module if any.
+    module: Option<Arc<str>>,
+    /// The names of the functions of the module indexed by their [`FuncIdx`].
+    ///
+    /// [`FuncIdx`]: [`super::FuncIdx`]
+    funcs: BTreeMap<u32, Arc<str>>,
+}
+
+impl ModuleNames {
+    /// Reads the [`ModuleNames`] from the data of a `name` custom section.
+    ///
+    /// The `offset` is the offset of the `data` within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// The `name` custom section is not validated by Wasm validation and
+    /// a malformed `name` custom section must not invalidate the Wasm module.
+    /// Therefore this procedure reads all names until it encounters the
+    /// first malformed entry and ignores the rest of the section.
+    pub fn read(data: &[u8], offset: usize) -> Self {
+        let mut names = Self::default();
+        let _ = names.read_section(data, offset);
+        names
+    }
+
+    /// Reads the names of the `name` custom section into `self`.
+    ///
+    /// # Errors
+    ///
+    /// If the `name` custom section is malformed.
+    fn read_section(
+        &mut self,
+        data: &[u8],
+        offset: usize,
+    ) -> Result<(), wasmparser::BinaryReaderError> {
+        for name in NameSectionReader::new(data, offset)? {
+            match name? {
+                Name::Module(name) => {
+                    self.module = Some(name.get_name()?.into());
+                }
+                Name::Function(names) => {
+                    let mut names = names.get_map()?;
+                    for _ in 0..names.get_count() {
+                        let naming = names.read()?;
+                        self.funcs.insert(naming.index, naming.name.into());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates new [`ModuleNames`] from the given `module` and function names.
+    pub fn new(module: Option<Arc<str>>, funcs: BTreeMap<u32, Arc<str>>) -> Self {
+        Self { module, funcs }
+    }
+
+    /// Returns an iterator over the indices and names of all named functions.
+    pub fn funcs(&self) -> impl ExactSizeIterator<Item = (u32, &Arc<str>)> {
+        self.funcs.iter().map(|(index, name)| (*index, name))
+    }
+
+    /// Returns the name of the module if any.
+    pub fn module(&self) -> Option<&Arc<str>> {
+        self.module.as_ref()
+    }
+
+    /// Returns the name of the function at `func_index` if any.
+    pub fn func(&self, func_index: u32) -> Option<&Arc<str>> {
+        self.funcs.get(&func_index)
+    }
+}
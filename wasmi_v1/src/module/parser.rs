@@ -1,14 +1,23 @@
+#[cfg(feature = "std")]
+use super::compile::{translate_parallel, PendingFuncBody};
 use super::{
-    compile::translate,
+    compile::{translate, validate},
+    float,
     import::FuncTypeIdx,
     FuncIdx,
     Module,
     ModuleBuilder,
     ModuleError,
+    ModuleNames,
     ModuleResources,
     Read,
+    SharedModuleResources,
+    UncompiledFuncBody,
 };
 use crate::Engine;
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use core::mem;
 use wasmparser::{
     Chunk,
     DataSectionReader,
@@ -27,6 +36,8 @@ use wasmparser::{
     Validator,
     WasmFeatures,
 };
+#[cfg(feature = "std")]
+use wasmparser::{FuncValidator, ValidatorResources};
 
 /// Parses and validates the given Wasm bytecode stream.
 ///
@@ -50,6 +61,22 @@ pub struct ModuleParser<'engine> {
     parser: WasmParser,
     /// Currently processed function.
     func: FuncIdx,
+    /// The module resources shared by all lazily translated function bodies.
+    ///
+    /// # Note
+    ///
+    /// Only used if lazy compilation is enabled.
+    shared_resources: Option<Arc<SharedModuleResources>>,
+    /// The function bodies of the code section that are yet to be translated.
+    ///
+    /// # Note
+    ///
+    /// Only used if parallel compilation is enabled.
+    #[cfg(feature = "std")]
+    pending_func_bodies: Vec<PendingFuncBody>,
+    /// The amount of function bodies of the code section.
+    #[cfg(feature = "std")]
+    len_func_bodies: usize,
 }
 
 impl<'engine> ModuleParser<'engine> {
@@ -64,6 +91,11 @@ impl<'engine> ModuleParser<'engine> {
             validator,
             parser,
             func: FuncIdx(0),
+            shared_resources: None,
+            #[cfg(feature = "std")]
+            pending_func_bodies: Vec::new(),
+            #[cfg(feature = "std")]
+            len_func_bodies: 0,
         }
     }
 
@@ -72,12 +104,12 @@ impl<'engine> ModuleParser<'engine> {
         WasmFeatures {
             reference_types: false,
             multi_value: engine.config().multi_value(),
-            bulk_memory: false,
+            bulk_memory: engine.config().bulk_memory(),
             module_linking: false,
             simd: false,
             relaxed_simd: false,
             threads: false,
-            tail_call: false,
+            tail_call: engine.config().tail_call(),
             deterministic_only: true,
             multi_memory: false,
             exceptions: false,
@@ -167,7 +199,12 @@ impl<'engine> ModuleParser<'engine> {
             Payload::ElementSection(section) => self.process_element(section),
             Payload::DataCountSection { count, range } => self.process_data_count(count, range),
             Payload::DataSection(section) => self.process_data(section),
-            Payload::CustomSection { .. } => Ok(()),
+            Payload::CustomSection {
+                name,
+                data_offset,
+                data,
+                ..
+            } => self.process_custom(name, data_offset, data),
             Payload::CodeSectionStart { count, range, .. } => self.process_code_start(count, range),
             Payload::CodeSectionEntry(func_body) => self.process_code_entry(func_body),
             Payload::ModuleSectionStart { count, range, .. } => {
@@ -225,8 +262,12 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// - If an import fails to validate.
     /// - If an unsupported import declaration is encountered.
+    /// - If an import uses floating point types while floating point support is disabled.
     fn process_imports(&mut self, mut section: ImportSectionReader) -> Result<(), ModuleError> {
         self.validator.import_section(&section)?;
+        if !self.builder.engine().config().floating_point() {
+            float::check_imports(&self.builder, section.clone())?;
+        }
         let len_imports = section.get_count();
         let imports = (0..len_imports).map(|_| section.read()?.try_into());
         self.builder.push_imports(imports)?;
@@ -269,9 +310,13 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If a function declaration fails to validate.
+    /// - If a function declaration fails to validate.
+    /// - If a function signature uses floating point types while floating point support is disabled.
     fn process_functions(&mut self, mut section: FunctionSectionReader) -> Result<(), ModuleError> {
         self.validator.function_section(&section)?;
+        if !self.builder.engine().config().floating_point() {
+            float::check_funcs(&self.builder, section.clone())?;
+        }
         let len_funcs = section.get_count();
         let funcs = (0..len_funcs).map(|_| section.read().map(FuncTypeIdx).map_err(Into::into));
         self.builder.push_funcs(funcs)?;
@@ -330,9 +375,13 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If a global variable declaration fails to validate.
+    /// - If a global variable declaration fails to validate.
+    /// - If a global variable is of floating point type while floating point support is disabled.
     fn process_globals(&mut self, mut section: GlobalSectionReader) -> Result<(), ModuleError> {
         self.validator.global_section(&section)?;
+        if !self.builder.engine().config().floating_point() {
+            float::check_globals(section.clone())?;
+        }
         let len_globals = section.get_count();
         let globals = (0..len_globals).map(|_| section.read()?.try_into());
         self.builder.push_globals(globals)?;
@@ -392,8 +441,9 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Note
     ///
-    /// This is part of the bulk memory operations Wasm proposal and not yet supported
-    /// by `wasmi`.
+    /// This is part of the bulk memory operations Wasm proposal.
+    /// The data count is only required for validation of `memory.init` and
+    /// `data.drop` instructions since function bodies precede the data section.
     fn process_data_count(&mut self, count: u32, range: Range) -> Result<(), ModuleError> {
         self.validator
             .data_count_section(count, &range)
@@ -421,9 +471,7 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Note
     ///
-    /// This currently does not do a lot but it might become important in the
-    /// future if we add parallel translation of function bodies to prepare for
-    /// the translation.
+    /// This prepares the parallel translation of the function bodies if enabled.
     ///
     /// # Errors
     ///
@@ -434,6 +482,10 @@ impl<'engine> ModuleParser<'engine> {
         // internal function before we process any of the internal functions.
         let len_func_imports = self.builder.imports.funcs.len() as u32;
         self.func = FuncIdx(len_func_imports);
+        #[cfg(feature = "std")]
+        {
+            self.len_func_bodies = count as usize;
+        }
         Ok(())
     }
 
@@ -454,14 +506,87 @@ impl<'engine> ModuleParser<'engine> {
     ///
     /// # Errors
     ///
-    /// If the function body fails to validate.
+    /// - If the function body fails to validate.
+    /// - If the function body uses floating point types or instructions
+    ///   while floating point support is disabled.
     fn process_code_entry(&mut self, func_body: FunctionBody) -> Result<(), ModuleError> {
         let func = self.next_func();
         let engine = self.builder.engine();
         let validator = self.validator.code_section_entry()?;
+        if !engine.config().floating_point() {
+            float::check_func_body(&self.builder, func, &func_body)?;
+        }
+        #[cfg(feature = "std")]
+        if engine.config().parallel_compilation() && !engine.config().lazy_compilation() {
+            return self.process_code_entry_parallel(func, func_body, validator);
+        }
+        let func_body = if engine.config().lazy_compilation() {
+            validate(func_body, validator)?;
+            let builder = &self.builder;
+            let res = self
+                .shared_resources
+                .get_or_insert_with(|| Arc::new(SharedModuleResources::new(builder)));
+            let uncompiled = UncompiledFuncBody::new(func, func_body, res.clone())?;
+            engine.alloc_uncompiled_func_body(uncompiled)
+        } else {
+            let module_resources = ModuleResources::new(&self.builder);
+            translate(engine, func, func_body, validator, module_resources)?
+        };
+        self.builder.push_func_body(func_body);
+        Ok(())
+    }
+
+    /// Process a single module code section entry if parallel compilation is enabled.
+    ///
+    /// # Note
+    ///
+    /// The function bodies are collected until the last function body of
+    /// the code section has been processed. Then all function bodies are
+    /// validated and translated on multiple threads and allocated to the
+    /// [`Engine`] in the order of the code section.
+    ///
+    /// # Errors
+    ///
+    /// If any function body fails to validate.
+    #[cfg(feature = "std")]
+    fn process_code_entry_parallel(
+        &mut self,
+        func: FuncIdx,
+        func_body: FunctionBody,
+        validator: FuncValidator<ValidatorResources>,
+    ) -> Result<(), ModuleError> {
+        let func_body = PendingFuncBody::new(func, func_body, validator)?;
+        self.pending_func_bodies.push(func_body);
+        if self.pending_func_bodies.len() < self.len_func_bodies {
+            return Ok(());
+        }
+        let engine = self.builder.engine();
+        let func_bodies = mem::take(&mut self.pending_func_bodies);
         let module_resources = ModuleResources::new(&self.builder);
-        let func_body = translate(engine, func, func_body, validator, module_resources)?;
-        self.builder.func_bodies.push(func_body);
+        let func_bodies = translate_parallel(engine, func_bodies, module_resources)?;
+        for func_body in func_bodies {
+            let func_body = engine.alloc_func_body(func_body);
+            self.builder.push_func_body(func_body);
+        }
+        Ok(())
+    }
+
+    /// Process a custom section.
+    ///
+    /// # Note
+    ///
+    /// Only the `name` custom section is processed in order to
+    /// describe Wasm functions in backtraces. All other custom
+    /// sections are ignored.
+    fn process_custom(
+        &mut self,
+        name: &str,
+        data_offset: usize,
+        data: &[u8],
+    ) -> Result<(), ModuleError> {
+        if name == "name" {
+            self.builder.set_names(ModuleNames::read(data, data_offset));
+        }
         Ok(())
     }
 
@@ -0,0 +1,2251 @@
+//! Encoding of the `wasmi` bytecode [`Instruction`] set.
+//!
+//! # Note
+//!
+//! Every instruction is encoded as its `u16` opcode followed by its operands.
+//! The opcodes follow the order of the [`Instruction`] variants. Therefore the
+//! [`FORMAT_VERSION`] must be incremented whenever the [`Instruction`] set changes.
+//!
+//! [`FORMAT_VERSION`]: [`super::FORMAT_VERSION`]
+
+use super::{Decode, Decoder, DeserializeError, Encode, Encoder};
+use crate::engine::{
+    bytecode::{
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        GlobalIdx,
+        Instruction,
+        Offset,
+        Register,
+        SignatureIdx,
+    },
+    DropKeep,
+    InstructionIdx,
+    Target,
+};
+use wasmi_core::UntypedValue;
+
+impl Encode for Instruction {
+    fn encode(&self, encoder: &mut Encoder) {
+        match *self {
+            Self::Copy { result, input } => {
+                encoder.u16(0);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::Br(operand) => {
+                encoder.u16(1);
+                operand.encode(encoder);
+            }
+            Self::BrIfEqz { condition, target } => {
+                encoder.u16(2);
+                condition.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfNez { condition, target } => {
+                encoder.u16(3);
+                condition.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32Eq { lhs, rhs, target } => {
+                encoder.u16(4);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32Ne { lhs, rhs, target } => {
+                encoder.u16(5);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32LtS { lhs, rhs, target } => {
+                encoder.u16(6);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32LtU { lhs, rhs, target } => {
+                encoder.u16(7);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32GtS { lhs, rhs, target } => {
+                encoder.u16(8);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32GtU { lhs, rhs, target } => {
+                encoder.u16(9);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32LeS { lhs, rhs, target } => {
+                encoder.u16(10);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32LeU { lhs, rhs, target } => {
+                encoder.u16(11);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32GeS { lhs, rhs, target } => {
+                encoder.u16(12);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI32GeU { lhs, rhs, target } => {
+                encoder.u16(13);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64Eq { lhs, rhs, target } => {
+                encoder.u16(14);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64Ne { lhs, rhs, target } => {
+                encoder.u16(15);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64LtS { lhs, rhs, target } => {
+                encoder.u16(16);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64LtU { lhs, rhs, target } => {
+                encoder.u16(17);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64GtS { lhs, rhs, target } => {
+                encoder.u16(18);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64GtU { lhs, rhs, target } => {
+                encoder.u16(19);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64LeS { lhs, rhs, target } => {
+                encoder.u16(20);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64LeU { lhs, rhs, target } => {
+                encoder.u16(21);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64GeS { lhs, rhs, target } => {
+                encoder.u16(22);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::BrIfI64GeU { lhs, rhs, target } => {
+                encoder.u16(23);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+                target.encode(encoder);
+            }
+            Self::ReturnIfNez { condition, results } => {
+                encoder.u16(24);
+                condition.encode(encoder);
+                results.encode(encoder);
+            }
+            Self::BrTable { index, len_targets } => {
+                encoder.u16(25);
+                index.encode(encoder);
+                len_targets.encode(encoder);
+            }
+            Self::Unreachable => encoder.u16(26),
+            Self::Return(operand) => {
+                encoder.u16(27);
+                operand.encode(encoder);
+            }
+            Self::Call {
+                func_index,
+                params_end,
+            } => {
+                encoder.u16(28);
+                func_index.encode(encoder);
+                params_end.encode(encoder);
+            }
+            Self::CallIndirect {
+                signature,
+                index,
+                params_end,
+            } => {
+                encoder.u16(29);
+                signature.encode(encoder);
+                index.encode(encoder);
+                params_end.encode(encoder);
+            }
+            Self::Select {
+                result,
+                condition,
+                if_false,
+            } => {
+                encoder.u16(30);
+                result.encode(encoder);
+                condition.encode(encoder);
+                if_false.encode(encoder);
+            }
+            Self::GetGlobal { result, global } => {
+                encoder.u16(31);
+                result.encode(encoder);
+                global.encode(encoder);
+            }
+            Self::SetGlobal { global, input } => {
+                encoder.u16(32);
+                global.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Load {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(33);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(34);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::F32Load {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(35);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::F64Load {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(36);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Load8S {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(37);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Load8U {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(38);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Load16S {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(39);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Load16U {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(40);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load8S {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(41);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load8U {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(42);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load16S {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(43);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load16U {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(44);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load32S {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(45);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Load32U {
+                result,
+                ptr,
+                offset,
+            } => {
+                encoder.u16(46);
+                result.encode(encoder);
+                ptr.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Store { ptr, value, offset } => {
+                encoder.u16(47);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Store { ptr, value, offset } => {
+                encoder.u16(48);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::F32Store { ptr, value, offset } => {
+                encoder.u16(49);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::F64Store { ptr, value, offset } => {
+                encoder.u16(50);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Store8 { ptr, value, offset } => {
+                encoder.u16(51);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I32Store16 { ptr, value, offset } => {
+                encoder.u16(52);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Store8 { ptr, value, offset } => {
+                encoder.u16(53);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Store16 { ptr, value, offset } => {
+                encoder.u16(54);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::I64Store32 { ptr, value, offset } => {
+                encoder.u16(55);
+                ptr.encode(encoder);
+                value.encode(encoder);
+                offset.encode(encoder);
+            }
+            Self::CurrentMemory { result } => {
+                encoder.u16(56);
+                result.encode(encoder);
+            }
+            Self::GrowMemory { result, delta } => {
+                encoder.u16(57);
+                result.encode(encoder);
+                delta.encode(encoder);
+            }
+            Self::Const { result, value } => {
+                encoder.u16(58);
+                result.encode(encoder);
+                value.encode(encoder);
+            }
+            Self::I32Eqz { result, input } => {
+                encoder.u16(59);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Eq { result, lhs, rhs } => {
+                encoder.u16(60);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Ne { result, lhs, rhs } => {
+                encoder.u16(61);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32LtS { result, lhs, rhs } => {
+                encoder.u16(62);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32LtU { result, lhs, rhs } => {
+                encoder.u16(63);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32GtS { result, lhs, rhs } => {
+                encoder.u16(64);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32GtU { result, lhs, rhs } => {
+                encoder.u16(65);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32LeS { result, lhs, rhs } => {
+                encoder.u16(66);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32LeU { result, lhs, rhs } => {
+                encoder.u16(67);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32GeS { result, lhs, rhs } => {
+                encoder.u16(68);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32GeU { result, lhs, rhs } => {
+                encoder.u16(69);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Eqz { result, input } => {
+                encoder.u16(70);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Eq { result, lhs, rhs } => {
+                encoder.u16(71);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Ne { result, lhs, rhs } => {
+                encoder.u16(72);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64LtS { result, lhs, rhs } => {
+                encoder.u16(73);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64LtU { result, lhs, rhs } => {
+                encoder.u16(74);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64GtS { result, lhs, rhs } => {
+                encoder.u16(75);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64GtU { result, lhs, rhs } => {
+                encoder.u16(76);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64LeS { result, lhs, rhs } => {
+                encoder.u16(77);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64LeU { result, lhs, rhs } => {
+                encoder.u16(78);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64GeS { result, lhs, rhs } => {
+                encoder.u16(79);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64GeU { result, lhs, rhs } => {
+                encoder.u16(80);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Eq { result, lhs, rhs } => {
+                encoder.u16(81);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Ne { result, lhs, rhs } => {
+                encoder.u16(82);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Lt { result, lhs, rhs } => {
+                encoder.u16(83);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Gt { result, lhs, rhs } => {
+                encoder.u16(84);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Le { result, lhs, rhs } => {
+                encoder.u16(85);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Ge { result, lhs, rhs } => {
+                encoder.u16(86);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Eq { result, lhs, rhs } => {
+                encoder.u16(87);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Ne { result, lhs, rhs } => {
+                encoder.u16(88);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Lt { result, lhs, rhs } => {
+                encoder.u16(89);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Gt { result, lhs, rhs } => {
+                encoder.u16(90);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Le { result, lhs, rhs } => {
+                encoder.u16(91);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Ge { result, lhs, rhs } => {
+                encoder.u16(92);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Clz { result, input } => {
+                encoder.u16(93);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Ctz { result, input } => {
+                encoder.u16(94);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Popcnt { result, input } => {
+                encoder.u16(95);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Add { result, lhs, rhs } => {
+                encoder.u16(96);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Sub { result, lhs, rhs } => {
+                encoder.u16(97);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32AddImm {
+                result,
+                input,
+                value,
+            } => {
+                encoder.u16(98);
+                result.encode(encoder);
+                input.encode(encoder);
+                value.encode(encoder);
+            }
+            Self::I32Mul { result, lhs, rhs } => {
+                encoder.u16(99);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32DivS { result, lhs, rhs } => {
+                encoder.u16(100);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32DivU { result, lhs, rhs } => {
+                encoder.u16(101);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32RemS { result, lhs, rhs } => {
+                encoder.u16(102);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32RemU { result, lhs, rhs } => {
+                encoder.u16(103);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32And { result, lhs, rhs } => {
+                encoder.u16(104);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Or { result, lhs, rhs } => {
+                encoder.u16(105);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Xor { result, lhs, rhs } => {
+                encoder.u16(106);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Shl { result, lhs, rhs } => {
+                encoder.u16(107);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32ShrS { result, lhs, rhs } => {
+                encoder.u16(108);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32ShrU { result, lhs, rhs } => {
+                encoder.u16(109);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Rotl { result, lhs, rhs } => {
+                encoder.u16(110);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32Rotr { result, lhs, rhs } => {
+                encoder.u16(111);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Clz { result, input } => {
+                encoder.u16(112);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Ctz { result, input } => {
+                encoder.u16(113);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Popcnt { result, input } => {
+                encoder.u16(114);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Add { result, lhs, rhs } => {
+                encoder.u16(115);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Sub { result, lhs, rhs } => {
+                encoder.u16(116);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64AddImm {
+                result,
+                input,
+                value,
+            } => {
+                encoder.u16(117);
+                result.encode(encoder);
+                input.encode(encoder);
+                value.encode(encoder);
+            }
+            Self::I64Mul { result, lhs, rhs } => {
+                encoder.u16(118);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64DivS { result, lhs, rhs } => {
+                encoder.u16(119);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64DivU { result, lhs, rhs } => {
+                encoder.u16(120);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64RemS { result, lhs, rhs } => {
+                encoder.u16(121);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64RemU { result, lhs, rhs } => {
+                encoder.u16(122);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64And { result, lhs, rhs } => {
+                encoder.u16(123);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Or { result, lhs, rhs } => {
+                encoder.u16(124);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Xor { result, lhs, rhs } => {
+                encoder.u16(125);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Shl { result, lhs, rhs } => {
+                encoder.u16(126);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64ShrS { result, lhs, rhs } => {
+                encoder.u16(127);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64ShrU { result, lhs, rhs } => {
+                encoder.u16(128);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Rotl { result, lhs, rhs } => {
+                encoder.u16(129);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I64Rotr { result, lhs, rhs } => {
+                encoder.u16(130);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Abs { result, input } => {
+                encoder.u16(131);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Neg { result, input } => {
+                encoder.u16(132);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Ceil { result, input } => {
+                encoder.u16(133);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Floor { result, input } => {
+                encoder.u16(134);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Trunc { result, input } => {
+                encoder.u16(135);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Nearest { result, input } => {
+                encoder.u16(136);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Sqrt { result, input } => {
+                encoder.u16(137);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32Add { result, lhs, rhs } => {
+                encoder.u16(138);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Sub { result, lhs, rhs } => {
+                encoder.u16(139);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Mul { result, lhs, rhs } => {
+                encoder.u16(140);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Div { result, lhs, rhs } => {
+                encoder.u16(141);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Min { result, lhs, rhs } => {
+                encoder.u16(142);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Max { result, lhs, rhs } => {
+                encoder.u16(143);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F32Copysign { result, lhs, rhs } => {
+                encoder.u16(144);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Abs { result, input } => {
+                encoder.u16(145);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Neg { result, input } => {
+                encoder.u16(146);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Ceil { result, input } => {
+                encoder.u16(147);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Floor { result, input } => {
+                encoder.u16(148);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Trunc { result, input } => {
+                encoder.u16(149);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Nearest { result, input } => {
+                encoder.u16(150);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Sqrt { result, input } => {
+                encoder.u16(151);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64Add { result, lhs, rhs } => {
+                encoder.u16(152);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Sub { result, lhs, rhs } => {
+                encoder.u16(153);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Mul { result, lhs, rhs } => {
+                encoder.u16(154);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Div { result, lhs, rhs } => {
+                encoder.u16(155);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Min { result, lhs, rhs } => {
+                encoder.u16(156);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Max { result, lhs, rhs } => {
+                encoder.u16(157);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::F64Copysign { result, lhs, rhs } => {
+                encoder.u16(158);
+                result.encode(encoder);
+                lhs.encode(encoder);
+                rhs.encode(encoder);
+            }
+            Self::I32WrapI64 { result, input } => {
+                encoder.u16(159);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSF32 { result, input } => {
+                encoder.u16(160);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncUF32 { result, input } => {
+                encoder.u16(161);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSF64 { result, input } => {
+                encoder.u16(162);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncUF64 { result, input } => {
+                encoder.u16(163);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64ExtendSI32 { result, input } => {
+                encoder.u16(164);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64ExtendUI32 { result, input } => {
+                encoder.u16(165);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSF32 { result, input } => {
+                encoder.u16(166);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncUF32 { result, input } => {
+                encoder.u16(167);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSF64 { result, input } => {
+                encoder.u16(168);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncUF64 { result, input } => {
+                encoder.u16(169);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32ConvertSI32 { result, input } => {
+                encoder.u16(170);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32ConvertUI32 { result, input } => {
+                encoder.u16(171);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32ConvertSI64 { result, input } => {
+                encoder.u16(172);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32ConvertUI64 { result, input } => {
+                encoder.u16(173);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F32DemoteF64 { result, input } => {
+                encoder.u16(174);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64ConvertSI32 { result, input } => {
+                encoder.u16(175);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64ConvertUI32 { result, input } => {
+                encoder.u16(176);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64ConvertSI64 { result, input } => {
+                encoder.u16(177);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64ConvertUI64 { result, input } => {
+                encoder.u16(178);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::F64PromoteF32 { result, input } => {
+                encoder.u16(179);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Extend8S { result, input } => {
+                encoder.u16(180);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32Extend16S { result, input } => {
+                encoder.u16(181);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Extend8S { result, input } => {
+                encoder.u16(182);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Extend16S { result, input } => {
+                encoder.u16(183);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64Extend32S { result, input } => {
+                encoder.u16(184);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSatF32S { result, input } => {
+                encoder.u16(185);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSatF32U { result, input } => {
+                encoder.u16(186);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSatF64S { result, input } => {
+                encoder.u16(187);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I32TruncSatF64U { result, input } => {
+                encoder.u16(188);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSatF32S { result, input } => {
+                encoder.u16(189);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSatF32U { result, input } => {
+                encoder.u16(190);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSatF64S { result, input } => {
+                encoder.u16(191);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::I64TruncSatF64U { result, input } => {
+                encoder.u16(192);
+                result.encode(encoder);
+                input.encode(encoder);
+            }
+            Self::ReturnCall {
+                func_index,
+                params_end,
+            } => {
+                encoder.u16(193);
+                func_index.encode(encoder);
+                params_end.encode(encoder);
+            }
+            Self::ReturnCallIndirect {
+                signature,
+                index,
+                params_end,
+            } => {
+                encoder.u16(194);
+                signature.encode(encoder);
+                index.encode(encoder);
+                params_end.encode(encoder);
+            }
+            Self::MemoryCopy { dst, src, len } => {
+                encoder.u16(195);
+                dst.encode(encoder);
+                src.encode(encoder);
+                len.encode(encoder);
+            }
+            Self::MemoryFill { dst, value, len } => {
+                encoder.u16(196);
+                dst.encode(encoder);
+                value.encode(encoder);
+                len.encode(encoder);
+            }
+            Self::MemoryInit { segment, operands } => {
+                encoder.u16(197);
+                segment.encode(encoder);
+                operands.encode(encoder);
+            }
+            Self::DataDrop { segment } => {
+                encoder.u16(198);
+                segment.encode(encoder);
+            }
+            Self::TableCopy { dst, src, len } => {
+                encoder.u16(199);
+                dst.encode(encoder);
+                src.encode(encoder);
+                len.encode(encoder);
+            }
+            Self::TableInit { segment, operands } => {
+                encoder.u16(200);
+                segment.encode(encoder);
+                operands.encode(encoder);
+            }
+            Self::ElemDrop { segment } => {
+                encoder.u16(201);
+                segment.encode(encoder);
+            }
+            Self::FuncBodyStart { .. } | Self::FuncBodyEnd => {
+                panic!(
+                    "encountered function body delimiter within function body: {:?}",
+                    self
+                )
+            }
+        }
+    }
+}
+
+impl Decode for Instruction {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let instruction = match u16::decode(decoder)? {
+            0 => Self::Copy {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            1 => Self::Br(Decode::decode(decoder)?),
+            2 => Self::BrIfEqz {
+                condition: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            3 => Self::BrIfNez {
+                condition: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            4 => Self::BrIfI32Eq {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            5 => Self::BrIfI32Ne {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            6 => Self::BrIfI32LtS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            7 => Self::BrIfI32LtU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            8 => Self::BrIfI32GtS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            9 => Self::BrIfI32GtU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            10 => Self::BrIfI32LeS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            11 => Self::BrIfI32LeU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            12 => Self::BrIfI32GeS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            13 => Self::BrIfI32GeU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            14 => Self::BrIfI64Eq {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            15 => Self::BrIfI64Ne {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            16 => Self::BrIfI64LtS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            17 => Self::BrIfI64LtU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            18 => Self::BrIfI64GtS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            19 => Self::BrIfI64GtU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            20 => Self::BrIfI64LeS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            21 => Self::BrIfI64LeU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            22 => Self::BrIfI64GeS {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            23 => Self::BrIfI64GeU {
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+                target: Decode::decode(decoder)?,
+            },
+            24 => Self::ReturnIfNez {
+                condition: Decode::decode(decoder)?,
+                results: Decode::decode(decoder)?,
+            },
+            25 => Self::BrTable {
+                index: Decode::decode(decoder)?,
+                len_targets: Decode::decode(decoder)?,
+            },
+            26 => Self::Unreachable,
+            27 => Self::Return(Decode::decode(decoder)?),
+            28 => Self::Call {
+                func_index: Decode::decode(decoder)?,
+                params_end: Decode::decode(decoder)?,
+            },
+            29 => Self::CallIndirect {
+                signature: Decode::decode(decoder)?,
+                index: Decode::decode(decoder)?,
+                params_end: Decode::decode(decoder)?,
+            },
+            30 => Self::Select {
+                result: Decode::decode(decoder)?,
+                condition: Decode::decode(decoder)?,
+                if_false: Decode::decode(decoder)?,
+            },
+            31 => Self::GetGlobal {
+                result: Decode::decode(decoder)?,
+                global: Decode::decode(decoder)?,
+            },
+            32 => Self::SetGlobal {
+                global: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            33 => Self::I32Load {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            34 => Self::I64Load {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            35 => Self::F32Load {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            36 => Self::F64Load {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            37 => Self::I32Load8S {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            38 => Self::I32Load8U {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            39 => Self::I32Load16S {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            40 => Self::I32Load16U {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            41 => Self::I64Load8S {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            42 => Self::I64Load8U {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            43 => Self::I64Load16S {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            44 => Self::I64Load16U {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            45 => Self::I64Load32S {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            46 => Self::I64Load32U {
+                result: Decode::decode(decoder)?,
+                ptr: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            47 => Self::I32Store {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            48 => Self::I64Store {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            49 => Self::F32Store {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            50 => Self::F64Store {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            51 => Self::I32Store8 {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            52 => Self::I32Store16 {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            53 => Self::I64Store8 {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            54 => Self::I64Store16 {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            55 => Self::I64Store32 {
+                ptr: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                offset: Decode::decode(decoder)?,
+            },
+            56 => Self::CurrentMemory {
+                result: Decode::decode(decoder)?,
+            },
+            57 => Self::GrowMemory {
+                result: Decode::decode(decoder)?,
+                delta: Decode::decode(decoder)?,
+            },
+            58 => Self::Const {
+                result: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+            },
+            59 => Self::I32Eqz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            60 => Self::I32Eq {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            61 => Self::I32Ne {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            62 => Self::I32LtS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            63 => Self::I32LtU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            64 => Self::I32GtS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            65 => Self::I32GtU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            66 => Self::I32LeS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            67 => Self::I32LeU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            68 => Self::I32GeS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            69 => Self::I32GeU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            70 => Self::I64Eqz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            71 => Self::I64Eq {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            72 => Self::I64Ne {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            73 => Self::I64LtS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            74 => Self::I64LtU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            75 => Self::I64GtS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            76 => Self::I64GtU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            77 => Self::I64LeS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            78 => Self::I64LeU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            79 => Self::I64GeS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            80 => Self::I64GeU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            81 => Self::F32Eq {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            82 => Self::F32Ne {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            83 => Self::F32Lt {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            84 => Self::F32Gt {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            85 => Self::F32Le {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            86 => Self::F32Ge {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            87 => Self::F64Eq {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            88 => Self::F64Ne {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            89 => Self::F64Lt {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            90 => Self::F64Gt {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            91 => Self::F64Le {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            92 => Self::F64Ge {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            93 => Self::I32Clz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            94 => Self::I32Ctz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            95 => Self::I32Popcnt {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            96 => Self::I32Add {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            97 => Self::I32Sub {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            98 => Self::I32AddImm {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+            },
+            99 => Self::I32Mul {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            100 => Self::I32DivS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            101 => Self::I32DivU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            102 => Self::I32RemS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            103 => Self::I32RemU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            104 => Self::I32And {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            105 => Self::I32Or {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            106 => Self::I32Xor {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            107 => Self::I32Shl {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            108 => Self::I32ShrS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            109 => Self::I32ShrU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            110 => Self::I32Rotl {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            111 => Self::I32Rotr {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            112 => Self::I64Clz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            113 => Self::I64Ctz {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            114 => Self::I64Popcnt {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            115 => Self::I64Add {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            116 => Self::I64Sub {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            117 => Self::I64AddImm {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+            },
+            118 => Self::I64Mul {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            119 => Self::I64DivS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            120 => Self::I64DivU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            121 => Self::I64RemS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            122 => Self::I64RemU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            123 => Self::I64And {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            124 => Self::I64Or {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            125 => Self::I64Xor {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            126 => Self::I64Shl {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            127 => Self::I64ShrS {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            128 => Self::I64ShrU {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            129 => Self::I64Rotl {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            130 => Self::I64Rotr {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            131 => Self::F32Abs {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            132 => Self::F32Neg {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            133 => Self::F32Ceil {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            134 => Self::F32Floor {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            135 => Self::F32Trunc {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            136 => Self::F32Nearest {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            137 => Self::F32Sqrt {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            138 => Self::F32Add {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            139 => Self::F32Sub {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            140 => Self::F32Mul {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            141 => Self::F32Div {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            142 => Self::F32Min {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            143 => Self::F32Max {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            144 => Self::F32Copysign {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            145 => Self::F64Abs {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            146 => Self::F64Neg {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            147 => Self::F64Ceil {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            148 => Self::F64Floor {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            149 => Self::F64Trunc {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            150 => Self::F64Nearest {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            151 => Self::F64Sqrt {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            152 => Self::F64Add {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            153 => Self::F64Sub {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            154 => Self::F64Mul {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            155 => Self::F64Div {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            156 => Self::F64Min {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            157 => Self::F64Max {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            158 => Self::F64Copysign {
+                result: Decode::decode(decoder)?,
+                lhs: Decode::decode(decoder)?,
+                rhs: Decode::decode(decoder)?,
+            },
+            159 => Self::I32WrapI64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            160 => Self::I32TruncSF32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            161 => Self::I32TruncUF32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            162 => Self::I32TruncSF64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            163 => Self::I32TruncUF64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            164 => Self::I64ExtendSI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            165 => Self::I64ExtendUI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            166 => Self::I64TruncSF32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            167 => Self::I64TruncUF32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            168 => Self::I64TruncSF64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            169 => Self::I64TruncUF64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            170 => Self::F32ConvertSI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            171 => Self::F32ConvertUI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            172 => Self::F32ConvertSI64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            173 => Self::F32ConvertUI64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            174 => Self::F32DemoteF64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            175 => Self::F64ConvertSI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            176 => Self::F64ConvertUI32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            177 => Self::F64ConvertSI64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            178 => Self::F64ConvertUI64 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            179 => Self::F64PromoteF32 {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            180 => Self::I32Extend8S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            181 => Self::I32Extend16S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            182 => Self::I64Extend8S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            183 => Self::I64Extend16S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            184 => Self::I64Extend32S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            185 => Self::I32TruncSatF32S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            186 => Self::I32TruncSatF32U {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            187 => Self::I32TruncSatF64S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            188 => Self::I32TruncSatF64U {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            189 => Self::I64TruncSatF32S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            190 => Self::I64TruncSatF32U {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            191 => Self::I64TruncSatF64S {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            192 => Self::I64TruncSatF64U {
+                result: Decode::decode(decoder)?,
+                input: Decode::decode(decoder)?,
+            },
+            193 => Self::ReturnCall {
+                func_index: Decode::decode(decoder)?,
+                params_end: Decode::decode(decoder)?,
+            },
+            194 => Self::ReturnCallIndirect {
+                signature: Decode::decode(decoder)?,
+                index: Decode::decode(decoder)?,
+                params_end: Decode::decode(decoder)?,
+            },
+            195 => Self::MemoryCopy {
+                dst: Decode::decode(decoder)?,
+                src: Decode::decode(decoder)?,
+                len: Decode::decode(decoder)?,
+            },
+            196 => Self::MemoryFill {
+                dst: Decode::decode(decoder)?,
+                value: Decode::decode(decoder)?,
+                len: Decode::decode(decoder)?,
+            },
+            197 => Self::MemoryInit {
+                segment: Decode::decode(decoder)?,
+                operands: Decode::decode(decoder)?,
+            },
+            198 => Self::DataDrop {
+                segment: Decode::decode(decoder)?,
+            },
+            199 => Self::TableCopy {
+                dst: Decode::decode(decoder)?,
+                src: Decode::decode(decoder)?,
+                len: Decode::decode(decoder)?,
+            },
+            200 => Self::TableInit {
+                segment: Decode::decode(decoder)?,
+                operands: Decode::decode(decoder)?,
+            },
+            201 => Self::ElemDrop {
+                segment: Decode::decode(decoder)?,
+            },
+            _ => return Err(DeserializeError::malformed("invalid instruction opcode")),
+        };
+        Ok(instruction)
+    }
+}
+
+macro_rules! impl_codec_for_index {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, encoder: &mut Encoder) {
+                    self.into_inner().encode(encoder)
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+                    u32::decode(decoder).map(<$ty>::from)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_index!(
+    Register,
+    FuncIdx,
+    SignatureIdx,
+    GlobalIdx,
+    Offset,
+    DataSegmentIdx,
+    ElementSegmentIdx,
+);
+
+impl Encode for Target {
+    fn encode(&self, encoder: &mut Encoder) {
+        // Note: Instruction indices are represented as `u32` values.
+        (self.destination_pc().into_usize() as u32).encode(encoder)
+    }
+}
+
+impl Decode for Target {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let destination_pc = u32::decode(decoder)? as usize;
+        Ok(Self::new(InstructionIdx::from_usize(destination_pc)))
+    }
+}
+
+impl Encode for DropKeep {
+    fn encode(&self, encoder: &mut Encoder) {
+        // Note: The amounts are represented as `u32` values.
+        (self.drop() as u32).encode(encoder);
+        (self.keep() as u32).encode(encoder);
+    }
+}
+
+impl Decode for DropKeep {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let drop = u32::decode(decoder)?;
+        let keep = u32::decode(decoder)?;
+        Ok(Self::new32(drop, keep))
+    }
+}
+
+impl Encode for UntypedValue {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.to_bits().encode(encoder)
+    }
+}
+
+impl Decode for UntypedValue {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        u64::decode(decoder).map(Self::from)
+    }
+}
@@ -0,0 +1,1005 @@
+//! Serialization and deserialization of translated Wasm modules.
+//!
+//! # Format
+//!
+//! A serialized [`Module`] starts with the [`MAGIC`] bytes followed by the
+//! [`FORMAT_VERSION`], the version of the `wasmi_v1` crate that serialized it
+//! and the enabled [`Config`] features that affect its translation.
+//! After that follow the module metadata and the translated `wasmi` bytecode
+//! of all internally defined functions.
+//!
+//! All integers are encoded in little-endian byte order.
+//! Sequences, strings and byte arrays are prefixed with their length.
+
+mod instruction;
+
+use super::{
+    DataSegment,
+    DataSegmentKind,
+    ElementSegment,
+    ElementSegmentKind,
+    Export,
+    External,
+    FuncIdx,
+    GlobalIdx,
+    ImportName,
+    Imported,
+    InitExpr,
+    InitExprOperand,
+    MemoryIdx,
+    Module,
+    ModuleImports,
+    ModuleNames,
+    TableIdx,
+};
+use crate::{
+    engine::{DedupFuncType, ModuleCode, TranslatedFuncBody},
+    Config,
+    Engine,
+    Error,
+    FuncType,
+    GlobalType,
+    MemoryType,
+    ModuleError,
+    Mutability,
+    TableType,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{fmt, fmt::Display, str};
+use wasmi_core::{memory_units::Pages, Value, ValueType, F32, F64};
+
+/// The magic bytes at the start of every serialized [`Module`].
+const MAGIC: [u8; 8] = *b"\0wasmiv1";
+
+/// The version of the serialization format.
+///
+/// # Note
+///
+/// This must be incremented whenever the format changes.
+/// This includes changes to the `wasmi` bytecode [`Instruction`] set.
+///
+/// [`Instruction`]: [`crate::engine::bytecode::Instruction`]
+const FORMAT_VERSION: u32 = 4;
+
+/// The version of the `wasmi_v1` crate.
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Returns the enabled features of the [`Config`] that affect the translation of a [`Module`].
+///
+/// # Note
+///
+/// Every feature is represented by a single bit. A serialized [`Module`] can only be
+/// deserialized by an [`Engine`] with the same features since the features determine
+/// which Wasm modules are valid and which `wasmi` bytecode their functions are translated to.
+fn config_features(config: &Config) -> u32 {
+    [
+        config.mutable_global(),
+        config.sign_extension(),
+        config.saturating_float_to_int(),
+        config.multi_value(),
+        config.bulk_memory(),
+        config.tail_call(),
+        config.nan_canonicalization(),
+        config.floating_point(),
+    ]
+    .into_iter()
+    .enumerate()
+    .fold(0, |features, (n, enabled)| {
+        features | (u32::from(enabled) << n)
+    })
+}
+
+/// Errors that may occur upon deserializing a [`Module`].
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// The bytes do not start with the magic bytes of a serialized [`Module`].
+    InvalidMagic,
+    /// The [`Module`] has been serialized by an incompatible version of `wasmi`
+    /// or by an [`Engine`] with different [`Config`] features.
+    VersionMismatch {
+        /// The version of the serialization format.
+        format_version: u32,
+        /// The version of the `wasmi_v1` crate.
+        crate_version: String,
+        /// The [`Config`] features of the serializing [`Engine`].
+        features: u32,
+        /// The [`Config`] features of the deserializing [`Engine`].
+        expected_features: u32,
+    },
+    /// The serialized [`Module`] ended unexpectedly.
+    UnexpectedEnd,
+    /// The serialized [`Module`] is malformed.
+    Malformed { message: &'static str },
+}
+
+impl DeserializeError {
+    /// Creates a new [`DeserializeError`] for malformed data described by `message`.
+    fn malformed(message: &'static str) -> Self {
+        Self::Malformed { message }
+    }
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "encountered bytes that are not a serialized module"),
+            Self::VersionMismatch {
+                format_version,
+                crate_version,
+                features,
+                expected_features,
+            } => write!(
+                f,
+                "encountered module serialized with format version {} and features {:#x} \
+                by wasmi_v1 {} but expected format version {} and features {:#x} by wasmi_v1 {}",
+                format_version,
+                features,
+                crate_version,
+                FORMAT_VERSION,
+                expected_features,
+                CRATE_VERSION,
+            ),
+            Self::UnexpectedEnd => write!(f, "encountered unexpected end of serialized module"),
+            Self::Malformed { message } => {
+                write!(f, "encountered malformed serialized module: {}", message)
+            }
+        }
+    }
+}
+
+/// Encodes the parts of a [`Module`] into bytes.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    /// The encoded bytes.
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Encodes the opcode of an [`Instruction`].
+    ///
+    /// [`Instruction`]: [`crate::engine::bytecode::Instruction`]
+    fn u16(&mut self, value: u16) {
+        value.encode(self)
+    }
+
+    /// Encodes the length of a sequence.
+    fn len(&mut self, len: usize) {
+        len.encode(self)
+    }
+
+    /// Encodes all `items` of a sequence prefixed by their amount.
+    fn seq<'a, T, I>(&mut self, items: I)
+    where
+        T: Encode + 'a,
+        I: IntoIterator<Item = &'a T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        self.len(items.len());
+        for item in items {
+            item.encode(self);
+        }
+    }
+}
+
+/// Decodes the parts of a [`Module`] from bytes.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    /// The bytes that are yet to be decoded.
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a new [`Decoder`] for the given `bytes`.
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the next `len` bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there are less than `len` bytes left.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DeserializeError> {
+        if self.bytes.len() < len {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Returns the next `N` bytes as array.
+    ///
+    /// # Errors
+    ///
+    /// If there are less than `N` bytes left.
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DeserializeError> {
+        let mut array = [0x00; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Decodes the length of a sequence.
+    ///
+    /// # Errors
+    ///
+    /// If the length exceeds the amount of bytes left since
+    /// every item of a sequence is encoded by at least one byte.
+    fn len(&mut self) -> Result<usize, DeserializeError> {
+        let len = usize::decode(self)?;
+        if len > self.bytes.len() {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    /// Decodes a sequence of items prefixed by their amount.
+    fn seq<T>(&mut self) -> Result<Vec<T>, DeserializeError>
+    where
+        T: Decode,
+    {
+        let len = self.len()?;
+        (0..len).map(|_| T::decode(self)).collect()
+    }
+
+    /// Finishes decoding.
+    ///
+    /// # Errors
+    ///
+    /// If there are bytes left that have not been decoded.
+    fn finish(self) -> Result<(), DeserializeError> {
+        if !self.bytes.is_empty() {
+            return Err(DeserializeError::malformed("encountered trailing bytes"));
+        }
+        Ok(())
+    }
+}
+
+/// Types that can be encoded by an [`Encoder`].
+trait Encode {
+    /// Encodes `self` using the `encoder`.
+    fn encode(&self, encoder: &mut Encoder);
+}
+
+/// Types that can be decoded by a [`Decoder`].
+trait Decode: Sized {
+    /// Decodes a value of `Self` using the `decoder`.
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError>;
+}
+
+macro_rules! impl_codec_for_int {
+    ( $( $int:ty ),* $(,)? ) => {
+        $(
+            impl Encode for $int {
+                fn encode(&self, encoder: &mut Encoder) {
+                    encoder.bytes.extend_from_slice(&self.to_le_bytes())
+                }
+            }
+
+            impl Decode for $int {
+                fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+                    decoder.array().map(<$int>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_int!(u8, u16, u32, u64, i32, i64);
+
+impl Encode for usize {
+    fn encode(&self, encoder: &mut Encoder) {
+        (*self as u64).encode(encoder)
+    }
+}
+
+impl Decode for usize {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        usize::try_from(u64::decode(decoder)?)
+            .map_err(|_| DeserializeError::malformed("encountered out of bounds integer"))
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            None => 0_u8.encode(encoder),
+            Some(value) => {
+                1_u8.encode(encoder);
+                value.encode(encoder);
+            }
+        }
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode,
+{
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(None),
+            1 => T::decode(decoder).map(Some),
+            _ => Err(DeserializeError::malformed("invalid option")),
+        }
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.len(self.len());
+        encoder.bytes.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decode for Box<str> {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let len = decoder.len()?;
+        let bytes = decoder.take(len)?;
+        str::from_utf8(bytes)
+            .map(Into::into)
+            .map_err(|_| DeserializeError::malformed("invalid UTF-8 string"))
+    }
+}
+
+impl Decode for Arc<str> {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        <Box<str>>::decode(decoder).map(Into::into)
+    }
+}
+
+impl Encode for [u8] {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.len(self.len());
+        encoder.bytes.extend_from_slice(self);
+    }
+}
+
+impl Decode for Box<[u8]> {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let len = decoder.len()?;
+        decoder.take(len).map(Into::into)
+    }
+}
+
+impl Encode for ValueType {
+    fn encode(&self, encoder: &mut Encoder) {
+        let tag: u8 = match self {
+            ValueType::I32 => 0,
+            ValueType::I64 => 1,
+            ValueType::F32 => 2,
+            ValueType::F64 => 3,
+        };
+        tag.encode(encoder)
+    }
+}
+
+impl Decode for ValueType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(ValueType::I32),
+            1 => Ok(ValueType::I64),
+            2 => Ok(ValueType::F32),
+            3 => Ok(ValueType::F64),
+            _ => Err(DeserializeError::malformed("invalid value type")),
+        }
+    }
+}
+
+impl Encode for Value {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.value_type().encode(encoder);
+        match self {
+            Value::I32(value) => value.encode(encoder),
+            Value::I64(value) => value.encode(encoder),
+            Value::F32(value) => value.to_bits().encode(encoder),
+            Value::F64(value) => value.to_bits().encode(encoder),
+        }
+    }
+}
+
+impl Decode for Value {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let value = match ValueType::decode(decoder)? {
+            ValueType::I32 => Value::I32(i32::decode(decoder)?),
+            ValueType::I64 => Value::I64(i64::decode(decoder)?),
+            ValueType::F32 => Value::F32(F32::from_bits(u32::decode(decoder)?)),
+            ValueType::F64 => Value::F64(F64::from_bits(u64::decode(decoder)?)),
+        };
+        Ok(value)
+    }
+}
+
+impl Encode for FuncType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.seq(self.params());
+        encoder.seq(self.results());
+    }
+}
+
+impl Decode for FuncType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let params = decoder.seq::<ValueType>()?;
+        let results = decoder.seq::<ValueType>()?;
+        Ok(FuncType::new(params, results))
+    }
+}
+
+impl Encode for GlobalType {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.value_type().encode(encoder);
+        let mutable = matches!(self.mutability(), Mutability::Mutable);
+        u8::from(mutable).encode(encoder);
+    }
+}
+
+impl Decode for GlobalType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let value_type = ValueType::decode(decoder)?;
+        let mutability = match u8::decode(decoder)? {
+            0 => Mutability::Const,
+            1 => Mutability::Mutable,
+            _ => return Err(DeserializeError::malformed("invalid mutability")),
+        };
+        Ok(GlobalType::new(value_type, mutability))
+    }
+}
+
+impl Encode for TableType {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.initial().encode(encoder);
+        self.maximum().encode(encoder);
+    }
+}
+
+impl Decode for TableType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let initial = usize::decode(decoder)?;
+        let maximum = <Option<usize>>::decode(decoder)?;
+        if maximum.is_some_and(|maximum| initial > maximum) {
+            return Err(DeserializeError::malformed("invalid table type"));
+        }
+        Ok(TableType::new(initial, maximum))
+    }
+}
+
+impl Encode for MemoryType {
+    fn encode(&self, encoder: &mut Encoder) {
+        // Note: The amounts of pages of memory types are constructed from `u32` values.
+        let Pages(initial) = self.initial_pages();
+        (initial as u32).encode(encoder);
+        self.maximum_pages()
+            .map(|Pages(maximum)| maximum as u32)
+            .encode(encoder);
+    }
+}
+
+impl Decode for MemoryType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let initial = u32::decode(decoder)?;
+        let maximum = <Option<u32>>::decode(decoder)?;
+        Ok(MemoryType::new(initial, maximum))
+    }
+}
+
+impl Encode for ImportName {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.module().encode(encoder);
+        self.field().encode(encoder);
+    }
+}
+
+impl Decode for ImportName {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let module = <Box<str>>::decode(decoder)?;
+        let field = <Option<Box<str>>>::decode(decoder)?;
+        Ok(ImportName::new(&module, field.as_deref()))
+    }
+}
+
+impl Encode for Imported {
+    fn encode(&self, encoder: &mut Encoder) {
+        let (tag, name): (u8, _) = match self {
+            Imported::Func(name) => (0, name),
+            Imported::Table(name) => (1, name),
+            Imported::Memory(name) => (2, name),
+            Imported::Global(name) => (3, name),
+        };
+        tag.encode(encoder);
+        name.encode(encoder);
+    }
+}
+
+impl Decode for Imported {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let tag = u8::decode(decoder)?;
+        let name = ImportName::decode(decoder)?;
+        match tag {
+            0 => Ok(Imported::Func(name)),
+            1 => Ok(Imported::Table(name)),
+            2 => Ok(Imported::Memory(name)),
+            3 => Ok(Imported::Global(name)),
+            _ => Err(DeserializeError::malformed("invalid import kind")),
+        }
+    }
+}
+
+impl Encode for InitExpr {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.seq(self.operators());
+    }
+}
+
+impl Decode for InitExpr {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        // Note: Initializer expressions currently consist of exactly one operand.
+        let mut operators = decoder.seq::<InitExprOperand>()?;
+        match (operators.pop(), operators.is_empty()) {
+            (Some(operand), true) => Ok(InitExpr::new(operand)),
+            _ => Err(DeserializeError::malformed(
+                "invalid initializer expression",
+            )),
+        }
+    }
+}
+
+impl Encode for InitExprOperand {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            InitExprOperand::Const(value) => {
+                0_u8.encode(encoder);
+                value.encode(encoder);
+            }
+            InitExprOperand::GlobalGet(global_index) => {
+                1_u8.encode(encoder);
+                global_index.into_u32().encode(encoder);
+            }
+        }
+    }
+}
+
+impl Decode for InitExprOperand {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        match u8::decode(decoder)? {
+            0 => Value::decode(decoder).map(InitExprOperand::Const),
+            1 => u32::decode(decoder).map(|index| InitExprOperand::GlobalGet(GlobalIdx(index))),
+            _ => Err(DeserializeError::malformed(
+                "invalid initializer expression operand",
+            )),
+        }
+    }
+}
+
+impl Encode for Export {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.field().encode(encoder);
+        let (tag, index): (u8, _) = match self.external() {
+            External::Func(index) => (0, index.into_u32()),
+            External::Table(index) => (1, index.into_u32()),
+            External::Memory(index) => (2, index.into_u32()),
+            External::Global(index) => (3, index.into_u32()),
+        };
+        tag.encode(encoder);
+        index.encode(encoder);
+    }
+}
+
+impl Decode for Export {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let field = <Box<str>>::decode(decoder)?;
+        let tag = u8::decode(decoder)?;
+        let index = u32::decode(decoder)?;
+        let external = match tag {
+            0 => External::Func(FuncIdx(index)),
+            1 => External::Table(TableIdx(index)),
+            2 => External::Memory(MemoryIdx(index)),
+            3 => External::Global(GlobalIdx(index)),
+            _ => return Err(DeserializeError::malformed("invalid export kind")),
+        };
+        Ok(Export::new(field, external))
+    }
+}
+
+impl Encode for ElementSegment {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self.kind() {
+            ElementSegmentKind::Passive => 0_u8.encode(encoder),
+            ElementSegmentKind::Active {
+                table_index,
+                offset,
+            } => {
+                1_u8.encode(encoder);
+                table_index.into_u32().encode(encoder);
+                offset.encode(encoder);
+            }
+            ElementSegmentKind::Declared => 2_u8.encode(encoder),
+        }
+        encoder.len(self.items().len());
+        for item in self.items() {
+            item.into_u32().encode(encoder);
+        }
+    }
+}
+
+impl Decode for ElementSegment {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let kind = match u8::decode(decoder)? {
+            0 => ElementSegmentKind::Passive,
+            1 => ElementSegmentKind::Active {
+                table_index: TableIdx(u32::decode(decoder)?),
+                offset: InitExpr::decode(decoder)?,
+            },
+            2 => ElementSegmentKind::Declared,
+            _ => return Err(DeserializeError::malformed("invalid element segment kind")),
+        };
+        let items = decoder
+            .seq::<u32>()?
+            .into_iter()
+            .map(FuncIdx)
+            .collect::<Box<[_]>>();
+        Ok(ElementSegment::new(kind, items))
+    }
+}
+
+impl Encode for DataSegment {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self.kind() {
+            DataSegmentKind::Passive => 0_u8.encode(encoder),
+            DataSegmentKind::Active {
+                memory_index,
+                offset,
+            } => {
+                1_u8.encode(encoder);
+                memory_index.into_u32().encode(encoder);
+                offset.encode(encoder);
+            }
+        }
+        self.data().encode(encoder);
+    }
+}
+
+impl Decode for DataSegment {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let kind = match u8::decode(decoder)? {
+            0 => DataSegmentKind::Passive,
+            1 => DataSegmentKind::Active {
+                memory_index: MemoryIdx(u32::decode(decoder)?),
+                offset: InitExpr::decode(decoder)?,
+            },
+            _ => return Err(DeserializeError::malformed("invalid data segment kind")),
+        };
+        let data = <Box<[u8]>>::decode(decoder)?;
+        Ok(DataSegment::new(kind, data.into()))
+    }
+}
+
+impl Encode for ModuleNames {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.module().map(|name| &**name).encode(encoder);
+        let funcs = self.funcs();
+        encoder.len(funcs.len());
+        for (index, name) in funcs {
+            index.encode(encoder);
+            name.encode(encoder);
+        }
+    }
+}
+
+impl Decode for ModuleNames {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let module = <Option<Arc<str>>>::decode(decoder)?;
+        let len_funcs = decoder.len()?;
+        let funcs = (0..len_funcs)
+            .map(|_| Ok((u32::decode(decoder)?, <Arc<str>>::decode(decoder)?)))
+            .collect::<Result<BTreeMap<_, _>, DeserializeError>>()?;
+        Ok(ModuleNames::new(module, funcs))
+    }
+}
+
+impl Encode for TranslatedFuncBody {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.len_params().encode(encoder);
+        self.len_locals().encode(encoder);
+        self.max_stack_height().encode(encoder);
+        encoder.seq(self.insts());
+        encoder.seq(self.offsets());
+    }
+}
+
+impl<T> Encode for &'_ T
+where
+    T: Encode + ?Sized,
+{
+    fn encode(&self, encoder: &mut Encoder) {
+        (**self).encode(encoder)
+    }
+}
+
+/// The decoded parts of a serialized [`Module`].
+///
+/// # Note
+///
+/// The parts are fully decoded before any of them is allocated to the
+/// [`Engine`] so that malformed serialized modules do not leave any
+/// allocations behind.
+struct DecodedModule {
+    func_types: Vec<FuncType>,
+    imports: ModuleImports,
+    funcs: Vec<u32>,
+    tables: Box<[TableType]>,
+    memories: Box<[MemoryType]>,
+    globals: Box<[GlobalType]>,
+    globals_init: Box<[InitExpr]>,
+    exports: Box<[Export]>,
+    start: Option<FuncIdx>,
+    func_bodies: Vec<DecodedFuncBody>,
+    element_segments: Box<[ElementSegment]>,
+    data_segments: Box<[DataSegment]>,
+    names: ModuleNames,
+}
+
+/// A decoded translated Wasm function body.
+struct DecodedFuncBody {
+    len_params: usize,
+    len_locals: usize,
+    max_stack_height: usize,
+    insts: Vec<crate::engine::bytecode::Instruction>,
+    offsets: Vec<u32>,
+}
+
+impl Decode for DecodedFuncBody {
+    fn decode(decoder: &mut Decoder) -> Result<Self, DeserializeError> {
+        let len_params = usize::decode(decoder)?;
+        let len_locals = usize::decode(decoder)?;
+        let max_stack_height = usize::decode(decoder)?;
+        let insts = decoder.seq()?;
+        let offsets = decoder.seq::<u32>()?;
+        if insts.len() != offsets.len() {
+            return Err(DeserializeError::malformed(
+                "mismatch of instructions and offsets",
+            ));
+        }
+        Ok(Self {
+            len_params,
+            len_locals,
+            max_stack_height,
+            insts,
+            offsets,
+        })
+    }
+}
+
+impl Module {
+    /// Serializes the [`Module`] into a versioned binary format.
+    ///
+    /// The result contains the metadata of the [`Module`] as well as the translated
+    /// `wasmi` bytecode of all its functions and can be turned back into a [`Module`]
+    /// via [`Module::deserialize`] without parsing, validating and translating
+    /// the original Wasm bytecode again.
+    ///
+    /// # Note
+    ///
+    /// Lazily translated functions of the [`Module`] are translated if necessary.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        encoder.bytes.extend_from_slice(&MAGIC);
+        FORMAT_VERSION.encode(&mut encoder);
+        CRATE_VERSION.encode(&mut encoder);
+        config_features(&self.engine.config()).encode(&mut encoder);
+        let func_types = self
+            .func_types
+            .iter()
+            .map(|func_type| self.engine.resolve_func_type(*func_type, Clone::clone))
+            .collect::<Vec<_>>();
+        encoder.seq(&func_types);
+        encoder.seq(&self.imports.items[..]);
+        self.imports.len_funcs.encode(&mut encoder);
+        self.imports.len_globals.encode(&mut encoder);
+        let funcs = self
+            .funcs
+            .iter()
+            .map(|func_type| self.func_type_index(*func_type))
+            .collect::<Vec<_>>();
+        encoder.seq(&funcs);
+        encoder.seq(&self.tables[..]);
+        encoder.seq(&self.memories[..]);
+        encoder.seq(&self.globals[..]);
+        encoder.seq(&self.globals_init[..]);
+        encoder.seq(&self.exports[..]);
+        self.start.map(FuncIdx::into_u32).encode(&mut encoder);
+        let func_bodies = self
+            .func_bodies
+            .iter()
+            .map(|func_body| self.engine.translated_func_body(*func_body))
+            .collect::<Vec<_>>();
+        encoder.seq(&func_bodies);
+        encoder.seq(&self.element_segments[..]);
+        encoder.seq(&self.data_segments[..]);
+        self.names.encode(&mut encoder);
+        encoder.bytes
+    }
+
+    /// Returns the index of the first function type of the [`Module`] equal to `func_type`.
+    ///
+    /// # Panics
+    ///
+    /// If `func_type` is not a function type of the [`Module`].
+    fn func_type_index(&self, func_type: DedupFuncType) -> u32 {
+        let index = self
+            .func_types
+            .iter()
+            .position(|candidate| *candidate == func_type)
+            .unwrap_or_else(|| panic!("encountered unknown function type: {:?}", func_type));
+        index as u32
+    }
+
+    /// Deserializes a [`Module`] for the `engine` from `bytes` created by [`Module::serialize`].
+    ///
+    /// This neither parses, validates nor translates Wasm bytecode.
+    ///
+    /// # Errors
+    ///
+    /// - If `bytes` have not been created by [`Module::serialize`].
+    /// - If `bytes` have been created by a different version of `wasmi`.
+    /// - If `bytes` have been created by an [`Engine`] with different [`Config`] features.
+    /// - If `bytes` are malformed.
+    ///
+    /// # Safety
+    ///
+    /// The `wasmi` bytecode contained in `bytes` is not validated and executed as is.
+    /// Therefore the caller must ensure that `bytes` have been created by
+    /// [`Module::serialize`] and have not been altered since then.
+    /// Executing the functions of a [`Module`] deserialized from arbitrary bytes
+    /// is undefined behavior.
+    pub unsafe fn deserialize(engine: &Engine, bytes: &[u8]) -> Result<Self, Error> {
+        Self::deserialize_impl(engine, bytes)
+            .map_err(ModuleError::from)
+            .map_err(Into::into)
+    }
+
+    /// Implementation of [`Module::deserialize`].
+    fn deserialize_impl(engine: &Engine, bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let mut decoder = Decoder::new(bytes);
+        if decoder.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err(DeserializeError::InvalidMagic);
+        }
+        let format_version = u32::decode(&mut decoder)?;
+        let crate_version = <Box<str>>::decode(&mut decoder)?;
+        let features = u32::decode(&mut decoder);
+        let expected_features = config_features(&engine.config());
+        let version_mismatch = |features| DeserializeError::VersionMismatch {
+            format_version,
+            crate_version: crate_version.to_string(),
+            features,
+            expected_features,
+        };
+        if format_version != FORMAT_VERSION || &*crate_version != CRATE_VERSION {
+            // Other format versions might not encode the features the same way.
+            return Err(version_mismatch(features.unwrap_or_default()));
+        }
+        let features = features?;
+        if features != expected_features {
+            return Err(version_mismatch(features));
+        }
+        let decoded = Self::decode_parts(&mut decoder)?;
+        decoder.finish()?;
+        Ok(Self::from_decoded(engine, decoded))
+    }
+
+    /// Decodes the parts of a serialized [`Module`] following its header.
+    ///
+    /// # Errors
+    ///
+    /// If the parts are malformed or inconsistent.
+    fn decode_parts(decoder: &mut Decoder) -> Result<DecodedModule, DeserializeError> {
+        let func_types = decoder.seq::<FuncType>()?;
+        let items = decoder.seq::<Imported>()?.into_boxed_slice();
+        let len_funcs = usize::decode(decoder)?;
+        let len_globals = usize::decode(decoder)?;
+        let imports = ModuleImports {
+            items,
+            len_funcs,
+            len_globals,
+        };
+        let funcs = decoder.seq::<u32>()?;
+        let tables = decoder.seq::<TableType>()?.into();
+        let memories = decoder.seq::<MemoryType>()?.into();
+        let globals = decoder.seq::<GlobalType>()?.into_boxed_slice();
+        let globals_init = decoder.seq::<InitExpr>()?.into_boxed_slice();
+        let exports = decoder.seq::<Export>()?.into();
+        let start = <Option<u32>>::decode(decoder)?.map(FuncIdx);
+        let func_bodies = decoder.seq::<DecodedFuncBody>()?;
+        let element_segments = decoder.seq::<ElementSegment>()?.into();
+        let data_segments = decoder.seq::<DataSegment>()?.into();
+        let names = ModuleNames::decode(decoder)?;
+        if funcs
+            .iter()
+            .any(|func_type| *func_type as usize >= func_types.len())
+        {
+            return Err(DeserializeError::malformed("invalid function type index"));
+        }
+        if len_funcs > funcs.len() || len_funcs + func_bodies.len() != funcs.len() {
+            return Err(DeserializeError::malformed(
+                "mismatch of functions and bodies",
+            ));
+        }
+        if len_globals > globals.len() || len_globals + globals_init.len() != globals.len() {
+            return Err(DeserializeError::malformed("mismatch of global variables"));
+        }
+        Ok(DecodedModule {
+            func_types,
+            imports,
+            funcs,
+            tables,
+            memories,
+            globals,
+            globals_init,
+            exports,
+            start,
+            func_bodies,
+            element_segments,
+            data_segments,
+            names,
+        })
+    }
+
+    /// Creates a new [`Module`] for the `engine` from its decoded parts.
+    fn from_decoded(engine: &Engine, decoded: DecodedModule) -> Self {
+        let mut code = ModuleCode::new(engine);
+        let func_types = decoded
+            .func_types
+            .into_iter()
+            .map(|func_type| code.alloc_func_type(func_type))
+            .collect::<Box<[_]>>();
+        let funcs = decoded
+            .funcs
+            .into_iter()
+            .map(|func_type| func_types[func_type as usize])
+            .collect::<Box<[_]>>();
+        let len_imported_funcs = decoded.imports.len_funcs;
+        let func_bodies = decoded
+            .func_bodies
+            .into_iter()
+            .enumerate()
+            .map(|(index, func_body)| {
+                let func_index = (len_imported_funcs + index) as u32;
+                let func_body = engine.alloc_func_body(TranslatedFuncBody::new(
+                    func_index,
+                    func_body.len_params,
+                    func_body.len_locals,
+                    func_body.max_stack_height,
+                    func_body.insts,
+                    func_body.offsets,
+                ));
+                code.push_func_body(func_body);
+                func_body
+            })
+            .collect::<Box<[_]>>();
+        Self {
+            engine: engine.clone(),
+            func_types,
+            imports: decoded.imports,
+            funcs,
+            tables: decoded.tables,
+            memories: decoded.memories,
+            globals: decoded.globals,
+            globals_init: decoded.globals_init,
+            exports: decoded.exports,
+            start: decoded.start,
+            func_bodies,
+            element_segments: decoded.element_segments,
+            data_segments: decoded.data_segments,
+            names: Arc::new(decoded.names),
+            code: Arc::new(code),
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use super::*;
 use crate::{
     engine::{
-        bytecode::{Instruction, LocalIdx},
+        bytecode::{self, Instruction, Register},
         DropKeep,
         InstructionIdx,
         Target,
     },
+    Config,
     Engine,
 };
 
@@ -14,13 +15,13 @@ fn wat2wasm(wat: &str) -> Vec<u8> {
     wat::parse_str(wat).unwrap()
 }
 
-/// Compiles the `wasm` encoded bytes into a [`Module`].
+/// Compiles the `wasm` encoded bytes into a [`Module`] using the given [`Config`].
 ///
 /// # Panics
 ///
 /// If an error occurred upon module compilation, validation or translation.
-fn create_module(bytes: &[u8]) -> Module {
-    let engine = Engine::default();
+fn create_module(config: &Config, bytes: &[u8]) -> Module {
+    let engine = Engine::new(config);
     Module::new(&engine, bytes).unwrap()
 }
 
@@ -71,6 +72,48 @@ fn assert_func_body<E>(
     }
 }
 
+/// Returns the [`Register`] with the given `index`.
+fn reg(index: u32) -> Register {
+    Register::from(index)
+}
+
+/// Returns a branch [`Target`] to the instruction at `inst_idx`.
+fn target(inst_idx: usize) -> Target {
+    Target::new(InstructionIdx::from_usize(inst_idx))
+}
+
+/// Returns an [`Instruction::BrIfEqz`] to `inst_idx` conditioned on register `condition`.
+fn br_if_eqz(condition: u32, inst_idx: usize) -> Instruction {
+    Instruction::BrIfEqz {
+        condition: reg(condition),
+        target: target(inst_idx),
+    }
+}
+
+/// Returns an [`Instruction::BrIfNez`] to `inst_idx` conditioned on register `condition`.
+fn br_if_nez(condition: u32, inst_idx: usize) -> Instruction {
+    Instruction::BrIfNez {
+        condition: reg(condition),
+        target: target(inst_idx),
+    }
+}
+
+/// Returns an [`Instruction::BrTable`] indexed by register `index`.
+fn br_table_inst(index: u32, len_targets: usize) -> Instruction {
+    Instruction::BrTable {
+        index: reg(index),
+        len_targets,
+    }
+}
+
+/// Returns an [`Instruction::Copy`] from register `input` to register `result`.
+fn copy(result: u32, input: u32) -> Instruction {
+    Instruction::Copy {
+        result: reg(result),
+        input: reg(input),
+    }
+}
+
 /// Asserts that the given `wasm` bytes yield functions with expected instructions.
 ///
 /// # Panics
@@ -78,13 +121,46 @@ fn assert_func_body<E>(
 /// If any of the yielded functions consists of instruction different from the
 /// expected instructions for that function.
 fn assert_func_bodies<E, T>(wasm_bytes: impl AsRef<[u8]>, expected: E)
+where
+    E: IntoIterator<Item = T>,
+    T: IntoIterator<Item = Instruction>,
+    <T as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    assert_func_bodies_with_config(&Config::default(), wasm_bytes, expected)
+}
+
+/// Asserts that the given `wasm` bytes yield functions with expected instructions
+/// when translated with translation-time optimizations enabled.
+///
+/// # Panics
+///
+/// If any of the yielded functions consists of instruction different from the
+/// expected instructions for that function.
+fn assert_optimized_func_bodies<E, T>(wasm_bytes: impl AsRef<[u8]>, expected: E)
+where
+    E: IntoIterator<Item = T>,
+    T: IntoIterator<Item = Instruction>,
+    <T as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    let config = Config::default().enable_optimizations(true);
+    assert_func_bodies_with_config(&config, wasm_bytes, expected)
+}
+
+/// Asserts that the given `wasm` bytes yield functions with expected instructions
+/// when translated using the given [`Config`].
+///
+/// # Panics
+///
+/// If any of the yielded functions consists of instruction different from the
+/// expected instructions for that function.
+fn assert_func_bodies_with_config<E, T>(config: &Config, wasm_bytes: impl AsRef<[u8]>, expected: E)
 where
     E: IntoIterator<Item = T>,
     T: IntoIterator<Item = Instruction>,
     <T as IntoIterator>::IntoIter: ExactSizeIterator,
 {
     let wasm_bytes = wasm_bytes.as_ref();
-    let module = create_module(wasm_bytes);
+    let module = create_module(config, wasm_bytes);
     let engine = module.engine();
     for ((func_type, func_body), expected) in module.internal_funcs().zip(expected) {
         assert_func_body(engine, func_type, func_body, expected);
@@ -117,7 +193,7 @@ fn implicit_return_with_value() {
     "#,
     );
     let expected = [
-        Instruction::constant(0),
+        Instruction::constant(reg(0), 0),
         Instruction::Return(DropKeep::new(0, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
@@ -133,7 +209,7 @@ fn implicit_return_param() {
         )
     "#,
     );
-    let expected = [Instruction::Return(DropKeep::new(1, 0))];
+    let expected = [Instruction::Return(DropKeep::new(0, 0))];
     assert_func_bodies(&wasm, [expected]);
 }
 
@@ -148,10 +224,7 @@ fn get_local() {
         )
     "#,
     );
-    let expected = [
-        Instruction::local_get(1),
-        Instruction::Return(DropKeep::new(1, 1)),
-    ];
+    let expected = [Instruction::Return(DropKeep::new(0, 1))];
     assert_func_bodies(&wasm, [expected]);
 }
 
@@ -168,12 +241,7 @@ fn get_local_2() {
         )
     "#,
     );
-    let expected = [
-        Instruction::local_get(2),
-        Instruction::local_get(2),
-        Instruction::Drop,
-        Instruction::Return(DropKeep::new(2, 1)),
-    ];
+    let expected = [Instruction::Return(DropKeep::new(0, 1))];
     assert_func_bodies(&wasm, [expected]);
 }
 
@@ -191,13 +259,7 @@ fn get_local_3() {
         )
     "#,
     );
-    let expected = [
-        Instruction::local_get(2),
-        Instruction::local_get(2),
-        Instruction::Drop,
-        Instruction::Drop,
-        Instruction::Return(DropKeep::new(2, 0)),
-    ];
+    let expected = [Instruction::Return(DropKeep::new(0, 0))];
     assert_func_bodies(&wasm, [expected]);
 }
 
@@ -213,10 +275,7 @@ fn explicit_return() {
         )
     "#,
     );
-    let expected = [
-        Instruction::local_get(1),
-        Instruction::Return(DropKeep::new(1, 1)),
-    ];
+    let expected = [Instruction::Return(DropKeep::new(0, 1))];
     assert_func_bodies(&wasm, [expected]);
 }
 
@@ -234,9 +293,11 @@ fn simple_add() {
     "#,
     );
     let expected = [
-        Instruction::local_get(2),
-        Instruction::local_get(2),
-        Instruction::I32Add,
+        Instruction::I32Add {
+            result: reg(2),
+            lhs: reg(0),
+            rhs: reg(1),
+        },
         Instruction::Return(DropKeep::new(2, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
@@ -260,13 +321,21 @@ fn simple_mul_add() {
     "#,
     );
     let expected = [
-        Instruction::local_get(2),
-        Instruction::local_get(2),
-        Instruction::local_get(4),
-        Instruction::local_get(4),
-        Instruction::I32Add,
-        Instruction::I32Add,
-        Instruction::I32Mul,
+        Instruction::I32Add {
+            result: reg(4),
+            lhs: reg(0),
+            rhs: reg(1),
+        },
+        Instruction::I32Add {
+            result: reg(3),
+            lhs: reg(1),
+            rhs: reg(4),
+        },
+        Instruction::I32Mul {
+            result: reg(2),
+            lhs: reg(0),
+            rhs: reg(3),
+        },
         Instruction::Return(DropKeep::new(2, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
@@ -285,21 +354,198 @@ fn drop_locals() {
         )
     "#,
     );
+    let expected = [copy(1, 0), Instruction::Return(DropKeep::new(0, 0))];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn local_set_retargets_result() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32)
+                local.get 0
+                i32.const 1
+                i32.mul
+                local.set 0
+            )
+        )
+    "#,
+    );
     let expected = [
-        Instruction::local_get(2),
-        Instruction::local_set(1),
-        Instruction::Return(DropKeep::new(2, 0)),
+        Instruction::constant(reg(2), 1),
+        Instruction::I32Mul {
+            result: reg(0),
+            lhs: reg(0),
+            rhs: reg(2),
+        },
+        Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
 
-macro_rules! target {
-    ( $inst_idx:expr, drop: $drop:expr, keep: $keep:expr ) => {
-        Target::new(
-            InstructionIdx::from_usize($inst_idx),
-            DropKeep::new($drop, $keep),
+#[test]
+fn local_set_preserves_aliased_value() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (result i32)
+                local.get 0
+                i32.const 5
+                local.set 0
+                local.get 0
+                i32.sub
+            )
         )
-    };
+    "#,
+    );
+    let expected = [
+        Instruction::constant(reg(2), 5),
+        copy(1, 0),
+        copy(0, 2),
+        Instruction::I32Sub {
+            result: reg(1),
+            lhs: reg(1),
+            rhs: reg(0),
+        },
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn add_imm_fuses_constant() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i64) (result i64)
+                local.get 0
+                i64.const 2
+                i64.sub
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::I64AddImm {
+            result: reg(1),
+            input: reg(0),
+            value: -2,
+        },
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn add_imm_requires_small_constant() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i64) (result i64)
+                local.get 0
+                i64.const 0x1_0000_0000
+                i64.add
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::constant(reg(2), 0x1_0000_0000_i64),
+        Instruction::I64Add {
+            result: reg(1),
+            lhs: reg(0),
+            rhs: reg(2),
+        },
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn compare_and_branch_fuses_br_if() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32)
+                (local i32)
+                (block
+                    (loop
+                        (br_if 1
+                            (i32.eq
+                                (local.tee 1 (i32.add (local.get 1) (i32.const 1)))
+                                (local.get 0)
+                            )
+                        )
+                        (br 0)
+                    )
+                )
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */
+        Instruction::I32AddImm {
+            result: reg(1),
+            input: reg(1),
+            value: 1,
+        },
+        /* 1 */
+        Instruction::BrIfI32Eq {
+            lhs: reg(1),
+            rhs: reg(0),
+            target: target(3),
+        },
+        /* 2 */ Instruction::Br(target(0)),
+        /* 3 */ Instruction::Return(DropKeep::new(0, 0)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn compare_and_branch_fuses_if() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i64) (result i64)
+                (if (i64.le_s (local.get 0) (i64.const 1))
+                    (then (return (local.get 0)))
+                )
+                (local.get 0)
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::constant(reg(2), 1_i64),
+        /* 1 */
+        Instruction::BrIfI64GtS {
+            lhs: reg(0),
+            rhs: reg(2),
+            target: target(3),
+        },
+        /* 2 */ Instruction::Return(DropKeep::new(0, 1)),
+        /* 3 */ Instruction::Return(DropKeep::new(0, 1)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn eqz_inverts_branch_condition() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32)
+                (block
+                    (br_if 0 (i32.eqz (local.get 0)))
+                )
+            )
+        )
+    "#,
+    );
+    let expected = [br_if_eqz(0, 1), Instruction::Return(DropKeep::new(0, 0))];
+    assert_func_bodies(&wasm, [expected]);
 }
 
 #[test]
@@ -319,11 +565,11 @@ fn if_without_else() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::constant(1),
-        /* 1 */ Instruction::BrIfEqz(target!(4, drop: 0, keep: 0)),
-        /* 2 */ Instruction::constant(2),
+        /* 0 */ Instruction::constant(reg(1), 1),
+        /* 1 */ br_if_eqz(1, 4),
+        /* 2 */ Instruction::constant(reg(1), 2),
         /* 3 */ Instruction::Return(DropKeep::new(1, 1)),
-        /* 4 */ Instruction::constant(3),
+        /* 4 */ Instruction::constant(reg(1), 3),
         /* 5 */ Instruction::Return(DropKeep::new(1, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
@@ -349,14 +595,12 @@ fn if_else() {
     "#,
     );
     let expected = [
-        Instruction::constant(1),
-        Instruction::BrIfEqz(target!(5, drop: 0, keep: 0)),
-        Instruction::constant(2),
-        Instruction::local_set(1),
-        Instruction::Br(target!(7, drop: 0, keep: 0)),
-        Instruction::constant(3),
-        Instruction::local_set(1),
-        Instruction::Return(DropKeep::new(1, 0)),
+        /* 0 */ Instruction::constant(reg(1), 1),
+        /* 1 */ br_if_eqz(1, 4),
+        /* 2 */ Instruction::constant(reg(0), 2),
+        /* 3 */ Instruction::Br(target(5)),
+        /* 4 */ Instruction::constant(reg(0), 3),
+        /* 5 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -379,13 +623,12 @@ fn if_else_returns_result() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::constant(1),
-        /* 1 */ Instruction::BrIfEqz(target!(4, drop: 0, keep: 0)),
-        /* 2 */ Instruction::constant(2),
-        /* 3 */ Instruction::Br(target!(5, drop: 0, keep: 0)),
-        /* 4 */ Instruction::constant(3),
-        /* 5 */ Instruction::Drop,
-        /* 6 */ Instruction::Return(DropKeep::new(0, 0)),
+        /* 0 */ Instruction::constant(reg(0), 1),
+        /* 1 */ br_if_eqz(0, 4),
+        /* 2 */ Instruction::constant(reg(0), 2),
+        /* 3 */ Instruction::Br(target(5)),
+        /* 4 */ Instruction::constant(reg(0), 3),
+        /* 5 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -412,17 +655,15 @@ fn if_else_branch_from_true_branch() {
     "#,
     );
     let expected = [
-        /*  0 */ Instruction::constant(1),
-        /*  1 */ Instruction::BrIfEqz(target!(8, drop: 0, keep: 0)),
-        /*  2 */ Instruction::constant(1),
-        /*  3 */ Instruction::constant(1),
-        /*  4 */ Instruction::BrIfNez(target!(9, drop: 0, keep: 1)),
-        /*  5 */ Instruction::Drop,
-        /*  6 */ Instruction::constant(2),
-        /*  7 */ Instruction::Br(target!(9, drop: 0, keep: 0)),
-        /*  8 */ Instruction::constant(3),
-        /*  9 */ Instruction::Drop,
-        /* 10 */ Instruction::Return(DropKeep::new(0, 0)),
+        /* 0 */ Instruction::constant(reg(0), 1),
+        /* 1 */ br_if_eqz(0, 7),
+        /* 2 */ Instruction::constant(reg(0), 1),
+        /* 3 */ Instruction::constant(reg(1), 1),
+        /* 4 */ br_if_nez(1, 8),
+        /* 5 */ Instruction::constant(reg(0), 2),
+        /* 6 */ Instruction::Br(target(8)),
+        /* 7 */ Instruction::constant(reg(0), 3),
+        /* 8 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -449,17 +690,15 @@ fn if_else_branch_from_false_branch() {
     "#,
     );
     let expected = [
-        /*  0 */ Instruction::constant(1),
-        /*  1 */ Instruction::BrIfEqz(target!(4, drop: 0, keep: 0)),
-        /*  2 */ Instruction::constant(1),
-        /*  3 */ Instruction::Br(target!(9, drop: 0, keep: 0)),
-        /*  4 */ Instruction::constant(2),
-        /*  5 */ Instruction::constant(1),
-        /*  6 */ Instruction::BrIfNez(target!(9, drop: 0, keep: 1)),
-        /*  7 */ Instruction::Drop,
-        /*  8 */ Instruction::constant(3),
-        /*  9 */ Instruction::Drop,
-        /* 10 */ Instruction::Return(DropKeep::new(0, 0)),
+        /* 0 */ Instruction::constant(reg(0), 1),
+        /* 1 */ br_if_eqz(0, 4),
+        /* 2 */ Instruction::constant(reg(0), 1),
+        /* 3 */ Instruction::Br(target(8)),
+        /* 4 */ Instruction::constant(reg(0), 2),
+        /* 5 */ Instruction::constant(reg(1), 1),
+        /* 6 */ br_if_nez(1, 8),
+        /* 7 */ Instruction::constant(reg(0), 3),
+        /* 8 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -487,15 +726,13 @@ fn if_else_both_unreachable_before_end() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::local_get(1),
-        /* 1 */ Instruction::BrIfEqz(target!(4, drop: 0, keep: 0)),
-        /* 2 */ Instruction::constant(1),
-        /* 3 */ Instruction::Return(DropKeep::new(1, 1)),
-        /* 4 */ Instruction::constant(2),
-        /* 5 */ Instruction::Return(DropKeep::new(1, 1)),
-        /* 6 */ Instruction::Drop,
-        /* 7 */ Instruction::constant(3),
-        /* 8 */ Instruction::Return(DropKeep::new(1, 1)),
+        /* 0 */ br_if_eqz(0, 3),
+        /* 1 */ Instruction::constant(reg(1), 1),
+        /* 2 */ Instruction::Return(DropKeep::new(1, 1)),
+        /* 3 */ Instruction::constant(reg(1), 2),
+        /* 4 */ Instruction::Return(DropKeep::new(1, 1)),
+        /* 5 */ Instruction::constant(reg(1), 3),
+        /* 6 */ Instruction::Return(DropKeep::new(1, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -517,11 +754,10 @@ fn loop_() {
     "#,
     );
     let expected = [
-        Instruction::constant(1),
-        Instruction::BrIfNez(target!(0, drop: 0, keep: 0)),
-        Instruction::constant(2),
-        Instruction::Drop,
-        Instruction::Return(DropKeep::new(0, 0)),
+        /* 0 */ Instruction::constant(reg(0), 1),
+        /* 1 */ br_if_nez(0, 0),
+        /* 2 */ Instruction::constant(reg(0), 2),
+        /* 3 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -563,13 +799,15 @@ fn spec_as_br_if_value_cond() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::constant(6),
-        /* 1 */ Instruction::constant(9),
-        /* 2 */ Instruction::constant(0),
-        /* 3 */ Instruction::BrTable { len_targets: 2 },
-        /* 4 */ Instruction::Br(target!(6, drop: 1, keep: 1)),
-        /* 5 */ Instruction::Br(target!(6, drop: 1, keep: 1)),
-        /* 6 */ Instruction::Return(DropKeep::new(0, 1)),
+        /* 0 */ Instruction::constant(reg(0), 6),
+        /* 1 */ Instruction::constant(reg(1), 9),
+        /* 2 */ Instruction::constant(reg(2), 0),
+        /* 3 */ br_table_inst(2, 2),
+        /* 4 */ Instruction::Br(target(6)),
+        /* 5 */ Instruction::Br(target(6)),
+        /* 6 */ copy(0, 1),
+        /* 7 */ Instruction::Br(target(8)),
+        /* 8 */ Instruction::Return(DropKeep::new(0, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -591,10 +829,10 @@ fn br_table() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::constant(0),
-        /* 1 */ Instruction::BrTable { len_targets: 2 },
-        /* 2 */ Instruction::Br(target!(0, drop: 0, keep: 0)),
-        /* 3 */ Instruction::Br(target!(4, drop: 0, keep: 0)),
+        /* 0 */ Instruction::constant(reg(0), 0),
+        /* 1 */ br_table_inst(0, 2),
+        /* 2 */ Instruction::Br(target(0)),
+        /* 3 */ Instruction::Br(target(4)),
         /* 4 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
@@ -620,14 +858,13 @@ fn br_table_returns_result() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::constant(0),
-        /* 1 */ Instruction::constant(1),
-        /* 2 */ Instruction::BrTable { len_targets: 2 },
-        /* 3 */ Instruction::Br(target!(5, drop: 0, keep: 1)),
-        /* 4 */ Instruction::Br(target!(6, drop: 0, keep: 1)),
+        /* 0 */ Instruction::constant(reg(0), 0),
+        /* 1 */ Instruction::constant(reg(1), 1),
+        /* 2 */ br_table_inst(1, 2),
+        /* 3 */ Instruction::Br(target(5)),
+        /* 4 */ Instruction::Br(target(6)),
         /* 5 */ Instruction::Unreachable,
-        /* 6 */ Instruction::Drop,
-        /* 7 */ Instruction::Return(DropKeep::new(0, 0)),
+        /* 6 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -651,12 +888,11 @@ fn wabt_example() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::local_get(1),
-        /* 1 */ Instruction::BrIfNez(target!(4, drop: 0, keep: 0)),
-        /* 2 */ Instruction::constant(1),
-        /* 3 */ Instruction::Return(DropKeep::new(1, 1)),
-        /* 4 */ Instruction::constant(2),
-        /* 5 */ Instruction::Return(DropKeep::new(1, 1)),
+        /* 0 */ br_if_nez(0, 3),
+        /* 1 */ Instruction::constant(reg(1), 1),
+        /* 2 */ Instruction::Return(DropKeep::new(1, 1)),
+        /* 3 */ Instruction::constant(reg(1), 2),
+        /* 4 */ Instruction::Return(DropKeep::new(1, 1)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -691,9 +927,11 @@ fn br_if_return() {
     "#,
     );
     let expected = [
-        Instruction::local_get(1),
-        Instruction::ReturnIfNez(DropKeep::new(1, 0)),
-        Instruction::Return(DropKeep::new(1, 0)),
+        Instruction::ReturnIfNez {
+            condition: reg(0),
+            results: DropKeep::new(0, 0),
+        },
+        Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
@@ -716,12 +954,684 @@ fn br_table_return() {
     "#,
     );
     let expected = [
-        /* 0 */ Instruction::local_get(1),
-        /* 1 */ Instruction::BrTable { len_targets: 3 },
-        /* 2 */ Instruction::Br(target!(5, drop: 0, keep: 0)),
-        /* 3 */ Instruction::Br(target!(5, drop: 0, keep: 0)),
-        /* 4 */ Instruction::Return(DropKeep::new(1, 0)),
-        /* 5 */ Instruction::Return(DropKeep::new(1, 0)),
+        /* 0 */ br_table_inst(0, 3),
+        /* 1 */ Instruction::Br(target(4)),
+        /* 2 */ Instruction::Br(target(4)),
+        /* 3 */ Instruction::Return(DropKeep::new(0, 0)),
+        /* 4 */ Instruction::Return(DropKeep::new(0, 0)),
     ];
     assert_func_bodies(&wasm, [expected]);
 }
+
+#[test]
+fn return_call() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (type $t (func (param i32 i32) (result i32)))
+            (table 1 funcref)
+            (func $f (export "call") (param i32 i32) (result i32)
+                local.get 1
+                local.get 0
+                return_call $f
+            )
+            (func (param i32) (result i32)
+                local.get 0
+                local.get 0
+                local.get 0
+                return_call_indirect (type $t)
+            )
+        )
+    "#,
+    );
+    // The Wasm `return_call` instructions are rejected unless the `tail-call` proposal is enabled.
+    assert!(Module::new(&Engine::default(), &wasm[..]).is_err());
+    let expected_call = [
+        copy(2, 1),
+        copy(3, 0),
+        Instruction::ReturnCall {
+            func_index: bytecode::FuncIdx::from(0),
+            params_end: reg(4),
+        },
+        Instruction::Return(DropKeep::new(2, 1)),
+    ];
+    let expected_call_indirect = [
+        copy(1, 0),
+        copy(2, 0),
+        Instruction::ReturnCallIndirect {
+            signature: bytecode::SignatureIdx::from(0),
+            index: reg(0),
+            params_end: reg(3),
+        },
+        Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    let config = Config::default().enable_tail_call(true);
+    assert_func_bodies_with_config(&config, &wasm, [expected_call, expected_call_indirect]);
+}
+
+#[test]
+fn bulk_memory() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (table 1 funcref)
+            (data "abc")
+            (elem func 0)
+            (func (param i32 i32 i32)
+                local.get 0
+                local.get 1
+                local.get 2
+                memory.copy
+                local.get 0
+                local.get 1
+                local.get 2
+                memory.fill
+                local.get 0
+                local.get 1
+                local.get 2
+                memory.init 0
+                data.drop 0
+                local.get 0
+                local.get 1
+                local.get 2
+                table.copy
+                local.get 0
+                i32.const 1
+                local.get 2
+                table.init 0
+                elem.drop 0
+            )
+        )
+    "#,
+    );
+    // The bulk memory instructions are rejected unless the `bulk-memory` proposal is enabled.
+    assert!(Module::new(&Engine::new(&Config::mvp()), &wasm[..]).is_err());
+    let data_segment = bytecode::DataSegmentIdx::from(0);
+    let element_segment = bytecode::ElementSegmentIdx::from(0);
+    let expected = [
+        Instruction::MemoryCopy {
+            dst: reg(0),
+            src: reg(1),
+            len: reg(2),
+        },
+        Instruction::MemoryFill {
+            dst: reg(0),
+            value: reg(1),
+            len: reg(2),
+        },
+        // The operands of `memory.init` are copied into consecutive registers.
+        copy(3, 0),
+        copy(4, 1),
+        copy(5, 2),
+        Instruction::MemoryInit {
+            segment: data_segment,
+            operands: reg(3),
+        },
+        Instruction::DataDrop {
+            segment: data_segment,
+        },
+        Instruction::TableCopy {
+            dst: reg(0),
+            src: reg(1),
+            len: reg(2),
+        },
+        Instruction::constant(reg(4), 1_i32),
+        copy(3, 0),
+        copy(5, 2),
+        Instruction::TableInit {
+            segment: element_segment,
+            operands: reg(3),
+        },
+        Instruction::ElemDrop {
+            segment: element_segment,
+        },
+        Instruction::Return(DropKeep::new(0, 0)),
+    ];
+    assert_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn optimize_folds_constants() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (result i32)
+                i32.const 2
+                i32.const 3
+                i32.mul
+                i32.const 1
+                i32.add
+                i32.eqz
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::constant(reg(0), 0_i32),
+        Instruction::Return(DropKeep::new(0, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn optimize_keeps_trapping_operations() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (result i32)
+                i32.const 1
+                i32.const 0
+                i32.div_u
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::constant(reg(0), 1_i32),
+        Instruction::constant(reg(1), 0_i32),
+        Instruction::I32DivU {
+            result: reg(0),
+            lhs: reg(0),
+            rhs: reg(1),
+        },
+        Instruction::Return(DropKeep::new(0, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn optimize_does_not_fold_across_branches() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i32) (result i32)
+                i32.const 1
+                block (param i32) (result i32)
+                    local.get 0
+                    br_if 0
+                end
+                i32.const 2
+                i32.add
+            )
+        )
+    "#,
+    );
+    let expected = [
+        /* 0 */ Instruction::constant(reg(1), 1_i32),
+        /* 1 */ br_if_nez(0, 2),
+        /* 2 */
+        Instruction::I32AddImm {
+            result: reg(1),
+            input: reg(1),
+            value: 2,
+        },
+        /* 3 */ Instruction::Return(DropKeep::new(1, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn optimize_removes_dropped_computations() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (global $g i32 (i32.const 0))
+            (func (export "call") (param i32 i32) (result i32)
+                local.get 0
+                local.get 1
+                i32.add
+                global.get $g
+                i32.const 1
+                i32.div_s
+                drop
+                drop
+                local.get 0
+                local.get 1
+                i32.mul
+                drop
+                local.get 0
+            )
+        )
+    "#,
+    );
+    let expected = [
+        Instruction::I32Add {
+            result: reg(2),
+            lhs: reg(0),
+            rhs: reg(1),
+        },
+        Instruction::GetGlobal {
+            result: reg(3),
+            global: bytecode::GlobalIdx::from(0),
+        },
+        Instruction::constant(reg(4), 1_i32),
+        Instruction::I32DivS {
+            result: reg(3),
+            lhs: reg(3),
+            rhs: reg(4),
+        },
+        Instruction::Return(DropKeep::new(0, 1)),
+    ];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+#[test]
+fn optimize_removes_addition_of_zero() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "call") (param i64) (result i64)
+                local.get 0
+                i64.const 0
+                i64.add
+            )
+        )
+    "#,
+    );
+    let expected = [Instruction::Return(DropKeep::new(0, 1))];
+    assert_optimized_func_bodies(&wasm, [expected]);
+}
+
+/// Returns the `.wat` source of a module with `len_funcs` functions of varying size.
+///
+/// The function at index `invalid` returns a value of the wrong type if any.
+fn many_funcs_wat(len_funcs: usize, invalid: &[usize]) -> String {
+    let mut wat = String::from("(module (global $g (mut i32) (i32.const 0))");
+    for n in 0..len_funcs {
+        let result = if invalid.contains(&n) { "i64" } else { "i32" };
+        wat.push_str(&format!(
+            "(func (param i32) (result {result}) (local.get 0)"
+        ));
+        for _ in 0..(n % 7) * 10 {
+            wat.push_str("(i32.const 1) (i32.add) (global.set $g (global.get $g)) (local.tee 0)");
+        }
+        wat.push(')');
+    }
+    wat.push(')');
+    wat
+}
+
+#[test]
+fn parallel_compilation_is_deterministic() {
+    let wasm = wat2wasm(&many_funcs_wat(100, &[]));
+    let sequential = create_module(&Config::default(), &wasm);
+    let parallel = create_module(&Config::default().enable_parallel_compilation(true), &wasm);
+    let funcs = sequential.internal_funcs().zip(parallel.internal_funcs());
+    for ((_, expected), (_, actual)) in funcs {
+        // The function bodies are allocated to the engine in the same order.
+        assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+        let mut index = 0;
+        while let Some(expected) = sequential.engine().resolve_inst(expected, index) {
+            assert_eq!(
+                parallel.engine().resolve_inst(actual, index),
+                Some(expected)
+            );
+            index += 1;
+        }
+        assert_eq!(parallel.engine().resolve_inst(actual, index), None);
+    }
+}
+
+#[test]
+fn parallel_compilation_reports_first_error() {
+    let wasm = wat2wasm(&many_funcs_wat(100, &[37, 42, 98]));
+    let config = Config::default().enable_parallel_compilation(true);
+    let expected = Module::new(&Engine::default(), &wasm[..]).unwrap_err();
+    for _ in 0..10 {
+        let actual = Module::new(&Engine::new(&config), &wasm[..]).unwrap_err();
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
+
+/// Returns the function, offset and definition of the [`ModuleError`] of compiling
+/// the `wasm` bytes with floating point support disabled.
+///
+/// # Panics
+///
+/// If the compilation does not fail due to floating point usage.
+fn deny_floating_point(wasm: &[u8]) -> (Option<u32>, usize, String) {
+    let config = Config::default().enable_floating_point(false);
+    match Module::new(&Engine::new(&config), wasm).unwrap_err() {
+        crate::Error::Module(ModuleError::FloatingPoint {
+            func,
+            offset,
+            definition,
+        }) => (func, offset, definition),
+        error => panic!("expected floating point error but found: {}", error),
+    }
+}
+
+/// Returns the offset of the first occurrence of `pattern` within the `wasm` bytes.
+fn offset_of(wasm: &[u8], pattern: &[u8]) -> usize {
+    wasm.windows(pattern.len())
+        .position(|window| window == pattern)
+        .unwrap()
+}
+
+#[test]
+fn deny_floating_point_accepts_integer_code() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (global $g (mut i64) (i64.const 0))
+            (func (export "run") (param i32) (result i32)
+                (local i64)
+                (global.set $g (i64.extend_i32_u (local.get 0)))
+                (i32.add (local.get 0) (i32.const 1))
+            )
+        )
+    "#,
+    );
+    let config = Config::default().enable_floating_point(false);
+    assert!(!config.floating_point());
+    assert!(Config::default().floating_point());
+    Module::new(&Engine::new(&config), &wasm[..]).unwrap();
+}
+
+#[test]
+fn deny_floating_point_rejects_instructions() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (result i32) (i32.const 0))
+            (func (result i32)
+                (i32.trunc_f32_s (f32.const 1.5))
+            )
+        )
+    "#,
+    );
+    let (func, offset, definition) = deny_floating_point(&wasm);
+    assert_eq!(func, Some(1));
+    // The `f32.const 1.5` instruction is encoded as `0x43` followed by its bits.
+    assert_eq!(offset, offset_of(&wasm, &[0x43, 0x00, 0x00, 0xC0, 0x3F]));
+    assert!(definition.contains("F32Const"));
+    // Floating point instructions are rejected for lazily compiled function bodies as well.
+    let config = Config::default()
+        .enable_floating_point(false)
+        .enable_lazy_compilation(true);
+    assert!(Module::new(&Engine::new(&config), &wasm[..]).is_err());
+}
+
+#[test]
+fn deny_floating_point_rejects_types() {
+    let signature = wat2wasm(
+        r#"
+        (module
+            (import "env" "host" (func (param i32)))
+            (func (param f64))
+        )
+    "#,
+    );
+    let (func, _, definition) = deny_floating_point(&signature);
+    assert_eq!((func, definition.as_str()), (Some(1), "signature"));
+    let import = wat2wasm(r#"(module (import "env" "host" (func (result f32))))"#);
+    let (func, _, definition) = deny_floating_point(&import);
+    assert_eq!((func, definition.as_str()), (Some(0), "signature"));
+    let global = wat2wasm(r#"(module (global f32 (f32.const 0)))"#);
+    let (func, _, definition) = deny_floating_point(&global);
+    assert_eq!((func, definition.as_str()), (None, "global"));
+    let local = wat2wasm(r#"(module (func (local i32 f64)))"#);
+    let (func, _, definition) = deny_floating_point(&local);
+    assert_eq!((func, definition.as_str()), (Some(0), "local"));
+    let block = wat2wasm(
+        r#"
+        (module
+            (func (block (result f32) (unreachable)) (drop))
+        )
+    "#,
+    );
+    let (func, _, definition) = deny_floating_point(&block);
+    assert_eq!(func, Some(0));
+    assert!(definition.contains("Block"));
+}
+
+#[test]
+fn deny_floating_point_error_names_function_and_offset() {
+    let wasm = wat2wasm(r#"(module (func (drop (f64.const 0))))"#);
+    let config = Config::default().enable_floating_point(false);
+    let error = Module::new(&Engine::new(&config), &wasm[..]).unwrap_err();
+    let (_, offset, _) = deny_floating_point(&wasm);
+    let message = error.to_string();
+    assert!(message.contains("in function 0"), "{}", message);
+    assert!(
+        message.contains(&format!("at offset {:#x}", offset)),
+        "{}",
+        message
+    );
+}
+
+/// Instantiates the `wasm` module with the `engine` and calls its exported `run` function.
+///
+/// Returns the result of the call.
+fn instantiate_and_run(engine: &Engine, wasm: &[u8]) -> i32 {
+    let mut store = crate::Store::new(engine, ());
+    let module = Module::new(engine, wasm).unwrap();
+    let instance = <crate::Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .unwrap();
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(crate::Extern::into_func)
+        .unwrap();
+    let mut results = [crate::core::Value::I32(0)];
+    run.call(&mut store, &[crate::core::Value::I32(1)], &mut results)
+        .unwrap();
+    results[0].try_into().unwrap()
+}
+
+/// Returns a Wasm module with a unique function type and code for every `n`.
+fn contract_module(n: usize) -> Vec<u8> {
+    let params = "(param i32)".repeat(n % 5 + 1);
+    wat2wasm(&format!(
+        r#"
+        (module
+            (type (func {params} (result i32)))
+            (func (export "run") (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const {n}))
+            )
+            (func (type 0) (i32.const {n}))
+        )
+    "#,
+    ))
+}
+
+#[test]
+fn dropped_modules_free_code_and_func_types() {
+    for config in [
+        Config::default(),
+        Config::default().enable_lazy_compilation(true),
+    ] {
+        let engine = Engine::new(&config);
+        let allocated = engine.len_allocated();
+        for n in 0..100 {
+            assert_eq!(
+                instantiate_and_run(&engine, &contract_module(n)),
+                1 + n as i32
+            );
+        }
+        assert_eq!(engine.len_allocated(), allocated);
+    }
+}
+
+#[test]
+fn freed_code_is_reused() {
+    let engine = Engine::default();
+    let wasm = contract_module(0);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let kept = Module::new(&engine, &wasm[..]).unwrap();
+    let allocated = engine.len_allocated();
+    // The code of the first module is not at the end of the engine's code.
+    drop(module);
+    assert_eq!(engine.len_allocated(), allocated);
+    for _ in 0..10 {
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        assert_eq!(engine.len_allocated(), allocated);
+        drop(module);
+    }
+    drop(kept);
+    assert_eq!(engine.len_allocated(), (0, 0));
+}
+
+#[test]
+fn instances_keep_code_alive() {
+    let engine = Engine::default();
+    let wasm = contract_module(42);
+    let mut store = crate::Store::new(&engine, ());
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <crate::Linker<()>>::new()
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .unwrap();
+    let allocated = engine.len_allocated();
+    drop(module);
+    // The code of other modules must not overwrite the code of the instance.
+    assert_eq!(instantiate_and_run(&engine, &contract_module(7)), 8);
+    assert_eq!(engine.len_allocated(), allocated);
+    let run = instance
+        .get_export(&store, "run")
+        .and_then(crate::Extern::into_func)
+        .unwrap();
+    let mut results = [crate::core::Value::I32(0)];
+    run.call(&mut store, &[crate::core::Value::I32(1)], &mut results)
+        .unwrap();
+    assert_eq!(results, [crate::core::Value::I32(43)]);
+    drop(store);
+    assert_eq!(engine.len_allocated(), (0, 0));
+}
+
+/// Returns a Wasm module owning one entity of every kind that traps in its
+/// `start` function if `trap_on_start` is `true`.
+fn owning_module(trap_on_start: bool) -> Vec<u8> {
+    let start = if trap_on_start { "unreachable" } else { "nop" };
+    wat2wasm(&format!(
+        r#"
+        (module
+            (import "env" "host" (func $host))
+            (memory 1)
+            (table 1 funcref)
+            (global (mut i32) (i32.const 0))
+            (func $start {start})
+            (func (export "run") (param i32) (result i32) (local.get 0))
+            (start $start)
+        )
+    "#,
+    ))
+}
+
+#[test]
+fn dropped_instances_free_entities() {
+    let engine = Engine::default();
+    let mut store = crate::Store::new(&engine, ());
+    let mut linker = <crate::Linker<()>>::new();
+    let host = crate::Func::wrap(&mut store, || {});
+    linker.define("env", "host", host).unwrap();
+    let module = Module::new(&engine, &owning_module(false)[..]).unwrap();
+    let allocated = store.len_entities();
+    for _ in 0..10 {
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|pre| pre.start(&mut store))
+            .unwrap();
+        assert_eq!(store.len_entities(), [1, 3, 1, 1, 1]);
+        assert!(store.drop_instance(instance));
+        assert_eq!(store.len_entities(), allocated);
+        assert!(!store.drop_instance(instance));
+    }
+    // The imported host function is not dropped with the instances.
+    assert_eq!(allocated, [0, 1, 0, 0, 0]);
+}
+
+#[test]
+fn imported_entities_outlive_dropped_instances() {
+    let engine = Engine::default();
+    let mut store = crate::Store::new(&engine, ());
+    let mut linker = <crate::Linker<()>>::new();
+    let exporter = wat2wasm(
+        r#"
+        (module
+            (memory (export "memory") 1)
+            (table (export "table") 1 funcref)
+            (global (export "global") (mut i32) (i32.const 0))
+            (func (export "func"))
+        )
+    "#,
+    );
+    let importer = wat2wasm(
+        r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (import "env" "table" (table 1 funcref))
+            (import "env" "global" (global (mut i32)))
+            (import "env" "func" (func))
+        )
+    "#,
+    );
+    let exporter = Module::new(&engine, &exporter[..]).unwrap();
+    let importer = Module::new(&engine, &importer[..]).unwrap();
+    let allocated = store.len_entities();
+    let exporter = linker
+        .instantiate(&mut store, &exporter)
+        .and_then(|pre| pre.start(&mut store))
+        .unwrap();
+    for name in ["memory", "table", "global", "func"] {
+        let export = exporter.get_export(&store, name).unwrap();
+        linker.define("env", name, export).unwrap();
+    }
+    let mut instantiate = || {
+        linker
+            .instantiate(&mut store, &importer)
+            .and_then(|pre| pre.start(&mut store))
+            .unwrap()
+    };
+    let first = instantiate();
+    let second = instantiate();
+    let [instances, funcs, tables, memories, globals] = store.len_entities();
+    // The imported memory, table and global are kept alive by their importers.
+    assert!(store.drop_instance(exporter));
+    assert_eq!(
+        store.len_entities(),
+        [instances - 1, funcs - 1, tables, memories, globals]
+    );
+    assert!(store.drop_instance(first));
+    assert!(store.drop_instance(second));
+    assert_eq!(store.len_entities(), allocated);
+}
+
+#[test]
+fn failed_instantiations_free_entities() {
+    let engine = Engine::default();
+    let mut store = crate::Store::new(&engine, ());
+    let mut linker = <crate::Linker<()>>::new();
+    let host = crate::Func::wrap(&mut store, || {});
+    linker.define("env", "host", host).unwrap();
+    let allocated = store.len_entities();
+    // The `start` function traps.
+    let module = Module::new(&engine, &owning_module(true)[..]).unwrap();
+    linker
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .unwrap_err();
+    assert_eq!(store.len_entities(), allocated);
+    // The `start` function is rejected.
+    linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .ensure_no_start(&mut store)
+        .unwrap_err();
+    assert_eq!(store.len_entities(), allocated);
+    // The active data segment is out of bounds.
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (global i32 (i32.const 0))
+            (data (i32.const 65536) "\01")
+        )
+    "#,
+    );
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    linker.instantiate(&mut store, &module).unwrap_err();
+    assert_eq!(store.len_entities(), allocated);
+}
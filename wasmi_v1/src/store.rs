@@ -1,7 +1,8 @@
 use super::{
-    arena::Arena,
-    engine::DedupFuncType,
+    arena::{GenerationalArena, GenerationalIdx},
+    engine::{DedupFuncType, Fuel, FuelError, InterruptHandle, ModuleCode, Stack},
     Engine,
+    Extern,
     Func,
     FuncEntity,
     FuncIdx,
@@ -19,7 +20,26 @@ use super::{
     TableEntity,
     TableIdx,
 };
-use crate::{GuardedEntity, Index};
+#[cfg(feature = "debugger")]
+use crate::engine::DebugState;
+#[cfg(feature = "profiler")]
+use crate::engine::{func_name, Profile, Profiler};
+#[cfg(feature = "coverage")]
+use crate::engine::{Coverage, CoverageRecorder};
+#[cfg(feature = "trace")]
+use crate::engine::{TraceEvent, TraceHook};
+#[cfg(feature = "coverage")]
+use crate::func::FuncEntityInternal;
+use crate::{
+    core::memory_units::Pages,
+    errors::{MemoryError, TableError},
+    limits::StoreLimiter,
+    Error,
+    GuardedEntity,
+    Index,
+    ResourceLimiter,
+};
+use alloc::{collections::BTreeMap, sync::Arc};
 use core::sync::atomic::{AtomicU32, Ordering};
 
 /// A unique store index.
@@ -54,44 +74,120 @@ impl StoreIdx {
 }
 
 /// A stored entity.
-pub type Stored<Idx> = GuardedEntity<StoreIdx, Idx>;
+///
+/// # Note
+///
+/// The generation of the entity index is used to reject entities
+/// that have been removed from their [`Store`].
+pub type Stored<Idx> = GuardedEntity<StoreIdx, GenerationalIdx<Idx>>;
+
+/// A stored table, linear memory or global variable that instances may import.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SharedEntity {
+    Table(GenerationalIdx<TableIdx>),
+    Memory(GenerationalIdx<MemoryIdx>),
+    Global(GenerationalIdx<GlobalIdx>),
+}
+
+/// The live instances importing a [`SharedEntity`].
+#[derive(Debug, Default)]
+struct Importers {
+    /// The number of live instances importing the entity.
+    len: usize,
+    /// Is `true` if the instance that owns the entity has been dropped.
+    ///
+    /// # Note
+    ///
+    /// Orphaned entities are removed once the last importing instance is dropped.
+    orphaned: bool,
+}
 
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
-pub struct Store<T, E> {
+pub struct Store<T> {
     /// The unique store index.
     ///
     /// Used to protect against invalid entity indices.
     store_idx: StoreIdx,
     /// Stored linear memories.
-    memories: Arena<MemoryIdx, MemoryEntity>,
+    memories: GenerationalArena<MemoryIdx, MemoryEntity>,
     /// Stored tables.
-    tables: Arena<TableIdx, TableEntity>,
+    tables: GenerationalArena<TableIdx, TableEntity>,
     /// Stored global variables.
-    globals: Arena<GlobalIdx, GlobalEntity>,
+    globals: GenerationalArena<GlobalIdx, GlobalEntity>,
     /// Stored Wasm or host functions.
-    funcs: Arena<FuncIdx, FuncEntity<T, E>>,
+    funcs: GenerationalArena<FuncIdx, FuncEntity<T>>,
     /// Stored module instances.
-    instances: Arena<InstanceIdx, InstanceEntity>,
+    instances: GenerationalArena<InstanceIdx, InstanceEntity>,
+    /// The importers of the tables, linear memories and global variables
+    /// that are imported by live instances.
+    ///
+    /// # Note
+    ///
+    /// Imported entities are kept alive even if their owning instance
+    /// is dropped since the importing instances still make use of them.
+    importers: BTreeMap<SharedEntity, Importers>,
     /// The [`Engine`] in use by the [`Store`].
     ///
     /// Amongst others the [`Engine`] stores the Wasm function definitions.
     engine: Engine,
+    /// Is `true` if fuel metering is enabled for the [`Engine`].
+    fuel_metering: bool,
+    /// The fuel of the [`Store`] if fuel metering is enabled.
+    fuel: Fuel,
+    /// The value and call stacks used to execute Wasm functions.
+    ///
+    /// # Note
+    ///
+    /// The stacks are owned by the [`Store`] instead of the [`Engine`] so that
+    /// multiple [`Store`] instances can share the same [`Engine`] concurrently.
+    stack: Stack,
+    /// The handle used to interrupt executions within the [`Store`].
+    interrupt: InterruptHandle,
+    /// The limiter of the resources allocated within the [`Store`] if any.
+    limiter: Option<StoreLimiter>,
+    /// The callback invoked before every executed instruction if any.
+    #[cfg(feature = "trace")]
+    trace_hook: Option<TraceHook>,
+    /// The breakpoints and the state of the current debug execution if any.
+    #[cfg(feature = "debugger")]
+    debugger: DebugState,
+    /// The profiler recording the executed functions if enabled.
+    #[cfg(feature = "profiler")]
+    profiler: Option<Profiler>,
+    /// The recorder of the executed Wasm instructions if coverage is enabled.
+    #[cfg(feature = "coverage")]
+    coverage: Option<CoverageRecorder>,
     /// User provided state.
     user_state: T,
 }
 
-impl<T, E> Store<T, E> {
+impl<T> Store<T> {
     /// Creates a new store.
     pub fn new(engine: &Engine, user_state: T) -> Self {
+        let config = engine.config();
         Self {
             store_idx: StoreIdx::new(),
-            memories: Arena::new(),
-            tables: Arena::new(),
-            globals: Arena::new(),
-            funcs: Arena::new(),
-            instances: Arena::new(),
+            memories: GenerationalArena::new(),
+            tables: GenerationalArena::new(),
+            globals: GenerationalArena::new(),
+            funcs: GenerationalArena::new(),
+            instances: GenerationalArena::new(),
+            importers: BTreeMap::new(),
             engine: engine.clone(),
+            fuel_metering: config.fuel_metering(),
+            fuel: Fuel::default(),
+            stack: Stack::new(&config),
+            interrupt: InterruptHandle::default(),
+            limiter: None,
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+            #[cfg(feature = "debugger")]
+            debugger: DebugState::default(),
+            #[cfg(feature = "profiler")]
+            profiler: None,
+            #[cfg(feature = "coverage")]
+            coverage: None,
             user_state,
         }
     }
@@ -116,6 +212,340 @@ impl<T, E> Store<T, E> {
         self.user_state
     }
 
+    /// Adds `delta` units of fuel to the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled in the [`Config`] of the [`Engine`].
+    ///
+    /// [`Config`]: [`crate::Config`]
+    pub fn add_fuel(&mut self, delta: u64) -> Result<(), FuelError> {
+        self.fuel_or_err()?.add(delta);
+        Ok(())
+    }
+
+    /// Returns the amount of fuel consumed by executions of the [`Store`] so far.
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn fuel_consumed(&self) -> Option<u64> {
+        self.fuel_metering.then(|| self.fuel.consumed())
+    }
+
+    /// Returns the amount of fuel remaining in the [`Store`].
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn fuel_remaining(&self) -> Option<u64> {
+        self.fuel_metering.then(|| self.fuel.remaining())
+    }
+
+    /// Consumes `delta` units of fuel from the [`Store`].
+    ///
+    /// Returns the remaining fuel after the consumption.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled in the [`Config`] of the [`Engine`].
+    /// - If there is not enough fuel remaining to consume `delta` units.
+    ///   In this case no fuel is consumed.
+    ///
+    /// [`Config`]: [`crate::Config`]
+    pub fn consume_fuel(&mut self, delta: u64) -> Result<u64, FuelError> {
+        let fuel = self.fuel_or_err()?;
+        fuel.consume(delta).map_err(|_| FuelError::OutOfFuel)?;
+        Ok(fuel.remaining())
+    }
+
+    /// Returns an exclusive reference to the [`Fuel`] if fuel metering is enabled.
+    fn fuel_or_err(&mut self) -> Result<&mut Fuel, FuelError> {
+        if !self.fuel_metering {
+            return Err(FuelError::FuelMeteringDisabled);
+        }
+        Ok(&mut self.fuel)
+    }
+
+    /// Returns an exclusive reference to the [`Fuel`] of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is used by the executor which only meters fuel if enabled.
+    pub(super) fn fuel_mut(&mut self) -> &mut Fuel {
+        &mut self.fuel
+    }
+
+    /// Returns an [`InterruptHandle`] to interrupt executions within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// The returned [`InterruptHandle`] can be sent to other threads in order
+    /// to interrupt long running executions without the need for fuel metering.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Returns `true` if an interrupt has been requested and resets the request.
+    ///
+    /// # Note
+    ///
+    /// This is used by the executor at loop back-edges and function calls.
+    pub(super) fn take_interrupt(&self) -> bool {
+        self.interrupt.take_interrupt()
+    }
+
+    /// Sets the [`ResourceLimiter`] that limits the resources allocated within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - This replaces a previously set [`ResourceLimiter`].
+    /// - Resources allocated before the [`ResourceLimiter`] has been set are not reported.
+    /// - The [`ResourceLimiter`] must not call back into the [`Engine`] of the [`Store`].
+    pub fn set_limiter<L>(&mut self, limiter: L)
+    where
+        L: ResourceLimiter + Send + Sync + 'static,
+    {
+        self.limiter = Some(StoreLimiter::new(limiter));
+    }
+
+    /// Removes the [`ResourceLimiter`] of the [`Store`] if any.
+    pub fn clear_limiter(&mut self) {
+        self.limiter = None;
+    }
+
+    /// Consults the [`ResourceLimiter`] before a linear memory is created or grown.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] denies the growth or traps.
+    pub(super) fn memory_growing(
+        &mut self,
+        current: Pages,
+        desired: Pages,
+        maximum: Option<Pages>,
+    ) -> Result<(), MemoryError> {
+        match self.limiter.as_mut() {
+            Some(limiter) => limiter.memory_growing(current, desired, maximum),
+            None => Ok(()),
+        }
+    }
+
+    /// Consults the [`ResourceLimiter`] before a table is created or grown.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] denies the growth or traps.
+    pub(super) fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> Result<(), TableError> {
+        match self.limiter.as_mut() {
+            Some(limiter) => limiter.table_growing(current, desired, maximum),
+            None => Ok(()),
+        }
+    }
+
+    /// Consults the [`ResourceLimiter`] before a module instance is created.
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] denies the creation or traps.
+    pub(super) fn instance_creating(&mut self) -> Result<(), Error> {
+        let current = self.instances.len();
+        match self.limiter.as_mut() {
+            Some(limiter) => limiter.instance_creating(current),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the `hook` that is invoked before every `wasmi` bytecode instruction
+    /// executed within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - This replaces a previously set tracing hook.
+    /// - Host functions are not traced.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&TraceEvent) + Send + Sync + 'static,
+    {
+        self.trace_hook = Some(TraceHook::new(hook));
+    }
+
+    /// Removes the tracing hook of the [`Store`] if any.
+    #[cfg(feature = "trace")]
+    pub fn clear_trace_hook(&mut self) {
+        self.trace_hook = None;
+    }
+
+    /// Returns an exclusive reference to the tracing hook of the [`Store`] if any.
+    #[cfg(feature = "trace")]
+    #[inline]
+    pub(super) fn trace_hook_mut(&mut self) -> Option<&mut TraceHook> {
+        self.trace_hook.as_mut()
+    }
+
+    /// Sets a breakpoint at the `wasm_offset` within the Wasm function at `func_index`.
+    ///
+    /// Returns `false` if the breakpoint was already set.
+    ///
+    /// # Note
+    ///
+    /// - Breakpoints only pause executions started via [`Func::call_debug`].
+    /// - The `func_index` is the index of the function within its Wasm module
+    ///   including imported functions. Breakpoints apply to all instances.
+    /// - The `wasm_offset` is the offset of the Wasm instruction within the Wasm binary.
+    ///   Wasm instructions that are not translated into their own `wasmi` bytecode,
+    ///   such as `local.get` or `i32.const`, never hit a breakpoint.
+    #[cfg(feature = "debugger")]
+    pub fn set_breakpoint(&mut self, func_index: u32, wasm_offset: usize) -> bool {
+        self.debugger.set_breakpoint(func_index, wasm_offset)
+    }
+
+    /// Removes the breakpoint at the `wasm_offset` within the Wasm function at `func_index`.
+    ///
+    /// Returns `false` if there was no such breakpoint.
+    #[cfg(feature = "debugger")]
+    pub fn remove_breakpoint(&mut self, func_index: u32, wasm_offset: usize) -> bool {
+        self.debugger.remove_breakpoint(func_index, wasm_offset)
+    }
+
+    /// Removes all breakpoints of the [`Store`].
+    #[cfg(feature = "debugger")]
+    pub fn clear_breakpoints(&mut self) {
+        self.debugger.clear_breakpoints()
+    }
+
+    /// Returns an exclusive reference to the debugger state of the [`Store`].
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub(super) fn debugger_mut(&mut self) -> &mut DebugState {
+        &mut self.debugger
+    }
+
+    /// Enables profiling of all functions executed within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - This discards the results of a previously enabled profiler.
+    /// - The [`Profile`] is queried via [`Store::profile`].
+    #[cfg(feature = "profiler")]
+    pub fn enable_profiler(&mut self) {
+        self.profiler = Some(Profiler::default());
+    }
+
+    /// Disables profiling and discards the results of the profiler if any.
+    #[cfg(feature = "profiler")]
+    pub fn disable_profiler(&mut self) {
+        self.profiler = None;
+    }
+
+    /// Returns the [`Profile`] of all functions executed since the profiler has been enabled.
+    ///
+    /// Returns `None` if profiling is disabled.
+    #[cfg(feature = "profiler")]
+    pub fn profile(&self) -> Option<Profile> {
+        self.profiler.as_ref().map(Profiler::profile)
+    }
+
+    /// Returns an exclusive reference to the profiler of the [`Store`] if enabled.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub(super) fn profiler_mut(&mut self) -> Option<&mut Profiler> {
+        self.profiler.as_mut()
+    }
+
+    /// Enters a profiled call to `func` if profiling is enabled.
+    ///
+    /// # Note
+    ///
+    /// - The `wasm` function index and [`Instance`] are `None` for host functions.
+    /// - If `resumed` is `true` the call is re-entered after its execution has been suspended.
+    #[cfg(feature = "profiler")]
+    pub(super) fn profile_enter(
+        &mut self,
+        func: Func,
+        wasm: Option<(Instance, u32)>,
+        resumed: bool,
+    ) {
+        let idx = self.unwrap_index(func.into_inner());
+        let registered = match &self.profiler {
+            Some(profiler) => profiler.contains(idx),
+            None => return,
+        };
+        if !registered {
+            let name = func_name(
+                idx,
+                wasm.map(|(instance, func_index)| {
+                    let names = self.resolve_instance(instance).names();
+                    (
+                        names.module().map(|name| &**name),
+                        func_index,
+                        names.func(func_index).map(|name| &**name),
+                    )
+                }),
+            );
+            if let Some(profiler) = &mut self.profiler {
+                profiler.register(idx, name);
+            }
+        }
+        if let Some(profiler) = &mut self.profiler {
+            profiler.enter(idx, resumed);
+        }
+    }
+
+    /// Enables coverage collection of all Wasm functions executed within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - This discards the coverage collected since coverage has previously been enabled.
+    /// - The [`Coverage`] is queried via [`Store::coverage`].
+    #[cfg(feature = "coverage")]
+    pub fn enable_coverage(&mut self) {
+        self.coverage = Some(CoverageRecorder::default());
+    }
+
+    /// Disables coverage collection and discards the collected coverage if any.
+    #[cfg(feature = "coverage")]
+    pub fn disable_coverage(&mut self) {
+        self.coverage = None;
+    }
+
+    /// Returns the [`Coverage`] of all Wasm functions of the [`Store`] since coverage has been enabled.
+    ///
+    /// Returns `None` if coverage is disabled.
+    ///
+    /// # Note
+    ///
+    /// Wasm functions that have not been executed are reported with zero counts.
+    #[cfg(feature = "coverage")]
+    pub fn coverage(&self) -> Option<Coverage> {
+        let recorder = self.coverage.as_ref()?;
+        let func_bodies = self
+            .funcs
+            .iter()
+            .filter_map(|(_, func)| match func.as_internal() {
+                FuncEntityInternal::Wasm(func) => Some((
+                    func.func_body(),
+                    self.resolve_instance(func.instance()).names(),
+                )),
+                FuncEntityInternal::Host(_) => None,
+            });
+        Some(self.engine.coverage(recorder, func_bodies))
+    }
+
+    /// Returns an exclusive reference to the coverage recorder of the [`Store`] if enabled.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub(super) fn coverage_mut(&mut self) -> Option<&mut CoverageRecorder> {
+        self.coverage.as_mut()
+    }
+
+    /// Returns an exclusive reference to the execution [`Stack`] of the [`Store`].
+    pub(super) fn stack_mut(&mut self) -> &mut Stack {
+        &mut self.stack
+    }
+
     /// Allocates a new function type to the store.
     pub(super) fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
         self.engine.alloc_func_type(func_type)
@@ -137,7 +567,7 @@ impl<T, E> Store<T, E> {
     }
 
     /// Allocates a new Wasm or host function to the store.
-    pub(super) fn alloc_func(&mut self, func: FuncEntity<T, E>) -> Func {
+    pub(super) fn alloc_func(&mut self, func: FuncEntity<T>) -> Func {
         Func::from_inner(Stored::new(self.store_idx, self.funcs.alloc(func)))
     }
 
@@ -149,10 +579,11 @@ impl<T, E> Store<T, E> {
     /// that require an [`Instance`] handle upon construction such as [`Func`].
     /// Using the [`Instance`] before fully initializing it using [`Store::initialize_instance`]
     /// will cause an execution panic.
-    pub(super) fn alloc_instance(&mut self) -> Instance {
+    /// The `code` of the instantiated module is kept alive by the [`Store`].
+    pub(super) fn alloc_instance(&mut self, code: Arc<ModuleCode>) -> Instance {
         Instance::from_inner(Stored::new(
             self.store_idx,
-            self.instances.alloc(InstanceEntity::uninitialized()),
+            self.instances.alloc(InstanceEntity::uninitialized(code)),
         ))
     }
 
@@ -160,7 +591,8 @@ impl<T, E> Store<T, E> {
     ///
     /// # Note
     ///
-    /// After this operation the [`Instance`] can be used.
+    /// After this operation the [`Instance`] can be used and
+    /// keeps its imported entities alive until it is dropped.
     ///
     /// # Panics
     ///
@@ -168,6 +600,11 @@ impl<T, E> Store<T, E> {
     /// - If the [`Instance`] is unknown to the [`Store`].
     /// - If the [`Instance`] already has been fully initialized.
     pub(super) fn initialize_instance(&mut self, instance: Instance, initialized: InstanceEntity) {
+        for imported in initialized.imported() {
+            if let Some(shared) = self.shared_entity(*imported) {
+                self.importers.entry(shared).or_default().len += 1;
+            }
+        }
         let entity_index = self.unwrap_index(instance.into_inner());
         let entity = self.instances.get_mut(entity_index).unwrap_or_else(|| {
             panic!(
@@ -188,12 +625,171 @@ impl<T, E> Store<T, E> {
         *entity = initialized;
     }
 
+    /// Drops the [`Instance`] and all entities allocated for it from the [`Store`].
+    ///
+    /// Returns `false` if the [`Instance`] has already been dropped.
+    ///
+    /// # Note
+    ///
+    /// - This releases the linear memories, tables, global variables and Wasm functions
+    ///   of the [`Instance`]. Linear memories, tables and global variables that are
+    ///   imported by other live instances are kept alive until the last of them is dropped.
+    /// - Entities imported by the [`Instance`] are not affected unless they belong to an
+    ///   already dropped instance and this [`Instance`] was their last live importer.
+    /// - Handles to dropped entities are rejected: using them panics like using
+    ///   entities of another [`Store`], while executions trap with
+    ///   [`TrapCode::DroppedInstance`] upon calling a Wasm function of a dropped [`Instance`].
+    /// - The compiled Wasm module is freed from the [`Engine`] once the module
+    ///   and all of its instances have been dropped.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Instance`] does not originate from this [`Store`].
+    ///
+    /// [`TrapCode::DroppedInstance`]: [`crate::core::TrapCode::DroppedInstance`]
+    pub fn drop_instance(&mut self, instance: Instance) -> bool {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        match self.instances.remove(entity_index) {
+            Some(entity) => {
+                self.release_imported(&entity);
+                self.remove_owned(&entity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops the [`Instance`] of an instantiation that failed and the entities allocated for it.
+    ///
+    /// # Note
+    ///
+    /// The `entity` is the [`InstanceEntity`] that was under construction.
+    pub(super) fn drop_uninitialized_instance(
+        &mut self,
+        instance: Instance,
+        entity: &InstanceEntity,
+    ) {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        self.instances.remove(entity_index);
+        self.remove_owned(entity);
+    }
+
+    /// Removes the entities allocated for the [`InstanceEntity`] from the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Entities imported by live instances are kept alive until their last importer is dropped.
+    fn remove_owned(&mut self, entity: &InstanceEntity) {
+        for owned in entity.owned() {
+            match self.shared_entity(*owned) {
+                Some(shared) => match self.importers.get_mut(&shared) {
+                    Some(importers) => importers.orphaned = true,
+                    None => self.remove_shared(shared),
+                },
+                None => {
+                    if let Extern::Func(func) = owned {
+                        let entity_index = self.unwrap_index(func.into_inner());
+                        self.funcs.remove(entity_index);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Releases the entities imported by the dropped [`InstanceEntity`].
+    ///
+    /// # Note
+    ///
+    /// Removes the imported entities of already dropped instances
+    /// from the [`Store`] if there are no other live importers left.
+    fn release_imported(&mut self, entity: &InstanceEntity) {
+        for imported in entity.imported() {
+            let shared = match self.shared_entity(*imported) {
+                Some(shared) => shared,
+                None => continue,
+            };
+            let importers = self.importers.get_mut(&shared).unwrap_or_else(|| {
+                panic!("encountered unregistered imported entity: {:?}", imported)
+            });
+            importers.len -= 1;
+            if importers.len == 0 {
+                let orphaned = importers.orphaned;
+                self.importers.remove(&shared);
+                if orphaned {
+                    self.remove_shared(shared);
+                }
+            }
+        }
+    }
+
+    /// Returns the [`SharedEntity`] of the [`Extern`] or `None` if it is a function.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Extern`] does not originate from this [`Store`].
+    fn shared_entity(&self, external: Extern) -> Option<SharedEntity> {
+        let shared = match external {
+            Extern::Func(_) => return None,
+            Extern::Table(table) => SharedEntity::Table(self.unwrap_index(table.into_inner())),
+            Extern::Memory(memory) => SharedEntity::Memory(self.unwrap_index(memory.into_inner())),
+            Extern::Global(global) => SharedEntity::Global(self.unwrap_index(global.into_inner())),
+        };
+        Some(shared)
+    }
+
+    /// Removes the [`SharedEntity`] from the [`Store`].
+    fn remove_shared(&mut self, shared: SharedEntity) {
+        match shared {
+            SharedEntity::Table(entity_index) => {
+                self.tables.remove(entity_index);
+            }
+            SharedEntity::Memory(entity_index) => {
+                self.memories.remove(entity_index);
+            }
+            SharedEntity::Global(entity_index) => {
+                self.globals.remove(entity_index);
+            }
+        }
+    }
+
+    /// Returns `true` if the Wasm or host function has not been dropped from the [`Store`].
+    ///
+    /// # Panics
+    ///
+    /// If the Wasm or host function does not originate from this [`Store`].
+    pub(super) fn contains_func(&self, func: Func) -> bool {
+        let entity_index = self.unwrap_index(func.into_inner());
+        self.funcs.contains(entity_index)
+    }
+
+    /// Returns `true` if the [`Instance`] has not been dropped from the [`Store`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Instance`] does not originate from this [`Store`].
+    pub(super) fn contains_instance(&self, instance: Instance) -> bool {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        self.instances.contains(entity_index)
+    }
+
+    /// Returns the number of allocated instances, funcs, tables, memories and globals.
+    #[cfg(test)]
+    pub(crate) fn len_entities(&self) -> [usize; 5] {
+        [
+            self.instances.len(),
+            self.funcs.len(),
+            self.tables.len(),
+            self.memories.len(),
+            self.globals.len(),
+        ]
+    }
+
     /// Unpacks and checks the stored entity index.
     ///
     /// # Panics
     ///
     /// If the stored entity does not originate from this store.
-    fn unwrap_index<Idx>(&self, stored: Stored<Idx>) -> Idx
+    fn unwrap_index<Idx>(&self, stored: Stored<Idx>) -> GenerationalIdx<Idx>
     where
         Idx: Index,
     {
@@ -305,7 +901,7 @@ impl<T, E> Store<T, E> {
     ///
     /// - If the Wasm or host function does not originate from this store.
     /// - If the Wasm or host function cannot be resolved to its entity.
-    pub(super) fn resolve_func(&self, func: Func) -> &FuncEntity<T, E> {
+    pub(super) fn resolve_func(&self, func: Func) -> &FuncEntity<T> {
         let entity_index = self.unwrap_index(func.into_inner());
         self.funcs.get(entity_index).unwrap_or_else(|| {
             panic!(
@@ -330,22 +926,37 @@ impl<T, E> Store<T, E> {
             )
         })
     }
+
+    /// Returns an exclusive reference to the associated entity of the [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] does not originate from this store.
+    /// - If the [`Instance`] cannot be resolved to its entity.
+    pub(super) fn resolve_instance_mut(&mut self, instance: Instance) -> &mut InstanceEntity {
+        let entity_index = self.unwrap_index(instance.into_inner());
+        self.instances.get_mut(entity_index).unwrap_or_else(|| {
+            panic!(
+                "failed to resolve stored module instance: {:?}",
+                entity_index
+            )
+        })
+    }
 }
 
 /// A trait used to get shared access to a [`Store`] in `wasmi`.
 pub trait AsContext {
     /// The user state associated with the [`Store`], aka the `T` in `Store<T>`.
     type UserState;
-    type Error;
 
     /// Returns the store context that this type provides access to.
-    fn as_context(&self) -> StoreContext<Self::UserState, Self::Error>;
+    fn as_context(&self) -> StoreContext<Self::UserState>;
 }
 
 /// A trait used to get exclusive access to a [`Store`] in `wasmi`.
 pub trait AsContextMut: AsContext {
     /// Returns the store context that this type provides access to.
-    fn as_context_mut(&mut self) -> StoreContextMut<Self::UserState, Self::Error>;
+    fn as_context_mut(&mut self) -> StoreContextMut<Self::UserState>;
 }
 
 /// A temporary handle to a `&Store<T>`.
@@ -354,23 +965,23 @@ pub trait AsContextMut: AsContext {
 /// For more information, see [`Store`].
 #[derive(Debug, Copy, Clone)]
 #[repr(transparent)]
-pub struct StoreContext<'a, T, E> {
-    pub(super) store: &'a Store<T, E>,
+pub struct StoreContext<'a, T> {
+    pub(super) store: &'a Store<T>,
 }
 
-impl<'a, T: AsContext> From<&'a T> for StoreContext<'a, T::UserState, T::Error> {
+impl<'a, T: AsContext> From<&'a T> for StoreContext<'a, T::UserState> {
     fn from(ctx: &'a T) -> Self {
         ctx.as_context()
     }
 }
 
-impl<'a, T: AsContext> From<&'a mut T> for StoreContext<'a, T::UserState, T::Error> {
+impl<'a, T: AsContext> From<&'a mut T> for StoreContext<'a, T::UserState> {
     fn from(ctx: &'a mut T) -> Self {
         T::as_context(ctx)
     }
 }
 
-impl<'a, T: AsContextMut> From<&'a mut T> for StoreContextMut<'a, T::UserState, T::Error> {
+impl<'a, T: AsContextMut> From<&'a mut T> for StoreContextMut<'a, T::UserState> {
     fn from(ctx: &'a mut T) -> Self {
         ctx.as_context_mut()
     }
@@ -382,8 +993,8 @@ impl<'a, T: AsContextMut> From<&'a mut T> for StoreContextMut<'a, T::UserState,
 /// For more information, see [`Store`].
 #[derive(Debug)]
 #[repr(transparent)]
-pub struct StoreContextMut<'a, T, E> {
-    pub(super) store: &'a mut Store<T, E>,
+pub struct StoreContextMut<'a, T> {
+    pub(super) store: &'a mut Store<T>,
 }
 
 impl<T> AsContext for &'_ T
@@ -391,10 +1002,9 @@ where
     T: AsContext,
 {
     type UserState = T::UserState;
-    type Error = T::Error;
 
     #[inline]
-    fn as_context(&self) -> StoreContext<'_, T::UserState, T::Error> {
+    fn as_context(&self) -> StoreContext<'_, T::UserState> {
         T::as_context(*self)
     }
 }
@@ -404,10 +1014,9 @@ where
     T: AsContext,
 {
     type UserState = T::UserState;
-    type Error = T::Error;
 
     #[inline]
-    fn as_context(&self) -> StoreContext<'_, T::UserState, T::Error> {
+    fn as_context(&self) -> StoreContext<'_, T::UserState> {
         T::as_context(*self)
     }
 }
@@ -417,48 +1026,45 @@ where
     T: AsContextMut,
 {
     #[inline]
-    fn as_context_mut(&mut self) -> StoreContextMut<'_, T::UserState, T::Error> {
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, T::UserState> {
         T::as_context_mut(*self)
     }
 }
 
-impl<T, E> AsContext for StoreContext<'_, T, E> {
+impl<T> AsContext for StoreContext<'_, T> {
     type UserState = T;
-    type Error = E;
 
-    fn as_context(&self) -> StoreContext<'_, Self::UserState, Self::Error> {
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
         StoreContext { store: self.store }
     }
 }
 
-impl<T, E> AsContext for StoreContextMut<'_, T, E> {
+impl<T> AsContext for StoreContextMut<'_, T> {
     type UserState = T;
-    type Error = E;
 
-    fn as_context(&self) -> StoreContext<'_, Self::UserState, Self::Error> {
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
         StoreContext { store: self.store }
     }
 }
 
-impl<T, E> AsContextMut for StoreContextMut<'_, T, E> {
-    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState, Self::Error> {
+impl<T> AsContextMut for StoreContextMut<'_, T> {
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState> {
         StoreContextMut {
             store: &mut *self.store,
         }
     }
 }
 
-impl<T, E> AsContext for Store<T, E> {
+impl<T> AsContext for Store<T> {
     type UserState = T;
-    type Error = E;
 
-    fn as_context(&self) -> StoreContext<'_, Self::UserState, Self::Error> {
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
         StoreContext { store: self }
     }
 }
 
-impl<T, E> AsContextMut for Store<T, E> {
-    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState, Self::Error> {
+impl<T> AsContextMut for Store<T> {
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState> {
         StoreContextMut { store: self }
     }
 }
@@ -2,7 +2,8 @@
 
 use super::{AsContext, AsContextMut, Func, Index, Stored};
 use alloc::vec::Vec;
-use core::{fmt, fmt::Display};
+use core::{fmt, fmt::Display, ops::Range};
+use wasmi_core::TrapCode;
 
 /// A raw index to a table entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -48,6 +49,10 @@ pub enum TableError {
         /// The required [`TableType`].
         required: TableType,
     },
+    /// Occurs when the resource limiter of the store denies to create or grow a table.
+    ResourceLimitReached,
+    /// Occurs when the resource limiter of the store traps upon creating or growing a table.
+    ResourceLimiterTrap(TrapCode),
 }
 
 impl Display for TableError {
@@ -81,6 +86,12 @@ impl Display for TableError {
                     unsatisfying, required,
                 )
             }
+            Self::ResourceLimitReached => {
+                write!(f, "the resource limiter denied the table growth")
+            }
+            Self::ResourceLimiterTrap(trap_code) => {
+                write!(f, "the resource limiter trapped: {}", trap_code)
+            }
         }
     }
 }
@@ -175,6 +186,20 @@ impl TableEntity {
         self.elements.len()
     }
 
+    /// Returns the maximum length of the table.
+    fn maximum(&self) -> usize {
+        self.table_type.maximum().unwrap_or(u32::MAX as usize)
+    }
+
+    /// Returns the length of the table after growing it by `grow_by` elements.
+    ///
+    /// Returns `None` if the table would grow beyond its maximum limits.
+    fn grown_len(&self, grow_by: usize) -> Option<usize> {
+        self.len()
+            .checked_add(grow_by)
+            .filter(|&new_len| new_len <= self.maximum())
+    }
+
     /// Grows the table by the given amount of elements.
     ///
     /// # Note
@@ -185,16 +210,11 @@ impl TableEntity {
     ///
     /// If the table is grown beyond its maximum limits.
     pub fn grow(&mut self, grow_by: usize) -> Result<(), TableError> {
-        let maximum = self.table_type.maximum().unwrap_or(u32::MAX as usize);
-        let current = self.len();
-        let new_len = current
-            .checked_add(grow_by)
-            .filter(|&new_len| new_len <= maximum)
-            .ok_or(TableError::GrowOutOfBounds {
-                maximum,
-                current,
-                grow_by,
-            })?;
+        let new_len = self.grown_len(grow_by).ok_or(TableError::GrowOutOfBounds {
+            maximum: self.maximum(),
+            current: self.len(),
+            grow_by,
+        })?;
         self.elements.resize(new_len, None);
         Ok(())
     }
@@ -230,6 +250,57 @@ impl TableEntity {
         *element = new_value;
         Ok(())
     }
+
+    /// Returns the range of `len` table elements starting at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If the range is out of bounds of the table.
+    fn checked_range(&self, offset: usize, len: usize) -> Result<Range<usize>, TableError> {
+        let current = self.len();
+        offset
+            .checked_add(len)
+            .filter(|&end| end <= current)
+            .map(|end| offset..end)
+            .ok_or(TableError::AccessOutOfBounds {
+                current,
+                offset: offset.saturating_add(len),
+            })
+    }
+
+    /// Copies `len` elements starting at `src_offset` to `dst_offset` within the table.
+    ///
+    /// # Note
+    ///
+    /// The source and destination ranges may overlap.
+    ///
+    /// # Errors
+    ///
+    /// If any of the accessed elements is out of bounds of the table.
+    pub fn copy_within(
+        &mut self,
+        dst_offset: usize,
+        src_offset: usize,
+        len: usize,
+    ) -> Result<(), TableError> {
+        let src = self.checked_range(src_offset, len)?;
+        self.checked_range(dst_offset, len)?;
+        self.elements.copy_within(src, dst_offset);
+        Ok(())
+    }
+
+    /// Initializes the table elements starting at `offset` with the `funcs`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the accessed elements is out of bounds of the table.
+    pub fn init(&mut self, offset: usize, funcs: &[Func]) -> Result<(), TableError> {
+        let dst = self.checked_range(offset, funcs.len())?;
+        for (element, func) in self.elements[dst].iter_mut().zip(funcs) {
+            *element = Some(*func);
+        }
+        Ok(())
+    }
 }
 
 /// A Wasm table reference.
@@ -249,10 +320,16 @@ impl Table {
     }
 
     /// Creates a new table to the store.
-    pub fn new(mut ctx: impl AsContextMut, table_type: TableType) -> Self {
-        ctx.as_context_mut()
-            .store
-            .alloc_table(TableEntity::new(table_type))
+    ///
+    /// # Errors
+    ///
+    /// If the [`ResourceLimiter`] of the store denies the creation or traps.
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
+    pub fn new(mut ctx: impl AsContextMut, table_type: TableType) -> Result<Self, TableError> {
+        let store = &mut ctx.as_context_mut().store;
+        store.table_growing(0, table_type.initial(), table_type.maximum())?;
+        Ok(store.alloc_table(TableEntity::new(table_type)))
     }
 
     /// Returns the type and limits of the table.
@@ -286,16 +363,25 @@ impl Table {
     ///
     /// # Errors
     ///
-    /// If the table is grown beyond its maximum limits.
+    /// - If the table is grown beyond its maximum limits.
+    /// - If the [`ResourceLimiter`] of the store denies the growth or traps.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Table`].
+    ///
+    /// [`ResourceLimiter`]: [`crate::ResourceLimiter`]
     pub fn grow(&self, mut ctx: impl AsContextMut, grow_by: usize) -> Result<(), TableError> {
-        ctx.as_context_mut()
-            .store
-            .resolve_table_mut(*self)
-            .grow(grow_by)
+        let store = &mut ctx.as_context_mut().store;
+        let entity = store.resolve_table(*self);
+        if grow_by != 0 {
+            if let Some(desired) = entity.grown_len(grow_by) {
+                let current = entity.len();
+                let maximum = entity.table_type().maximum();
+                store.table_growing(current, desired, maximum)?;
+            }
+        }
+        store.resolve_table_mut(*self).grow(grow_by)
     }
 
     /// Returns the element at the given offset if any.
@@ -331,4 +417,47 @@ impl Table {
             .resolve_table_mut(*self)
             .set(offset, new_value)
     }
+
+    /// Copies `len` elements starting at `src_offset` to `dst_offset` within the table.
+    ///
+    /// # Errors
+    ///
+    /// If any of the accessed elements is out of bounds of the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub(crate) fn copy_within(
+        &self,
+        mut ctx: impl AsContextMut,
+        dst_offset: usize,
+        src_offset: usize,
+        len: usize,
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .resolve_table_mut(*self)
+            .copy_within(dst_offset, src_offset, len)
+    }
+
+    /// Initializes the table elements starting at `offset` with the `funcs`.
+    ///
+    /// # Errors
+    ///
+    /// If any of the accessed elements is out of bounds of the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub(crate) fn init(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: usize,
+        funcs: &[Func],
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .resolve_table_mut(*self)
+            .init(offset, funcs)
+    }
 }
@@ -0,0 +1,414 @@
+use crate::{
+    core::{CanResume, TrapCode},
+    Error,
+    Instance,
+};
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+};
+
+/// Trait that allows host functions to trap with custom errors.
+///
+/// # Note
+///
+/// Host errors are wrapped into a [`Trap`] which can be downcast back
+/// into the original host error via [`Trap::downcast_ref`].
+///
+/// # Example
+///
+/// ```
+/// use wasmi_v1::{HostError, Trap};
+///
+/// #[derive(Debug)]
+/// struct MyError(u32);
+///
+/// impl core::fmt::Display for MyError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+///         write!(f, "my error: {}", self.0)
+///     }
+/// }
+///
+/// impl HostError for MyError {}
+///
+/// let trap = Trap::host(MyError(42));
+/// assert_eq!(trap.downcast_ref::<MyError>().map(|error| error.0), Some(42));
+/// ```
+///
+/// The downcasting of host errors cannot be customized by implementors:
+///
+/// ```compile_fail
+/// use core::any::Any;
+/// use wasmi_v1::HostError;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// impl core::fmt::Display for MyError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+///         write!(f, "my error")
+///     }
+/// }
+///
+/// impl HostError for MyError {
+///     fn as_any(&self) -> &dyn Any {
+///         &0_u32
+///     }
+/// }
+/// ```
+pub trait HostError: 'static + Display + Debug + Send + Sync + private::AsAny {
+    /// Returns `true` if an execution suspended by this host error can be resumed.
+    ///
+    /// # Note
+    ///
+    /// - Defaults to `false`.
+    /// - This is only relevant for executions started via [`Func::call_resumable`].
+    ///
+    /// [`Func::call_resumable`]: [`crate::Func::call_resumable`]
+    fn can_resume(&self) -> bool {
+        false
+    }
+}
+
+mod private {
+    use core::any::Any;
+
+    /// Allows to downcast host errors via [`Any`].
+    ///
+    /// # Note
+    ///
+    /// This trait is implemented for all types and cannot be named outside of
+    /// this crate. Therefore implementors of [`HostError`] cannot customize
+    /// it and downcasting host errors is always sound.
+    ///
+    /// [`HostError`]: [`super::HostError`]
+    pub trait AsAny {
+        /// Returns a shared reference to `self` as [`Any`].
+        fn as_any(&self) -> &dyn Any;
+
+        /// Returns an exclusive reference to `self` as [`Any`].
+        fn as_any_mut(&mut self) -> &mut dyn Any;
+    }
+
+    impl<T> AsAny for T
+    where
+        T: Any,
+    {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+}
+
+impl dyn HostError {
+    /// Returns `true` if the host error is of type `T`.
+    pub fn is<T: HostError>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Returns a shared reference to the host error if it is of type `T`.
+    pub fn downcast_ref<T: HostError>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns an exclusive reference to the host error if it is of type `T`.
+    pub fn downcast_mut<T: HostError>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+/// A trap that occurred during the execution of Wasm or host functions.
+///
+/// # Note
+///
+/// Traps that occur while executing Wasm functions carry a [`WasmBacktrace`]
+/// of the Wasm function frames that were live when the trap occurred.
+#[derive(Debug)]
+pub struct Trap {
+    /// The reason of the trap.
+    reason: TrapReason,
+    /// The Wasm backtrace at the point where the trap occurred if any.
+    backtrace: Option<WasmBacktrace>,
+}
+
+/// The reason of a [`Trap`].
+#[derive(Debug)]
+enum TrapReason {
+    /// A Wasm trap, e.g. caused by executing an `unreachable` instruction.
+    Code(TrapCode),
+    /// An exit with the given status, e.g. caused by a WASI `proc_exit` call.
+    I32Exit(i32),
+    /// A custom error returned by a host function.
+    Host(Box<dyn HostError>),
+}
+
+impl Trap {
+    /// Creates a new [`Trap`] from the given [`TrapReason`].
+    fn with_reason(reason: TrapReason) -> Self {
+        Self {
+            reason,
+            backtrace: None,
+        }
+    }
+
+    /// Creates a new [`Trap`] representing an exit with the given `status`.
+    ///
+    /// # Note
+    ///
+    /// This is useful for host functions such as WASI `proc_exit`.
+    pub fn i32_exit(status: i32) -> Self {
+        Self::with_reason(TrapReason::I32Exit(status))
+    }
+
+    /// Creates a new [`Trap`] from the given host `error`.
+    pub fn host<E>(error: E) -> Self
+    where
+        E: HostError,
+    {
+        Self::with_reason(TrapReason::Host(Box::new(error)))
+    }
+
+    /// Attaches the [`WasmBacktrace`] to the [`Trap`] if it has none so far.
+    pub(crate) fn with_backtrace(mut self, backtrace: impl FnOnce() -> WasmBacktrace) -> Self {
+        if self.backtrace.is_none() {
+            self.backtrace = Some(backtrace());
+        }
+        self
+    }
+
+    /// Returns the [`TrapCode`] if the [`Trap`] is a Wasm trap.
+    pub fn trap_code(&self) -> Option<TrapCode> {
+        match self.reason {
+            TrapReason::Code(code) => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Returns the exit status if the [`Trap`] represents an exit.
+    ///
+    /// See [`Trap::i32_exit`] for more information.
+    pub fn i32_exit_status(&self) -> Option<i32> {
+        match self.reason {
+            TrapReason::I32Exit(status) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Returns a shared reference to the host error if the [`Trap`] has been
+    /// created from a host error.
+    pub fn as_host(&self) -> Option<&dyn HostError> {
+        match &self.reason {
+            TrapReason::Host(error) => Some(&**error),
+            _ => None,
+        }
+    }
+
+    /// Consumes the [`Trap`] and returns the host error if the [`Trap`]
+    /// has been created from a host error.
+    pub fn into_host(self) -> Option<Box<dyn HostError>> {
+        match self.reason {
+            TrapReason::Host(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns a shared reference to the host error if it is of type `T`.
+    pub fn downcast_ref<T: HostError>(&self) -> Option<&T> {
+        self.as_host().and_then(<dyn HostError>::downcast_ref)
+    }
+
+    /// Returns an exclusive reference to the host error if it is of type `T`.
+    pub fn downcast_mut<T: HostError>(&mut self) -> Option<&mut T> {
+        match &mut self.reason {
+            TrapReason::Host(error) => error.downcast_mut(),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`WasmBacktrace`] of the [`Trap`] if any.
+    ///
+    /// # Note
+    ///
+    /// Traps created outside of the execution of Wasm functions have no
+    /// [`WasmBacktrace`]. Traps returned by host functions called from Wasm
+    /// carry the [`WasmBacktrace`] of the calling Wasm functions.
+    pub fn backtrace(&self) -> Option<&WasmBacktrace> {
+        self.backtrace.as_ref()
+    }
+}
+
+impl From<TrapCode> for Trap {
+    fn from(code: TrapCode) -> Self {
+        Self::with_reason(TrapReason::Code(code))
+    }
+}
+
+impl From<Error> for Trap {
+    /// Converts the [`Error`] into a [`Trap`].
+    ///
+    /// # Note
+    ///
+    /// This allows host functions to propagate errors of nested calls.
+    /// Traps are passed through as is, all other errors are wrapped as host errors.
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Trap(trap) => trap,
+            error => Self::host(error),
+        }
+    }
+}
+
+impl CanResume for Trap {
+    fn can_resume(&self) -> bool {
+        match &self.reason {
+            TrapReason::Host(error) => error.can_resume(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Trap {}
+
+impl Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.reason {
+            TrapReason::Code(code) => Display::fmt(code, f)?,
+            TrapReason::I32Exit(status) => write!(f, "exited with i32 exit status {}", status)?,
+            TrapReason::Host(error) => Display::fmt(error, f)?,
+        }
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\nwasm backtrace:\n{}", backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+/// The Wasm function frames that were live when a [`Trap`] occurred.
+///
+/// The frames are ordered from the innermost frame that caused
+/// the [`Trap`] to the outermost frame that has been called by the host.
+#[derive(Debug, Clone)]
+pub struct WasmBacktrace {
+    /// The live Wasm function frames.
+    frames: Box<[FrameInfo]>,
+}
+
+impl WasmBacktrace {
+    /// Creates a new [`WasmBacktrace`] from the given `frames`.
+    pub(crate) fn new(frames: Box<[FrameInfo]>) -> Self {
+        Self { frames }
+    }
+
+    /// Returns the frames of the [`WasmBacktrace`] starting with the innermost frame.
+    pub fn frames(&self) -> &[FrameInfo] {
+        &self.frames
+    }
+}
+
+impl Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (n, frame) in self.frames.iter().enumerate() {
+            if n != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:>4}: {}", n, frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// Information about a single Wasm function frame of a [`WasmBacktrace`].
+#[derive(Debug, Clone)]
+pub struct FrameInfo {
+    /// The instance of the executed Wasm function.
+    instance: Instance,
+    /// The name of the module of the executed Wasm function if any.
+    module_name: Option<Arc<str>>,
+    /// The index of the executed Wasm function within its module.
+    func_index: u32,
+    /// The name of the executed Wasm function if any.
+    func_name: Option<Arc<str>>,
+    /// The offset of the executed Wasm instruction within the Wasm binary.
+    wasm_offset: usize,
+}
+
+impl FrameInfo {
+    /// Creates a new [`FrameInfo`].
+    pub(crate) fn new(
+        instance: Instance,
+        module_name: Option<Arc<str>>,
+        func_index: u32,
+        func_name: Option<Arc<str>>,
+        wasm_offset: usize,
+    ) -> Self {
+        Self {
+            instance,
+            module_name,
+            func_index,
+            func_name,
+            wasm_offset,
+        }
+    }
+
+    /// Returns the [`Instance`] of the executed Wasm function.
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the name of the module of the executed Wasm function if any.
+    ///
+    /// # Note
+    ///
+    /// The name is taken from the `name` custom section of the Wasm binary.
+    pub fn module_name(&self) -> Option<&str> {
+        self.module_name.as_deref()
+    }
+
+    /// Returns the index of the executed Wasm function within its module.
+    ///
+    /// # Note
+    ///
+    /// The index includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the executed Wasm function if any.
+    ///
+    /// # Note
+    ///
+    /// The name is taken from the `name` custom section of the Wasm binary.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the offset of the executed Wasm instruction within the Wasm binary.
+    ///
+    /// # Note
+    ///
+    /// For the innermost frame this is the instruction that caused the [`Trap`].
+    /// For all other frames this is the instruction that called the next inner frame.
+    pub fn wasm_offset(&self) -> usize {
+        self.wasm_offset
+    }
+}
+
+impl Display for FrameInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x} - ", self.wasm_offset)?;
+        match self.module_name() {
+            Some(module_name) => write!(f, "{}!", module_name)?,
+            None => write!(f, "<unknown>!")?,
+        }
+        match self.func_name() {
+            Some(func_name) => write!(f, "{}", func_name),
+            None => write!(f, "<wasm function {}>", self.func_index),
+        }
+    }
+}